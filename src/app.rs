@@ -10,40 +10,11 @@ pub struct App {
 impl App {
     /// Crée une nouvelle instance de l'application.
     pub fn new(repo: GitRepo, repo_path: String) -> Result<Self> {
+        // Le graphe, le status et les branches sont chargés en arrière-plan
+        // par l'EventHandler dès le premier tour de boucle (état initial dirty).
         let mut state = crate::state::AppState::new(repo, repo_path)?;
-
-        // Rafraîchir l'état initial.
-        state.current_branch = state.repo.current_branch().ok();
-        state.graph = state
-            .repo
-            .build_graph(crate::state::MAX_COMMITS)
-            .unwrap_or_default();
-        state.status_entries = state.repo.status().unwrap_or_default();
-
-        // Synchroniser graph_view.rows avec le graphe initial
-        state.graph_view.rows.set_items(state.graph.clone());
-
-        // Charger les données initiales.
-        if let Some(row) = state.graph.get(state.selected_index) {
-            state.commit_files = state.repo.commit_diff(row.node.oid).unwrap_or_default();
-        }
-
-        // Rafraîchir l'état de staging.
-        let all_entries = state.repo.status().unwrap_or_default();
-        state.staging_state.set_staged_files(
-            all_entries
-                .iter()
-                .filter(|e| e.is_staged())
-                .cloned()
-                .collect(),
-        );
-        state.staging_state.set_unstaged_files(
-            all_entries
-                .iter()
-                .filter(|e| e.is_unstaged())
-                .cloned()
-                .collect(),
-        );
+        // Un rebase interrompu (application quittée pendant un arrêt) est repris.
+        crate::handler::rebase::restore_session(&mut state);
 
         Ok(Self { state })
     }
@@ -0,0 +1,360 @@
+//! Commandes personnalisées de l'utilisateur.
+//!
+//! Le fichier `commands.toml` du répertoire de configuration
+//! (`~/.config/git_sv/commands.toml` sous Linux) associe des commandes shell
+//! à des touches, dans un contexte du keymap :
+//!
+//! ```toml
+//! [[command]]
+//! name = "deploy-tag"
+//! key = "D"
+//! context = "graph"               # contexte du keymap (défaut : "global")
+//! command = "./scripts/deploy-tag {commit}"
+//! mode = "popup"                  # "popup" (sortie capturée) ou "interactive"
+//! description = "Déployer le commit sélectionné"
+//! ```
+//!
+//! Les placeholders `{commit}`, `{branch}`, `{file}` et `{repo}` sont remplacés
+//! par la sélection courante, protégés pour le shell. Ils s'écrivent donc hors
+//! guillemets (`echo {file}`, pas `echo '{file}'`) : un placeholder entre
+//! guillemets est refusé au chargement.
+//!
+//! Les commandes sont lancées par `sh` (celui de Git for Windows sous Windows,
+//! voir [`crate::utils::shell`]).
+//!
+//! Une commande en mode popup s'exécute dans un thread ([`CommandTask`]) : sa
+//! sortie est affichée par la boucle principale une fois la commande terminée.
+
+use std::path::{Path, PathBuf};
+use std::process::Output;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::OnceLock;
+use std::thread;
+
+use serde::Deserialize;
+
+use crate::error::{GitSvError, Result};
+use crate::ui::keymap::{parse_sequence, KeyContext};
+use crate::utils::shell;
+
+/// Nom du fichier des commandes personnalisées.
+const COMMANDS_FILE: &str = "commands.toml";
+
+/// Placeholders reconnus dans les modèles de commande.
+pub const PLACEHOLDERS: [&str; 4] = ["commit", "branch", "file", "repo"];
+
+/// Mode d'exécution d'une commande personnalisée.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommandMode {
+    /// Sortie capturée et affichée dans un popup.
+    #[default]
+    Popup,
+    /// TUI suspendue, la commande utilise le terminal.
+    Interactive,
+}
+
+/// Commande personnalisée associée à une touche.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomCommand {
+    /// Nom affiché (titre du popup, palette).
+    pub name: String,
+    /// Touche ou séquence de touches (ex: "D", "ctrl-t", "g d").
+    pub key: String,
+    /// Contexte du keymap où la touche est active.
+    #[serde(default = "default_context")]
+    pub context: String,
+    /// Modèle de la commande shell.
+    pub command: String,
+    /// Mode d'exécution.
+    #[serde(default)]
+    pub mode: CommandMode,
+    /// Description affichée dans l'aide (défaut : la commande).
+    pub description: Option<String>,
+}
+
+fn default_context() -> String {
+    KeyContext::Global.name().to_string()
+}
+
+impl CustomCommand {
+    /// Description affichée dans l'aide et la palette.
+    pub fn description(&self) -> &str {
+        self.description.as_deref().unwrap_or(&self.command)
+    }
+
+    /// Vérifie le contexte, la touche et les placeholders.
+    fn validate(&self) -> std::result::Result<(), String> {
+        let invalid = |message: String| format!("commande '{}' : {}", self.name, message);
+
+        KeyContext::from_name(&self.context)
+            .ok_or_else(|| invalid(format!("contexte inconnu '{}'", self.context)))?;
+        parse_sequence(&self.key).map_err(invalid)?;
+        expand(&self.command, |_| Some(String::new())).map_err(invalid)?;
+        Ok(())
+    }
+}
+
+/// Contenu du fichier des commandes.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CommandsFile {
+    pub command: Vec<CustomCommand>,
+}
+
+impl CommandsFile {
+    /// Charge le fichier de l'utilisateur (vide s'il n'existe pas).
+    pub fn load() -> Result<Self> {
+        match dirs::config_dir() {
+            Some(dir) => Self::load_from_path(&dir.join("git_sv").join(COMMANDS_FILE)),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Charge et valide le fichier donné (vide s'il n'existe pas).
+    pub fn load_from_path(path: &Path) -> Result<Self> {
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+        let invalid = |message: String| GitSvError::Config {
+            path: PathBuf::from(path),
+            message,
+        };
+
+        let content = std::fs::read_to_string(path).map_err(|e| invalid(e.to_string()))?;
+        let file: Self = toml::from_str(&content).map_err(|e| invalid(e.to_string()))?;
+        for command in &file.command {
+            command.validate().map_err(invalid)?;
+        }
+        Ok(file)
+    }
+}
+
+/// Remplace les placeholders `{nom}` du modèle par les valeurs de `value`.
+///
+/// Seuls les `{mot}` en minuscules sont des placeholders : `${VAR}` et les blocs
+/// shell (`awk '{print $1}'`) sont laissés tels quels. Les valeurs sont protégées
+/// pour le shell. Erreur si un placeholder est inconnu, entre guillemets ou sans
+/// valeur.
+pub fn expand(
+    template: &str,
+    value: impl Fn(&str) -> Option<String>,
+) -> std::result::Result<String, String> {
+    let mut result = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        let (before, after) = rest.split_at(start);
+        result.push_str(before);
+
+        let name_len = after[1..]
+            .find(|c: char| !(c.is_ascii_lowercase() || c == '_'))
+            .unwrap_or(after.len() - 1);
+        let is_placeholder =
+            name_len > 0 && after[1 + name_len..].starts_with('}') && !before.ends_with('$');
+        if !is_placeholder {
+            result.push('{');
+            rest = &after[1..];
+            continue;
+        }
+
+        let name = &after[1..1 + name_len];
+        if !PLACEHOLDERS.contains(&name) {
+            return Err(format!(
+                "placeholder inconnu '{{{}}}' (attendu : {})",
+                name,
+                PLACEHOLDERS.map(|p| format!("{{{}}}", p)).join(", ")
+            ));
+        }
+        if let Some(quote) = quote_at(template, template.len() - after.len()) {
+            return Err(format!(
+                "placeholder '{{{}}}' entre {} : sa valeur est déjà protégée pour le shell",
+                name,
+                if quote == '\'' {
+                    "apostrophes"
+                } else {
+                    "guillemets"
+                }
+            ));
+        }
+        let value =
+            value(name).ok_or_else(|| format!("{{{}}} indisponible dans ce contexte", name))?;
+        result.push_str(&shell_quote(&value));
+        rest = &after[name_len + 2..];
+    }
+
+    result.push_str(rest);
+    Ok(result)
+}
+
+/// Guillemet shell (`'` ou `"`) encore ouvert à la position `offset` du modèle.
+fn quote_at(template: &str, offset: usize) -> Option<char> {
+    let mut quote = None;
+    let mut chars = template[..offset].chars();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (None, '\\') | (Some('"'), '\\') => {
+                chars.next();
+            }
+            (None, '\'' | '"') => quote = Some(c),
+            (Some(open), _) if c == open => quote = None,
+            _ => {}
+        }
+    }
+    quote
+}
+
+/// Protège une valeur pour le shell (entre apostrophes).
+fn shell_quote(value: &str) -> String {
+    if !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:@+=,".contains(c))
+    {
+        return value.to_string();
+    }
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// Commande en mode popup en cours d'exécution.
+#[derive(Debug)]
+pub struct CommandTask {
+    /// Nom de la commande (titre du popup).
+    pub name: String,
+    /// Canal de réception de la sortie.
+    result: Receiver<Result<Output>>,
+}
+
+impl CommandTask {
+    /// Lance le script dans `sh`, depuis `dir`, dans un thread dédié.
+    pub fn spawn(name: String, script: String, dir: PathBuf) -> Result<Self> {
+        let (result_tx, result_rx) = mpsc::channel();
+        thread::Builder::new()
+            .name("git_sv-command".to_string())
+            .spawn(move || {
+                let output = shell::sh(&script)
+                    .current_dir(dir)
+                    .output()
+                    .map_err(|e| shell::spawn_error(e, "lancement de sh".to_string()));
+                let _ = result_tx.send(output);
+            })
+            .map_err(|e| GitSvError::Io {
+                source: e,
+                context: "démarrage du thread de commande".to_string(),
+            })?;
+
+        Ok(Self {
+            name,
+            result: result_rx,
+        })
+    }
+
+    /// Récupère la sortie si la commande est terminée, sans bloquer.
+    pub fn try_finish(&self) -> Option<Result<Output>> {
+        match self.result.try_recv() {
+            Ok(output) => Some(output),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err(GitSvError::Other(
+                "le thread de commande s'est arrêté".to_string(),
+            ))),
+        }
+    }
+}
+
+/// Commandes globales, chargées une fois au démarrage.
+static COMMANDS: OnceLock<CommandsFile> = OnceLock::new();
+
+/// Installe les commandes globales (sans effet si elles sont déjà initialisées).
+pub fn init(commands: CommandsFile) {
+    let _ = COMMANDS.set(commands);
+}
+
+/// Retourne les commandes personnalisées (aucune si non initialisées).
+pub fn current() -> &'static [CustomCommand] {
+    &COMMANDS.get_or_init(CommandsFile::default).command
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(name: &str) -> Option<String> {
+        match name {
+            "commit" => Some("abc1234".to_string()),
+            "file" => Some("docs/mon fichier.md".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_expand_placeholders() {
+        assert_eq!(
+            expand("deploy {commit} -- {file}", values).unwrap(),
+            "deploy abc1234 -- 'docs/mon fichier.md'"
+        );
+        // Les blocs shell ne sont pas des placeholders.
+        assert_eq!(
+            expand("echo ${HOME} | awk '{print $1}' {}", values).unwrap(),
+            "echo ${HOME} | awk '{print $1}' {}"
+        );
+    }
+
+    #[test]
+    fn test_expand_errors() {
+        let err = expand("open {ticket}", values).unwrap_err();
+        assert!(err.contains("placeholder inconnu '{ticket}'"), "{}", err);
+
+        let err = expand("checkout {branch}", values).unwrap_err();
+        assert!(err.contains("{branch} indisponible"), "{}", err);
+
+        let err = expand("echo '{file}'", values).unwrap_err();
+        assert!(err.contains("'{file}' entre apostrophes"), "{}", err);
+        let err = expand("git log \"{commit}\"", values).unwrap_err();
+        assert!(err.contains("'{commit}' entre guillemets"), "{}", err);
+
+        // Guillemets refermés ou échappés : le placeholder est hors guillemets.
+        assert_eq!(
+            expand("echo 'a' \\\" {commit} \"it's\" {commit}", values).unwrap(),
+            "echo 'a' \\\" abc1234 \"it's\" abc1234"
+        );
+    }
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("main"), "main");
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+        assert_eq!(shell_quote(""), "''");
+    }
+
+    #[test]
+    fn test_load_validates_commands() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("commands.toml");
+
+        std::fs::write(
+            &path,
+            "[[command]]\nname = \"deploy\"\nkey = \"D\"\ncontext = \"graph\"\ncommand = \"deploy {commit}\"\n",
+        )
+        .unwrap();
+        let file = CommandsFile::load_from_path(&path).unwrap();
+        assert_eq!(file.command.len(), 1);
+        assert_eq!(file.command[0].mode, CommandMode::Popup);
+
+        std::fs::write(
+            &path,
+            "[[command]]\nname = \"ticket\"\nkey = \"O\"\ncommand = \"open {ticket}\"\n",
+        )
+        .unwrap();
+        let message = CommandsFile::load_from_path(&path).unwrap_err().to_string();
+        assert!(message.contains("commande 'ticket'"), "{}", message);
+
+        std::fs::write(
+            &path,
+            "[[command]]\nname = \"show\"\nkey = \"S\"\ncommand = \"cat '{file}'\"\n",
+        )
+        .unwrap();
+        let message = CommandsFile::load_from_path(&path).unwrap_err().to_string();
+        assert!(message.contains("entre apostrophes"), "{}", message);
+    }
+}
@@ -0,0 +1,347 @@
+//! Configuration utilisateur chargée au démarrage.
+//!
+//! Le fichier `config.toml` est lu dans le répertoire de configuration XDG
+//! (`~/.config/git_sv/config.toml` sous Linux), puis surchargé par le fichier
+//! `git_sv.toml` du répertoire `.git/` du repository ouvert.
+//!
+//! ```toml
+//! commit_limit = 500
+//! diff_cache_size = 100
+//! poll_interval_ms = 1000
+//!
+//! [theme]
+//! base = "dark"        # "auto", "dark" ou "light"
+//! primary = "#ff8800" # nom de couleur, #rrggbb ou index 0-255
+//!
+//! [keys.graph]          # voir ui::keymap pour les contextes et les actions
+//! n = "move_down"
+//! "ctrl-n" = "page_down"
+//!
+//! [conventional_commits] # voir conventional pour les règles
+//! enabled = true
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use ratatui::style::Color;
+use serde::Deserialize;
+
+use crate::conventional::ConventionalConfig;
+use crate::error::{GitSvError, Result};
+use crate::ui::keymap::Keymap;
+use crate::ui::theme::Theme;
+
+/// Nom du fichier de configuration utilisateur.
+const USER_CONFIG_FILE: &str = "config.toml";
+/// Nom du fichier de surcharge propre au repository (dans `.git/`).
+const REPO_CONFIG_FILE: &str = "git_sv.toml";
+/// Intervalle de polling minimal accepté.
+const MIN_POLL_INTERVAL_MS: u64 = 100;
+
+/// Configuration de l'application.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Nombre de commits chargés par page dans le graphe.
+    pub commit_limit: usize,
+    /// Nombre de diffs conservés dans le cache.
+    pub diff_cache_size: usize,
+    /// Intervalle du polling de repli, sans surveillance native du repository (ms).
+    pub poll_interval_ms: u64,
+    /// Thème de couleurs.
+    pub theme: ThemeConfig,
+    /// Raccourcis par contexte : séquence de touches → nom d'action.
+    pub keys: BTreeMap<String, BTreeMap<String, String>>,
+    /// Règles de l'assistant Conventional Commits.
+    pub conventional_commits: ConventionalConfig,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            commit_limit: crate::state::MAX_COMMITS,
+            diff_cache_size: 50,
+            poll_interval_ms: 2000,
+            theme: ThemeConfig::default(),
+            keys: BTreeMap::new(),
+            conventional_commits: ConventionalConfig::default(),
+        }
+    }
+}
+
+impl Config {
+    /// Charge la configuration utilisateur puis la surcharge du repository.
+    pub fn load(git_dir: &Path) -> Result<Self> {
+        let mut paths = Vec::new();
+        if let Some(dir) = dirs::config_dir() {
+            paths.push(dir.join("git_sv").join(USER_CONFIG_FILE));
+        }
+        paths.push(git_dir.join(REPO_CONFIG_FILE));
+        Self::load_from_paths(&paths)
+    }
+
+    /// Charge et fusionne les fichiers existants parmi `paths` (les derniers l'emportent).
+    pub fn load_from_paths(paths: &[PathBuf]) -> Result<Self> {
+        let mut merged = toml::Table::new();
+
+        for path in paths.iter().filter(|p| p.is_file()) {
+            let invalid = |message: String| GitSvError::Config {
+                path: path.clone(),
+                message,
+            };
+            let content = std::fs::read_to_string(path).map_err(|e| invalid(e.to_string()))?;
+
+            // Validation du fichier seul, pour des erreurs localisées (ligne, clé).
+            let config: Config = toml::from_str(&content).map_err(|e| invalid(e.to_string()))?;
+            config.validate().map_err(invalid)?;
+
+            let table: toml::Table =
+                toml::from_str(&content).map_err(|e| invalid(e.to_string()))?;
+            merge_tables(&mut merged, table);
+        }
+
+        let path = paths.last().cloned().unwrap_or_default();
+        merged
+            .try_into()
+            .map_err(|e: toml::de::Error| GitSvError::Config {
+                path,
+                message: e.to_string(),
+            })
+    }
+
+    /// Vérifie les valeurs numériques et les raccourcis.
+    fn validate(&self) -> std::result::Result<(), String> {
+        if self.commit_limit == 0 {
+            return Err("commit_limit doit être supérieur à 0".to_string());
+        }
+        if self.diff_cache_size == 0 {
+            return Err("diff_cache_size doit être supérieur à 0".to_string());
+        }
+        if self.poll_interval_ms < MIN_POLL_INTERVAL_MS {
+            return Err(format!(
+                "poll_interval_ms doit être au moins {} (reçu {})",
+                MIN_POLL_INTERVAL_MS, self.poll_interval_ms
+            ));
+        }
+        Keymap::with_overrides(&self.keys)?;
+        self.conventional_commits.validate()?;
+        Ok(())
+    }
+
+    /// Intervalle de polling du watcher.
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.poll_interval_ms)
+    }
+}
+
+/// Fusionne récursivement `overlay` dans `base` (les valeurs d'`overlay` l'emportent).
+fn merge_tables(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(existing)), toml::Value::Table(table)) => {
+                merge_tables(existing, table);
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Thème de base sur lequel s'appliquent les couleurs personnalisées.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeBase {
+    /// Détection selon la luminosité du terminal.
+    #[default]
+    Auto,
+    /// Thème sombre.
+    Dark,
+    /// Thème clair.
+    Light,
+}
+
+/// Couleur lue depuis la configuration (nom, `#rrggbb` ou index 0-255).
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct ConfigColor(pub Color);
+
+impl TryFrom<String> for ConfigColor {
+    type Error = String;
+
+    fn try_from(value: String) -> std::result::Result<Self, Self::Error> {
+        Color::from_str(&value).map(ConfigColor).map_err(|_| {
+            format!(
+                "couleur invalide '{}' (attendu : nom, #rrggbb ou index 0-255)",
+                value
+            )
+        })
+    }
+}
+
+/// Thème personnalisé : thème de base et couleurs surchargées.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeConfig {
+    pub base: ThemeBase,
+    pub primary: Option<ConfigColor>,
+    pub secondary: Option<ConfigColor>,
+    pub selection_bg: Option<ConfigColor>,
+    pub selection_fg: Option<ConfigColor>,
+    pub border_inactive: Option<ConfigColor>,
+    pub border_active: Option<ConfigColor>,
+    pub status_bar_bg: Option<ConfigColor>,
+    pub status_bar_fg: Option<ConfigColor>,
+    pub error: Option<ConfigColor>,
+    pub success: Option<ConfigColor>,
+    pub warning: Option<ConfigColor>,
+    pub info: Option<ConfigColor>,
+    pub commit_hash: Option<ConfigColor>,
+    pub text_normal: Option<ConfigColor>,
+    pub text_secondary: Option<ConfigColor>,
+    pub background: Option<ConfigColor>,
+    pub ours_bg: Option<ConfigColor>,
+    pub theirs_bg: Option<ConfigColor>,
+}
+
+impl ThemeConfig {
+    /// Applique les couleurs personnalisées sur le thème donné.
+    pub fn apply(&self, mut theme: Theme) -> Theme {
+        macro_rules! override_colors {
+            ($($field:ident),* $(,)?) => {
+                $(
+                    if let Some(ConfigColor(color)) = self.$field {
+                        theme.$field = color;
+                    }
+                )*
+            };
+        }
+
+        override_colors!(
+            primary,
+            secondary,
+            selection_bg,
+            selection_fg,
+            border_inactive,
+            border_active,
+            status_bar_bg,
+            status_bar_fg,
+            error,
+            success,
+            warning,
+            info,
+            commit_hash,
+            text_normal,
+            text_secondary,
+            background,
+            ours_bg,
+            theirs_bg,
+        );
+        theme
+    }
+}
+
+/// Configuration globale, initialisée une fois au démarrage.
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Installe la configuration globale (sans effet si elle est déjà initialisée).
+pub fn init(config: Config) {
+    let _ = CONFIG.set(config);
+}
+
+/// Retourne la configuration courante (valeurs par défaut si non initialisée).
+pub fn current() -> &'static Config {
+    CONFIG.get_or_init(Config::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn write_config(dir: &Path, name: &str, content: &str) -> PathBuf {
+        let path = dir.join(name);
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_missing_files_give_defaults() {
+        let config = Config::load_from_paths(&[PathBuf::from("/nonexistent/git_sv.toml")]).unwrap();
+        assert_eq!(config.commit_limit, crate::state::MAX_COMMITS);
+        assert_eq!(config.poll_interval(), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_repo_config_overrides_user_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let user = write_config(
+            dir.path(),
+            "user.toml",
+            "commit_limit = 500\ndiff_cache_size = 10\n[theme]\nprimary = \"red\"\n",
+        );
+        let repo = write_config(
+            dir.path(),
+            "repo.toml",
+            "commit_limit = 50\n[theme]\nsecondary = \"#102030\"\n",
+        );
+
+        let config = Config::load_from_paths(&[user, repo]).unwrap();
+        assert_eq!(config.commit_limit, 50);
+        assert_eq!(config.diff_cache_size, 10);
+        assert_eq!(config.theme.primary, Some(ConfigColor(Color::Red)));
+        assert_eq!(
+            config.theme.secondary,
+            Some(ConfigColor(Color::Rgb(0x10, 0x20, 0x30)))
+        );
+    }
+
+    #[test]
+    fn test_unknown_key_is_reported_with_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_config(dir.path(), "config.toml", "commit_limt = 10\n");
+
+        let err = Config::load_from_paths(std::slice::from_ref(&path)).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("commit_limt"), "{}", message);
+        assert!(message.contains(&path.display().to_string()), "{}", message);
+    }
+
+    #[test]
+    fn test_invalid_values_are_reported() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let path = write_config(dir.path(), "color.toml", "[theme]\nprimary = \"bleu\"\n");
+        let message = Config::load_from_paths(&[path]).unwrap_err().to_string();
+        assert!(message.contains("couleur invalide 'bleu'"), "{}", message);
+
+        let path = write_config(dir.path(), "poll.toml", "poll_interval_ms = 10\n");
+        let message = Config::load_from_paths(&[path]).unwrap_err().to_string();
+        assert!(message.contains("poll_interval_ms"), "{}", message);
+
+        let path = write_config(
+            dir.path(),
+            "keys.toml",
+            "[keys.graph]\n\"ctrl-foo\" = \"quit\"\n",
+        );
+        let message = Config::load_from_paths(&[path]).unwrap_err().to_string();
+        assert!(
+            message.contains("touche invalide 'ctrl-foo'"),
+            "{}",
+            message
+        );
+    }
+
+    #[test]
+    fn test_keys_section_is_loaded() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_config(dir.path(), "keys.toml", "[keys.graph]\nn = \"move_down\"\n");
+        let config = Config::load_from_paths(&[path]).unwrap();
+
+        assert_eq!(config.keys["graph"]["n"], "move_down");
+    }
+}
@@ -0,0 +1,328 @@
+//! Assistant Conventional Commits : en-tête `type(scope)!: description`.
+//!
+//! Les règles se configurent dans la section `[conventional_commits]` de
+//! `config.toml` (ou `.git/git_sv.toml`) :
+//!
+//! ```toml
+//! [conventional_commits]
+//! enabled = true                    # valider le message avant le commit
+//! types = ["feat", "fix", "docs"]   # types autorisés
+//! scopes = ["ui", "git"]            # scopes proposés en plus de ceux de l'historique
+//! require_scope = false
+//! max_subject_length = 72
+//! require_breaking_footer = true    # "!" exige un footer "BREAKING CHANGE: ..."
+//! ticket_pattern = "[A-Z]+-[0-9]+"  # référence de ticket exigée dans le message
+//! ```
+
+use regex::Regex;
+use serde::Deserialize;
+
+/// Types proposés par défaut, avec leur description.
+pub const DEFAULT_TYPES: [(&str, &str); 11] = [
+    ("feat", "Nouvelle fonctionnalité"),
+    ("fix", "Correction de bug"),
+    ("docs", "Documentation"),
+    ("style", "Mise en forme, sans changement de code"),
+    (
+        "refactor",
+        "Restructuration sans changement de comportement",
+    ),
+    ("perf", "Amélioration des performances"),
+    ("test", "Ajout ou correction de tests"),
+    ("build", "Système de build, dépendances"),
+    ("ci", "Intégration continue"),
+    ("chore", "Maintenance"),
+    ("revert", "Annulation d'un commit"),
+];
+
+/// Footers signalant un changement cassant.
+const BREAKING_FOOTERS: [&str; 2] = ["BREAKING CHANGE:", "BREAKING-CHANGE:"];
+
+/// Règles de l'assistant Conventional Commits.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConventionalConfig {
+    /// Valider le message avant le commit.
+    pub enabled: bool,
+    /// Types autorisés.
+    pub types: Vec<String>,
+    /// Scopes proposés en plus de ceux appris de l'historique.
+    pub scopes: Vec<String>,
+    /// Scope obligatoire.
+    pub require_scope: bool,
+    /// Longueur maximale de l'en-tête.
+    pub max_subject_length: usize,
+    /// Un changement cassant (`!`) exige un footer `BREAKING CHANGE:`.
+    pub require_breaking_footer: bool,
+    /// Expression régulière d'une référence de ticket exigée dans le message.
+    pub ticket_pattern: Option<String>,
+}
+
+impl Default for ConventionalConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            types: DEFAULT_TYPES.iter().map(|(t, _)| t.to_string()).collect(),
+            scopes: Vec::new(),
+            require_scope: false,
+            max_subject_length: 72,
+            require_breaking_footer: false,
+            ticket_pattern: None,
+        }
+    }
+}
+
+impl ConventionalConfig {
+    /// Vérifie les règles (types non vides, motif de ticket valide).
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        if self.types.is_empty() {
+            return Err("conventional_commits.types ne peut pas être vide".to_string());
+        }
+        if self.max_subject_length == 0 {
+            return Err("conventional_commits.max_subject_length doit être supérieur à 0".into());
+        }
+        self.ticket_regex()?;
+        Ok(())
+    }
+
+    /// Expression régulière du ticket, si configurée.
+    fn ticket_regex(&self) -> std::result::Result<Option<Regex>, String> {
+        self.ticket_pattern
+            .as_deref()
+            .map(|pattern| {
+                Regex::new(pattern)
+                    .map_err(|e| format!("conventional_commits.ticket_pattern invalide : {}", e))
+            })
+            .transpose()
+    }
+}
+
+/// En-tête Conventional Commits décomposé.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Header {
+    /// Type (ex: "feat").
+    pub kind: String,
+    /// Scope entre parenthèses (ex: "ui").
+    pub scope: Option<String>,
+    /// Changement cassant (`!` avant les deux-points).
+    pub breaking: bool,
+    /// Description après `: `.
+    pub description: String,
+}
+
+/// Décompose un en-tête `type(scope)!: description` (None s'il n'a pas cette forme).
+pub fn parse_header(subject: &str) -> Option<Header> {
+    let (prefix, description) = subject.split_once(": ")?;
+    let (prefix, breaking) = match prefix.strip_suffix('!') {
+        Some(prefix) => (prefix, true),
+        None => (prefix, false),
+    };
+    let (kind, scope) = match prefix.split_once('(') {
+        Some((kind, scope)) => (kind, Some(scope.strip_suffix(')')?)),
+        None => (prefix, None),
+    };
+    let is_word = |s: &str| {
+        !s.is_empty()
+            && s.chars()
+                .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '/' | '.'))
+    };
+    if !is_word(kind) || scope.is_some_and(|s| !is_word(s)) {
+        return None;
+    }
+    Some(Header {
+        kind: kind.to_string(),
+        scope: scope.map(str::to_string),
+        breaking,
+        description: description.trim().to_string(),
+    })
+}
+
+/// Remplace le préfixe `type(scope)` du sujet, en conservant la description et le `!`.
+pub fn apply_header(subject: &str, kind: &str, scope: Option<&str>) -> String {
+    let (breaking, description) = match parse_header(subject) {
+        Some(header) => (header.breaking, header.description),
+        None => (false, subject.trim().to_string()),
+    };
+    let scope = scope
+        .filter(|s| !s.is_empty())
+        .map(|s| format!("({})", s))
+        .unwrap_or_default();
+    let bang = if breaking { "!" } else { "" };
+    format!("{}{}{}: {}", kind, scope, bang, description)
+}
+
+/// Scopes proposés : ceux de la configuration, puis ceux des sujets donnés,
+/// les plus fréquents d'abord.
+pub fn learn_scopes<'a>(
+    subjects: impl IntoIterator<Item = &'a str>,
+    configured: &[String],
+) -> Vec<String> {
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for scope in subjects.into_iter().filter_map(|s| parse_header(s)?.scope) {
+        match counts.iter_mut().find(|(s, _)| *s == scope) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((scope, 1)),
+        }
+    }
+    // Tri stable : à fréquence égale, le plus récent d'abord.
+    counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+
+    let mut scopes = configured.to_vec();
+    for (scope, _) in counts {
+        if !scopes.contains(&scope) {
+            scopes.push(scope);
+        }
+    }
+    scopes
+}
+
+/// Règles non respectées par le message, déjà nettoyé par `cleanup_message`
+/// (commentaires retirés avec le caractère du repository).
+pub fn check(message: &str, config: &ConventionalConfig) -> Vec<String> {
+    let lines: Vec<&str> = message.lines().map(str::trim_end).collect();
+    let subject = lines.first().copied().unwrap_or("");
+    let mut violations = Vec::new();
+
+    let length = subject.chars().count();
+    if length > config.max_subject_length {
+        violations.push(format!(
+            "En-tête trop long ({}/{})",
+            length, config.max_subject_length
+        ));
+    }
+
+    let Some(header) = parse_header(subject) else {
+        violations.push("En-tête attendu : type(scope): description".to_string());
+        return violations;
+    };
+    if !config.types.contains(&header.kind) {
+        violations.push(format!(
+            "Type « {} » non autorisé ({})",
+            header.kind,
+            config.types.join(", ")
+        ));
+    }
+    if config.require_scope && header.scope.is_none() {
+        violations.push("Scope requis : type(scope): description".to_string());
+    }
+    if header.description.is_empty() {
+        violations.push("Description manquante après « : »".to_string());
+    }
+    if lines.get(1).is_some_and(|l| !l.is_empty()) {
+        violations.push("Ligne vide requise après l'en-tête".to_string());
+    }
+
+    let body = &lines[1.min(lines.len())..];
+    let has_breaking_footer = body
+        .iter()
+        .any(|l| BREAKING_FOOTERS.iter().any(|f| l.starts_with(f)));
+    if let Some(line) = body.iter().find(|l| {
+        BREAKING_FOOTERS
+            .iter()
+            .any(|f| l.to_uppercase().starts_with(f) && !l.starts_with(f))
+    }) {
+        violations.push(format!("Footer à écrire en majuscules : « {} »", line));
+    }
+    if header.breaking && config.require_breaking_footer && !has_breaking_footer {
+        violations.push("Changement cassant (!) : footer « BREAKING CHANGE: » requis".to_string());
+    }
+
+    if let Ok(Some(ticket)) = config.ticket_regex() {
+        if !lines.iter().any(|l| ticket.is_match(l)) {
+            violations.push(format!("Référence de ticket requise ({})", ticket.as_str()));
+        }
+    }
+    violations
+}
+
+/// Description d'un type connu (vide pour un type ajouté par la configuration).
+pub fn type_description(kind: &str) -> &'static str {
+    DEFAULT_TYPES
+        .iter()
+        .find(|(t, _)| *t == kind)
+        .map_or("", |(_, description)| description)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_apply_header() {
+        let header = parse_header("feat(ui)!: ajoute le picker").unwrap();
+        assert_eq!(header.kind, "feat");
+        assert_eq!(header.scope.as_deref(), Some("ui"));
+        assert!(header.breaking);
+        assert_eq!(header.description, "ajoute le picker");
+        assert_eq!(parse_header("Ajoute le picker"), None);
+        assert_eq!(parse_header("feat(ui: oups"), None);
+
+        // Le type et le scope sont remplacés, la description et le "!" conservés
+        assert_eq!(
+            apply_header("feat(ui)!: ajoute le picker", "fix", None),
+            "fix!: ajoute le picker"
+        );
+        assert_eq!(
+            apply_header("Corrige le rendu", "fix", Some("ui")),
+            "fix(ui): Corrige le rendu"
+        );
+    }
+
+    #[test]
+    fn test_learn_scopes_by_frequency() {
+        let subjects = [
+            "fix(git): corrige le push",
+            "feat(ui): ajoute un popup",
+            "Merge branch 'main'",
+            "feat(git): ajoute le pull",
+        ];
+        let scopes = learn_scopes(subjects, &["docs".to_string()]);
+        assert_eq!(scopes, ["docs", "git", "ui"]);
+    }
+
+    #[test]
+    fn test_check_rules() {
+        let config = ConventionalConfig {
+            enabled: true,
+            require_scope: true,
+            require_breaking_footer: true,
+            ticket_pattern: Some("[A-Z]+-[0-9]+".to_string()),
+            ..ConventionalConfig::default()
+        };
+        assert!(config.validate().is_ok());
+
+        let valid =
+            "feat(ui)!: ajoute le picker\n\nRefs: GSV-12\nBREAKING CHANGE: raccourci modifié";
+        assert!(check(valid, &config).is_empty());
+
+        let violations = check("wip: ajoute le picker\nsuite", &config);
+        assert_eq!(
+            violations,
+            [
+                "Type « wip » non autorisé (feat, fix, docs, style, refactor, perf, test, build, ci, chore, revert)",
+                "Scope requis : type(scope): description",
+                "Ligne vide requise après l'en-tête",
+                "Référence de ticket requise ([A-Z]+-[0-9]+)",
+            ]
+        );
+
+        let violations = check("fix(ui)!: GSV-1\n\nbreaking change: oups", &config);
+        assert_eq!(
+            violations,
+            [
+                "Footer à écrire en majuscules : « breaking change: oups »",
+                "Changement cassant (!) : footer « BREAKING CHANGE: » requis",
+            ]
+        );
+        assert_eq!(
+            check("Ajoute le picker", &config),
+            ["En-tête attendu : type(scope): description"]
+        );
+
+        let invalid = ConventionalConfig {
+            ticket_pattern: Some("[".to_string()),
+            ..ConventionalConfig::default()
+        };
+        assert!(invalid.validate().is_err());
+    }
+}
@@ -19,10 +19,6 @@ pub enum GitSvError {
     #[error("Erreur clipboard : {0}")]
     Clipboard(String),
 
-    /// Repository non trouvé ou invalide
-    #[error("Repository git non trouvé dans {path}")]
-    RepoNotFound { path: PathBuf },
-
     /// Opération git échouée avec contexte
     #[error("Opération '{operation}' échouée: {details}")]
     OperationFailed {
@@ -30,21 +26,29 @@ pub enum GitSvError {
         details: String,
     },
 
-    /// Branche non trouvée
-    #[error("Branche '{name}' non trouvée")]
-    BranchNotFound { name: String },
+    /// Push refusé par le remote (non fast-forward, lease périmé)
+    #[error("Push de '{branch}' refusé par {remote}: {reason}")]
+    PushRejected {
+        branch: String,
+        remote: String,
+        reason: String,
+    },
+
+    /// Hook git terminé en erreur (l'opération est annulée)
+    #[error("Hook '{hook}' en échec ({status})")]
+    HookFailed {
+        hook: String,
+        status: String,
+        output: String,
+    },
 
     /// Fichier non trouvé
     #[error("Fichier '{path}' non trouvé")]
     FileNotFound { path: String },
 
-    /// État invalide de l'application
-    #[error("État invalide: {0}")]
-    InvalidState(String),
-
-    /// Index hors limites
-    #[error("Index {index} hors limites (max: {max})")]
-    IndexOutOfBounds { index: usize, max: usize },
+    /// Fichier de configuration invalide
+    #[error("Configuration invalide ({}): {message}", path.display())]
+    Config { path: PathBuf, message: String },
 
     /// Erreur générique
     #[error("{0}")]
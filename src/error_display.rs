@@ -6,15 +6,20 @@ pub fn format_error_message(err: &GitSvError) -> String {
         GitSvError::Git(e) => format!("❌ Git: {}", e),
         GitSvError::Io { context, source } => format!("❌ I/O ({}): {}", context, source),
         GitSvError::Clipboard(msg) => format!("❌ Presse-papier: {}", msg),
-        GitSvError::RepoNotFound { path } => format!("❌ Repo non trouvé: {}", path.display()),
         GitSvError::OperationFailed { operation, details } => {
             format!("❌ {} échoué: {}", operation, details)
         }
-        GitSvError::BranchNotFound { name } => format!("❌ Branche '{}' non trouvée", name),
+        GitSvError::PushRejected {
+            branch,
+            remote,
+            reason,
+        } => format!("❌ Push de '{}' refusé par {}: {}", branch, remote, reason),
+        GitSvError::HookFailed { hook, status, .. } => {
+            format!("❌ Hook '{}' en échec ({})", hook, status)
+        }
         GitSvError::FileNotFound { path } => format!("❌ Fichier '{}' non trouvé", path),
-        GitSvError::InvalidState(msg) => format!("❌ État invalide: {}", msg),
-        GitSvError::IndexOutOfBounds { index, max } => {
-            format!("❌ Index {} hors limites (max: {})", index, max)
+        GitSvError::Config { path, message } => {
+            format!("❌ Configuration {}: {}", path.display(), message)
         }
         GitSvError::Other(msg) => format!("❌ {}", msg),
     }
@@ -1,4 +1,4 @@
-use git2::{Branch, BranchType, Repository};
+use git2::{Branch, BranchType, Oid, Repository};
 use std::time::SystemTime;
 
 use crate::error::Result;
@@ -156,6 +156,13 @@ pub fn create_branch(repo: &Repository, name: &str) -> Result<()> {
     Ok(())
 }
 
+/// Crée une nouvelle branche sur le commit donné.
+pub fn create_branch_at(repo: &Repository, name: &str, oid: Oid) -> Result<()> {
+    let commit = repo.find_commit(oid)?;
+    repo.branch(name, &commit, false)?;
+    Ok(())
+}
+
 /// Checkout une branche existante.
 pub fn checkout_branch(repo: &Repository, name: &str) -> Result<()> {
     let refname = format!("refs/heads/{}", name);
@@ -248,6 +255,18 @@ mod tests {
         assert!(!new_branch.is_remote);
     }
 
+    #[test]
+    fn test_create_branch_at() {
+        let (_temp_dir, repo) = create_test_repo();
+        let first = commit_file(&repo, "test.txt", "v1", "First");
+        commit_file(&repo, "test.txt", "v2", "Second");
+
+        create_branch_at(&repo, "rescue", first).unwrap();
+
+        let branch = repo.find_branch("rescue", BranchType::Local).unwrap();
+        assert_eq!(branch.get().target(), Some(first));
+    }
+
     #[test]
     fn test_checkout_branch() {
         let (_temp_dir, repo) = create_test_repo();
@@ -1,6 +1,10 @@
 use git2::{Oid, Repository, Signature};
 
-use crate::error::Result;
+use crate::error::{GitSvError, Result};
+use crate::git::hooks::{self, HookRun};
+use crate::git::repo::StatusEntry;
+use crate::git::signing::{reflog_message, update_reference, write_commit};
+use crate::utils::shell;
 
 /// Informations essentielles d'un commit.
 #[derive(Debug, Clone)]
@@ -38,13 +42,24 @@ impl CommitInfo {
     }
 }
 
-/// Crée un commit avec le message donné sur l'index courant.
-pub fn create_commit(repo: &Repository, message: &str) -> Result<Oid> {
+/// Crée un commit sur l'index courant en lançant les hooks (pre-commit, commit-msg, post-commit...).
+///
+/// La sortie des hooks est relevée dans `hooks` ; un hook en échec annule le commit.
+pub fn create_commit_with_hooks(
+    repo: &Repository,
+    message: &str,
+    hooks: &mut HookRun,
+) -> Result<Oid> {
+    let message = hooks::prepare_commit(repo, message, &["message"], hooks)?;
+    let message = message.as_str();
+
     let sig = repo
         .signature()
         .or_else(|_| Signature::now("git_sv", "git_sv@local"))?;
 
+    // Le pre-commit a pu modifier l'index (formatteur qui restage les fichiers).
     let mut index = repo.index()?;
+    index.read(false)?;
     let tree_oid = index.write_tree()?;
     let tree = repo.find_tree(tree_oid)?;
 
@@ -60,38 +75,180 @@ pub fn create_commit(repo: &Repository, message: &str) -> Result<Oid> {
 
     let parents: Vec<&git2::Commit> = parent_commit.iter().collect();
 
-    let oid = repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)?;
+    let oid = write_commit(repo, Some("HEAD"), &sig, &sig, message, &tree, &parents)?;
+    hooks.notify(repo, "post-commit", &[]);
 
     Ok(oid)
 }
 
-/// Amende le dernier commit avec le message donné et l'index courant.
-pub fn amend_commit(repo: &Repository, message: &str) -> Result<Oid> {
-    let _sig = repo
-        .signature()
-        .or_else(|_| Signature::now("git_sv", "git_sv@local"))?;
+/// Amende le dernier commit avec l'index courant, en lançant les hooks comme
+/// [`create_commit_with_hooks`].
+///
+/// L'auteur et le committer du commit d'origine sont conservés.
+pub fn amend_commit_with_hooks(
+    repo: &Repository,
+    message: &str,
+    hooks: &mut HookRun,
+) -> Result<Oid> {
+    let message = hooks::prepare_commit(repo, message, &["commit", "HEAD"], hooks)?;
+    let message = message.as_str();
 
     let mut index = repo.index()?;
+    index.read(false)?;
     let tree_oid = index.write_tree()?;
     let tree = repo.find_tree(tree_oid)?;
 
     // Récupérer le commit HEAD
     let head = repo.head()?;
     let head_commit = head.peel_to_commit()?;
-
-    // Amender le commit (remplace HEAD)
-    let oid = head_commit.amend(
-        Some("HEAD"),  // Mettre à jour HEAD
-        None,          // Ne pas changer l'auteur
-        None,          // Ne pas changer le committer
-        None,          // Ne pas changer l'encodage
-        Some(message), // Nouveau message
-        Some(&tree),   // Nouveau tree
+    let parents: Vec<git2::Commit> = head_commit.parents().collect();
+    let parents: Vec<&git2::Commit> = parents.iter().collect();
+
+    // Créer le nouveau commit (signé si configuré), puis remplacer HEAD
+    let oid = write_commit(
+        repo,
+        None,
+        &head_commit.author(),
+        &head_commit.committer(),
+        message,
+        &tree,
+        &parents,
+    )?;
+    update_reference(
+        repo,
+        "HEAD",
+        oid,
+        Some(head_commit.id()),
+        &reflog_message("commit (amend)", message),
     )?;
+    hooks.notify(repo, "post-commit", &[]);
 
     Ok(oid)
 }
 
+/// Modèle de message de commit (`commit.template`), s'il est configuré et lisible.
+///
+/// Un chemin relatif l'est à la racine de l'arbre de travail.
+pub fn commit_template(repo: &Repository) -> Option<String> {
+    let path = repo.config().ok()?.get_path("commit.template").ok()?;
+    let path = match repo.workdir() {
+        Some(workdir) if path.is_relative() => workdir.join(path),
+        _ => path,
+    };
+    std::fs::read_to_string(path).ok()
+}
+
+/// Caractère des lignes de commentaire (`core.commentChar`, `#` par défaut).
+///
+/// Seul un caractère ASCII est pris en compte ; `auto` revient au défaut.
+pub fn comment_char(repo: &Repository) -> char {
+    repo.config()
+        .and_then(|config| config.get_string("core.commentChar"))
+        .ok()
+        .filter(|value| value != "auto")
+        .and_then(|value| value.chars().next())
+        .filter(char::is_ascii)
+        .unwrap_or('#')
+}
+
+/// Nettoie un message de commit comme `git commit` : les blancs en fin de ligne
+/// et les lignes vides superflues sont retirés.
+///
+/// Les lignes commençant par `comment` ne sont retirées que s'il est donné : un
+/// message saisi directement (comme `git commit -m`) garde ses lignes `#123 ...`.
+pub fn cleanup_message(message: &str, comment: Option<char>) -> Result<String> {
+    let comment = comment.filter(char::is_ascii).map(|c| c as u8);
+    Ok(git2::message_prettify(message, comment)?)
+}
+
+/// Éditeur de message configuré, dans l'ordre de git : `GIT_EDITOR`,
+/// `core.editor`, `VISUAL`, `EDITOR`, puis `vi`.
+pub fn editor_command(repo: &Repository) -> String {
+    let core_editor = repo
+        .config()
+        .and_then(|config| config.get_string("core.editor"))
+        .ok();
+    [
+        std::env::var("GIT_EDITOR").ok(),
+        core_editor,
+        std::env::var("VISUAL").ok(),
+        std::env::var("EDITOR").ok(),
+    ]
+    .into_iter()
+    .flatten()
+    .find(|editor| !editor.trim().is_empty())
+    .unwrap_or_else(|| "vi".to_string())
+}
+
+/// Contenu de `COMMIT_EDITMSG` : le message suivi d'un résumé des fichiers
+/// stagés en lignes de commentaire `comment`, comme `git commit`.
+pub fn commit_editmsg(
+    message: &str,
+    branch: Option<&str>,
+    staged: &[StatusEntry],
+    comment: char,
+) -> String {
+    let mut contents = message.trim_end().to_string();
+    contents.push_str(&format!(
+        "\n\n{c} Saisissez le message de commit. Les lignes commençant par '{c}' sont\n\
+         {c} ignorées, et un message vide annule le commit.\n{c}\n",
+        c = comment
+    ));
+    if let Some(branch) = branch {
+        contents.push_str(&format!("{} Sur la branche {}\n", comment, branch));
+    }
+    contents.push_str(&format!(
+        "{} Modifications qui seront validées :\n",
+        comment
+    ));
+    for entry in staged {
+        let label = if entry.status.contains(git2::Status::INDEX_NEW) {
+            "nouveau fichier :"
+        } else if entry.status.contains(git2::Status::INDEX_DELETED) {
+            "supprimé :"
+        } else if entry.status.contains(git2::Status::INDEX_RENAMED) {
+            "renommé :"
+        } else {
+            "modifié :"
+        };
+        contents.push_str(&format!("{}\t{:<18}{}\n", comment, label, entry.path));
+    }
+    contents
+}
+
+/// Ouvre `contents` dans l'éditeur sur `COMMIT_EDITMSG` et retourne le message
+/// saisi, nettoyé de ses commentaires.
+///
+/// L'éditeur, lancé par `sh`, occupe le terminal : la TUI doit être suspendue
+/// pendant l'appel.
+pub fn edit_message(repo: &Repository, editor: &str, contents: &str) -> Result<String> {
+    let path = repo.path().join("COMMIT_EDITMSG");
+    std::fs::write(&path, contents).map_err(|e| GitSvError::Io {
+        source: e,
+        context: "écriture de COMMIT_EDITMSG".to_string(),
+    })?;
+
+    // Comme git, la commande de l'éditeur peut contenir des arguments.
+    let status = shell::sh(&format!("{} \"$@\"", editor))
+        .arg(editor)
+        .arg(&path)
+        .current_dir(repo.workdir().unwrap_or_else(|| repo.path()))
+        .status()
+        .map_err(|e| shell::spawn_error(e, format!("lancement de l'éditeur {}", editor)))?;
+    if !status.success() {
+        return Err(GitSvError::OperationFailed {
+            operation: "éditeur",
+            details: format!("{} ({})", editor, status),
+        });
+    }
+
+    let edited = std::fs::read_to_string(&path).map_err(|e| GitSvError::Io {
+        source: e,
+        context: "lecture de COMMIT_EDITMSG".to_string(),
+    })?;
+    cleanup_message(&edited, Some(comment_char(repo)))
+}
+
 /// Stage un fichier dans l'index.
 pub fn stage_file(repo: &Repository, path: &str) -> Result<()> {
     let mut index = repo.index()?;
@@ -158,7 +315,15 @@ pub fn cherry_pick(repo: &Repository, commit_oid: Oid) -> Result<()> {
         commit_oid
     );
 
-    repo.commit(Some("HEAD"), &sig, &sig, &message, &tree, &[&parent_commit])?;
+    write_commit(
+        repo,
+        Some("HEAD"),
+        &sig,
+        &sig,
+        &message,
+        &tree,
+        &[&parent_commit],
+    )?;
 
     // Nettoyer l'état de cherry-pick
     repo.cleanup_state()?;
@@ -203,7 +368,15 @@ pub fn cherry_pick_with_result(
         commit_oid
     );
 
-    repo.commit(Some("HEAD"), &sig, &sig, &message, &tree, &[&parent_commit])?;
+    write_commit(
+        repo,
+        Some("HEAD"),
+        &sig,
+        &sig,
+        &message,
+        &tree,
+        &[&parent_commit],
+    )?;
 
     // Nettoyer l'état de cherry-pick
     repo.cleanup_state()?;
@@ -211,6 +384,93 @@ pub fn cherry_pick_with_result(
     Ok(MergeResult::Success)
 }
 
+/// Message standard d'un revert.
+///
+/// Pour un commit de merge, `mainline` (1-based) désigne le parent conservé.
+pub fn revert_message(commit: &git2::Commit, mainline: Option<u32>) -> String {
+    let mut message = format!(
+        "Revert \"{}\"\n\nThis reverts commit {}",
+        commit.summary().unwrap_or(""),
+        commit.id()
+    );
+    match mainline.and_then(|m| commit.parent_id(m as usize - 1).ok()) {
+        Some(parent) => message.push_str(&format!(",\nreversing changes made to {}.\n", parent)),
+        None => message.push_str(".\n"),
+    }
+    message
+}
+
+/// Revert avec résultat typé pour gérer les conflits.
+///
+/// `mainline` (1-based) est obligatoire pour un commit de merge.
+pub fn revert_with_result(
+    repo: &Repository,
+    commit_oid: Oid,
+    mainline: Option<u32>,
+) -> crate::error::Result<crate::git::conflict::MergeResult> {
+    use crate::error::GitSvError;
+    use crate::git::conflict::{list_conflict_files, MergeResult};
+
+    let commit = repo.find_commit(commit_oid)?;
+
+    let mut opts = git2::RevertOptions::new();
+    if commit.parent_count() > 1 {
+        match mainline {
+            Some(m) if (m as usize) <= commit.parent_count() && m > 0 => {
+                opts.mainline(m);
+            }
+            _ => {
+                return Err(GitSvError::Other(
+                    "Commit de merge : choisissez le parent principal (mainline)".to_string(),
+                ))
+            }
+        }
+    }
+    let mainline = (commit.parent_count() > 1).then_some(mainline).flatten();
+    let message = revert_message(&commit, mainline);
+
+    // Effectuer le revert
+    repo.revert(&commit, Some(&mut opts))?;
+
+    // Vérifier s'il y a des conflits
+    let mut index = repo.index()?;
+    if index.has_conflicts() {
+        // Préparer le message utilisé à la finalisation
+        std::fs::write(repo.path().join("MERGE_MSG"), &message).map_err(|e| GitSvError::Io {
+            source: e,
+            context: "écriture de MERGE_MSG".to_string(),
+        })?;
+        let conflict_files = list_conflict_files(repo)?;
+        return Ok(MergeResult::Conflicts(conflict_files));
+    }
+
+    // Si pas de conflits, créer le commit
+    let sig = repo
+        .signature()
+        .or_else(|_| Signature::now("git_sv", "git_sv@local"))?;
+
+    let tree_oid = index.write_tree()?;
+    let tree = repo.find_tree(tree_oid)?;
+
+    let head = repo.head()?;
+    let parent_commit = head.peel_to_commit()?;
+
+    write_commit(
+        repo,
+        Some("HEAD"),
+        &sig,
+        &sig,
+        &message,
+        &tree,
+        &[&parent_commit],
+    )?;
+
+    // Nettoyer l'état de revert
+    repo.cleanup_state()?;
+
+    Ok(MergeResult::Success)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -325,10 +585,177 @@ mod tests {
         index.write().unwrap();
 
         // Créer un commit
-        let oid = create_commit(&repo, "My commit message").unwrap();
+        let oid =
+            create_commit_with_hooks(&repo, "My commit message", &mut HookRun::default()).unwrap();
 
         // Vérifier que le commit existe
         let commit = repo.find_commit(oid).unwrap();
         assert_eq!(commit.summary().unwrap(), "My commit message");
     }
+
+    #[test]
+    fn test_commit_template_and_cleanup() {
+        let (dir, repo) = create_test_repo();
+        assert_eq!(commit_template(&repo), None);
+
+        let template = "\n\n# Pourquoi ce changement ?\n";
+        std::fs::write(dir.path().join(".gitmessage"), template).unwrap();
+        repo.config()
+            .unwrap()
+            .set_str("commit.template", ".gitmessage")
+            .unwrap();
+        assert_eq!(commit_template(&repo).as_deref(), Some(template));
+
+        let message = "Sujet  \n\n\n# Pourquoi ce changement ?\nParce que.\n\n";
+        assert_eq!(
+            cleanup_message(message, Some('#')).unwrap(),
+            "Sujet\n\nParce que.\n"
+        );
+        assert_eq!(cleanup_message(template, Some('#')).unwrap(), "");
+    }
+
+    #[test]
+    fn test_cleanup_message_keeps_hash_lines_without_comments() {
+        let message = "#123 Corrige le crash  \n\n# Détails\n";
+        assert_eq!(
+            cleanup_message(message, None).unwrap(),
+            "#123 Corrige le crash\n\n# Détails\n"
+        );
+    }
+
+    #[test]
+    fn test_comment_char_from_config() {
+        let (_dir, repo) = create_test_repo();
+        assert_eq!(comment_char(&repo), '#');
+
+        repo.config()
+            .unwrap()
+            .set_str("core.commentChar", ";")
+            .unwrap();
+        assert_eq!(comment_char(&repo), ';');
+        assert_eq!(
+            cleanup_message("Sujet\n; note\n#123\n", Some(comment_char(&repo))).unwrap(),
+            "Sujet\n#123\n"
+        );
+
+        repo.config()
+            .unwrap()
+            .set_str("core.commentChar", "auto")
+            .unwrap();
+        assert_eq!(comment_char(&repo), '#');
+    }
+
+    #[test]
+    fn test_edit_message_in_external_editor() {
+        let (_dir, repo) = create_test_repo();
+        let staged = [
+            StatusEntry {
+                path: "a.txt".to_string(),
+                status: git2::Status::INDEX_MODIFIED,
+            },
+            StatusEntry {
+                path: "b.txt".to_string(),
+                status: git2::Status::INDEX_NEW,
+            },
+        ];
+        let contents = commit_editmsg("Sujet", Some("main"), &staged, '#');
+        assert!(contents.starts_with("Sujet\n\n# "));
+        assert!(contents.contains("# Sur la branche main\n"));
+        assert!(contents.contains("#\tmodifié :         a.txt\n"));
+        assert!(contents.contains("#\tnouveau fichier : b.txt\n"));
+
+        // L'éditeur (avec ses arguments) réécrit le message ; les commentaires sont retirés
+        let editor = r#"sh -c 'printf "Sujet\n\nCorps du message\n# Note\n" > "$1"' editor"#;
+        let message = edit_message(&repo, editor, &contents).unwrap();
+        assert_eq!(message, "Sujet\n\nCorps du message\n");
+
+        let err = edit_message(&repo, "false", &contents).unwrap_err();
+        assert!(matches!(
+            err,
+            GitSvError::OperationFailed {
+                operation: "éditeur",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_revert_with_result_creates_revert_commit() {
+        let (_temp_dir, repo) = create_test_repo();
+        commit_file(&repo, "test.txt", "v1\n", "Initial");
+        let target = commit_file(&repo, "test.txt", "v2\n", "Change");
+
+        let result = revert_with_result(&repo, target, None).unwrap();
+        assert!(matches!(result, crate::git::conflict::MergeResult::Success));
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.summary(), Some("Revert \"Change\""));
+        assert!(head.message().unwrap().contains(&target.to_string()));
+        let content = std::fs::read_to_string(repo.workdir().unwrap().join("test.txt")).unwrap();
+        assert_eq!(content, "v1\n");
+    }
+
+    #[test]
+    fn test_revert_merge_requires_mainline() {
+        let (_temp_dir, repo) = create_test_repo();
+        let base = commit_file(&repo, "a.txt", "a\n", "Base");
+        let ours = commit_file(&repo, "a.txt", "a\n", "Ours");
+        let ours = repo.find_commit(ours).unwrap();
+
+        // Branche "feature" ajoutant un fichier, puis merge dans main
+        let base_commit = repo.find_commit(base).unwrap();
+        repo.branch("feature", &base_commit, false).unwrap();
+        repo.set_head("refs/heads/feature").unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+        let theirs = commit_file(&repo, "b.txt", "b\n", "Feature");
+        let theirs = repo.find_commit(theirs).unwrap();
+        repo.set_head("refs/heads/main").unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+
+        let mut index = repo.merge_commits(&ours, &theirs, None).unwrap();
+        let tree = repo.find_tree(index.write_tree_to(&repo).unwrap()).unwrap();
+        let sig = repo.signature().unwrap();
+        let merge = repo
+            .commit(
+                Some("HEAD"),
+                &sig,
+                &sig,
+                "Merge feature",
+                &tree,
+                &[&ours, &theirs],
+            )
+            .unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+
+        assert!(revert_with_result(&repo, merge, None).is_err());
+
+        revert_with_result(&repo, merge, Some(1)).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert!(head
+            .message()
+            .unwrap()
+            .contains(&format!("reversing changes made to {}", ours.id())));
+        assert!(!repo.workdir().unwrap().join("b.txt").exists());
+    }
+
+    #[test]
+    fn test_revert_conflict_prepares_message() {
+        let (_temp_dir, repo) = create_test_repo();
+        commit_file(&repo, "test.txt", "v1\n", "Initial");
+        let target = commit_file(&repo, "test.txt", "v2\n", "Change");
+        commit_file(&repo, "test.txt", "v3\n", "Later");
+
+        let result = revert_with_result(&repo, target, None).unwrap();
+        assert!(matches!(
+            result,
+            crate::git::conflict::MergeResult::Conflicts(_)
+        ));
+
+        let message = crate::git::conflict::prepared_message(&repo).unwrap();
+        assert!(message.starts_with("Revert \"Change\""));
+        assert!(message.contains(&target.to_string()));
+    }
 }
@@ -4,6 +4,7 @@ use std::io::Write;
 use std::path::Path;
 
 use crate::error::{GitSvError, Result};
+use crate::git::signing::write_commit;
 
 /// Source d'une ligne dans le résultat résolu.
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -235,6 +236,13 @@ fn read_file_lines(path: &str) -> Result<Vec<String>> {
     Ok(content.lines().map(|l| l.to_string()).collect())
 }
 
+/// Chemin d'un fichier du repository relatif au répertoire de travail.
+fn workdir_path(repo: &Repository, path: &str) -> String {
+    repo.workdir()
+        .map(|dir| dir.join(path).to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.to_string())
+}
+
 /// Liste tous les fichiers en conflit dans le repository.
 pub fn list_conflict_files(repo: &Repository) -> Result<Vec<ConflictFile>> {
     let index = repo
@@ -288,7 +296,7 @@ pub fn list_conflict_files(repo: &Repository) -> Result<Vec<ConflictFile>> {
         let sections = match conflict_type {
             ConflictType::BothModified | ConflictType::BothAdded => {
                 // Parser les marqueurs de conflit dans le fichier
-                parse_conflict_file(&path)?
+                parse_conflict_file(&workdir_path(repo, &path))?
             }
             ConflictType::DeletedByUs => {
                 // Le fichier n'existe pas en local (supprimé par nous), lire depuis theirs
@@ -309,7 +317,7 @@ pub fn list_conflict_files(repo: &Repository) -> Result<Vec<ConflictFile>> {
             }
             ConflictType::DeletedByThem => {
                 // Le fichier existe en local (nous l'avons gardé), theirs est vide
-                let ours_content = read_file_lines(&path).unwrap_or_default();
+                let ours_content = read_file_lines(&workdir_path(repo, &path)).unwrap_or_default();
                 vec![ConflictSection {
                     context_before: vec![],
                     ours: ours_content.clone(),
@@ -541,6 +549,17 @@ pub fn abort_merge(repo: &Repository) -> Result<()> {
     Ok(())
 }
 
+/// Message préparé pour l'opération en cours (MERGE_MSG), sans les commentaires.
+pub fn prepared_message(repo: &Repository) -> Option<String> {
+    let content = std::fs::read_to_string(repo.path().join("MERGE_MSG")).ok()?;
+    let message: Vec<&str> = content
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .collect();
+    let message = message.join("\n").trim().to_string();
+    (!message.is_empty()).then_some(message)
+}
+
 /// Finalise le merge en créant le commit de merge.
 pub fn finalize_merge(repo: &Repository, message: &str) -> Result<()> {
     // Obtenir l'index pour vérification
@@ -599,7 +618,8 @@ pub fn finalize_merge(repo: &Repository, message: &str) -> Result<()> {
 
     // Créer le commit
     let _commit_oid = if let Some(ref merge_commit) = merge_commit {
-        repo.commit(
+        write_commit(
+            repo,
             Some("HEAD"),
             &signature,
             &signature,
@@ -608,7 +628,8 @@ pub fn finalize_merge(repo: &Repository, message: &str) -> Result<()> {
             &[&head_commit, merge_commit],
         )
     } else {
-        repo.commit(
+        write_commit(
+            repo,
             Some("HEAD"),
             &signature,
             &signature,
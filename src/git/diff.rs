@@ -55,7 +55,7 @@ pub enum DiffLineType {
 }
 
 /// Ligne individuelle d'un diff.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct DiffLine {
     /// Type de la ligne.
     pub line_type: DiffLineType,
@@ -155,7 +155,8 @@ fn count_patch_lines(patch: &git2::Patch) -> (usize, usize) {
 
 /// Extrait les lignes d'un patch pour un fichier donné.
 ///
-/// Cette fonction factorise la logique commune entre get_file_diff() et working_dir_file_diff().
+/// Cette fonction factorise la logique commune entre get_file_diff(),
+/// unstaged_file_diff() et staged_file_diff().
 fn extract_diff_lines(patch: &git2::Patch) -> (Vec<DiffLine>, usize, usize) {
     let mut lines = Vec::new();
     let mut additions = 0;
@@ -238,33 +239,50 @@ pub fn get_file_diff(repo: &Repository, oid: Oid, file_path: &str) -> Result<Fil
     find_and_extract_file_diff(&diff, file_path, "Fichier non trouvé dans le commit")
 }
 
-/// Récupère le diff d'un fichier du working directory (non committé).
-pub fn working_dir_file_diff(repo: &Repository, file_path: &str) -> Result<FileDiff> {
-    let head = repo.head()?;
-    let head_oid = head
-        .target()
-        .ok_or_else(|| git2::Error::from_str("HEAD ne pointe pas vers un commit"))?;
-    let head_commit = repo.find_commit(head_oid)?;
-    let head_tree = head_commit.tree()?;
+/// Récupère le diff non stagé d'un fichier (index → working directory).
+///
+/// Les fichiers non suivis sont inclus avec leur contenu complet.
+pub fn unstaged_file_diff(repo: &Repository, file_path: &str) -> Result<FileDiff> {
+    let mut opts = unstaged_diff_options(file_path);
+    let diff = repo.diff_index_to_workdir(None, Some(&mut opts))?;
+
+    find_and_extract_file_diff(&diff, file_path, "Aucune modification non stagée")
+}
 
-    // Options pour le diff entre HEAD et working directory.
+/// Récupère le diff stagé d'un fichier (HEAD → index).
+pub fn staged_file_diff(repo: &Repository, file_path: &str) -> Result<FileDiff> {
     let mut opts = git2::DiffOptions::new();
     opts.pathspec(file_path);
 
-    let diff = repo.diff_tree_to_workdir_with_index(Some(&head_tree), Some(&mut opts))?;
+    let head_tree = head_tree(repo)?;
+    let diff = repo.diff_tree_to_index(head_tree.as_ref(), None, Some(&mut opts))?;
 
-    // Trouver le delta correspondant au fichier.
-    find_and_extract_file_diff(
-        &diff,
-        file_path,
-        "Fichier non trouvé dans le working directory",
-    )
+    find_and_extract_file_diff(&diff, file_path, "Aucune modification stagée")
+}
+
+/// Options du diff index → working directory pour un fichier.
+pub(crate) fn unstaged_diff_options(file_path: &str) -> git2::DiffOptions {
+    let mut opts = git2::DiffOptions::new();
+    opts.pathspec(file_path)
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .show_untracked_content(true);
+    opts
+}
+
+/// Arbre de HEAD, ou None si le repository n'a pas encore de commit.
+pub(crate) fn head_tree(repo: &Repository) -> Result<Option<git2::Tree<'_>>> {
+    match repo.head() {
+        Ok(head) => Ok(Some(head.peel_to_tree()?)),
+        Err(e) if e.code() == git2::ErrorCode::UnbornBranch => Ok(None),
+        Err(e) => Err(e.into()),
+    }
 }
 
 /// Trouve un fichier dans un diff et extrait son contenu.
 ///
 /// Cette fonction factorise la logique de recherche et d'extraction
-/// utilisée par get_file_diff() et working_dir_file_diff().
+/// utilisée par get_file_diff(), unstaged_file_diff() et staged_file_diff().
 fn find_and_extract_file_diff(
     diff: &git2::Diff,
     file_path: &str,
@@ -283,7 +301,7 @@ fn find_and_extract_file_diff(
         }
 
         let status = match delta.status() {
-            git2::Delta::Added => DiffStatus::Added,
+            git2::Delta::Added | git2::Delta::Untracked => DiffStatus::Added,
             git2::Delta::Modified => DiffStatus::Modified,
             git2::Delta::Deleted => DiffStatus::Deleted,
             git2::Delta::Renamed => DiffStatus::Renamed,
@@ -427,20 +445,43 @@ mod tests {
     }
 
     #[test]
-    fn test_working_dir_file_diff() {
+    fn test_unstaged_and_staged_file_diff() {
         let (_temp_dir, repo) = create_test_repo();
 
         // Commit initial
-        commit_file(&repo, "test.txt", "Initial content", "Initial commit");
-
-        // Modifier le fichier sans committer
-        create_file(&repo, "test.txt", "Modified in working dir");
+        commit_file(&repo, "test.txt", "Initial content\n", "Initial commit");
+
+        // Stager une modification, puis modifier à nouveau le working directory
+        create_file(&repo, "test.txt", "Staged content\n");
+        crate::git::commit::stage_file(&repo, "test.txt").unwrap();
+        create_file(&repo, "test.txt", "Working dir content\n");
+
+        let staged = staged_file_diff(&repo, "test.txt").unwrap();
+        assert!(matches!(staged.status, DiffStatus::Modified));
+        assert!(staged
+            .lines
+            .iter()
+            .any(|l| l.line_type == DiffLineType::Addition && l.content == "Staged content"));
+
+        let unstaged = unstaged_file_diff(&repo, "test.txt").unwrap();
+        assert!(unstaged
+            .lines
+            .iter()
+            .any(|l| l.line_type == DiffLineType::Deletion && l.content == "Staged content"));
+        assert!(unstaged
+            .lines
+            .iter()
+            .any(|l| l.line_type == DiffLineType::Addition && l.content == "Working dir content"));
+    }
 
-        // Obtenir le diff du working directory
-        let file_diff = working_dir_file_diff(&repo, "test.txt").unwrap();
+    #[test]
+    fn test_unstaged_file_diff_includes_untracked() {
+        let (_temp_dir, repo) = create_test_repo();
+        commit_file(&repo, "a.txt", "a\n", "Initial commit");
+        create_file(&repo, "new.txt", "hello\n");
 
-        assert_eq!(file_diff.path, "test.txt");
-        assert!(matches!(file_diff.status, DiffStatus::Modified));
-        assert!(!file_diff.lines.is_empty());
+        let diff = unstaged_file_diff(&repo, "new.txt").unwrap();
+        assert!(matches!(diff.status, DiffStatus::Added));
+        assert_eq!(diff.additions, 1);
     }
 }
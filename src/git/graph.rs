@@ -1,8 +1,9 @@
-use git2::{Oid, Repository};
+use git2::{Oid, Repository, Revwalk};
 use std::collections::HashMap;
 
 use super::commit::CommitInfo;
 use crate::error::Result;
+use crate::state::GraphFilter;
 
 /// Type de référence git.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -160,40 +161,68 @@ struct ColumnState {
     branch_name: Option<String>,
 }
 
-/// Construit le graphe de commits avec placement en colonnes et edges de connexion.
-pub fn build_graph(repo: &Repository, commits: &[CommitInfo]) -> Result<Vec<GraphRow>> {
-    let mut rows = Vec::with_capacity(commits.len());
+/// Constructeur incrémental du graphe.
+///
+/// Conserve l'état des colonnes et des couleurs entre deux pages de commits
+/// pour que les lignes du graphe restent continues lors du chargement paginé.
+#[derive(Debug, Clone, Default)]
+pub struct GraphBuilder {
+    /// Colonnes actives : chaque slot contient l'état de la colonne (OID attendu + couleur).
+    active_columns: Vec<ColumnState>,
+    /// Mapping branche -> index de couleur stable.
+    branch_colors: HashMap<String, usize>,
+    /// Prochain index de couleur à attribuer.
+    next_color_index: usize,
+    /// Références (branches, tags) associées à chaque OID.
+    refs_map: HashMap<Oid, Vec<RefInfo>>,
+}
+
+impl GraphBuilder {
+    /// Crée un constructeur vide en collectant les références du repository.
+    pub fn new(repo: &Repository) -> Result<Self> {
+        Ok(Self {
+            refs_map: collect_refs(repo)?,
+            ..Self::default()
+        })
+    }
 
-    // Colonnes actives : chaque slot contient l'état de la colonne (OID attendu + couleur).
-    let mut active_columns: Vec<ColumnState> = Vec::new();
+    /// Ajoute une page de commits au graphe et retourne les rangées correspondantes.
+    ///
+    /// `has_more` indique si d'autres commits suivront : la dernière rangée de la
+    /// page reçoit alors sa ligne de connexion vers la page suivante.
+    pub fn push_commits(&mut self, commits: &[CommitInfo], has_more: bool) -> Vec<GraphRow> {
+        let mut rows = Vec::with_capacity(commits.len());
 
-    // Mapping branche -> index de couleur stable.
-    let mut branch_colors: HashMap<String, usize> = HashMap::new();
-    let mut next_color_index: usize = 0;
+        for (commit_idx, ci) in commits.iter().enumerate() {
+            let is_last = commit_idx + 1 == commits.len() && !has_more;
+            rows.push(self.push_commit(ci, is_last));
+        }
 
-    // Collecter les refs pour chaque OID.
-    let refs_map = collect_refs(repo)?;
+        rows
+    }
 
-    for (commit_idx, ci) in commits.iter().enumerate() {
+    /// Place un commit dans le graphe et met à jour l'état des colonnes.
+    fn push_commit(&mut self, ci: &CommitInfo, is_last: bool) -> GraphRow {
+        let active_columns = &mut self.active_columns;
         let oid = ci.oid;
 
         // Trouver la colonne de ce commit.
-        let column = find_or_assign_column(&mut active_columns, oid);
+        let column = find_or_assign_column(active_columns, oid);
 
         // Récupérer les refs de ce commit.
-        let refs = refs_map.get(&oid).cloned().unwrap_or_default();
+        let refs = self.refs_map.get(&oid).cloned().unwrap_or_default();
 
         // Déterminer la couleur pour ce commit et cette colonne.
         let color_index = determine_color_index(
             column,
             &refs,
-            &mut branch_colors,
-            &mut next_color_index,
-            &active_columns,
+            &mut self.branch_colors,
+            &mut self.next_color_index,
+            active_columns,
         );
 
         // Déterminer le nom de la branche pour ce commit.
-        let branch_name = determine_branch_name(column, &refs, &active_columns);
+        let branch_name = determine_branch_name(column, &refs, active_columns);
 
         // Mettre à jour la couleur et le nom de la branche de la colonne.
         if column < active_columns.len() {
@@ -217,7 +246,7 @@ pub fn build_graph(repo: &Repository, commits: &[CommitInfo]) -> Result<Vec<Grap
         };
 
         // Générer les cellules pour la ligne du commit.
-        let cells = build_commit_cells(column, &active_columns, color_index);
+        let cells = build_commit_cells(column, active_columns, color_index);
 
         // Libérer la colonne courante.
         if column < active_columns.len() {
@@ -225,8 +254,7 @@ pub fn build_graph(repo: &Repository, commits: &[CommitInfo]) -> Result<Vec<Grap
         }
 
         // Calculer les edges pour les parents et mettre à jour les colonnes.
-        let parent_assignments =
-            assign_parent_columns(&mut active_columns, column, ci, color_index);
+        let parent_assignments = assign_parent_columns(active_columns, column, ci, color_index);
 
         // Compacter : supprimer les colonnes terminales vides (celles sans expected_oid).
         // On ne supprime que par la droite pour maintenir l'alignement des colonnes internes.
@@ -236,24 +264,110 @@ pub fn build_graph(repo: &Repository, commits: &[CommitInfo]) -> Result<Vec<Grap
 
         // Générer la ligne de connexion vers le commit suivant (s'il existe).
         // La connexion est générée APRÈS compaction pour refléter l'état compacté.
-        let connection = if commit_idx + 1 < commits.len() {
+        let connection = if is_last {
+            None
+        } else {
             Some(build_connection_row(
-                &active_columns,
+                active_columns,
                 &parent_assignments,
                 column,
             ))
-        } else {
-            None
         };
 
-        rows.push(GraphRow {
+        GraphRow {
             node,
             cells,
             connection,
-        });
+        }
+    }
+}
+
+/// Parcours paginé de l'historique de toutes les branches.
+///
+/// Le revwalk libgit2 et le constructeur du graphe sont conservés entre deux
+/// pages : chaque page reprend le parcours là où la précédente s'est arrêtée,
+/// sans reparcourir ni décaler l'historique déjà chargé.
+pub struct GraphWalk<'repo> {
+    repo: &'repo Repository,
+    revwalk: Revwalk<'repo>,
+    builder: GraphBuilder,
+    /// Filtre des commits émis (None : tout l'historique).
+    filter: Option<GraphFilter>,
+    /// Commit lu en avance pour savoir s'il reste de l'historique.
+    lookahead: Option<CommitInfo>,
+    /// Nombre de commits déjà émis.
+    loaded: usize,
+}
+
+impl<'repo> GraphWalk<'repo> {
+    /// Démarre le parcours depuis toutes les références du repository.
+    ///
+    /// Avec un filtre, seuls les commits correspondants sont émis : les pages
+    /// contiennent tout de même `page_size` commits tant qu'il en reste.
+    pub fn new(repo: &'repo Repository, filter: Option<GraphFilter>) -> Result<Self> {
+        Ok(Self {
+            repo,
+            revwalk: all_refs_revwalk(repo)?,
+            builder: GraphBuilder::new(repo)?,
+            filter,
+            lookahead: None,
+            loaded: 0,
+        })
+    }
+
+    /// Nombre de commits déjà émis par le parcours.
+    pub fn loaded(&self) -> usize {
+        self.loaded
+    }
+
+    /// Références (branches, tags) associées à chaque commit.
+    pub fn refs(&self) -> &HashMap<Oid, Vec<RefInfo>> {
+        &self.builder.refs_map
+    }
+
+    /// Construit la page suivante du graphe.
+    ///
+    /// Retourne les nouvelles rangées et un booléen indiquant s'il reste de l'historique.
+    pub fn next_page(&mut self, page_size: usize) -> Result<(Vec<GraphRow>, bool)> {
+        let mut commits = Vec::with_capacity(page_size);
+        commits.extend(self.lookahead.take());
+
+        // Lire un commit de plus pour savoir si une page suivante existe.
+        while commits.len() <= page_size {
+            match self.revwalk.next() {
+                Some(oid) => {
+                    let commit = self.repo.find_commit(oid?)?;
+                    let info = CommitInfo::from_git2_commit(&commit);
+                    if self.filter.as_ref().is_none_or(|f| f.matches(&info)) {
+                        commits.push(info);
+                    }
+                }
+                None => break,
+            }
+        }
+
+        let has_more = commits.len() > page_size;
+        if has_more {
+            self.lookahead = commits.pop();
+        }
+        self.loaded += commits.len();
+
+        let rows = self.builder.push_commits(&commits, has_more);
+        Ok((rows, has_more))
     }
+}
 
-    Ok(rows)
+/// Crée un revwalk partant de toutes les références (branches, tags), du plus récent au plus ancien.
+pub fn all_refs_revwalk(repo: &Repository) -> Result<Revwalk<'_>> {
+    let mut revwalk = repo.revwalk()?;
+    for reference in repo.references()? {
+        let reference = reference?;
+        if let Some(oid) = reference.target() {
+            revwalk.push(oid).ok();
+        }
+    }
+    revwalk.set_sorting(git2::Sort::TIME | git2::Sort::TOPOLOGICAL)?;
+    Ok(revwalk)
 }
 
 /// Construit les cellules pour la ligne du commit.
@@ -620,7 +734,9 @@ mod tests {
         ];
 
         // Construire le graphe
-        let graph = build_graph(&repo, &commits).unwrap();
+        let graph = GraphBuilder::new(&repo)
+            .unwrap()
+            .push_commits(&commits, false);
 
         // Devrait avoir 3 rangées
         assert_eq!(graph.len(), 3);
@@ -631,6 +747,68 @@ mod tests {
         assert_eq!(graph[2].node.column, 0);
     }
 
+    #[test]
+    fn test_graph_builder_pages_match_full_build() {
+        let (_temp_dir, repo) = create_test_repo();
+
+        // Historique avec une branche mergée : A -> B -> M, A -> F -> M
+        let oid_a = commit_file(&repo, "file.txt", "A", "Base");
+        let base = repo.find_commit(oid_a).unwrap();
+        repo.branch("feature", &base, false).unwrap();
+        commit_file(&repo, "file.txt", "B", "Main work");
+        repo.set_head("refs/heads/feature").unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+        let oid_f = commit_file(&repo, "other.txt", "F", "Feature work");
+        repo.set_head("refs/heads/main").unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        let feature = repo.find_commit(oid_f).unwrap();
+        let sig = git2::Signature::now("Test User", "test@example.com").unwrap();
+        repo.commit(
+            Some("HEAD"),
+            &sig,
+            &sig,
+            "Merge feature",
+            &head.tree().unwrap(),
+            &[&head, &feature],
+        )
+        .unwrap();
+
+        let mut revwalk = repo.revwalk().unwrap();
+        revwalk.push_head().unwrap();
+        revwalk
+            .set_sorting(git2::Sort::TIME | git2::Sort::TOPOLOGICAL)
+            .unwrap();
+        let commits: Vec<CommitInfo> = revwalk
+            .map(|oid| CommitInfo::from_git2_commit(&repo.find_commit(oid.unwrap()).unwrap()))
+            .collect();
+        assert_eq!(commits.len(), 4);
+
+        let full = GraphBuilder::new(&repo)
+            .unwrap()
+            .push_commits(&commits, false);
+
+        // Construire le même graphe en deux pages
+        let mut builder = GraphBuilder::new(&repo).unwrap();
+        let mut paged = builder.push_commits(&commits[..2], true);
+        paged.extend(builder.push_commits(&commits[2..], false));
+
+        assert_eq!(paged.len(), full.len());
+        for (p, f) in paged.iter().zip(full.iter()) {
+            assert_eq!(p.node.oid, f.node.oid);
+            assert_eq!(p.node.column, f.node.column);
+            assert_eq!(p.node.color_index, f.node.color_index);
+            assert_eq!(p.cells.len(), f.cells.len());
+            assert_eq!(p.connection.is_some(), f.connection.is_some());
+        }
+
+        // La dernière rangée de la première page est reliée à la page suivante
+        assert!(paged[1].connection.is_some());
+        assert!(paged.last().unwrap().connection.is_none());
+    }
+
     #[test]
     fn test_find_or_assign_column() {
         let mut columns: Vec<ColumnState> = vec![];
@@ -0,0 +1,344 @@
+//! Exécution des hooks git (`core.hooksPath` ou `.git/hooks`).
+//!
+//! libgit2 n'exécute aucun hook : git_sv les lance lui-même autour des commits
+//! (pre-commit, prepare-commit-msg, commit-msg, post-commit) et du push
+//! (pre-push). Un hook qui se termine en erreur annule l'opération. Sous
+//! Windows, les hooks sont lancés par le `sh` de Git for Windows, comme git.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+use git2::Repository;
+
+use crate::error::{GitSvError, Result};
+use crate::git::commit::{cleanup_message, comment_char};
+use crate::utils::shell;
+
+/// Hooks de vérification, ignorés avec `no_verify` (comme `git commit --no-verify`).
+const VERIFY_HOOKS: [&str; 3] = ["pre-commit", "commit-msg", "pre-push"];
+
+/// Sortie d'un hook exécuté.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookOutput {
+    /// Nom du hook (ex: "pre-commit").
+    pub hook: String,
+    /// Sortie standard puis sortie d'erreur.
+    pub output: String,
+    /// Le hook s'est terminé avec succès.
+    pub success: bool,
+}
+
+/// Hooks lancés pendant une opération, et leur sortie.
+#[derive(Debug, Default)]
+pub struct HookRun {
+    /// Ne pas lancer les hooks de vérification (pre-commit, commit-msg, pre-push).
+    pub no_verify: bool,
+    /// Sortie des hooks exécutés, dans l'ordre.
+    pub outputs: Vec<HookOutput>,
+}
+
+impl HookRun {
+    /// Crée une exécution, les hooks de vérification ignorés si `no_verify`.
+    pub fn new(no_verify: bool) -> Self {
+        Self {
+            no_verify,
+            outputs: Vec::new(),
+        }
+    }
+
+    /// Lance le hook s'il existe ; un code de sortie non nul est une erreur.
+    pub fn run(
+        &mut self,
+        repo: &Repository,
+        hook: &str,
+        args: &[&str],
+        stdin: Option<&str>,
+    ) -> Result<()> {
+        if self.no_verify && VERIFY_HOOKS.contains(&hook) {
+            return Ok(());
+        }
+        let Some(path) = find_hook(repo, hook) else {
+            return Ok(());
+        };
+
+        let (output, status) = execute(repo, &path, args, stdin)?;
+        let success = status.success();
+        self.outputs.push(HookOutput {
+            hook: hook.to_string(),
+            output: output.clone(),
+            success,
+        });
+        if success {
+            return Ok(());
+        }
+        Err(GitSvError::HookFailed {
+            hook: hook.to_string(),
+            status: match status.code() {
+                Some(code) => format!("code {}", code),
+                None => "interrompu".to_string(),
+            },
+            output,
+        })
+    }
+
+    /// Lance un hook de notification (post-commit) : son échec n'annule rien.
+    pub fn notify(&mut self, repo: &Repository, hook: &str, args: &[&str]) {
+        let _ = self.run(repo, hook, args, None);
+    }
+
+    /// Sortie des hooks qui ont affiché quelque chose, précédée de leur nom.
+    pub fn text(&self) -> String {
+        self.outputs
+            .iter()
+            .filter(|o| !o.output.trim().is_empty())
+            .map(|o| format!("── {} ──\n{}", o.hook, o.output.trim_end()))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+/// Lance les hooks précédant un commit et retourne le message final.
+///
+/// `source` est passé à prepare-commit-msg après le fichier du message
+/// (ex: `["message"]`, ou `["commit", "HEAD"]` pour un amend). Les hooks
+/// prepare-commit-msg et commit-msg peuvent réécrire le message.
+pub fn prepare_commit(
+    repo: &Repository,
+    message: &str,
+    source: &[&str],
+    hooks: &mut HookRun,
+) -> Result<String> {
+    hooks.run(repo, "pre-commit", &[], None)?;
+
+    let edits_message = ["prepare-commit-msg", "commit-msg"]
+        .iter()
+        .any(|hook| find_hook(repo, hook).is_some());
+    if !edits_message {
+        return Ok(message.to_string());
+    }
+
+    // Comme git, le fichier se termine par un retour à la ligne.
+    let mut contents = message.to_string();
+    if !contents.ends_with('\n') {
+        contents.push('\n');
+    }
+    let message_file = repo.path().join("COMMIT_EDITMSG");
+    fs::write(&message_file, &contents).map_err(|e| GitSvError::Io {
+        source: e,
+        context: "écriture de COMMIT_EDITMSG".to_string(),
+    })?;
+    let message_path = message_file.to_string_lossy();
+
+    let mut args = vec![message_path.as_ref()];
+    args.extend_from_slice(source);
+    hooks.run(repo, "prepare-commit-msg", &args, None)?;
+    hooks.run(repo, "commit-msg", &[&message_path], None)?;
+
+    let edited = fs::read_to_string(&message_file).map_err(|e| GitSvError::Io {
+        source: e,
+        context: "lecture de COMMIT_EDITMSG".to_string(),
+    })?;
+    if edited == contents {
+        return Ok(message.to_string());
+    }
+    // Comme git, les lignes de commentaire ajoutées par les hooks sont retirées.
+    let edited = cleanup_message(&edited, Some(comment_char(repo)))?;
+    if edited.is_empty() {
+        return Err(GitSvError::Other(
+            "Message de commit vide après les hooks".to_string(),
+        ));
+    }
+    Ok(edited)
+}
+
+/// Répertoire des hooks : `core.hooksPath` ou `hooks/` du répertoire git commun.
+pub fn hooks_dir(repo: &Repository) -> PathBuf {
+    let configured = repo
+        .config()
+        .and_then(|config| config.get_path("core.hooksPath"))
+        .ok();
+    match configured {
+        // Un chemin relatif l'est à la racine de l'arbre de travail, comme pour git.
+        Some(path) if path.is_relative() => hook_cwd(repo).join(path),
+        Some(path) => path,
+        None => common_dir(repo).join("hooks"),
+    }
+}
+
+/// Chemin du hook s'il existe et est exécutable.
+pub fn find_hook(repo: &Repository, hook: &str) -> Option<PathBuf> {
+    let path = hooks_dir(repo).join(hook);
+    let metadata = fs::metadata(&path).ok()?;
+    (metadata.is_file() && is_executable(&metadata)).then_some(path)
+}
+
+#[cfg(unix)]
+fn is_executable(metadata: &fs::Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.permissions().mode() & 0o111 != 0
+}
+
+/// Hors unix, tout fichier est un hook : il est lancé par `sh` (voir [`shell::script`]).
+#[cfg(not(unix))]
+fn is_executable(_metadata: &fs::Metadata) -> bool {
+    true
+}
+
+/// Répertoire git commun (celui du repository principal pour un worktree).
+fn common_dir(repo: &Repository) -> PathBuf {
+    match fs::read_to_string(repo.path().join("commondir")) {
+        Ok(common) => repo.path().join(common.trim()),
+        Err(_) => repo.path().to_path_buf(),
+    }
+}
+
+/// Répertoire d'exécution des hooks : racine de l'arbre de travail (git dir si bare).
+fn hook_cwd(repo: &Repository) -> &Path {
+    repo.workdir().unwrap_or_else(|| repo.path())
+}
+
+/// Exécute le hook (via `sh` sous Windows) et retourne sa sortie (stdout puis stderr) et son statut.
+fn execute(
+    repo: &Repository,
+    path: &Path,
+    args: &[&str],
+    stdin: Option<&str>,
+) -> Result<(String, std::process::ExitStatus)> {
+    let context = || format!("exécution du hook {}", path.display());
+    let mut child = shell::script(path)
+        .args(args)
+        .current_dir(hook_cwd(repo))
+        .stdin(if stdin.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| shell::spawn_error(e, context()))?;
+
+    if let (Some(input), Some(mut pipe)) = (stdin, child.stdin.take()) {
+        // Un hook peut se terminer sans lire son entrée : l'erreur est ignorée.
+        let _ = pipe.write_all(input.as_bytes());
+    }
+    let output = child.wait_with_output().map_err(|e| GitSvError::Io {
+        source: e,
+        context: context(),
+    })?;
+
+    let mut text = String::from_utf8_lossy(&output.stdout).to_string();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    Ok((text, output.status))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::commit::create_commit_with_hooks;
+    use crate::git::tests::test_utils::*;
+
+    fn stage(repo: &Repository, path: &str, content: &str) {
+        create_file(repo, path, content);
+        crate::git::commit::stage_all(repo).unwrap();
+    }
+
+    #[test]
+    fn test_failing_pre_commit_aborts_commit() {
+        let (_dir, repo) = create_test_repo();
+        let first = commit_file(&repo, "a.txt", "v1", "First");
+        install_hook(
+            &repo.path().join("hooks"),
+            "pre-commit",
+            "echo 'lint: a.txt mal formaté'; exit 1",
+        );
+
+        stage(&repo, "a.txt", "v2");
+        let err = create_commit_with_hooks(&repo, "Second", &mut HookRun::default()).unwrap_err();
+        match err {
+            GitSvError::HookFailed {
+                hook,
+                status,
+                output,
+            } => {
+                assert_eq!(hook, "pre-commit");
+                assert_eq!(status, "code 1");
+                assert!(output.contains("mal formaté"));
+            }
+            other => panic!("erreur inattendue: {}", other),
+        }
+        assert_eq!(repo.head().unwrap().target(), Some(first));
+
+        // Les hooks de vérification peuvent être ignorés
+        let mut hooks = HookRun::new(true);
+        let second = create_commit_with_hooks(&repo, "Second", &mut hooks).unwrap();
+        assert_eq!(repo.head().unwrap().target(), Some(second));
+        assert!(hooks.outputs.is_empty());
+    }
+
+    #[test]
+    fn test_commit_msg_hook_edits_message_from_hooks_path() {
+        let (dir, repo) = create_test_repo();
+        commit_file(&repo, "a.txt", "v1", "First");
+        repo.config()
+            .unwrap()
+            .set_str("core.hooksPath", "githooks")
+            .unwrap();
+        let hooks_path = dir.path().join("githooks");
+        install_hook(&hooks_path, "commit-msg", "echo 'Refs: #42' >> \"$1\"");
+        install_hook(&hooks_path, "post-commit", "echo 'commit enregistré'");
+        assert_eq!(hooks_dir(&repo), hooks_path);
+
+        stage(&repo, "b.txt", "b");
+        let mut hooks = HookRun::default();
+        let oid = create_commit_with_hooks(&repo, "Ajoute b", &mut hooks).unwrap();
+
+        let commit = repo.find_commit(oid).unwrap();
+        assert_eq!(commit.message(), Some("Ajoute b\nRefs: #42\n"));
+        assert!(hooks
+            .text()
+            .contains("── post-commit ──\ncommit enregistré"));
+    }
+
+    #[test]
+    fn test_hook_comments_are_stripped_with_comment_char() {
+        let (_dir, repo) = create_test_repo();
+        commit_file(&repo, "a.txt", "v1", "First");
+        repo.config()
+            .unwrap()
+            .set_str("core.commentChar", ";")
+            .unwrap();
+        install_hook(
+            &repo.path().join("hooks"),
+            "prepare-commit-msg",
+            "printf '\\n; Branche : main\\n\\n\\n' >> \"$1\"",
+        );
+
+        stage(&repo, "b.txt", "b");
+        let oid = create_commit_with_hooks(&repo, "Ajoute b", &mut HookRun::default()).unwrap();
+
+        let commit = repo.find_commit(oid).unwrap();
+        assert_eq!(commit.message(), Some("Ajoute b\n"));
+    }
+
+    #[test]
+    fn test_pre_commit_can_restage_files() {
+        let (dir, repo) = create_test_repo();
+        commit_file(&repo, "a.txt", "v1", "First");
+        // Formatteur : réécrit le fichier et le restage
+        install_hook(
+            &repo.path().join("hooks"),
+            "pre-commit",
+            "echo formaté > a.txt && git add a.txt",
+        );
+        assert!(dir.path().join("a.txt").exists());
+
+        stage(&repo, "a.txt", "v2");
+        let oid = create_commit_with_hooks(&repo, "Second", &mut HookRun::default()).unwrap();
+        let tree = repo.find_commit(oid).unwrap().tree().unwrap();
+        let blob = tree.get_name("a.txt").unwrap().to_object(&repo).unwrap();
+        assert_eq!(blob.as_blob().unwrap().content(), "formaté\n".as_bytes());
+    }
+}
@@ -2,6 +2,7 @@ use git2::{build::CheckoutBuilder, Repository, Signature};
 
 use crate::error::{GitSvError, Result};
 use crate::git::conflict::{list_conflict_files, MergeResult};
+use crate::git::signing::write_commit;
 
 /// Effectue un merge de la branche source dans la branche courante.
 pub fn merge_branch(repo: &Repository, branch_name: &str) -> Result<()> {
@@ -42,7 +43,8 @@ pub fn merge_branch_with_result(repo: &Repository, branch_name: &str) -> Result<
         let tree_oid = index.write_tree_to(repo)?;
         let tree = repo.find_tree(tree_oid)?;
 
-        repo.commit(
+        write_commit(
+            repo,
             Some("HEAD"),
             &signature,
             &signature,
@@ -73,7 +75,8 @@ pub fn merge_branch_with_result(repo: &Repository, branch_name: &str) -> Result<
         let tree_oid = index.write_tree()?;
         let tree = repo.find_tree(tree_oid)?;
 
-        repo.commit(
+        write_commit(
+            repo,
             Some("HEAD"),
             &signature,
             &signature,
@@ -6,11 +6,19 @@ pub mod diff;
 pub mod discard;
 pub mod graph;
 pub mod helpers;
+pub mod hooks;
 pub mod merge;
+pub mod patch;
+pub mod rebase;
+pub mod reflog;
 pub mod remote;
 pub mod repo;
+pub mod reset;
 pub mod search;
+pub mod signing;
 pub mod stash;
+pub mod tag;
+pub mod undo;
 pub mod worktree;
 
 pub use repo::GitRepo;
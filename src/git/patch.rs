@@ -0,0 +1,508 @@
+//! Application partielle de diffs (staging, unstaging et discard par hunk ou par ligne).
+//!
+//! Un patch réduit aux lignes sélectionnées est reconstruit au format unifié, puis
+//! appliqué à l'index ou au working directory via `git2::Repository::apply`.
+
+use git2::{ApplyLocation, Delta, Repository};
+
+use super::diff::{head_tree, unstaged_diff_options};
+use crate::error::{GitSvError, Result};
+
+/// Diff d'origine d'un patch partiel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PatchSource {
+    /// Index → working directory.
+    Unstaged,
+    /// HEAD → index.
+    Staged,
+}
+
+/// Ligne d'un hunk telle que fournie par git2.
+#[derive(Debug, Clone)]
+struct HunkLine {
+    /// Origine de la ligne (' ', '+' ou '-').
+    origin: char,
+    /// Contenu brut, avec le retour à la ligne s'il existe.
+    content: Vec<u8>,
+}
+
+/// Hunk extrait d'un diff, avec les informations du fichier.
+#[derive(Debug, Clone)]
+struct Hunk {
+    /// Statut du fichier dans le diff.
+    status: Delta,
+    /// Mode du fichier (octal, ex: 100644).
+    mode: u32,
+    /// Première ligne du hunk dans l'ancien fichier.
+    old_start: u32,
+    /// Première ligne du hunk dans le nouveau fichier.
+    new_start: u32,
+    /// Lignes du hunk.
+    lines: Vec<HunkLine>,
+    /// Le fichier contient-il d'autres hunks?
+    is_only_hunk: bool,
+}
+
+/// Ajoute le hunk `hunk_index` des modifications non stagées à l'index.
+pub fn stage_hunk(repo: &Repository, path: &str, hunk_index: usize) -> Result<()> {
+    let hunk = find_hunk(repo, path, PatchSource::Unstaged, hunk_index)?;
+    apply_hunks(repo, path, &[select_all(hunk)], false, ApplyLocation::Index)
+}
+
+/// Retire le hunk `hunk_index` des modifications stagées de l'index.
+pub fn unstage_hunk(repo: &Repository, path: &str, hunk_index: usize) -> Result<()> {
+    let hunk = find_hunk(repo, path, PatchSource::Staged, hunk_index)?;
+    apply_hunks(repo, path, &[select_all(hunk)], true, ApplyLocation::Index)
+}
+
+/// Annule le hunk `hunk_index` des modifications non stagées dans le working directory.
+pub fn discard_hunk(repo: &Repository, path: &str, hunk_index: usize) -> Result<()> {
+    let hunk = find_hunk(repo, path, PatchSource::Unstaged, hunk_index)?;
+    apply_hunks(
+        repo,
+        path,
+        &[select_all(hunk)],
+        true,
+        ApplyLocation::WorkDir,
+    )
+}
+
+/// Ajoute à l'index les lignes non stagées comprises entre `start` et `end` (inclus).
+///
+/// Les indices portent sur `FileDiff::lines` du diff non stagé (en-têtes de hunk compris).
+pub fn stage_lines(repo: &Repository, path: &str, start: usize, end: usize) -> Result<()> {
+    let hunks = select_lines(find_hunks(repo, path, PatchSource::Unstaged)?, start, end)?;
+    apply_hunks(repo, path, &hunks, false, ApplyLocation::Index)
+}
+
+/// Retire de l'index les lignes stagées comprises entre `start` et `end` (inclus).
+///
+/// Les indices portent sur `FileDiff::lines` du diff stagé (en-têtes de hunk compris).
+pub fn unstage_lines(repo: &Repository, path: &str, start: usize, end: usize) -> Result<()> {
+    let hunks = select_lines(find_hunks(repo, path, PatchSource::Staged)?, start, end)?;
+    apply_hunks(repo, path, &hunks, true, ApplyLocation::Index)
+}
+
+/// Hunk dont toutes les lignes sont sélectionnées.
+fn select_all(hunk: Hunk) -> (Hunk, Vec<bool>) {
+    let selected = vec![true; hunk.lines.len()];
+    (hunk, selected)
+}
+
+/// Sélectionne les lignes des hunks comprises dans la plage donnée.
+///
+/// La plage suit la numérotation de `FileDiff::lines` : chaque hunk occupe
+/// une ligne d'en-tête suivie de ses lignes. Seuls les hunks contenant au
+/// moins un ajout ou une suppression sélectionné sont conservés.
+fn select_lines(hunks: Vec<Hunk>, start: usize, end: usize) -> Result<Vec<(Hunk, Vec<bool>)>> {
+    let mut selection = Vec::new();
+    let mut position = 0;
+    for hunk in hunks {
+        // Sauter la ligne d'en-tête du hunk.
+        position += 1;
+        let selected: Vec<bool> = (position..position + hunk.lines.len())
+            .map(|pos| (start..=end).contains(&pos))
+            .collect();
+        position += hunk.lines.len();
+
+        let has_change = hunk
+            .lines
+            .iter()
+            .zip(&selected)
+            .any(|(line, &sel)| sel && line.origin != ' ');
+        if has_change {
+            selection.push((hunk, selected));
+        }
+    }
+
+    if selection.is_empty() {
+        return Err(GitSvError::Other(
+            "Aucune ligne modifiée dans la sélection".to_string(),
+        ));
+    }
+    Ok(selection)
+}
+
+/// Calcule le diff d'un fichier selon la source demandée.
+fn source_diff<'a>(
+    repo: &'a Repository,
+    path: &str,
+    source: PatchSource,
+) -> Result<git2::Diff<'a>> {
+    let diff = match source {
+        PatchSource::Unstaged => {
+            let mut opts = unstaged_diff_options(path);
+            repo.diff_index_to_workdir(None, Some(&mut opts))?
+        }
+        PatchSource::Staged => {
+            let mut opts = git2::DiffOptions::new();
+            opts.pathspec(path);
+            let tree = head_tree(repo)?;
+            repo.diff_tree_to_index(tree.as_ref(), None, Some(&mut opts))?
+        }
+    };
+    Ok(diff)
+}
+
+/// Extrait un hunk du diff d'un fichier.
+fn find_hunk(
+    repo: &Repository,
+    path: &str,
+    source: PatchSource,
+    hunk_index: usize,
+) -> Result<Hunk> {
+    find_hunks(repo, path, source)?
+        .into_iter()
+        .nth(hunk_index)
+        .ok_or_else(|| {
+            GitSvError::Other(format!("Hunk {} introuvable dans {}", hunk_index + 1, path))
+        })
+}
+
+/// Extrait tous les hunks du diff d'un fichier.
+fn find_hunks(repo: &Repository, path: &str, source: PatchSource) -> Result<Vec<Hunk>> {
+    let diff = source_diff(repo, path, source)?;
+
+    for (idx, delta) in diff.deltas().enumerate() {
+        if delta.new_file().path().and_then(|p| p.to_str()) != Some(path) {
+            continue;
+        }
+
+        let Some(patch) = git2::Patch::from_diff(&diff, idx)? else {
+            break;
+        };
+
+        let file = if delta.status() == Delta::Deleted {
+            delta.old_file()
+        } else {
+            delta.new_file()
+        };
+
+        let mut hunks = Vec::with_capacity(patch.num_hunks());
+        for hunk_idx in 0..patch.num_hunks() {
+            let (git_hunk, num_lines) = patch.hunk(hunk_idx)?;
+            let mut lines = Vec::with_capacity(num_lines);
+            for line_idx in 0..num_lines {
+                let line = patch.line_in_hunk(hunk_idx, line_idx)?;
+                // Les marqueurs "\ No newline at end of file" sont recalculés
+                // à partir du contenu lors de la génération du patch.
+                if matches!(line.origin(), ' ' | '+' | '-') {
+                    lines.push(HunkLine {
+                        origin: line.origin(),
+                        content: line.content().to_vec(),
+                    });
+                }
+            }
+
+            hunks.push(Hunk {
+                status: delta.status(),
+                mode: u32::from(file.mode()),
+                old_start: git_hunk.old_start(),
+                new_start: git_hunk.new_start(),
+                lines,
+                is_only_hunk: patch.num_hunks() == 1,
+            });
+        }
+        return Ok(hunks);
+    }
+
+    Err(GitSvError::Other(format!("Aucun diff pour {}", path)))
+}
+
+/// Construit le patch de chaque hunk et l'applique à l'emplacement donné.
+///
+/// Les hunks sont appliqués du dernier au premier afin que les numéros de
+/// ligne des hunks restants ne soient pas décalés.
+fn apply_hunks(
+    repo: &Repository,
+    path: &str,
+    hunks: &[(Hunk, Vec<bool>)],
+    reverse: bool,
+    location: ApplyLocation,
+) -> Result<()> {
+    for (hunk, selected) in hunks.iter().rev() {
+        let patch = build_hunk_patch(path, hunk, selected, reverse);
+        let diff = git2::Diff::from_buffer(&patch)?;
+        repo.apply(&diff, location, None)?;
+    }
+    Ok(())
+}
+
+/// Génère un patch unifié ne contenant que les lignes sélectionnées d'un hunk.
+///
+/// En mode `reverse`, les ajouts et suppressions sont inversés pour annuler
+/// le hunk au lieu de l'appliquer. Les ajouts non sélectionnés sont omis et
+/// les suppressions non sélectionnées deviennent des lignes de contexte.
+///
+/// Le contenu des lignes est recopié octet par octet : un fichier qui n'est
+/// pas en UTF-8 doit rester identique au blob pour que le patch s'applique.
+fn build_hunk_patch(path: &str, hunk: &Hunk, selected: &[bool], reverse: bool) -> Vec<u8> {
+    let is_added = |status| matches!(status, Delta::Added | Delta::Untracked);
+    let (creates_file, deletes_file) = if reverse {
+        (hunk.status == Delta::Deleted, is_added(hunk.status))
+    } else {
+        (is_added(hunk.status), hunk.status == Delta::Deleted)
+    };
+    // Une suppression n'est complète que si tout le fichier est sélectionné.
+    let deletes_file = deletes_file && hunk.is_only_hunk && selected.iter().all(|&s| s);
+
+    let mut body = Vec::new();
+    let mut old_count = 0;
+    let mut new_count = 0;
+    for (line, &is_selected) in hunk.lines.iter().zip(selected) {
+        let origin = match (line.origin, reverse) {
+            ('+', true) => '-',
+            ('-', true) => '+',
+            (origin, _) => origin,
+        };
+        let origin = match (origin, is_selected) {
+            ('+', false) => continue,
+            ('-', false) => ' ',
+            (origin, _) => origin,
+        };
+        match origin {
+            '+' => new_count += 1,
+            '-' => old_count += 1,
+            _ => {
+                old_count += 1;
+                new_count += 1;
+            }
+        }
+
+        body.push(origin as u8);
+        body.extend_from_slice(&line.content);
+        if !line.content.ends_with(b"\n") {
+            body.extend_from_slice(b"\n\\ No newline at end of file\n");
+        }
+    }
+
+    let old_start = if reverse {
+        hunk.new_start
+    } else {
+        hunk.old_start
+    };
+    let new_start = if reverse {
+        hunk.old_start
+    } else {
+        hunk.new_start
+    };
+
+    let mut patch = format!("diff --git a/{path} b/{path}\n");
+    if creates_file {
+        patch.push_str(&format!("new file mode {:o}\n", hunk.mode));
+        patch.push_str(&format!("--- /dev/null\n+++ b/{path}\n"));
+    } else if deletes_file {
+        patch.push_str(&format!("deleted file mode {:o}\n", hunk.mode));
+        patch.push_str(&format!("--- a/{path}\n+++ /dev/null\n"));
+    } else {
+        patch.push_str(&format!("--- a/{path}\n+++ b/{path}\n"));
+    }
+    patch.push_str(&format!(
+        "@@ -{},{} +{},{} @@\n",
+        old_start, old_count, new_start, new_count
+    ));
+    let mut patch = patch.into_bytes();
+    patch.extend_from_slice(&body);
+    patch
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::diff::{staged_file_diff, unstaged_file_diff, DiffLineType};
+    use crate::git::tests::test_utils::*;
+
+    /// Contenu de 20 lignes numérotées.
+    fn numbered_lines() -> Vec<String> {
+        (1..=20).map(|i| format!("line {}", i)).collect()
+    }
+
+    /// Repo avec un fichier modifié en deux endroits éloignés (deux hunks).
+    fn repo_with_two_hunks() -> (tempfile::TempDir, Repository) {
+        let (temp_dir, repo) = create_test_repo();
+        let mut lines = numbered_lines();
+        commit_file(&repo, "file.txt", &(lines.join("\n") + "\n"), "Initial");
+
+        lines[1] = "line 2 modifiée".to_string();
+        lines[17] = "line 18 modifiée".to_string();
+        create_file(&repo, "file.txt", &(lines.join("\n") + "\n"));
+
+        (temp_dir, repo)
+    }
+
+    fn hunk_count(diff: &crate::git::diff::FileDiff) -> usize {
+        diff.lines
+            .iter()
+            .filter(|l| l.line_type == DiffLineType::HunkHeader)
+            .count()
+    }
+
+    fn read_file(repo: &Repository, path: &str) -> String {
+        std::fs::read_to_string(repo.workdir().unwrap().join(path)).unwrap()
+    }
+
+    #[test]
+    fn test_stage_hunk_stages_only_selected_hunk() {
+        let (_temp_dir, repo) = repo_with_two_hunks();
+        assert_eq!(
+            hunk_count(&unstaged_file_diff(&repo, "file.txt").unwrap()),
+            2
+        );
+
+        stage_hunk(&repo, "file.txt", 1).unwrap();
+
+        let staged = staged_file_diff(&repo, "file.txt").unwrap();
+        assert_eq!(hunk_count(&staged), 1);
+        assert!(staged
+            .lines
+            .iter()
+            .any(|l| l.line_type == DiffLineType::Addition && l.content == "line 18 modifiée"));
+
+        let unstaged = unstaged_file_diff(&repo, "file.txt").unwrap();
+        assert_eq!(hunk_count(&unstaged), 1);
+        assert!(unstaged
+            .lines
+            .iter()
+            .any(|l| l.line_type == DiffLineType::Addition && l.content == "line 2 modifiée"));
+    }
+
+    #[test]
+    fn test_unstage_hunk_restores_index() {
+        let (_temp_dir, repo) = repo_with_two_hunks();
+        crate::git::commit::stage_file(&repo, "file.txt").unwrap();
+        assert_eq!(hunk_count(&staged_file_diff(&repo, "file.txt").unwrap()), 2);
+
+        unstage_hunk(&repo, "file.txt", 0).unwrap();
+
+        let staged = staged_file_diff(&repo, "file.txt").unwrap();
+        assert_eq!(hunk_count(&staged), 1);
+        assert!(staged
+            .lines
+            .iter()
+            .any(|l| l.line_type == DiffLineType::Addition && l.content == "line 18 modifiée"));
+        assert_eq!(
+            hunk_count(&unstaged_file_diff(&repo, "file.txt").unwrap()),
+            1
+        );
+    }
+
+    #[test]
+    fn test_discard_hunk_reverts_working_directory() {
+        let (_temp_dir, repo) = repo_with_two_hunks();
+
+        discard_hunk(&repo, "file.txt", 0).unwrap();
+
+        let content = read_file(&repo, "file.txt");
+        assert!(content.contains("line 2\n"));
+        assert!(content.contains("line 18 modifiée\n"));
+    }
+
+    #[test]
+    fn test_stage_hunk_of_untracked_file() {
+        let (_temp_dir, repo) = create_test_repo();
+        commit_file(&repo, "a.txt", "a\n", "Initial");
+        create_file(&repo, "new.txt", "first\nsecond");
+
+        stage_hunk(&repo, "new.txt", 0).unwrap();
+
+        let staged = staged_file_diff(&repo, "new.txt").unwrap();
+        assert_eq!(staged.additions, 2);
+        assert!(unstaged_file_diff(&repo, "new.txt").is_err());
+    }
+
+    #[test]
+    fn test_stage_hunk_of_non_utf8_file() {
+        let (_temp_dir, repo) = create_test_repo();
+        let path = repo.workdir().unwrap().join("latin1.txt");
+        // "café" et "été" en Latin-1, avec des fins de ligne CRLF
+        std::fs::write(&path, b"caf\xe9\r\n\xe9t\xe9\r\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(std::path::Path::new("latin1.txt")).unwrap();
+        index.write().unwrap();
+        commit(&repo, "Initial");
+        std::fs::write(&path, b"caf\xe9\r\n\xe9t\xe9\r\nhiver \xe0 No\xebl\r\n").unwrap();
+
+        stage_hunk(&repo, "latin1.txt", 0).unwrap();
+
+        let mut index = repo.index().unwrap();
+        index.read(true).unwrap();
+        let entry = index
+            .get_path(std::path::Path::new("latin1.txt"), 0)
+            .unwrap();
+        let blob = repo.find_blob(entry.id).unwrap();
+        assert_eq!(blob.content(), std::fs::read(&path).unwrap().as_slice());
+    }
+
+    #[test]
+    fn test_hunk_out_of_range_is_an_error() {
+        let (_temp_dir, repo) = repo_with_two_hunks();
+        assert!(stage_hunk(&repo, "file.txt", 5).is_err());
+        assert!(unstage_hunk(&repo, "file.txt", 0).is_err());
+    }
+
+    #[test]
+    fn test_stage_lines_stages_only_selected_lines() {
+        let (_temp_dir, repo) = create_test_repo();
+        commit_file(&repo, "file.txt", "a\nb\nc\n", "Initial");
+        create_file(&repo, "file.txt", "a\nB\nc\nd\n");
+
+        // Lignes du diff : 0 en-tête, 1 " a", 2 "-b", 3 "+B", 4 " c", 5 "+d"
+        let unstaged = unstaged_file_diff(&repo, "file.txt").unwrap();
+        assert_eq!(unstaged.lines[2].line_type, DiffLineType::Deletion);
+        assert_eq!(unstaged.lines[5].content, "d");
+
+        stage_lines(&repo, "file.txt", 5, 5).unwrap();
+
+        let staged = staged_file_diff(&repo, "file.txt").unwrap();
+        assert_eq!(staged.additions, 1);
+        assert_eq!(staged.deletions, 0);
+        let unstaged = unstaged_file_diff(&repo, "file.txt").unwrap();
+        assert_eq!(unstaged.additions, 1);
+        assert_eq!(unstaged.deletions, 1);
+
+        // Seule la suppression de "b" : "B" reste non stagé
+        stage_lines(&repo, "file.txt", 2, 2).unwrap();
+        let staged = staged_file_diff(&repo, "file.txt").unwrap();
+        assert_eq!(staged.additions, 1);
+        assert_eq!(staged.deletions, 1);
+        assert!(!staged.lines.iter().any(|l| l.content == "B"));
+    }
+
+    #[test]
+    fn test_stage_lines_across_hunks() {
+        let (_temp_dir, repo) = repo_with_two_hunks();
+        let unstaged = unstaged_file_diff(&repo, "file.txt").unwrap();
+
+        stage_lines(&repo, "file.txt", 0, unstaged.lines.len() - 1).unwrap();
+
+        assert_eq!(hunk_count(&staged_file_diff(&repo, "file.txt").unwrap()), 2);
+        assert!(unstaged_file_diff(&repo, "file.txt").is_err());
+    }
+
+    #[test]
+    fn test_unstage_lines_keeps_unselected_lines_staged() {
+        let (_temp_dir, repo) = create_test_repo();
+        commit_file(&repo, "file.txt", "a\n", "Initial");
+        create_file(&repo, "file.txt", "a\nb\nc\n");
+        crate::git::commit::stage_file(&repo, "file.txt").unwrap();
+
+        // Lignes du diff stagé : 0 en-tête, 1 " a", 2 "+b", 3 "+c"
+        unstage_lines(&repo, "file.txt", 3, 3).unwrap();
+
+        let staged = staged_file_diff(&repo, "file.txt").unwrap();
+        assert_eq!(staged.additions, 1);
+        assert!(staged.lines.iter().any(|l| l.content == "b"));
+        let unstaged = unstaged_file_diff(&repo, "file.txt").unwrap();
+        assert!(unstaged
+            .lines
+            .iter()
+            .any(|l| l.content == "c" && l.line_type == DiffLineType::Addition));
+    }
+
+    #[test]
+    fn test_stage_lines_without_changes_is_an_error() {
+        let (_temp_dir, repo) = repo_with_two_hunks();
+        // Ligne 1 : contexte uniquement
+        assert!(stage_lines(&repo, "file.txt", 1, 1).is_err());
+    }
+}
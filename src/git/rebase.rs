@@ -0,0 +1,850 @@
+//! Rebase interactif piloté par un séquenceur interne.
+//!
+//! Les commits sont rejoués un par un (cherry-pick) sur la base choisie,
+//! HEAD restant détaché jusqu'à la fin du rebase. La branche d'origine
+//! n'est mise à jour qu'une fois tous les commits appliqués.
+//!
+//! Quand le rebase s'arrête (conflit, edit), son état est sauvegardé dans
+//! `.git/rebase-merge`, au format de `git rebase -i` complété de fichiers
+//! propres à git_sv : il est repris au prochain lancement, et `git status` ou
+//! `git rebase --abort` le reconnaissent.
+
+use git2::{Oid, Repository, Signature, Sort};
+use std::collections::VecDeque;
+use std::fs;
+use std::path::Path;
+
+use crate::error::{GitSvError, IoErrorContext, Result};
+use crate::git::conflict::{list_conflict_files, ConflictFile};
+use crate::git::signing::write_commit;
+
+/// Action appliquée à un commit de la todo list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TodoAction {
+    /// Garder le commit tel quel.
+    #[default]
+    Pick,
+    /// Garder le commit en changeant son message.
+    Reword,
+    /// Garder le commit et s'arrêter pour le modifier.
+    Edit,
+    /// Fusionner dans le commit précédent en concaténant les messages.
+    Squash,
+    /// Fusionner dans le commit précédent en gardant son message.
+    Fixup,
+    /// Supprimer le commit.
+    Drop,
+}
+
+impl TodoAction {
+    /// Nom de l'action tel qu'affiché dans la todo list.
+    pub fn label(&self) -> &'static str {
+        match self {
+            TodoAction::Pick => "pick",
+            TodoAction::Reword => "reword",
+            TodoAction::Edit => "edit",
+            TodoAction::Squash => "squash",
+            TodoAction::Fixup => "fixup",
+            TodoAction::Drop => "drop",
+        }
+    }
+
+    /// Retourne true si l'action fusionne le commit dans le précédent.
+    pub fn is_fold(&self) -> bool {
+        matches!(self, TodoAction::Squash | TodoAction::Fixup)
+    }
+
+    /// Action correspondant au nom affiché dans la todo list.
+    fn from_label(label: &str) -> Option<Self> {
+        [
+            TodoAction::Pick,
+            TodoAction::Reword,
+            TodoAction::Edit,
+            TodoAction::Squash,
+            TodoAction::Fixup,
+            TodoAction::Drop,
+        ]
+        .into_iter()
+        .find(|action| action.label() == label)
+    }
+}
+
+/// Entrée de la todo list du rebase.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RebaseTodoEntry {
+    /// Commit à rejouer.
+    pub oid: Oid,
+    /// Première ligne du message du commit.
+    pub summary: String,
+    /// Action à appliquer.
+    pub action: TodoAction,
+    /// Nouveau message (reword).
+    pub message: Option<String>,
+}
+
+/// Résultat d'une étape du séquenceur.
+#[derive(Debug)]
+pub enum RebaseProgress {
+    /// Tous les commits ont été appliqués, la branche est à jour.
+    Completed,
+    /// Le commit courant est en conflit.
+    Conflicts(Vec<ConflictFile>),
+    /// Arrêt demandé (edit) après l'application du commit.
+    Stopped(Oid),
+}
+
+/// Construit la todo list des commits situés entre `base` (exclu) et HEAD.
+///
+/// Les commits sont retournés du plus ancien au plus récent.
+pub fn rebase_todo(repo: &Repository, base: Oid) -> Result<Vec<RebaseTodoEntry>> {
+    let head = repo.head()?.peel_to_commit()?.id();
+    if head == base || !repo.graph_descendant_of(head, base)? {
+        return Err(GitSvError::Other(
+            "Le commit de base doit être un ancêtre de HEAD".to_string(),
+        ));
+    }
+
+    let mut revwalk = repo.revwalk()?;
+    revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
+    revwalk.push(head)?;
+    revwalk.hide(base)?;
+
+    let mut entries = Vec::new();
+    for oid in revwalk {
+        let commit = repo.find_commit(oid?)?;
+        if commit.parent_count() > 1 {
+            return Err(GitSvError::Other(
+                "Les commits de merge ne sont pas supportés par le rebase interactif".to_string(),
+            ));
+        }
+        entries.push(pick_entry(&commit));
+    }
+
+    Ok(entries)
+}
+
+/// Construit la todo list des commits de HEAD absents de `upstream`, comme `git rebase`.
+///
+/// Les commits de merge sont ignorés : l'historique est linéarisé en rejouant
+/// les commits qu'ils fusionnent. La liste est vide si HEAD n'a que des merges.
+pub fn linear_todo(repo: &Repository, upstream: Oid) -> Result<Vec<RebaseTodoEntry>> {
+    let mut revwalk = repo.revwalk()?;
+    revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
+    revwalk.push_head()?;
+    revwalk.hide(upstream)?;
+
+    let mut entries = Vec::new();
+    for oid in revwalk {
+        let commit = repo.find_commit(oid?)?;
+        if commit.parent_count() <= 1 {
+            entries.push(pick_entry(&commit));
+        }
+    }
+
+    Ok(entries)
+}
+
+fn pick_entry(commit: &git2::Commit) -> RebaseTodoEntry {
+    RebaseTodoEntry {
+        oid: commit.id(),
+        summary: commit.summary().unwrap_or("").to_string(),
+        action: TodoAction::Pick,
+        message: None,
+    }
+}
+
+/// Répertoire de l'état du rebase, dans le répertoire git.
+const STATE_DIR: &str = "rebase-merge";
+
+/// Fichier propre à git_sv (nombre total d'entrées) : sa présence distingue
+/// un rebase lancé par git_sv d'un rebase lancé par git.
+const TOTAL_FILE: &str = "git_sv-total";
+
+/// Valeur de `head-name` quand HEAD était détaché, comme git.
+const DETACHED_HEAD: &str = "detached HEAD";
+
+/// Rebase interactif en cours.
+#[derive(Debug, PartialEq)]
+pub struct RebaseSession {
+    /// Commit sur lequel les entrées sont rejouées.
+    pub onto: Oid,
+    /// HEAD avant le rebase (pour l'abandon).
+    pub orig_head: Oid,
+    /// Référence de la branche rebasée (None si HEAD était détaché).
+    pub branch: Option<String>,
+    /// Entrées restant à appliquer.
+    pub todo: VecDeque<RebaseTodoEntry>,
+    /// Entrée en cours d'application (en conflit).
+    pub current: Option<RebaseTodoEntry>,
+    /// Dernière entrée appliquée (arrêt sur edit).
+    pub stopped: Option<RebaseTodoEntry>,
+    /// Nombre total d'entrées.
+    pub total: usize,
+}
+
+impl RebaseSession {
+    /// Démarre un rebase de HEAD sur `onto` avec la todo list donnée.
+    pub fn start(
+        repo: &Repository,
+        onto: Oid,
+        entries: Vec<RebaseTodoEntry>,
+    ) -> Result<(Self, RebaseProgress)> {
+        validate_todo(&entries)?;
+        ensure_clean_worktree(repo)?;
+
+        let head = repo.head()?;
+        let orig_head = head.peel_to_commit()?.id();
+        let branch = if head.is_branch() {
+            head.name().map(String::from)
+        } else {
+            None
+        };
+
+        // Détacher HEAD sur la base.
+        let onto_commit = repo.find_commit(onto)?;
+        let mut checkout = git2::build::CheckoutBuilder::new();
+        checkout.force();
+        repo.checkout_tree(onto_commit.as_object(), Some(&mut checkout))?;
+        repo.set_head_detached(onto)?;
+
+        let mut session = Self {
+            onto,
+            orig_head,
+            branch,
+            total: entries.len(),
+            todo: entries.into(),
+            current: None,
+            stopped: None,
+        };
+        match session.run(repo) {
+            Ok(progress) => Ok((session, progress)),
+            // Aucune session n'est rendue à l'appelant : restaurer la branche.
+            Err(error) => match session.abort(repo) {
+                Ok(()) => Err(error),
+                Err(abort_error) => Err(GitSvError::Other(format!(
+                    "{} (restauration de la branche impossible : {})",
+                    error, abort_error
+                ))),
+            },
+        }
+    }
+
+    /// Recharge le rebase interrompu lors d'un lancement précédent de git_sv.
+    ///
+    /// Retourne None s'il n'y a pas de rebase en cours ou s'il a été lancé par git.
+    /// La progression indique où le rebase s'était arrêté.
+    pub fn load(repo: &Repository) -> Result<Option<(Self, RebaseProgress)>> {
+        let dir = repo.path().join(STATE_DIR);
+        let Ok(total) = fs::read_to_string(dir.join(TOTAL_FILE)) else {
+            return Ok(None);
+        };
+
+        let head_name = read_state(&dir, "head-name")?;
+        let session = Self {
+            onto: parse_state_oid(&read_state(&dir, "onto")?)?,
+            orig_head: parse_state_oid(&read_state(&dir, "orig-head")?)?,
+            branch: (head_name != DETACHED_HEAD).then_some(head_name),
+            todo: read_state(&dir, "git-rebase-todo")?
+                .lines()
+                .map(|line| parse_todo_line(&dir, line))
+                .collect::<Result<_>>()?,
+            current: read_entry(&dir, "git_sv-current")?,
+            stopped: read_entry(&dir, "git_sv-stopped")?,
+            total: total
+                .trim()
+                .parse()
+                .map_err(|_| invalid_state(TOTAL_FILE))?,
+        };
+
+        let progress = match (&session.current, &session.stopped) {
+            (Some(_), _) => RebaseProgress::Conflicts(list_conflict_files(repo)?),
+            (None, Some(entry)) => RebaseProgress::Stopped(entry.oid),
+            // Interrompu par une erreur avant l'application de l'entrée suivante.
+            (None, None) if !session.todo.is_empty() => {
+                RebaseProgress::Stopped(repo.head()?.peel_to_commit()?.id())
+            }
+            (None, None) => return Err(invalid_state("git_sv-current")),
+        };
+        Ok(Some((session, progress)))
+    }
+
+    /// Sauvegarde l'état du rebase arrêté, pour le reprendre après un redémarrage.
+    fn save(&self, repo: &Repository) -> Result<()> {
+        let dir = repo.path().join(STATE_DIR);
+        remove_state(repo)?;
+        fs::create_dir_all(&dir).with_context("création de l'état du rebase")?;
+
+        let head_name = self.branch.as_deref().unwrap_or(DETACHED_HEAD);
+        let todo: String = self.todo.iter().map(todo_line).collect();
+        let files = [
+            ("head-name", head_name.to_string()),
+            ("onto", self.onto.to_string()),
+            ("orig-head", self.orig_head.to_string()),
+            ("interactive", String::new()),
+            ("end", self.total.to_string()),
+            ("msgnum", self.done_count().to_string()),
+            (TOTAL_FILE, self.total.to_string()),
+            ("git-rebase-todo", todo),
+        ];
+        for (name, content) in files {
+            write_state(&dir, name, &content)?;
+        }
+        let stops = [
+            ("git_sv-current", &self.current),
+            ("git_sv-stopped", &self.stopped),
+        ];
+        for (name, entry) in stops {
+            if let Some(entry) = entry {
+                write_state(&dir, name, &todo_line(entry))?;
+            }
+        }
+
+        // Les nouveaux messages (reword) peuvent tenir sur plusieurs lignes.
+        let entries = self.todo.iter().chain(&self.current).chain(&self.stopped);
+        for entry in entries {
+            if let Some(ref message) = entry.message {
+                fs::write(dir.join(format!("git_sv-message-{}", entry.oid)), message)
+                    .with_context("écriture d'un message du rebase")?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Nombre d'entrées déjà traitées.
+    pub fn done_count(&self) -> usize {
+        self.total - self.todo.len()
+    }
+
+    /// Reprend le rebase après résolution des conflits ou un arrêt sur edit.
+    pub fn continue_rebase(&mut self, repo: &Repository) -> Result<RebaseProgress> {
+        self.stopped = None;
+        if let Some(entry) = self.current.take() {
+            if repo.index()?.has_conflicts() {
+                self.current = Some(entry);
+                return Err(GitSvError::Other(
+                    "Des conflits non résolus subsistent".to_string(),
+                ));
+            }
+            match self.commit_entry(repo, &entry) {
+                Ok(Some(progress)) => return Ok(progress),
+                Ok(None) => {}
+                Err(error) => {
+                    self.current = Some(entry);
+                    return Err(error);
+                }
+            }
+        }
+        self.run(repo)
+    }
+
+    /// Abandonne l'entrée courante et passe à la suivante.
+    pub fn skip(&mut self, repo: &Repository) -> Result<RebaseProgress> {
+        self.current = None;
+        self.stopped = None;
+        let head = repo.head()?.peel_to_commit()?;
+        repo.reset(head.as_object(), git2::ResetType::Hard, None)?;
+        repo.cleanup_state()?;
+        self.run(repo)
+    }
+
+    /// Annule le rebase et restaure la branche dans son état initial.
+    pub fn abort(&self, repo: &Repository) -> Result<()> {
+        repo.cleanup_state()?;
+        remove_state(repo)?;
+        match self.branch {
+            // La branche n'a pas bougé pendant le rebase.
+            Some(ref branch) => repo.set_head(branch)?,
+            None => repo.set_head_detached(self.orig_head)?,
+        }
+        let orig = repo.find_commit(self.orig_head)?;
+        repo.reset(orig.as_object(), git2::ResetType::Hard, None)?;
+        Ok(())
+    }
+
+    /// Applique les entrées restantes jusqu'à la fin ou au prochain arrêt.
+    fn run(&mut self, repo: &Repository) -> Result<RebaseProgress> {
+        while let Some(entry) = self.todo.pop_front() {
+            if entry.action == TodoAction::Drop {
+                continue;
+            }
+
+            let picked = repo
+                .find_commit(entry.oid)
+                .and_then(|commit| repo.cherrypick(&commit, None));
+            if let Err(error) = picked {
+                // L'entrée n'a pas été appliquée : elle sera retentée par Continue.
+                self.todo.push_front(entry);
+                return Err(self.interrupted(repo, error.into()));
+            }
+
+            if repo.index()?.has_conflicts() {
+                self.current = Some(entry);
+                self.save(repo)?;
+                return Ok(RebaseProgress::Conflicts(list_conflict_files(repo)?));
+            }
+
+            match self.commit_entry(repo, &entry) {
+                Ok(Some(progress)) => return Ok(progress),
+                Ok(None) => {}
+                Err(error) => {
+                    // Les changements restent dans l'index : Continue retentera le commit.
+                    self.current = Some(entry);
+                    return Err(self.interrupted(repo, error));
+                }
+            }
+        }
+
+        self.finish(repo)?;
+        Ok(RebaseProgress::Completed)
+    }
+
+    /// Sauvegarde la session après une erreur, pour que Continue et Abort restent possibles.
+    fn interrupted(&self, repo: &Repository, error: GitSvError) -> GitSvError {
+        match self.save(repo) {
+            Ok(()) => error,
+            Err(save_error) => GitSvError::Other(format!(
+                "{} (sauvegarde du rebase impossible : {})",
+                error, save_error
+            )),
+        }
+    }
+
+    /// Crée le commit correspondant à une entrée dont les changements sont dans l'index.
+    fn commit_entry(
+        &mut self,
+        repo: &Repository,
+        entry: &RebaseTodoEntry,
+    ) -> Result<Option<RebaseProgress>> {
+        let original = repo.find_commit(entry.oid)?;
+        let head = repo.head()?.peel_to_commit()?;
+        let committer = repo
+            .signature()
+            .or_else(|_| Signature::now("git_sv", "git_sv@local"))?;
+
+        let mut index = repo.index()?;
+        let tree = repo.find_tree(index.write_tree()?)?;
+
+        // Tant qu'aucun commit n'a été rebasé (précédents passés ou devenus vides),
+        // HEAD est encore `onto`, qui ne fait pas partie du rebase : un squash ou un
+        // fixup n'a alors rien à fusionner et s'applique comme un pick.
+        if entry.action.is_fold() && head.id() != self.onto {
+            let message = match entry.action {
+                TodoAction::Squash => format!(
+                    "{}\n\n{}",
+                    head.message().unwrap_or("").trim_end(),
+                    original.message().unwrap_or("")
+                ),
+                _ => head.message().unwrap_or("").to_string(),
+            };
+            let parents: Vec<git2::Commit> = head.parents().collect();
+            let parents: Vec<&git2::Commit> = parents.iter().collect();
+            let oid = write_commit(
+                repo,
+                None,
+                &head.author(),
+                &committer,
+                &message,
+                &tree,
+                &parents,
+            )?;
+            repo.set_head_detached(oid)?;
+        } else if tree.id() != head.tree_id() {
+            let message = entry
+                .message
+                .clone()
+                .unwrap_or_else(|| original.message().unwrap_or("").to_string());
+            write_commit(
+                repo,
+                Some("HEAD"),
+                &original.author(),
+                &committer,
+                &message,
+                &tree,
+                &[&head],
+            )?;
+        }
+        // Sinon le commit est devenu vide : il est ignoré.
+
+        repo.cleanup_state()?;
+
+        if entry.action == TodoAction::Edit {
+            self.stopped = Some(entry.clone());
+            self.save(repo)?;
+            return Ok(Some(RebaseProgress::Stopped(entry.oid)));
+        }
+        Ok(None)
+    }
+
+    /// Déplace la branche d'origine sur le résultat du rebase.
+    fn finish(&self, repo: &Repository) -> Result<()> {
+        if let Some(ref branch) = self.branch {
+            let new_head = repo.head()?.peel_to_commit()?.id();
+            repo.reference(branch, new_head, true, "rebase (finish)")?;
+            repo.set_head(branch)?;
+        }
+        remove_state(repo)
+    }
+}
+
+/// Supprime l'état sauvegardé du rebase, s'il existe.
+fn remove_state(repo: &Repository) -> Result<()> {
+    let dir = repo.path().join(STATE_DIR);
+    if dir.exists() {
+        fs::remove_dir_all(&dir).with_context("suppression de l'état du rebase")?;
+    }
+    Ok(())
+}
+
+fn read_state(dir: &Path, name: &str) -> Result<String> {
+    let content = fs::read_to_string(dir.join(name))
+        .with_context(format!("lecture de l'état du rebase ({})", name))?;
+    Ok(content.trim_end_matches('\n').to_string())
+}
+
+fn write_state(dir: &Path, name: &str, content: &str) -> Result<()> {
+    let content = if content.is_empty() || content.ends_with('\n') {
+        content.to_string()
+    } else {
+        format!("{}\n", content)
+    };
+    fs::write(dir.join(name), content)
+        .with_context(format!("écriture de l'état du rebase ({})", name))
+}
+
+/// Entrée sauvegardée seule dans un fichier (None si le fichier n'existe pas).
+fn read_entry(dir: &Path, name: &str) -> Result<Option<RebaseTodoEntry>> {
+    if !dir.join(name).exists() {
+        return Ok(None);
+    }
+    parse_todo_line(dir, &read_state(dir, name)?).map(Some)
+}
+
+/// Ligne de la todo list : `<action> <oid> <résumé>`, comme `git rebase -i`.
+fn todo_line(entry: &RebaseTodoEntry) -> String {
+    format!("{} {} {}\n", entry.action.label(), entry.oid, entry.summary)
+}
+
+fn parse_todo_line(dir: &Path, line: &str) -> Result<RebaseTodoEntry> {
+    let mut parts = line.splitn(3, ' ');
+    let action = parts
+        .next()
+        .and_then(TodoAction::from_label)
+        .ok_or_else(|| invalid_state("git-rebase-todo"))?;
+    let oid = parse_state_oid(parts.next().unwrap_or(""))?;
+    let message_file = dir.join(format!("git_sv-message-{}", oid));
+    let message = if message_file.exists() {
+        Some(fs::read_to_string(message_file).with_context("lecture d'un message du rebase")?)
+    } else {
+        None
+    };
+    Ok(RebaseTodoEntry {
+        oid,
+        summary: parts.next().unwrap_or("").to_string(),
+        action,
+        message,
+    })
+}
+
+fn parse_state_oid(value: &str) -> Result<Oid> {
+    Oid::from_str(value.trim()).map_err(|_| invalid_state(value))
+}
+
+fn invalid_state(detail: &str) -> GitSvError {
+    GitSvError::Other(format!("État du rebase sauvegardé invalide ({})", detail))
+}
+
+/// Vérifie la cohérence de la todo list avant de démarrer.
+fn validate_todo(entries: &[RebaseTodoEntry]) -> Result<()> {
+    let first = entries.iter().find(|e| e.action != TodoAction::Drop);
+    match first {
+        None => Err(GitSvError::Other(
+            "Aucun commit à appliquer dans la todo list".to_string(),
+        )),
+        Some(entry) if entry.action.is_fold() => Err(GitSvError::Other(format!(
+            "Impossible de faire un {} sans commit précédent",
+            entry.action.label()
+        ))),
+        Some(_) => Ok(()),
+    }
+}
+
+/// Refuse de démarrer si le working directory contient des modifications suivies.
+pub(crate) fn ensure_clean_worktree(repo: &Repository) -> Result<()> {
+    let mut opts = git2::StatusOptions::new();
+    opts.include_untracked(false).include_ignored(false);
+    if !repo.statuses(Some(&mut opts))?.is_empty() {
+        return Err(GitSvError::Other(
+            "Le working directory doit être propre pour lancer un rebase".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::tests::test_utils::{commit_file, create_test_repo};
+
+    fn messages(repo: &Repository) -> Vec<String> {
+        let mut revwalk = repo.revwalk().unwrap();
+        revwalk.push_head().unwrap();
+        revwalk
+            .map(|oid| {
+                let commit = repo.find_commit(oid.unwrap()).unwrap();
+                commit.message().unwrap().to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_rebase_todo_lists_commits_oldest_first() {
+        let (_dir, repo) = create_test_repo();
+        let base = commit_file(&repo, "a.txt", "a\n", "base");
+        commit_file(&repo, "b.txt", "b\n", "second");
+        commit_file(&repo, "c.txt", "c\n", "third");
+
+        let todo = rebase_todo(&repo, base).unwrap();
+        let summaries: Vec<&str> = todo.iter().map(|e| e.summary.as_str()).collect();
+        assert_eq!(summaries, vec!["second", "third"]);
+        assert!(todo.iter().all(|e| e.action == TodoAction::Pick));
+    }
+
+    #[test]
+    fn test_rebase_reorder_reword_and_drop() {
+        let (_dir, repo) = create_test_repo();
+        let base = commit_file(&repo, "a.txt", "a\n", "base");
+        commit_file(&repo, "b.txt", "b\n", "second");
+        commit_file(&repo, "c.txt", "c\n", "third");
+        commit_file(&repo, "d.txt", "d\n", "fourth");
+
+        let mut todo = rebase_todo(&repo, base).unwrap();
+        todo.swap(0, 1);
+        todo[1].action = TodoAction::Reword;
+        todo[1].message = Some("second reworded".to_string());
+        todo[2].action = TodoAction::Drop;
+
+        let (_session, progress) = RebaseSession::start(&repo, base, todo).unwrap();
+        assert!(matches!(progress, RebaseProgress::Completed));
+
+        assert_eq!(messages(&repo), vec!["second reworded", "third", "base"]);
+        assert!(repo.head().unwrap().is_branch());
+        assert!(!repo.workdir().unwrap().join("d.txt").exists());
+    }
+
+    #[test]
+    fn test_rebase_squash_and_fixup() {
+        let (_dir, repo) = create_test_repo();
+        let base = commit_file(&repo, "a.txt", "a\n", "base");
+        commit_file(&repo, "b.txt", "b\n", "feature");
+        commit_file(&repo, "c.txt", "c\n", "more");
+        commit_file(&repo, "d.txt", "d\n", "typo");
+
+        let mut todo = rebase_todo(&repo, base).unwrap();
+        todo[1].action = TodoAction::Squash;
+        todo[2].action = TodoAction::Fixup;
+
+        let (_session, progress) = RebaseSession::start(&repo, base, todo).unwrap();
+        assert!(matches!(progress, RebaseProgress::Completed));
+
+        let messages = messages(&repo);
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0], "feature\n\nmore");
+        let head = repo.head().unwrap().peel_to_tree().unwrap();
+        assert!(head.get_name("d.txt").is_some());
+    }
+
+    #[test]
+    fn test_rebase_rejects_leading_squash() {
+        let (_dir, repo) = create_test_repo();
+        let base = commit_file(&repo, "a.txt", "a\n", "base");
+        commit_file(&repo, "b.txt", "b\n", "second");
+
+        let mut todo = rebase_todo(&repo, base).unwrap();
+        todo[0].action = TodoAction::Fixup;
+        assert!(RebaseSession::start(&repo, base, todo).is_err());
+    }
+
+    #[test]
+    fn test_rebase_stops_on_edit_then_continues() {
+        let (_dir, repo) = create_test_repo();
+        let base = commit_file(&repo, "a.txt", "a\n", "base");
+        commit_file(&repo, "b.txt", "b\n", "second");
+        commit_file(&repo, "c.txt", "c\n", "third");
+
+        let mut todo = rebase_todo(&repo, base).unwrap();
+        todo[0].action = TodoAction::Edit;
+
+        let (mut session, progress) = RebaseSession::start(&repo, base, todo).unwrap();
+        assert!(matches!(progress, RebaseProgress::Stopped(_)));
+        assert!(repo.head_detached().unwrap());
+        assert_eq!(session.done_count(), 1);
+
+        let progress = session.continue_rebase(&repo).unwrap();
+        assert!(matches!(progress, RebaseProgress::Completed));
+        assert_eq!(messages(&repo), vec!["third", "second", "base"]);
+    }
+
+    #[test]
+    fn test_stopped_rebase_is_saved_and_reloaded() {
+        let (_dir, repo) = create_test_repo();
+        let base = commit_file(&repo, "a.txt", "a\n", "base");
+        commit_file(&repo, "b.txt", "b\n", "second");
+        commit_file(&repo, "c.txt", "c\n", "third");
+
+        let mut todo = rebase_todo(&repo, base).unwrap();
+        todo[0].action = TodoAction::Edit;
+        todo[1].action = TodoAction::Reword;
+        todo[1].message = Some("third\n\nreworded".to_string());
+
+        let (session, _) = RebaseSession::start(&repo, base, todo).unwrap();
+        assert_eq!(repo.state(), git2::RepositoryState::RebaseInteractive);
+
+        // Un nouveau lancement reprend le rebase là où il s'était arrêté
+        let (mut loaded, progress) = RebaseSession::load(&repo).unwrap().unwrap();
+        assert_eq!(loaded, session);
+        assert!(matches!(progress, RebaseProgress::Stopped(_)));
+
+        let progress = loaded.continue_rebase(&repo).unwrap();
+        assert!(matches!(progress, RebaseProgress::Completed));
+        assert_eq!(messages(&repo), vec!["third\n\nreworded", "second", "base"]);
+        assert!(RebaseSession::load(&repo).unwrap().is_none());
+        assert_eq!(repo.state(), git2::RepositoryState::Clean);
+    }
+
+    #[test]
+    fn test_reloaded_conflicting_rebase_can_be_aborted() {
+        let (_dir, repo) = create_test_repo();
+        let base = commit_file(&repo, "a.txt", "base\n", "base");
+        commit_file(&repo, "a.txt", "first\n", "first");
+        let orig = commit_file(&repo, "a.txt", "second\n", "second");
+
+        let mut todo = rebase_todo(&repo, base).unwrap();
+        todo.swap(0, 1);
+        RebaseSession::start(&repo, base, todo).unwrap();
+
+        let (loaded, progress) = RebaseSession::load(&repo).unwrap().unwrap();
+        assert!(matches!(progress, RebaseProgress::Conflicts(ref files) if files.len() == 1));
+        assert_eq!(loaded.current.as_ref().unwrap().summary, "second");
+
+        loaded.abort(&repo).unwrap();
+        assert_eq!(repo.head().unwrap().target(), Some(orig));
+        assert!(RebaseSession::load(&repo).unwrap().is_none());
+    }
+
+    /// Fait échouer toute création de commit (format de signature inconnu).
+    fn break_signing(repo: &Repository, broken: bool) {
+        let mut config = repo.config().unwrap();
+        config.set_bool("commit.gpgsign", broken).unwrap();
+        config.set_str("gpg.format", "inconnu").unwrap();
+    }
+
+    #[test]
+    fn test_failed_rebase_start_restores_branch() {
+        let (_dir, repo) = create_test_repo();
+        let base = commit_file(&repo, "a.txt", "a\n", "base");
+        commit_file(&repo, "b.txt", "b\n", "second");
+        let orig = commit_file(&repo, "c.txt", "c\n", "third");
+
+        let mut todo = rebase_todo(&repo, base).unwrap();
+        todo.swap(0, 1);
+        break_signing(&repo, true);
+        assert!(RebaseSession::start(&repo, base, todo).is_err());
+
+        assert!(repo.head().unwrap().is_branch());
+        assert_eq!(repo.head().unwrap().target(), Some(orig));
+        assert_eq!(repo.state(), git2::RepositoryState::Clean);
+        assert!(repo.workdir().unwrap().join("c.txt").exists());
+        assert!(RebaseSession::load(&repo).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_failed_rebase_step_keeps_the_session() {
+        let (_dir, repo) = create_test_repo();
+        let base = commit_file(&repo, "a.txt", "a\n", "base");
+        commit_file(&repo, "b.txt", "b\n", "second");
+        commit_file(&repo, "c.txt", "c\n", "third");
+
+        let mut todo = rebase_todo(&repo, base).unwrap();
+        todo[0].action = TodoAction::Edit;
+        let (mut session, _) = RebaseSession::start(&repo, base, todo).unwrap();
+
+        // Le commit de "third" échoue : l'entrée reste en cours, sur disque aussi
+        break_signing(&repo, true);
+        assert!(session.continue_rebase(&repo).is_err());
+        assert_eq!(session.current.as_ref().unwrap().summary, "third");
+        let (loaded, _) = RebaseSession::load(&repo).unwrap().unwrap();
+        assert_eq!(loaded, session);
+        assert!(session.continue_rebase(&repo).is_err());
+        assert_eq!(session.current.as_ref().unwrap().summary, "third");
+
+        break_signing(&repo, false);
+        let progress = session.continue_rebase(&repo).unwrap();
+        assert!(matches!(progress, RebaseProgress::Completed));
+        assert_eq!(messages(&repo), vec!["third", "second", "base"]);
+    }
+
+    #[test]
+    fn test_rebase_conflict_then_abort_restores_branch() {
+        let (_dir, repo) = create_test_repo();
+        let base = commit_file(&repo, "a.txt", "base\n", "base");
+        commit_file(&repo, "a.txt", "first\n", "first");
+        let orig = commit_file(&repo, "a.txt", "second\n", "second");
+
+        // Inverser les deux commits modifiant la même ligne provoque un conflit.
+        let mut todo = rebase_todo(&repo, base).unwrap();
+        todo.swap(0, 1);
+
+        let (session, progress) = RebaseSession::start(&repo, base, todo).unwrap();
+        match progress {
+            RebaseProgress::Conflicts(files) => assert_eq!(files[0].path, "a.txt"),
+            other => panic!("conflit attendu, obtenu {:?}", other),
+        }
+
+        session.abort(&repo).unwrap();
+        assert_eq!(repo.head().unwrap().name(), Some("refs/heads/main"));
+        assert_eq!(repo.head().unwrap().target(), Some(orig));
+        let content = std::fs::read_to_string(repo.workdir().unwrap().join("a.txt")).unwrap();
+        assert_eq!(content, "second\n");
+    }
+
+    #[test]
+    fn test_rebase_skip_conflicting_commit() {
+        let (_dir, repo) = create_test_repo();
+        let base = commit_file(&repo, "a.txt", "base\n", "base");
+        commit_file(&repo, "a.txt", "first\n", "first");
+        commit_file(&repo, "a.txt", "second\n", "second");
+
+        let mut todo = rebase_todo(&repo, base).unwrap();
+        todo.swap(0, 1);
+
+        let (mut session, progress) = RebaseSession::start(&repo, base, todo).unwrap();
+        assert!(matches!(progress, RebaseProgress::Conflicts(_)));
+
+        // Le commit "second" est abandonné, "first" s'applique proprement.
+        let progress = session.skip(&repo).unwrap();
+        assert!(matches!(progress, RebaseProgress::Completed));
+        assert_eq!(messages(&repo), vec!["first", "base"]);
+    }
+
+    #[test]
+    fn test_fold_without_rebased_predecessor_is_picked() {
+        let (_dir, repo) = create_test_repo();
+        let base = commit_file(&repo, "a.txt", "base\n", "base");
+        commit_file(&repo, "a.txt", "first\n", "first");
+        commit_file(&repo, "a.txt", "second\n", "second");
+
+        let mut todo = rebase_todo(&repo, base).unwrap();
+        todo.swap(0, 1);
+        todo[1].action = TodoAction::Fixup;
+
+        let (mut session, progress) = RebaseSession::start(&repo, base, todo).unwrap();
+        assert!(matches!(progress, RebaseProgress::Conflicts(_)));
+
+        // "second" est passé : le fixup ne doit pas réécrire la base.
+        let progress = session.skip(&repo).unwrap();
+        assert!(matches!(progress, RebaseProgress::Completed));
+        assert_eq!(messages(&repo), vec!["first", "base"]);
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.parent_id(0).unwrap(), base);
+    }
+}
@@ -0,0 +1,116 @@
+//! Lecture du reflog et restauration de ses entrées.
+
+use git2::{Oid, Repository};
+
+use crate::error::Result;
+
+/// Entrée du reflog d'une référence.
+#[derive(Debug, Clone)]
+pub struct ReflogEntry {
+    /// Position dans le reflog (0 = la plus récente, comme `HEAD@{0}`).
+    pub index: usize,
+    /// Oid avant le mouvement (zéro pour la création de la référence).
+    pub old_oid: Oid,
+    /// Oid après le mouvement.
+    pub new_oid: Oid,
+    /// Message de l'action (ex: "commit (amend): ...", "reset: moving to ...").
+    pub message: String,
+    /// Auteur du mouvement.
+    pub committer: String,
+    /// Date du mouvement (timestamp unix).
+    pub timestamp: i64,
+}
+
+impl Default for ReflogEntry {
+    fn default() -> Self {
+        Self {
+            index: 0,
+            old_oid: Oid::zero(),
+            new_oid: Oid::zero(),
+            message: String::new(),
+            committer: String::new(),
+            timestamp: 0,
+        }
+    }
+}
+
+/// Lit le reflog d'une référence (ex: "HEAD", "refs/heads/main"), du plus récent au plus ancien.
+pub fn read_reflog(repo: &Repository, refname: &str) -> Result<Vec<ReflogEntry>> {
+    let reflog = repo.reflog(refname)?;
+    let entries = reflog
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let committer = entry.committer();
+            ReflogEntry {
+                index,
+                old_oid: entry.id_old(),
+                new_oid: entry.id_new(),
+                message: entry.message().unwrap_or("").to_string(),
+                committer: committer.name().unwrap_or("Inconnu").to_string(),
+                timestamp: committer.when().seconds(),
+            }
+        })
+        .collect();
+    Ok(entries)
+}
+
+/// Checkout d'un commit en HEAD détachée.
+///
+/// Échoue si des modifications locales seraient écrasées.
+pub fn checkout_commit(repo: &Repository, oid: Oid) -> Result<()> {
+    let commit = repo.find_commit(oid)?;
+    repo.checkout_tree(commit.as_object(), None)?;
+    repo.set_head_detached(oid)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::reset::{reset_to, ResetMode};
+    use crate::git::tests::test_utils::*;
+
+    #[test]
+    fn test_read_reflog_records_reset() {
+        let (_temp_dir, repo) = create_test_repo();
+        let first = commit_file(&repo, "a.txt", "v1", "First");
+        let second = commit_file(&repo, "a.txt", "v2", "Second");
+
+        reset_to(&repo, first, ResetMode::Hard).unwrap();
+
+        let entries = read_reflog(&repo, "HEAD").unwrap();
+        assert!(!entries.is_empty());
+        let latest = &entries[0];
+        assert_eq!(latest.index, 0);
+        assert_eq!(latest.old_oid, second);
+        assert_eq!(latest.new_oid, first);
+    }
+
+    #[test]
+    fn test_read_reflog_of_branch() {
+        let (_temp_dir, repo) = create_test_repo();
+        let oid = commit_file(&repo, "a.txt", "v1", "First");
+        let commit = repo.find_commit(oid).unwrap();
+        repo.branch("feature", &commit, false).unwrap();
+
+        let entries = read_reflog(&repo, "refs/heads/feature").unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].new_oid, oid);
+        assert!(entries[0].old_oid.is_zero());
+    }
+
+    #[test]
+    fn test_checkout_commit_detaches_head() {
+        let (dir, repo) = create_test_repo();
+        let first = commit_file(&repo, "a.txt", "v1", "First");
+        commit_file(&repo, "a.txt", "v2", "Second");
+
+        checkout_commit(&repo, first).unwrap();
+
+        assert!(repo.head_detached().unwrap());
+        assert_eq!(repo.head().unwrap().target(), Some(first));
+        let content = std::fs::read_to_string(dir.path().join("a.txt")).unwrap();
+        assert_eq!(content, "v1");
+    }
+}
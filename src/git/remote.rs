@@ -1,11 +1,88 @@
-use crate::error::Result;
+use crate::error::{GitSvError, IoErrorContext, Result};
 use git2::{Cred, CredentialType, FetchOptions, PushOptions, RemoteCallbacks, Repository};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// Intervalle de vérification de l'annulation pendant une commande git CLI.
+const CLI_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Avancement d'un transfert avec le remote.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TransferProgress {
+    /// Objets transférés (reçus pour un fetch, envoyés pour un push).
+    pub objects: usize,
+    /// Nombre total d'objets à transférer (0 : inconnu).
+    pub total_objects: usize,
+    /// Octets transférés.
+    pub bytes: usize,
+    /// Dernier message du serveur ou de git (ex: "Compressing objects: 45%").
+    pub message: String,
+}
+
+/// Suivi partagé d'un transfert : avancement et demande d'annulation.
+///
+/// Les callbacks de git2 (ou la sortie de git CLI) le mettent à jour depuis le
+/// thread de l'opération ; l'interface le lit et peut demander l'annulation.
+#[derive(Debug, Clone, Default)]
+pub struct ProgressTracker {
+    progress: Arc<Mutex<TransferProgress>>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl ProgressTracker {
+    /// Copie de l'avancement courant.
+    pub fn snapshot(&self) -> TransferProgress {
+        self.progress
+            .lock()
+            .map(|progress| progress.clone())
+            .unwrap_or_default()
+    }
+
+    /// Demande l'annulation du transfert.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// L'annulation a-t-elle été demandée ?
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    fn update(&self, apply: impl FnOnce(&mut TransferProgress)) {
+        if let Ok(mut progress) = self.progress.lock() {
+            apply(&mut progress);
+        }
+    }
+
+    /// Retient la dernière ligne non vide d'un message de progression.
+    fn set_message(&self, text: &str) {
+        if let Some(line) = text
+            .split(['\r', '\n'])
+            .map(str::trim)
+            .rfind(|line| !line.is_empty())
+        {
+            self.update(|progress| progress.message = line.to_string());
+        }
+    }
+}
+
+/// Erreur d'un transfert interrompu par l'utilisateur.
+fn cancelled_error() -> GitSvError {
+    GitSvError::OperationFailed {
+        operation: "transfert",
+        details: "annulé par l'utilisateur".to_string(),
+    }
+}
 
 /// Résout le nom du remote à partir du nom de branche.
 /// Extrait le nom du remote depuis une référence upstream comme "refs/remotes/origin/main" -> "origin".
-fn resolve_remote_name(repo: &Repository, branch_name: &str) -> String {
+pub fn resolve_remote_name(repo: &Repository, branch_name: &str) -> String {
     repo.branch_upstream_name(&format!("refs/heads/{}", branch_name))
         .ok()
         .and_then(|name| name.as_str().map(|s| s.to_string()))
@@ -218,12 +295,51 @@ fn resolve_ssh_credentials(
     )))
 }
 
-/// Construit les RemoteCallbacks avec la résolution SSH améliorée
-fn build_remote_callbacks() -> RemoteCallbacks<'static> {
+/// Construit les RemoteCallbacks avec la résolution SSH améliorée.
+///
+/// La progression est reportée dans `progress` ; une annulation interrompt le
+/// transfert au prochain callback qui le permet (réception d'objets, message du
+/// serveur ou négociation du push).
+fn build_remote_callbacks(progress: &ProgressTracker) -> RemoteCallbacks<'static> {
     let mut callbacks = RemoteCallbacks::new();
     callbacks.credentials(|url, username_from_url, allowed_types| {
         resolve_ssh_credentials(url, username_from_url, allowed_types)
     });
+
+    let tracker = progress.clone();
+    callbacks.transfer_progress(move |stats| {
+        tracker.update(|progress| {
+            progress.objects = stats.received_objects();
+            progress.total_objects = stats.total_objects();
+            progress.bytes = stats.received_bytes();
+        });
+        !tracker.is_cancelled()
+    });
+
+    let tracker = progress.clone();
+    callbacks.push_transfer_progress(move |current, total, bytes| {
+        tracker.update(|progress| {
+            progress.objects = current;
+            progress.total_objects = total;
+            progress.bytes = bytes;
+        });
+    });
+
+    let tracker = progress.clone();
+    callbacks.sideband_progress(move |data| {
+        tracker.set_message(&String::from_utf8_lossy(data));
+        !tracker.is_cancelled()
+    });
+
+    let tracker = progress.clone();
+    callbacks.push_negotiation(move |_| {
+        if tracker.is_cancelled() {
+            Err(git2::Error::from_str("push annulé"))
+        } else {
+            Ok(())
+        }
+    });
+
     callbacks
 }
 
@@ -247,139 +363,507 @@ fn resolve_remote_url(url: &str) -> String {
     url.to_string()
 }
 
-/// Push la branche courante vers le remote.
+/// Options d'un push de branche.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PushRequest {
+    /// Branche locale à pousser.
+    pub branch: String,
+    /// Remote de destination.
+    pub remote: String,
+    /// Branche distante mise à jour (par défaut, celle de l'upstream ou le même nom).
+    pub remote_branch: String,
+    /// Écraser la branche distante si elle n'a pas bougé depuis le dernier fetch.
+    pub force_with_lease: bool,
+    /// Configurer la branche distante comme upstream.
+    pub set_upstream: bool,
+    /// Pousser aussi les tags locaux.
+    pub include_tags: bool,
+}
+
+impl PushRequest {
+    /// Push simple de `branch` vers `remote`.
+    ///
+    /// La branche distante est celle de l'upstream s'il est sur ce remote ;
+    /// l'upstream est configuré si la branche n'en a pas.
+    pub fn new(repo: &Repository, branch: &str, remote: &str) -> Self {
+        let upstream = upstream_of(repo, branch);
+        let remote_branch = match upstream {
+            Some((ref upstream_remote, ref merge)) if upstream_remote == remote => merge.clone(),
+            _ => branch.to_string(),
+        };
+        Self {
+            branch: branch.to_string(),
+            remote: remote.to_string(),
+            remote_branch,
+            force_with_lease: false,
+            set_upstream: upstream.is_none(),
+            include_tags: false,
+        }
+    }
+
+    /// Push simple de `branch` vers son remote (fallback vers "origin").
+    pub fn for_branch(repo: &Repository, branch: &str) -> Self {
+        Self::new(repo, branch, &resolve_remote_name(repo, branch))
+    }
+
+    /// Référence de suivi de la branche distante (ex: "refs/remotes/origin/main").
+    fn tracking_ref(&self) -> String {
+        format!("refs/remotes/{}/{}", self.remote, self.remote_branch)
+    }
+}
+
+/// Remote et branche distante (sans "refs/heads/") de l'upstream d'une branche locale.
+fn upstream_of(repo: &Repository, branch: &str) -> Option<(String, String)> {
+    let config = repo.config().ok()?;
+    let remote = config
+        .get_string(&format!("branch.{}.remote", branch))
+        .ok()?;
+    let merge = config
+        .get_string(&format!("branch.{}.merge", branch))
+        .ok()?;
+    let merge = merge
+        .strip_prefix("refs/heads/")
+        .unwrap_or(&merge)
+        .to_string();
+    Some((remote, merge))
+}
+
+/// Push refusé : la branche distante contient des commits absents en local.
+const REJECTED_NON_FAST_FORWARD: &str =
+    "la branche distante contient des commits absents en local (non fast-forward)";
+
+/// Push refusé : le lease ne correspond plus à la branche distante.
+const REJECTED_STALE_LEASE: &str =
+    "la branche distante a changé depuis le dernier fetch (lease périmé)";
+
+fn push_rejected(request: &PushRequest, reason: &str) -> GitSvError {
+    GitSvError::PushRejected {
+        branch: request.branch.clone(),
+        remote: request.remote.clone(),
+        reason: reason.to_string(),
+    }
+}
+
+/// Push la branche courante vers son remote.
 /// Retourne un message décrivant l'action effectuée.
 /// Utilise le remote configuré avec résolution SSH des alias.
-pub fn push_current_branch(repo: &Repository) -> Result<String> {
+/// La progression est reportée dans `progress`.
+pub fn push_current_branch(repo: &Repository, progress: &ProgressTracker) -> Result<String> {
     // Récupérer la branche courante
     let head = repo.head()?;
     let branch_name = head
         .shorthand()
         .ok_or_else(|| git2::Error::from_str("HEAD détachée, impossible de pousser"))?;
 
-    // Vérifier si la branche a un upstream configuré
-    let has_upstream = repo
-        .branch_upstream_name(&format!("refs/heads/{}", branch_name))
-        .is_ok();
+    push_branch(repo, &PushRequest::for_branch(repo, branch_name), progress)
+}
 
-    // Récupérer le nom du remote (fallback vers "origin")
-    let remote_name = resolve_remote_name(repo, branch_name);
+/// Push une branche locale selon les options données.
+/// Retourne un message décrivant l'action effectuée.
+///
+/// Un refus du remote (non fast-forward, lease périmé) est signalé par
+/// `GitSvError::PushRejected`. Avec force-with-lease, la branche distante
+/// n'est écrasée que si elle pointe encore sur la référence de suivi locale
+/// (vérifié pendant la négociation, comme `git push --force-with-lease`).
+pub fn push_branch(
+    repo: &Repository,
+    request: &PushRequest,
+    progress: &ProgressTracker,
+) -> Result<String> {
+    // Lease : oid de la branche distante au dernier fetch (zéro si elle n'existait pas)
+    let expected = repo
+        .refname_to_id(&request.tracking_ref())
+        .unwrap_or_else(|_| git2::Oid::zero());
+    let remote_ref = format!("refs/heads/{}", request.remote_branch);
+
+    let force = if request.force_with_lease { "+" } else { "" };
+    let mut refspecs = vec![format!(
+        "{}refs/heads/{}:{}",
+        force, request.branch, remote_ref
+    )];
+    if request.include_tags {
+        refspecs.extend(
+            repo.tag_names(None)?
+                .iter()
+                .flatten()
+                .map(|name| format!("refs/tags/{}:refs/tags/{}", name, name)),
+        );
+    }
 
-    // Récupérer le remote et résoudre l'URL
-    let remote = repo.find_remote(&remote_name)?;
-    let raw_url = remote.url().unwrap_or("");
-    let resolved_url = resolve_remote_url(raw_url);
+    // Callbacks avec vérification du lease et relevé des refus du serveur
+    let stale = Arc::new(AtomicBool::new(false));
+    let rejection = Arc::new(Mutex::new(None::<String>));
+    let mut callbacks = build_remote_callbacks(progress);
+    let tracker = progress.clone();
+    let lease_stale = Arc::clone(&stale);
+    let lease = request.force_with_lease;
+    let lease_ref = remote_ref.clone();
+    callbacks.push_negotiation(move |updates| {
+        if tracker.is_cancelled() {
+            return Err(git2::Error::from_str("push annulé"));
+        }
+        let moved = updates
+            .iter()
+            .any(|u| u.dst_refname() == Some(lease_ref.as_str()) && u.src() != expected);
+        if lease && moved {
+            lease_stale.store(true, Ordering::SeqCst);
+            return Err(git2::Error::from_str(REJECTED_STALE_LEASE));
+        }
+        Ok(())
+    });
+    let refused = Arc::clone(&rejection);
+    callbacks.push_update_reference(move |refname, status| {
+        if let Some(status) = status {
+            if let Ok(mut refused) = refused.lock() {
+                refused.get_or_insert_with(|| format!("{}: {}", refname, status));
+            }
+        }
+        Ok(())
+    });
 
-    // Options de push avec callbacks SSH
     let mut push_options = PushOptions::new();
-    push_options.remote_callbacks(build_remote_callbacks());
+    push_options.remote_callbacks(callbacks);
 
-    // Pousser la branche courante
-    let push_refspec = format!("refs/heads/{}:refs/heads/{}", branch_name, branch_name);
+    let remote = repo.find_remote(&request.remote)?;
+    let raw_url = remote.url().unwrap_or("");
+    run_pre_push(repo, &request.remote, raw_url, expected, &refspecs)?;
 
+    let resolved_url = resolve_remote_url(raw_url);
     let result = if resolved_url != raw_url {
         // L'URL a été réécrite, utiliser un remote anonyme
         let mut push_remote = repo.remote_anonymous(&resolved_url)?;
-        push_remote.push(&[&push_refspec], Some(&mut push_options))
+        push_remote.push(&refspecs, Some(&mut push_options))
     } else {
         // Utiliser le remote configuré
-        let mut push_remote = repo.find_remote(&remote_name)?;
-        push_remote.push(&[&push_refspec], Some(&mut push_options))
+        let mut push_remote = repo.find_remote(&request.remote)?;
+        push_remote.push(&refspecs, Some(&mut push_options))
     };
 
-    // Si le push échoue, essayer avec git CLI en fallback
-    if let Err(_) = result {
-        return push_current_branch_cli(repo);
+    match result {
+        Err(_) if progress.is_cancelled() => return Err(cancelled_error()),
+        Err(_) if stale.load(Ordering::SeqCst) => {
+            return Err(push_rejected(request, REJECTED_STALE_LEASE))
+        }
+        Err(e) if e.code() == git2::ErrorCode::NotFastForward => {
+            return Err(push_rejected(request, REJECTED_NON_FAST_FORWARD))
+        }
+        // Autre échec (ex: alias SSH) : essayer avec git CLI en fallback
+        Err(_) => return push_branch_cli(repo, request, expected, progress),
+        Ok(()) => {}
+    }
+    if let Some(status) = rejection.lock().ok().and_then(|mut r| r.take()) {
+        return Err(
+            if status.contains("non-fast-forward") || status.contains("fetch first") {
+                push_rejected(request, REJECTED_NON_FAST_FORWARD)
+            } else {
+                GitSvError::OperationFailed {
+                    operation: "push",
+                    details: status,
+                }
+            },
+        );
+    }
+
+    // Configurer l'upstream comme `git push --set-upstream`
+    if request.set_upstream {
+        let mut config = repo.config()?;
+        config.set_str(
+            &format!("branch.{}.remote", request.branch),
+            &request.remote,
+        )?;
+        config.set_str(&format!("branch.{}.merge", request.branch), &remote_ref)?;
+    }
+
+    Ok(push_message(request))
+}
+
+/// Lance le hook pre-push (libgit2 ne l'exécute pas).
+///
+/// Son entrée décrit les références poussées : `<ref locale> <oid> <ref distante> <oid distant>`.
+fn run_pre_push(
+    repo: &Repository,
+    remote_name: &str,
+    url: &str,
+    expected: git2::Oid,
+    refspecs: &[String],
+) -> Result<()> {
+    let mut updates = String::new();
+    for refspec in refspecs {
+        let Some((local, remote)) = refspec.trim_start_matches('+').split_once(':') else {
+            continue;
+        };
+        let local_oid = repo.refname_to_id(local)?;
+        let remote_oid = if local.starts_with("refs/heads/") {
+            expected
+        } else {
+            git2::Oid::zero()
+        };
+        updates.push_str(&format!(
+            "{} {} {} {}\n",
+            local, local_oid, remote, remote_oid
+        ));
     }
+    crate::git::hooks::HookRun::default().run(repo, "pre-push", &[remote_name, url], Some(&updates))
+}
 
-    // Retourner un message descriptif
-    if has_upstream {
-        Ok(format!("Push de '{}' vers {}", branch_name, remote_name))
+/// Message décrivant un push réussi.
+fn push_message(request: &PushRequest) -> String {
+    let mut message = if request.remote_branch == request.branch {
+        format!("Push de '{}' vers {}", request.branch, request.remote)
     } else {
-        Ok(format!(
-            "Push de '{}' vers {}/{} (upstream configuré)",
-            branch_name, remote_name, branch_name
-        ))
+        format!(
+            "Push de '{}' vers {}/{}",
+            request.branch, request.remote, request.remote_branch
+        )
+    };
+    if request.force_with_lease {
+        message.push_str(" (force-with-lease)");
+    }
+    if request.set_upstream {
+        message.push_str(" (upstream configuré)");
+    }
+    if request.include_tags {
+        message.push_str(" avec les tags");
     }
+    message
 }
 
-/// Pull (fetch + merge) depuis le remote.
-pub fn pull_current_branch(repo: &Repository) -> Result<()> {
-    // D'abord, faire un fetch
-    fetch_all(repo)?;
+/// Push un tag vers le remote.
+/// Retourne un message décrivant l'action effectuée.
+pub fn push_tag(repo: &Repository, tag_name: &str, progress: &ProgressTracker) -> Result<String> {
+    let remote_name = default_remote_name(repo);
+    let refspec = format!("refs/tags/{}:refs/tags/{}", tag_name, tag_name);
+
+    push_tags(
+        repo,
+        &remote_name,
+        &[refspec],
+        &[&format!("refs/tags/{}", tag_name)],
+        progress,
+    )?;
+
+    Ok(format!("Push du tag '{}' vers {}", tag_name, remote_name))
+}
 
-    // Récupérer la branche courante
-    let head = repo.head()?;
-    let branch_name = head
-        .shorthand()
-        .ok_or_else(|| git2::Error::from_str("HEAD détachée, impossible de pull"))?;
+/// Push tous les tags locaux vers le remote.
+/// Retourne un message décrivant l'action effectuée.
+pub fn push_all_tags(repo: &Repository, progress: &ProgressTracker) -> Result<String> {
+    let remote_name = default_remote_name(repo);
+    let tag_names = repo.tag_names(None)?;
+    let refspecs: Vec<String> = tag_names
+        .iter()
+        .flatten()
+        .map(|name| format!("refs/tags/{}:refs/tags/{}", name, name))
+        .collect();
+
+    if refspecs.is_empty() {
+        return Ok("Aucun tag à pousser".to_string());
+    }
 
-    // Récupérer le nom complet de la branche upstream
-    let upstream_name = repo.branch_upstream_name(&format!("refs/heads/{}", branch_name))?;
-    let upstream_name = upstream_name
-        .as_str()
-        .ok_or_else(|| git2::Error::from_str("Nom de branche upstream invalide"))?;
+    push_tags(repo, &remote_name, &refspecs, &["--tags"], progress)?;
 
-    // Trouver le commit de l'upstream et créer un AnnotatedCommit
-    let upstream_ref = repo.find_reference(upstream_name)?;
-    let upstream_oid = upstream_ref.peel_to_commit()?.id();
-    let upstream_commit = repo.find_annotated_commit(upstream_oid)?;
+    Ok(format!(
+        "Push de {} tag(s) vers {}",
+        refspecs.len(),
+        remote_name
+    ))
+}
 
-    // Merge avec fast-forward si possible
-    let analysis = repo.merge_analysis(&[&upstream_commit])?;
+/// Nom du remote de la branche courante (fallback vers "origin", y compris en HEAD détachée).
+fn default_remote_name(repo: &Repository) -> String {
+    repo.head()
+        .ok()
+        .and_then(|head| head.shorthand().map(|name| resolve_remote_name(repo, name)))
+        .unwrap_or_else(|| "origin".to_string())
+}
 
-    if analysis.0.is_up_to_date() {
-        // Déjà à jour
-        Ok(())
-    } else if analysis.0.is_fast_forward() {
-        // Fast-forward possible
-        let mut reference = repo.find_reference(&format!("refs/heads/{}", branch_name))?;
-        reference.set_target(upstream_oid, &format!("Fast-forward to {}", upstream_oid))?;
-        repo.set_head(&format!("refs/heads/{}", branch_name))?;
-        repo.checkout_head(Some(git2::build::CheckoutBuilder::default().force()))?;
+/// Pousse des tags via git2, avec git CLI en fallback si le transport échoue.
+///
+/// Un tag refusé par le serveur est une erreur : le fallback ne ferait qu'essuyer
+/// le même refus.
+fn push_tags(
+    repo: &Repository,
+    remote_name: &str,
+    refspecs: &[String],
+    cli_args: &[&str],
+    progress: &ProgressTracker,
+) -> Result<()> {
+    let remote = repo.find_remote(remote_name)?;
+    let url = remote.url().unwrap_or("");
+    run_pre_push(repo, remote_name, url, git2::Oid::zero(), refspecs)?;
+
+    match push_refspecs(repo, remote_name, refspecs, progress) {
+        Ok(None) => Ok(()),
+        Ok(Some(details)) => Err(GitSvError::OperationFailed {
+            operation: "push",
+            details,
+        }),
+        Err(_) if progress.is_cancelled() => Err(cancelled_error()),
+        Err(_) => push_tags_cli(repo, remote_name, cli_args, progress),
+    }
+}
+
+/// Pousse des refspecs vers le remote avec résolution SSH des alias.
+///
+/// Retourne le premier refus du serveur (`"<ref>: <raison>"`), s'il y en a un.
+fn push_refspecs(
+    repo: &Repository,
+    remote_name: &str,
+    refspecs: &[String],
+    progress: &ProgressTracker,
+) -> std::result::Result<Option<String>, git2::Error> {
+    let remote = repo.find_remote(remote_name)?;
+    let raw_url = remote.url().unwrap_or("");
+    let resolved_url = resolve_remote_url(raw_url);
+
+    let rejection = Arc::new(Mutex::new(None::<String>));
+    let mut callbacks = build_remote_callbacks(progress);
+    let refused = Arc::clone(&rejection);
+    callbacks.push_update_reference(move |refname, status| {
+        if let Some(status) = status {
+            if let Ok(mut refused) = refused.lock() {
+                refused.get_or_insert_with(|| format!("{}: {}", refname, status));
+            }
+        }
         Ok(())
+    });
+
+    let mut push_options = PushOptions::new();
+    push_options.remote_callbacks(callbacks);
+
+    if resolved_url != raw_url {
+        // L'URL a été réécrite, utiliser un remote anonyme
+        let mut push_remote = repo.remote_anonymous(&resolved_url)?;
+        push_remote.push(refspecs, Some(&mut push_options))?;
     } else {
-        // Merge nécessaire (conflits possibles)
-        repo.merge(
-            &[&upstream_commit],
-            Some(&mut git2::MergeOptions::default()),
-            Some(&mut git2::build::CheckoutBuilder::default()),
-        )?;
+        let mut push_remote = repo.find_remote(remote_name)?;
+        push_remote.push(refspecs, Some(&mut push_options))?;
+    }
+    Ok(rejection.lock().ok().and_then(|mut r| r.take()))
+}
+
+/// Push des tags en utilisant git CLI (fallback).
+fn push_tags_cli(
+    repo: &Repository,
+    remote_name: &str,
+    args: &[&str],
+    progress: &ProgressTracker,
+) -> Result<()> {
+    let repo_path = repo
+        .workdir()
+        .ok_or_else(|| git2::Error::from_str("Impossible de trouver le chemin du repository"))?;
+
+    // Le hook pre-push a déjà été lancé avant la tentative avec libgit2.
+    let mut cli_args = vec!["push", "--progress", "--no-verify", remote_name];
+    cli_args.extend(args);
+    run_git_with_progress(repo_path, &cli_args, progress)
+}
+
+/// Stratégie d'intégration des commits distants lors d'un pull.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PullMode {
+    /// Merge de la branche distante (commit de merge si les branches ont divergé).
+    #[default]
+    Merge,
+    /// Rebase des commits locaux sur la branche distante.
+    Rebase,
+    /// Fast-forward uniquement : échoue si les branches ont divergé.
+    FastForwardOnly,
+}
 
-        // Vérifier s'il y a des conflits
-        let mut index = repo.index()?;
-        if index.has_conflicts() {
-            return Err(git2::Error::from_str("Conflits détectés lors du pull").into());
+impl PullMode {
+    /// Toutes les stratégies, dans l'ordre d'affichage.
+    pub const ALL: [PullMode; 3] = [PullMode::Merge, PullMode::Rebase, PullMode::FastForwardOnly];
+
+    /// Nom court (ex: "ff-only").
+    pub fn label(&self) -> &'static str {
+        match self {
+            PullMode::Merge => "merge",
+            PullMode::Rebase => "rebase",
+            PullMode::FastForwardOnly => "ff-only",
         }
+    }
 
-        // Créer le commit de merge
-        let signature = repo.signature()?;
-        let head_commit = head.peel_to_commit()?;
-        let tree_id = index.write_tree()?;
-        let tree = repo.find_tree(tree_id)?;
-        let upstream_real_commit = repo.find_commit(upstream_oid)?;
+    /// Description affichée dans le sélecteur.
+    pub fn description(&self) -> &'static str {
+        match self {
+            PullMode::Merge => "Crée un commit de merge si les branches ont divergé",
+            PullMode::Rebase => "Rejoue les commits locaux sur la branche distante",
+            PullMode::FastForwardOnly => "Refuse le pull si les branches ont divergé",
+        }
+    }
 
-        repo.commit(
-            Some("HEAD"),
-            &signature,
-            &signature,
-            &format!("Merge {}", upstream_name),
-            &tree,
-            &[&head_commit, &upstream_real_commit],
-        )?;
+    /// Stratégie configurée pour la branche, comme `git pull`.
+    ///
+    /// `branch.<nom>.rebase` prime sur `pull.rebase` ; sans rebase configuré,
+    /// `pull.ff=only` impose le fast-forward.
+    pub fn from_config(repo: &Repository, branch: &str) -> Self {
+        let Ok(config) = repo.config().and_then(|mut c| c.snapshot()) else {
+            return Self::default();
+        };
+        let rebase = config_rebase(&config, &format!("branch.{}.rebase", branch))
+            .or_else(|| config_rebase(&config, "pull.rebase"));
+        match rebase {
+            Some(true) => PullMode::Rebase,
+            _ if config
+                .get_str("pull.ff")
+                .is_ok_and(|ff| ff.eq_ignore_ascii_case("only")) =>
+            {
+                PullMode::FastForwardOnly
+            }
+            _ => PullMode::Merge,
+        }
+    }
 
-        Ok(())
+    /// Stratégie configurée pour la branche courante (merge si HEAD est détachée).
+    pub fn for_current_branch(repo: &Repository) -> Self {
+        repo.head()
+            .ok()
+            .filter(|head| head.is_branch())
+            .and_then(|head| head.shorthand().map(|name| Self::from_config(repo, name)))
+            .unwrap_or_default()
     }
 }
 
-/// Pull avec résultat typé pour gérer les conflits.
-pub fn pull_current_branch_with_result(
-    repo: &Repository,
-) -> crate::error::Result<crate::git::conflict::MergeResult> {
-    use crate::git::conflict::{list_conflict_files, MergeResult};
+/// Valeur booléenne d'une clé `*.rebase` (`merges`, `interactive`... valent true).
+fn config_rebase(config: &git2::Config, key: &str) -> Option<bool> {
+    let value = config.get_string(key).ok()?;
+    Some(!matches!(
+        value.to_ascii_lowercase().as_str(),
+        "false" | "no" | "off" | "0" | ""
+    ))
+}
+
+/// Nom court de l'upstream de la branche courante (ex: "origin/main").
+pub fn current_upstream(repo: &Repository) -> Option<String> {
+    let head = repo.head().ok().filter(|head| head.is_branch())?;
+    let branch = repo
+        .find_branch(head.shorthand()?, git2::BranchType::Local)
+        .ok()?;
+    let upstream = branch.upstream().ok()?;
+    upstream.name().ok().flatten().map(str::to_string)
+}
 
-    // D'abord, faire un fetch
-    fetch_all(repo)?;
+/// Résultat d'un pull.
+#[derive(Debug)]
+pub enum PullOutcome {
+    /// Branche déjà à jour, avancée en fast-forward ou mergée.
+    Merged(crate::git::conflict::MergeResult),
+    /// Commits locaux rebasés : la session reste ouverte en cas de conflit.
+    Rebased(
+        Box<crate::git::rebase::RebaseSession>,
+        crate::git::rebase::RebaseProgress,
+    ),
+}
+
+/// Intègre l'upstream, déjà fetché, dans la branche courante (seconde étape d'un pull).
+///
+/// Le fetch se fait en arrière-plan ; l'intégration modifie HEAD et l'arbre de
+/// travail, elle est donc lancée par la boucle principale. Si les branches ont
+/// divergé, `mode` choisit entre un merge, un rebase ou un refus (ff-only).
+pub fn integrate_upstream(repo: &Repository, mode: PullMode) -> Result<PullOutcome> {
+    use crate::git::conflict::{list_conflict_files, MergeResult};
+    use crate::git::rebase::{ensure_clean_worktree, linear_todo, RebaseSession};
 
     // Récupérer la branche courante
     let head = repo.head()?;
@@ -403,112 +887,285 @@ pub fn pull_current_branch_with_result(
 
     if analysis.0.is_up_to_date() {
         // Déjà à jour
-        Ok(MergeResult::UpToDate)
-    } else if analysis.0.is_fast_forward() {
+        return Ok(PullOutcome::Merged(MergeResult::UpToDate));
+    }
+    if analysis.0.is_fast_forward() {
         // Fast-forward possible
         let mut reference = repo.find_reference(&format!("refs/heads/{}", branch_name))?;
         reference.set_target(upstream_oid, &format!("Fast-forward to {}", upstream_oid))?;
         repo.set_head(&format!("refs/heads/{}", branch_name))?;
         repo.checkout_head(Some(git2::build::CheckoutBuilder::default().force()))?;
-        Ok(MergeResult::FastForward)
-    } else {
-        // Merge nécessaire (conflits possibles)
-        repo.merge(
-            &[&upstream_commit],
-            Some(&mut git2::MergeOptions::default()),
-            Some(&mut git2::build::CheckoutBuilder::default()),
-        )?;
+        return Ok(PullOutcome::Merged(MergeResult::FastForward));
+    }
 
-        // Vérifier s'il y a des conflits
-        let mut index = repo.index()?;
-        if index.has_conflicts() {
-            // Lister les fichiers en conflit
-            let conflict_files = list_conflict_files(repo)?;
-            return Ok(MergeResult::Conflicts(conflict_files));
+    match mode {
+        PullMode::FastForwardOnly => Err(GitSvError::OperationFailed {
+            operation: "pull",
+            details: format!(
+                "{} et {} ont divergé, fast-forward impossible (pull.ff=only)",
+                branch_name, upstream_name
+            ),
+        }),
+        PullMode::Rebase => {
+            // Rejouer les commits locaux au-dessus de l'upstream, merges exclus
+            let entries = linear_todo(repo, upstream_oid)?;
+            if entries.is_empty() {
+                // Seulement des merges locaux : la branche rejoint l'upstream
+                ensure_clean_worktree(repo)?;
+                let mut reference = repo.find_reference(&format!("refs/heads/{}", branch_name))?;
+                reference
+                    .set_target(upstream_oid, &format!("pull: rebase onto {}", upstream_oid))?;
+                repo.checkout_head(Some(git2::build::CheckoutBuilder::default().force()))?;
+                return Ok(PullOutcome::Merged(MergeResult::FastForward));
+            }
+            let (session, progress) = RebaseSession::start(repo, upstream_oid, entries)?;
+            Ok(PullOutcome::Rebased(Box::new(session), progress))
         }
+        PullMode::Merge => {
+            // Merge nécessaire (conflits possibles)
+            repo.merge(
+                &[&upstream_commit],
+                Some(&mut git2::MergeOptions::default()),
+                Some(&mut git2::build::CheckoutBuilder::default()),
+            )?;
+
+            // Vérifier s'il y a des conflits
+            let mut index = repo.index()?;
+            if index.has_conflicts() {
+                // Lister les fichiers en conflit
+                let conflict_files = list_conflict_files(repo)?;
+                return Ok(PullOutcome::Merged(MergeResult::Conflicts(conflict_files)));
+            }
 
-        // Créer le commit de merge
-        let signature = repo.signature()?;
-        let head_commit = head.peel_to_commit()?;
-        let tree_id = index.write_tree()?;
-        let tree = repo.find_tree(tree_id)?;
-        let upstream_real_commit = repo.find_commit(upstream_oid)?;
-
-        repo.commit(
-            Some("HEAD"),
-            &signature,
-            &signature,
-            &format!("Merge {}", upstream_name),
-            &tree,
-            &[&head_commit, &upstream_real_commit],
-        )?;
-
-        Ok(MergeResult::Success)
+            // Créer le commit de merge
+            let signature = repo.signature()?;
+            let head_commit = head.peel_to_commit()?;
+            let tree_id = index.write_tree()?;
+            let tree = repo.find_tree(tree_id)?;
+            let upstream_real_commit = repo.find_commit(upstream_oid)?;
+
+            crate::git::signing::write_commit(
+                repo,
+                Some("HEAD"),
+                &signature,
+                &signature,
+                &format!("Merge {}", upstream_name),
+                &tree,
+                &[&head_commit, &upstream_real_commit],
+            )?;
+
+            Ok(PullOutcome::Merged(MergeResult::Success))
+        }
     }
 }
 
-/// Fetch toutes les refs depuis le remote en utilisant git CLI (fallback).
-pub fn fetch_all_cli(repo: &Repository) -> Result<()> {
-    use std::process::Command;
-
-    // Récupérer le chemin du repository
-    let repo_path = repo
-        .workdir()
-        .ok_or_else(|| git2::Error::from_str("Impossible de trouver le chemin du repository"))?;
-
-    // Exécuter git fetch
-    let output = Command::new("git")
-        .args(["fetch", "--all"])
+/// Exécute une commande git en reportant sa progression (stderr) dans `progress`.
+///
+/// La commande est tuée si l'annulation est demandée.
+fn run_git_with_progress(
+    repo_path: &Path,
+    args: &[&str],
+    progress: &ProgressTracker,
+) -> Result<()> {
+    let mut child = Command::new("git")
+        .args(args)
         .current_dir(repo_path)
-        .output()
-        .map_err(|e| git2::Error::from_str(&format!("Erreur exécuter git fetch: {}", e)))?;
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(format!("exécution de git {}", args[0]))?;
+
+    // Les lignes de progression de git se terminent par '\r', les messages par '\n'.
+    let stderr = child.stderr.take();
+    let tracker = progress.clone();
+    let reader = thread::spawn(move || {
+        let mut messages = String::new();
+        let Some(mut stderr) = stderr else {
+            return messages;
+        };
+        let mut line = Vec::new();
+        let mut buffer = [0u8; 1024];
+        while let Ok(read) = stderr.read(&mut buffer) {
+            if read == 0 {
+                break;
+            }
+            for &byte in &buffer[..read] {
+                if byte != b'\r' && byte != b'\n' {
+                    line.push(byte);
+                    continue;
+                }
+                let text = String::from_utf8_lossy(&line);
+                tracker.set_message(&text);
+                if byte == b'\n' {
+                    messages.push_str(&text);
+                    messages.push('\n');
+                }
+                line.clear();
+            }
+        }
+        messages
+    });
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(git2::Error::from_str(&format!("Erreur git fetch: {}", stderr)).into());
-    }
+    let status = loop {
+        if let Some(status) = child
+            .try_wait()
+            .with_context(format!("attente de git {}", args[0]))?
+        {
+            break status;
+        }
+        if progress.is_cancelled() {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(cancelled_error());
+        }
+        thread::sleep(CLI_POLL_INTERVAL);
+    };
 
+    let messages = reader.join().unwrap_or_default();
+    if !status.success() {
+        return Err(
+            git2::Error::from_str(&format!("Erreur git {}: {}", args[0], messages.trim())).into(),
+        );
+    }
     Ok(())
 }
 
 /// Fetch toutes les refs depuis le remote.
 /// Utilise le remote configuré avec résolution SSH des alias et fallback CLI.
-pub fn fetch_all(repo: &Repository) -> Result<()> {
+/// La progression est reportée dans `progress`.
+pub fn fetch_all(repo: &Repository, progress: &ProgressTracker) -> Result<()> {
     // Récupérer le remote configuré pour la branche courante (fallback vers "origin")
-    let remote_name = if let Ok(head) = repo.head() {
-        if let Some(branch_name) = head.shorthand() {
-            resolve_remote_name(repo, branch_name)
-        } else {
-            "origin".to_string()
-        }
-    } else {
-        "origin".to_string()
-    };
+    fetch_remote(repo, &default_remote_name(repo), false, progress)
+}
 
-    let remote = repo.find_remote(&remote_name)?;
+/// Fetch toutes les refs d'un remote donné.
+///
+/// Avec `prune`, les branches de suivi supprimées sur le remote sont retirées.
+/// La progression est reportée dans `progress`.
+pub fn fetch_remote(
+    repo: &Repository,
+    remote_name: &str,
+    prune: bool,
+    progress: &ProgressTracker,
+) -> Result<()> {
+    let remote = repo.find_remote(remote_name)?;
     let raw_url = remote.url().unwrap_or("");
     let resolved_url = resolve_remote_url(raw_url);
 
-    // Options de fetch avec callbacks SSH
+    // Options de fetch avec callbacks SSH et progression
     let mut fetch_options = FetchOptions::new();
-    fetch_options.remote_callbacks(build_remote_callbacks());
+    fetch_options.remote_callbacks(build_remote_callbacks(progress));
+    if prune {
+        fetch_options.prune(git2::FetchPrune::On);
+    }
 
     // Fetch toutes les branches
     let result = if resolved_url != raw_url {
-        // L'URL a été réécrite, utiliser un remote anonyme
+        // L'URL a été réécrite : un remote anonyme n'a pas les refspecs du remote,
+        // on les reprend pour mettre à jour ses branches de suivi.
+        let refspecs: Vec<String> = remote
+            .fetch_refspecs()?
+            .iter()
+            .flatten()
+            .map(str::to_string)
+            .collect();
         let mut fetch_remote = repo.remote_anonymous(&resolved_url)?;
-        fetch_remote.fetch(&[] as &[&str], Some(&mut fetch_options), None)
+        fetch_remote.fetch(&refspecs, Some(&mut fetch_options), None)
     } else {
         // Utiliser le remote configuré
-        let mut fetch_remote = repo.find_remote(&remote_name)?;
+        let mut fetch_remote = repo.find_remote(remote_name)?;
         fetch_remote.fetch(&[] as &[&str], Some(&mut fetch_options), None)
     };
 
-    // Si le fetch échoue, fallback sur CLI
+    // Si le fetch échoue (sans annulation), fallback sur CLI
     match result {
         Ok(()) => Ok(()),
-        Err(_) => fetch_all_cli(repo),
+        Err(_) if progress.is_cancelled() => Err(cancelled_error()),
+        Err(_) => {
+            let repo_path = repo.workdir().ok_or_else(|| {
+                git2::Error::from_str("Impossible de trouver le chemin du repository")
+            })?;
+            let mut args = vec!["fetch", "--progress"];
+            if prune {
+                args.push("--prune");
+            }
+            args.push(remote_name);
+            run_git_with_progress(repo_path, &args, progress)
+        }
+    }
+}
+
+/// Remote configuré et ses URLs.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RemoteInfo {
+    /// Nom du remote (ex: "origin").
+    pub name: String,
+    /// URL de fetch.
+    pub fetch_url: Option<String>,
+    /// URL de push spécifique (None : l'URL de fetch est utilisée).
+    pub push_url: Option<String>,
+}
+
+impl RemoteInfo {
+    /// URL effectivement utilisée pour le push.
+    pub fn effective_push_url(&self) -> Option<&str> {
+        self.push_url.as_deref().or(self.fetch_url.as_deref())
+    }
+}
+
+/// Liste les remotes configurés, triés par nom.
+pub fn list_remotes(repo: &Repository) -> Result<Vec<RemoteInfo>> {
+    let names = repo.remotes()?;
+    let mut remotes = Vec::new();
+    for name in names.iter().flatten() {
+        let remote = repo.find_remote(name)?;
+        remotes.push(RemoteInfo {
+            name: name.to_string(),
+            fetch_url: remote.url().map(str::to_string),
+            push_url: remote.pushurl().map(str::to_string),
+        });
     }
+    remotes.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(remotes)
+}
+
+/// Ajoute un remote.
+pub fn add_remote(repo: &Repository, name: &str, url: &str) -> Result<()> {
+    repo.remote(name, url)?;
+    Ok(())
+}
+
+/// Renomme un remote (ses branches de suivi et la configuration des upstreams suivent).
+pub fn rename_remote(repo: &Repository, old_name: &str, new_name: &str) -> Result<()> {
+    let problems = repo.remote_rename(old_name, new_name)?;
+    // Refspecs non standards que git n'a pas pu renommer automatiquement.
+    let problems: Vec<&str> = problems.iter().flatten().collect();
+    if !problems.is_empty() {
+        return Err(GitSvError::OperationFailed {
+            operation: "renommage du remote",
+            details: format!("refspecs à corriger manuellement : {}", problems.join(", ")),
+        });
+    }
+    Ok(())
+}
+
+/// Change l'URL de fetch d'un remote.
+pub fn set_remote_url(repo: &Repository, name: &str, url: &str) -> Result<()> {
+    repo.remote_set_url(name, url)?;
+    Ok(())
+}
+
+/// Change l'URL de push d'un remote (None : revenir à l'URL de fetch).
+pub fn set_remote_push_url(repo: &Repository, name: &str, url: Option<&str>) -> Result<()> {
+    repo.remote_set_pushurl(name, url)?;
+    Ok(())
+}
+
+/// Supprime un remote et ses branches de suivi.
+pub fn remove_remote(repo: &Repository, name: &str) -> Result<()> {
+    repo.remote_delete(name)?;
+    Ok(())
 }
 
 /// Vérifie si le repository a un remote configuré.
@@ -530,56 +1187,567 @@ pub fn get_default_remote(repo: &Repository) -> Result<String> {
     Ok(remote_name)
 }
 
-/// Push la branche courante en utilisant git CLI (fallback).
+/// Push une branche en utilisant git CLI (fallback).
 /// Utilise le processus git standard qui gère correctement les alias SSH.
-pub fn push_current_branch_cli(repo: &Repository) -> Result<String> {
-    use std::process::Command;
-
-    // Récupérer la branche courante
-    let head = repo.head()?;
-    let branch_name = head
-        .shorthand()
-        .ok_or_else(|| git2::Error::from_str("HEAD détachée, impossible de pousser"))?;
-
+fn push_branch_cli(
+    repo: &Repository,
+    request: &PushRequest,
+    expected: git2::Oid,
+    progress: &ProgressTracker,
+) -> Result<String> {
     // Récupérer le chemin du repository
     let repo_path = repo
         .workdir()
         .ok_or_else(|| git2::Error::from_str("Impossible de trouver le chemin du repository"))?;
 
-    // Vérifier si la branche a un upstream configuré
-    let has_upstream = repo
-        .branch_upstream_name(&format!("refs/heads/{}", branch_name))
-        .is_ok();
+    let remote_ref = format!("refs/heads/{}", request.remote_branch);
+    // Lease vide : la branche distante ne doit pas exister
+    let lease = if expected.is_zero() {
+        format!("--force-with-lease={}:", remote_ref)
+    } else {
+        format!("--force-with-lease={}:{}", remote_ref, expected)
+    };
+    let refspec = format!("refs/heads/{}:{}", request.branch, remote_ref);
+
+    // Le hook pre-push a déjà été lancé avant la tentative avec libgit2.
+    let mut args = vec!["push", "--progress", "--no-verify"];
+    if request.force_with_lease {
+        args.push(&lease);
+    }
+    if request.set_upstream {
+        args.push("--set-upstream");
+    }
+    if request.include_tags {
+        args.push("--tags");
+    }
+    args.extend([request.remote.as_str(), &refspec]);
+
+    if let Err(e) = run_git_with_progress(repo_path, &args, progress) {
+        let details = e.to_string();
+        return Err(if details.contains("(stale info)") {
+            push_rejected(request, REJECTED_STALE_LEASE)
+        } else if details.contains("non-fast-forward") || details.contains("(fetch first)") {
+            push_rejected(request, REJECTED_NON_FAST_FORWARD)
+        } else {
+            e
+        });
+    }
+
+    Ok(push_message(request))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::tests::test_utils::*;
+
+    /// Crée un repository avec un remote "origin" bare local.
+    fn repo_with_bare_remote() -> (tempfile::TempDir, Repository, tempfile::TempDir, Repository) {
+        let (dir, repo) = create_test_repo();
+        let remote_dir = tempfile::TempDir::new().unwrap();
+        let bare = Repository::init_bare(remote_dir.path()).unwrap();
+        repo.remote("origin", remote_dir.path().to_str().unwrap())
+            .unwrap();
+        (dir, repo, remote_dir, bare)
+    }
+
+    #[test]
+    fn test_push_tag() {
+        let (_dir, repo, _remote_dir, bare) = repo_with_bare_remote();
+        let oid = commit_file(&repo, "a.txt", "v1", "First");
+        crate::git::tag::create_annotated_tag(&repo, "v1.0", oid, "Release").unwrap();
+        crate::git::tag::create_lightweight_tag(&repo, "v0.1", oid).unwrap();
 
-    // Construire la commande git push
-    let mut cmd = Command::new("git");
-    cmd.arg("push");
+        push_tag(&repo, "v1.0", &ProgressTracker::default()).unwrap();
 
-    // Ajouter --set-upstream si pas d'upstream
-    if !has_upstream {
-        cmd.arg("--set-upstream");
+        assert!(bare.find_reference("refs/tags/v1.0").is_ok());
+        assert!(bare.find_reference("refs/tags/v0.1").is_err());
     }
 
-    // Exécuter depuis le chemin du repository
-    let output = cmd
-        .current_dir(repo_path)
-        .output()
-        .map_err(|e| git2::Error::from_str(&format!("Erreur exécuter git push: {}", e)))?;
+    #[test]
+    fn test_push_tag_reports_rejection() {
+        let (_dir, repo, _remote_dir, _bare) = repo_with_bare_remote();
+        let first = commit_file(&repo, "a.txt", "v1", "First");
+        let second = commit_file(&repo, "a.txt", "v2", "Second");
+        crate::git::tag::create_lightweight_tag(&repo, "v1.0", second).unwrap();
+        push_tag(&repo, "v1.0", &ProgressTracker::default()).unwrap();
+
+        // Le tag local est déplacé : le remote refuse de le mettre à jour sans force
+        repo.tag_lightweight("v1.0", &repo.find_object(first, None).unwrap(), true)
+            .unwrap();
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(git2::Error::from_str(&format!("Erreur git push: {}", stderr)).into());
+        assert!(push_tag(&repo, "v1.0", &ProgressTracker::default()).is_err());
     }
 
-    let _stdout = String::from_utf8_lossy(&output.stdout);
-    let remote_name = resolve_remote_name(repo, branch_name);
+    #[test]
+    fn test_manage_remotes() {
+        let (_dir, repo, _remote_dir, _bare) = repo_with_bare_remote();
+
+        add_remote(&repo, "backup", "https://example.com/backup.git").unwrap();
+        let names: Vec<String> = list_remotes(&repo)
+            .unwrap()
+            .into_iter()
+            .map(|r| r.name)
+            .collect();
+        assert_eq!(names, vec!["backup", "origin"]);
+
+        rename_remote(&repo, "backup", "mirror").unwrap();
+        set_remote_push_url(&repo, "mirror", Some("git@example.com:mirror.git")).unwrap();
+        let mirror = list_remotes(&repo).unwrap().remove(0);
+        assert_eq!(mirror.name, "mirror");
+        assert_eq!(
+            mirror.fetch_url.as_deref(),
+            Some("https://example.com/backup.git")
+        );
+        assert_eq!(
+            mirror.effective_push_url(),
+            Some("git@example.com:mirror.git")
+        );
+
+        set_remote_url(&repo, "mirror", "https://example.com/mirror.git").unwrap();
+        set_remote_push_url(&repo, "mirror", None).unwrap();
+        let mirror = list_remotes(&repo).unwrap().remove(0);
+        assert_eq!(
+            mirror.effective_push_url(),
+            Some("https://example.com/mirror.git")
+        );
+
+        remove_remote(&repo, "mirror").unwrap();
+        assert_eq!(list_remotes(&repo).unwrap().len(), 1);
+    }
 
-    if has_upstream {
-        Ok(format!("Push de '{}' vers {}", branch_name, remote_name))
-    } else {
-        Ok(format!(
-            "Push de '{}' vers {}/{} (upstream configuré)",
-            branch_name, remote_name, branch_name
-        ))
+    #[test]
+    fn test_push_branch_to_chosen_remote() {
+        let (_dir, repo, _remote_dir, origin) = repo_with_bare_remote();
+        let other_dir = tempfile::TempDir::new().unwrap();
+        let other = Repository::init_bare(other_dir.path()).unwrap();
+        add_remote(&repo, "other", other_dir.path().to_str().unwrap()).unwrap();
+        commit_file(&repo, "a.txt", "v1", "First");
+        let branch = repo.head().unwrap().shorthand().unwrap().to_string();
+
+        let request = PushRequest::new(&repo, &branch, "other");
+        push_branch(&repo, &request, &ProgressTracker::default()).unwrap();
+
+        let reference = format!("refs/heads/{}", branch);
+        assert!(other.find_reference(&reference).is_ok());
+        assert!(origin.find_reference(&reference).is_err());
+        assert_eq!(resolve_remote_name(&repo, &branch), "other");
+    }
+
+    /// Pousse deux commits puis réécrit le second en local (comme après un rebase).
+    fn diverged_repo() -> (
+        tempfile::TempDir,
+        Repository,
+        tempfile::TempDir,
+        Repository,
+        String,
+    ) {
+        let (dir, repo, remote_dir, bare) = repo_with_bare_remote();
+        let first = commit_file(&repo, "a.txt", "v1", "First");
+        commit_file(&repo, "a.txt", "v2", "Second");
+        let branch = repo.head().unwrap().shorthand().unwrap().to_string();
+        push_current_branch(&repo, &ProgressTracker::default()).unwrap();
+
+        let first = repo.find_object(first, None).unwrap();
+        repo.reset(&first, git2::ResetType::Hard, None).unwrap();
+        drop(first);
+        commit_file(&repo, "a.txt", "v2 réécrit", "Second (réécrit)");
+        (dir, repo, remote_dir, bare, branch)
+    }
+
+    #[test]
+    fn test_push_non_fast_forward_is_rejected() {
+        let (_dir, repo, _remote_dir, bare, branch) = diverged_repo();
+
+        let result = push_current_branch(&repo, &ProgressTracker::default());
+        assert!(matches!(result, Err(GitSvError::PushRejected { .. })));
+
+        // Avec force-with-lease, la branche distante est écrasée
+        let mut request = PushRequest::for_branch(&repo, &branch);
+        request.force_with_lease = true;
+        push_branch(&repo, &request, &ProgressTracker::default()).unwrap();
+        let head = repo.head().unwrap().target().unwrap();
+        let remote_head = bare
+            .refname_to_id(&format!("refs/heads/{}", branch))
+            .unwrap();
+        assert_eq!(remote_head, head);
+    }
+
+    #[test]
+    fn test_force_with_lease_rejects_stale_tracking_ref() {
+        let (_dir, repo, _remote_dir, bare, branch) = diverged_repo();
+        let remote_ref = format!("refs/heads/{}", branch);
+        let before = bare.refname_to_id(&remote_ref).unwrap();
+
+        // Le remote a bougé depuis le dernier fetch : la référence de suivi est périmée
+        let parent = repo
+            .head()
+            .unwrap()
+            .peel_to_commit()
+            .unwrap()
+            .parent_id(0)
+            .unwrap();
+        repo.reference(
+            &format!("refs/remotes/origin/{}", branch),
+            parent,
+            true,
+            "test",
+        )
+        .unwrap();
+
+        let mut request = PushRequest::for_branch(&repo, &branch);
+        request.force_with_lease = true;
+        let result = push_branch(&repo, &request, &ProgressTracker::default());
+        assert!(matches!(result, Err(GitSvError::PushRejected { .. })));
+        assert_eq!(bare.refname_to_id(&remote_ref).unwrap(), before);
+    }
+
+    #[test]
+    fn test_push_to_renamed_remote_branch_with_tags() {
+        let (_dir, repo, _remote_dir, bare) = repo_with_bare_remote();
+        let oid = commit_file(&repo, "a.txt", "v1", "First");
+        crate::git::tag::create_lightweight_tag(&repo, "v1.0", oid).unwrap();
+        let branch = repo.head().unwrap().shorthand().unwrap().to_string();
+
+        let mut request = PushRequest::for_branch(&repo, &branch);
+        request.remote_branch = "review/topic".to_string();
+        request.include_tags = true;
+        push_branch(&repo, &request, &ProgressTracker::default()).unwrap();
+
+        assert!(bare.find_reference("refs/heads/review/topic").is_ok());
+        assert!(bare.find_reference("refs/tags/v1.0").is_ok());
+        // L'upstream suit la branche distante choisie
+        let upstream = PushRequest::for_branch(&repo, &branch);
+        assert_eq!(upstream.remote_branch, "review/topic");
+        assert!(!upstream.set_upstream);
+    }
+
+    #[test]
+    fn test_push_all_tags() {
+        let (_dir, repo, _remote_dir, bare) = repo_with_bare_remote();
+        let oid = commit_file(&repo, "a.txt", "v1", "First");
+        crate::git::tag::create_annotated_tag(&repo, "v1.0", oid, "Release").unwrap();
+        crate::git::tag::create_lightweight_tag(&repo, "v0.1", oid).unwrap();
+
+        push_all_tags(&repo, &ProgressTracker::default()).unwrap();
+
+        let mut names: Vec<String> = bare
+            .tag_names(None)
+            .unwrap()
+            .iter()
+            .flatten()
+            .map(|s| s.to_string())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["v0.1".to_string(), "v1.0".to_string()]);
+    }
+
+    #[test]
+    fn test_fetch_reports_progress() {
+        let (_dir, repo, remote_dir, _bare) = repo_with_bare_remote();
+        commit_file(&repo, "a.txt", "v1", "First");
+        let branch = repo.head().unwrap().shorthand().unwrap().to_string();
+        push_refspecs(
+            &repo,
+            "origin",
+            &[format!("refs/heads/{}:refs/heads/{}", branch, branch)],
+            &ProgressTracker::default(),
+        )
+        .unwrap();
+
+        let (_clone_dir, clone) = create_test_repo();
+        clone
+            .remote("origin", remote_dir.path().to_str().unwrap())
+            .unwrap();
+        let progress = ProgressTracker::default();
+        fetch_all(&clone, &progress).unwrap();
+
+        let snapshot = progress.snapshot();
+        assert!(snapshot.total_objects > 0);
+        assert_eq!(snapshot.objects, snapshot.total_objects);
+        assert!(clone
+            .find_reference(&format!("refs/remotes/origin/{}", branch))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_cancelled_fetch_fails() {
+        let (_dir, repo, remote_dir, _bare) = repo_with_bare_remote();
+        commit_file(&repo, "a.txt", "v1", "First");
+        let branch = repo.head().unwrap().shorthand().unwrap().to_string();
+        push_refspecs(
+            &repo,
+            "origin",
+            &[format!("refs/heads/{}:refs/heads/{}", branch, branch)],
+            &ProgressTracker::default(),
+        )
+        .unwrap();
+
+        let (_clone_dir, clone) = create_test_repo();
+        clone
+            .remote("origin", remote_dir.path().to_str().unwrap())
+            .unwrap();
+        let progress = ProgressTracker::default();
+        progress.cancel();
+
+        let err = fetch_all(&clone, &progress).unwrap_err();
+        assert!(err.to_string().contains("annulé"), "{}", err);
+    }
+
+    #[test]
+    fn test_pull_mode_from_config() {
+        let (_dir, repo) = create_test_repo();
+        assert_eq!(PullMode::from_config(&repo, "main"), PullMode::Merge);
+
+        let mut config = repo.config().unwrap();
+        config.set_str("pull.ff", "only").unwrap();
+        assert_eq!(
+            PullMode::from_config(&repo, "main"),
+            PullMode::FastForwardOnly
+        );
+
+        // Le rebase prime sur pull.ff, la configuration de branche sur pull.rebase
+        config.set_str("pull.rebase", "merges").unwrap();
+        assert_eq!(PullMode::from_config(&repo, "main"), PullMode::Rebase);
+        config.set_bool("branch.main.rebase", false).unwrap();
+        assert_eq!(
+            PullMode::from_config(&repo, "main"),
+            PullMode::FastForwardOnly
+        );
+        assert_eq!(PullMode::from_config(&repo, "other"), PullMode::Rebase);
+    }
+
+    /// Pull complet : fetch puis intégration, comme le fait l'interface.
+    fn pull(repo: &Repository, mode: PullMode) -> Result<PullOutcome> {
+        fetch_all(repo, &ProgressTracker::default())?;
+        integrate_upstream(repo, mode)
+    }
+
+    /// Pousse un commit distant puis le remplace, en local, par un commit sur un autre fichier.
+    fn pull_diverged_repo() -> (tempfile::TempDir, Repository, tempfile::TempDir, git2::Oid) {
+        let (dir, repo, remote_dir, _bare) = repo_with_bare_remote();
+        let first = commit_file(&repo, "a.txt", "v1", "First");
+        let remote_tip = commit_file(&repo, "b.txt", "distant", "Remote");
+        push_current_branch(&repo, &ProgressTracker::default()).unwrap();
+
+        let first = repo.find_object(first, None).unwrap();
+        repo.reset(&first, git2::ResetType::Hard, None).unwrap();
+        drop(first);
+        commit_file(&repo, "c.txt", "local", "Local");
+        (dir, repo, remote_dir, remote_tip)
+    }
+
+    #[test]
+    fn test_pull_modes_on_diverged_branches() {
+        use crate::git::conflict::MergeResult;
+        use crate::git::rebase::RebaseProgress;
+
+        fn head_commit(repo: &Repository) -> git2::Commit<'_> {
+            repo.head().unwrap().peel_to_commit().unwrap()
+        }
+
+        // ff-only : refus, la branche locale est intacte
+        let (_dir, repo, _remote_dir, _) = pull_diverged_repo();
+        let before = head_commit(&repo).id();
+        let result = pull(&repo, PullMode::FastForwardOnly);
+        assert!(result.unwrap_err().to_string().contains("divergé"));
+        assert_eq!(head_commit(&repo).id(), before);
+
+        // merge : commit de merge
+        let (_dir, repo, _remote_dir, _) = pull_diverged_repo();
+        let outcome = pull(&repo, PullMode::Merge).unwrap();
+        assert!(matches!(outcome, PullOutcome::Merged(MergeResult::Success)));
+        assert_eq!(head_commit(&repo).parent_count(), 2);
+
+        // rebase : le commit local est rejoué au-dessus du commit distant
+        let (_dir, repo, _remote_dir, remote_tip) = pull_diverged_repo();
+        let outcome = pull(&repo, PullMode::Rebase).unwrap();
+        assert!(matches!(
+            outcome,
+            PullOutcome::Rebased(_, RebaseProgress::Completed)
+        ));
+        let head = head_commit(&repo);
+        assert_eq!(head.summary(), Some("Local"));
+        assert_eq!(head.parent_ids().collect::<Vec<_>>(), vec![remote_tip]);
+        assert!(repo.head().unwrap().is_branch());
+    }
+
+    #[test]
+    fn test_pull_rebase_linearizes_local_merges() {
+        let (_dir, repo, _remote_dir, remote_tip) = pull_diverged_repo();
+        let branch = repo.head().unwrap().shorthand().unwrap().to_string();
+        let local = repo.head().unwrap().target().unwrap();
+        let side = commit_file(&repo, "d.txt", "side", "Side");
+        let local = repo.find_object(local, None).unwrap();
+        repo.reset(&local, git2::ResetType::Hard, None).unwrap();
+        drop(local);
+        let main = commit_file(&repo, "e.txt", "main", "Main");
+
+        // Merge local de "Side" dans la branche
+        let (main, side) = (
+            repo.find_commit(main).unwrap(),
+            repo.find_commit(side).unwrap(),
+        );
+        let mut index = repo.merge_commits(&main, &side, None).unwrap();
+        let tree = repo.find_tree(index.write_tree_to(&repo).unwrap()).unwrap();
+        let signature = repo.signature().unwrap();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            "Merge side",
+            &tree,
+            &[&main, &side],
+        )
+        .unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::default().force()))
+            .unwrap();
+        assert_eq!(current_upstream(&repo), Some(format!("origin/{}", branch)));
+
+        let outcome = pull(&repo, PullMode::Rebase).unwrap();
+        assert!(matches!(
+            outcome,
+            PullOutcome::Rebased(_, crate::git::rebase::RebaseProgress::Completed)
+        ));
+
+        // Les trois commits locaux sont rejoués à plat, sans le merge
+        let mut revwalk = repo.revwalk().unwrap();
+        revwalk.push_head().unwrap();
+        revwalk.hide(remote_tip).unwrap();
+        let commits: Vec<_> = revwalk
+            .map(|oid| repo.find_commit(oid.unwrap()).unwrap())
+            .collect();
+        assert_eq!(commits.len(), 3);
+        assert!(commits.iter().all(|c| c.parent_count() == 1));
+        for file in ["b.txt", "c.txt", "d.txt", "e.txt"] {
+            assert!(repo.workdir().unwrap().join(file).exists(), "{}", file);
+        }
+    }
+
+    #[test]
+    fn test_pull_rebase_of_local_merges_refuses_dirty_worktree() {
+        let (dir, repo, _remote_dir, _bare) = repo_with_bare_remote();
+        let first = commit_file(&repo, "a.txt", "v1", "First");
+        let remote_tip = commit_file(&repo, "b.txt", "distant", "Remote");
+        push_current_branch(&repo, &ProgressTracker::default()).unwrap();
+        commit_file(&repo, "c.txt", "distant", "Remote 2");
+        push_current_branch(&repo, &ProgressTracker::default()).unwrap();
+
+        // Localement, seulement un merge du premier commit distant
+        let (first, remote_tip) = (
+            repo.find_commit(first).unwrap(),
+            repo.find_commit(remote_tip).unwrap(),
+        );
+        repo.reset(first.as_object(), git2::ResetType::Hard, None)
+            .unwrap();
+        let signature = repo.signature().unwrap();
+        let merge = repo
+            .commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                "Merge remote",
+                &remote_tip.tree().unwrap(),
+                &[&first, &remote_tip],
+            )
+            .unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::default().force()))
+            .unwrap();
+        std::fs::write(dir.path().join("a.txt"), "non commité").unwrap();
+
+        let err = pull(&repo, PullMode::Rebase).unwrap_err();
+        assert!(err.to_string().contains("propre"), "{}", err);
+        assert_eq!(repo.head().unwrap().target(), Some(merge));
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("a.txt")).unwrap(),
+            "non commité"
+        );
+    }
+
+    #[test]
+    fn test_failed_pull_rebase_restores_branch() {
+        let (_dir, repo, _remote_dir, _) = pull_diverged_repo();
+        let before = repo.head().unwrap().target();
+        let mut config = repo.config().unwrap();
+        config.set_bool("commit.gpgsign", true).unwrap();
+        config.set_str("gpg.format", "inconnu").unwrap();
+
+        assert!(pull(&repo, PullMode::Rebase).is_err());
+        assert!(repo.head().unwrap().is_branch());
+        assert_eq!(repo.head().unwrap().target(), before);
+        assert_eq!(repo.state(), git2::RepositoryState::Clean);
+        assert!(crate::git::rebase::RebaseSession::load(&repo)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_pull_rebase_stops_on_conflicts() {
+        let (_dir, repo, _remote_dir, _bare, _branch) = diverged_repo();
+
+        let outcome = pull(&repo, PullMode::Rebase).unwrap();
+        let PullOutcome::Rebased(session, crate::git::rebase::RebaseProgress::Conflicts(files)) =
+            outcome
+        else {
+            panic!("le rebase aurait dû s'arrêter sur un conflit");
+        };
+        assert_eq!(files.len(), 1);
+
+        // L'abandon restaure la branche locale
+        session.abort(&repo).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.summary(), Some("Second (réécrit)"));
+    }
+
+    #[test]
+    fn test_pre_push_hook_blocks_push() {
+        let (_dir, repo, _remote_dir, bare) = repo_with_bare_remote();
+        commit_file(&repo, "a.txt", "v1", "First");
+        let branch = repo.head().unwrap().shorthand().unwrap().to_string();
+        install_hook(
+            &repo.path().join("hooks"),
+            "pre-push",
+            "read local_ref local_oid remote_ref remote_oid\necho \"refusé: $1 $remote_ref\"\nexit 1",
+        );
+
+        let err = push_current_branch(&repo, &ProgressTracker::default()).unwrap_err();
+        let GitSvError::HookFailed { hook, output, .. } = err else {
+            panic!("erreur inattendue: {}", err);
+        };
+        assert_eq!(hook, "pre-push");
+        assert_eq!(
+            output.trim(),
+            format!("refusé: origin refs/heads/{}", branch)
+        );
+        assert!(bare
+            .find_reference(&format!("refs/heads/{}", branch))
+            .is_err());
+    }
+
+    #[test]
+    fn test_pre_push_hook_blocks_tag_push() {
+        let (_dir, repo, _remote_dir, bare) = repo_with_bare_remote();
+        let oid = commit_file(&repo, "a.txt", "v1", "First");
+        crate::git::tag::create_lightweight_tag(&repo, "v1.0", oid).unwrap();
+        install_hook(
+            &repo.path().join("hooks"),
+            "pre-push",
+            "read local_ref local_oid remote_ref remote_oid\necho \"refusé: $remote_ref\"\nexit 1",
+        );
+
+        let err = push_tag(&repo, "v1.0", &ProgressTracker::default()).unwrap_err();
+        assert!(matches!(err, GitSvError::HookFailed { .. }));
+        assert!(push_all_tags(&repo, &ProgressTracker::default()).is_err());
+        assert!(bare.find_reference("refs/tags/v1.0").is_err());
+    }
+
+    #[test]
+    fn test_progress_message_keeps_last_line() {
+        let progress = ProgressTracker::default();
+        progress.set_message("Counting objects: 10%\rCounting objects: 100%\r\n");
+        assert_eq!(progress.snapshot().message, "Counting objects: 100%");
+
+        progress.set_message("\r\n");
+        assert_eq!(progress.snapshot().message, "Counting objects: 100%");
     }
 }
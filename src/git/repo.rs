@@ -2,10 +2,11 @@ use git2::{Repository, StatusOptions};
 
 use super::branch::BranchInfo;
 use super::commit::CommitInfo;
-use super::graph::GraphRow;
+use super::graph::GraphWalk;
 use super::stash::StashEntry;
 use super::worktree::WorktreeInfo;
 use crate::error::Result;
+use crate::state::GraphFilter;
 
 /// Wrapper haut-niveau autour de git2::Repository.
 pub struct GitRepo {
@@ -44,58 +45,9 @@ impl GitRepo {
         Ok(commits)
     }
 
-    /// Retourne la liste des commits depuis toutes les branches.
-    pub fn log_all_branches(&self, max_count: usize) -> Result<Vec<CommitInfo>> {
-        let mut revwalk = self.repo.revwalk()?;
-
-        // Pousser toutes les refs locales (branches, tags)
-        for reference in self.repo.references()? {
-            let reference = reference?;
-            if let Some(oid) = reference.target() {
-                revwalk.push(oid).ok();
-            }
-        }
-
-        revwalk.set_sorting(git2::Sort::TIME | git2::Sort::TOPOLOGICAL)?;
-
-        let mut commits = Vec::new();
-        for (i, oid) in revwalk.enumerate() {
-            if i >= max_count {
-                break;
-            }
-            let oid = oid?;
-            let commit = self.repo.find_commit(oid)?;
-            commits.push(CommitInfo::from_git2_commit(&commit));
-        }
-        Ok(commits)
-    }
-
-    /// Construit le graphe de commits pour l'affichage.
-    pub fn build_graph(&self, max_count: usize) -> Result<Vec<GraphRow>> {
-        let commits = self.log_all_branches(max_count)?;
-        let graph = super::graph::build_graph(&self.repo, &commits)?;
-        Ok(graph)
-    }
-
-    /// Construit le graphe de commits avec filtrage.
-    pub fn build_graph_filtered(
-        &self,
-        max_count: usize,
-        filter: &crate::state::GraphFilter,
-    ) -> Result<Vec<GraphRow>> {
-        // Récupérer plus de commits que demandé car le filtrage peut réduire la liste
-        let fetch_count = max_count * 3;
-        let commits = self.log_all_branches(fetch_count)?;
-
-        // Appliquer le filtre
-        let filtered_commits = filter.filter_commits(&commits);
-
-        // Limiter au nombre demandé
-        let limited_commits: Vec<_> = filtered_commits.into_iter().take(max_count).collect();
-
-        // Construire le graphe avec les commits filtrés
-        let graph = super::graph::build_graph(&self.repo, &limited_commits)?;
-        Ok(graph)
+    /// Démarre le parcours paginé du graphe de toutes les branches, filtré ou non.
+    pub fn graph_walk(&self, filter: Option<GraphFilter>) -> Result<GraphWalk<'_>> {
+        GraphWalk::new(&self.repo, filter)
     }
 
     /// Retourne le status du working directory.
@@ -329,6 +281,33 @@ mod tests {
         assert_eq!(commits[2].message, "First commit");
     }
 
+    #[test]
+    fn test_git_repo_graph_walk_pages() {
+        let (_temp_dir, repo) = create_test_repo();
+
+        for i in 0..5 {
+            commit_file(&repo, "test.txt", &i.to_string(), &format!("Commit {}", i));
+        }
+
+        let git_repo = GitRepo::open(repo.workdir().unwrap().to_str().unwrap()).unwrap();
+        let mut walk = git_repo.graph_walk(None).unwrap();
+
+        // Première page : 3 commits, il en reste
+        let (first, has_more) = walk.next_page(3).unwrap();
+        assert_eq!(first.len(), 3);
+        assert!(has_more);
+        assert_eq!(first[0].node.message, "Commit 4");
+        assert_eq!(walk.loaded(), 3);
+
+        // Deuxième page : les 2 commits restants, sans doublon
+        let (second, has_more) = walk.next_page(3).unwrap();
+        assert_eq!(second.len(), 2);
+        assert_eq!(walk.loaded(), 5);
+        assert!(!has_more);
+        assert_eq!(second[0].node.message, "Commit 1");
+        assert!(second[1].connection.is_none());
+    }
+
     #[test]
     fn test_git_repo_status() {
         let (_temp_dir, repo) = create_test_repo();
@@ -0,0 +1,111 @@
+//! Reset de la branche courante sur un commit.
+
+use git2::{Oid, Repository, ResetType};
+
+use crate::error::Result;
+
+/// Mode de reset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResetMode {
+    /// Déplace HEAD, conserve l'index et le working directory.
+    Soft,
+    /// Déplace HEAD et réinitialise l'index, conserve le working directory.
+    #[default]
+    Mixed,
+    /// Déplace HEAD et réinitialise l'index et le working directory.
+    Hard,
+}
+
+impl ResetMode {
+    /// Tous les modes, dans l'ordre d'affichage.
+    pub const ALL: [ResetMode; 3] = [ResetMode::Soft, ResetMode::Mixed, ResetMode::Hard];
+
+    /// Nom du mode.
+    pub fn label(&self) -> &'static str {
+        match self {
+            ResetMode::Soft => "soft",
+            ResetMode::Mixed => "mixed",
+            ResetMode::Hard => "hard",
+        }
+    }
+
+    /// Description courte de l'effet du mode.
+    pub fn description(&self) -> &'static str {
+        match self {
+            ResetMode::Soft => "garde l'index et le working directory",
+            ResetMode::Mixed => "vide l'index, garde le working directory",
+            ResetMode::Hard => "supprime toutes les modifications",
+        }
+    }
+
+    fn reset_type(&self) -> ResetType {
+        match self {
+            ResetMode::Soft => ResetType::Soft,
+            ResetMode::Mixed => ResetType::Mixed,
+            ResetMode::Hard => ResetType::Hard,
+        }
+    }
+}
+
+/// Reset la branche courante sur `target`.
+///
+/// Retourne l'oid de HEAD avant le reset pour pouvoir l'annuler.
+pub fn reset_to(repo: &Repository, target: Oid, mode: ResetMode) -> Result<Oid> {
+    let previous = repo.head()?.peel_to_commit()?.id();
+    let commit = repo.find_commit(target)?;
+    repo.reset(commit.as_object(), mode.reset_type(), None)?;
+    Ok(previous)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::tests::test_utils::{commit_file, create_test_repo};
+
+    #[test]
+    fn test_reset_soft_keeps_changes_staged() {
+        let (dir, repo) = create_test_repo();
+        let first = commit_file(&repo, "a.txt", "v1\n", "first");
+        let second = commit_file(&repo, "a.txt", "v2\n", "second");
+
+        let previous = reset_to(&repo, first, ResetMode::Soft).unwrap();
+        assert_eq!(previous, second);
+        assert_eq!(repo.head().unwrap().target(), Some(first));
+
+        let statuses = repo.statuses(None).unwrap();
+        assert!(statuses.get(0).unwrap().status().is_index_modified());
+        let content = std::fs::read_to_string(dir.path().join("a.txt")).unwrap();
+        assert_eq!(content, "v2\n");
+    }
+
+    #[test]
+    fn test_reset_mixed_unstages_changes() {
+        let (dir, repo) = create_test_repo();
+        let first = commit_file(&repo, "a.txt", "v1\n", "first");
+        commit_file(&repo, "a.txt", "v2\n", "second");
+
+        reset_to(&repo, first, ResetMode::Mixed).unwrap();
+
+        let statuses = repo.statuses(None).unwrap();
+        assert!(statuses.get(0).unwrap().status().is_wt_modified());
+        let content = std::fs::read_to_string(dir.path().join("a.txt")).unwrap();
+        assert_eq!(content, "v2\n");
+    }
+
+    #[test]
+    fn test_reset_hard_discards_changes_and_can_be_undone() {
+        let (dir, repo) = create_test_repo();
+        let first = commit_file(&repo, "a.txt", "v1\n", "first");
+        commit_file(&repo, "a.txt", "v2\n", "second");
+
+        let previous = reset_to(&repo, first, ResetMode::Hard).unwrap();
+        assert!(repo.statuses(None).unwrap().is_empty());
+        let content = std::fs::read_to_string(dir.path().join("a.txt")).unwrap();
+        assert_eq!(content, "v1\n");
+
+        // Revenir à l'ancien HEAD annule le reset.
+        reset_to(&repo, previous, ResetMode::Hard).unwrap();
+        let content = std::fs::read_to_string(dir.path().join("a.txt")).unwrap();
+        assert_eq!(content, "v2\n");
+    }
+}
@@ -0,0 +1,552 @@
+//! Signature des commits (`commit.gpgsign`).
+//!
+//! Tous les chemins qui créent un commit (commit, amend, merge, cherry-pick,
+//! revert, pull, rebase) passent par [`write_commit`] : si la signature est
+//! activée, le buffer du commit est signé avec `gpg.program` (OpenPGP, X.509)
+//! ou `ssh-keygen` (`gpg.format=ssh`) puis écrit avec `commit_signed`.
+//!
+//! La TUI occupe le terminal : les signataires ne doivent pas y demander de
+//! phrase de passe. gpg est lancé sans pinentry et ssh-keygen sans invite sur
+//! le terminal ; une clé verrouillée doit être déverrouillée dans l'agent.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use git2::{Commit, Oid, Repository, Signature, Tree};
+
+use crate::error::{GitSvError, Result};
+
+/// Format de signature (`gpg.format`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureFormat {
+    /// Signature OpenPGP via gpg (défaut).
+    OpenPgp,
+    /// Signature X.509 via gpgsm.
+    X509,
+    /// Signature SSH via ssh-keygen.
+    Ssh,
+}
+
+/// Configuration de signature des commits, lue depuis la configuration git.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SigningConfig {
+    /// Format de signature.
+    pub format: SignatureFormat,
+    /// Programme de signature (`gpg.program`, `gpg.ssh.program`...).
+    pub program: String,
+    /// Clé de signature (`user.signingkey`).
+    pub key: Option<String>,
+}
+
+impl SigningConfig {
+    /// Configuration de signature du repository (None si `commit.gpgsign` est désactivé).
+    pub fn from_repo(repo: &Repository) -> Result<Option<Self>> {
+        let config = repo.config()?.snapshot()?;
+        if !config.get_bool("commit.gpgsign").unwrap_or(false) {
+            return Ok(None);
+        }
+
+        let format = match config.get_string("gpg.format").as_deref() {
+            Err(_) | Ok("openpgp") => SignatureFormat::OpenPgp,
+            Ok("x509") => SignatureFormat::X509,
+            Ok("ssh") => SignatureFormat::Ssh,
+            Ok(other) => {
+                return Err(signing_error(format!(
+                    "format de signature inconnu (gpg.format={})",
+                    other
+                )))
+            }
+        };
+        let program = match format {
+            SignatureFormat::OpenPgp => config
+                .get_string("gpg.openpgp.program")
+                .or_else(|_| config.get_string("gpg.program"))
+                .unwrap_or_else(|_| "gpg".to_string()),
+            SignatureFormat::X509 => config
+                .get_string("gpg.x509.program")
+                .unwrap_or_else(|_| "gpgsm".to_string()),
+            SignatureFormat::Ssh => config
+                .get_string("gpg.ssh.program")
+                .unwrap_or_else(|_| "ssh-keygen".to_string()),
+        };
+        let key = config
+            .get_string("user.signingkey")
+            .ok()
+            .filter(|key| !key.trim().is_empty());
+
+        Ok(Some(Self {
+            format,
+            program,
+            key,
+        }))
+    }
+
+    /// Signe le buffer d'un commit et retourne la signature (armure ASCII).
+    pub fn sign(&self, repo: &Repository, buffer: &str) -> Result<String> {
+        match self.format {
+            SignatureFormat::OpenPgp | SignatureFormat::X509 => {
+                // Sans clé configurée, gpg choisit la clé de l'identité du committer.
+                let key = match self.key {
+                    Some(ref key) => key.clone(),
+                    None => {
+                        let sig = repo.signature()?;
+                        format!(
+                            "{} <{}>",
+                            sig.name().unwrap_or(""),
+                            sig.email().unwrap_or("")
+                        )
+                    }
+                };
+                run_signer(
+                    &self.program,
+                    &[
+                        "--batch",
+                        "--pinentry-mode",
+                        "error",
+                        "--status-fd=2",
+                        "-bsau",
+                        &key,
+                    ],
+                    buffer,
+                )
+            }
+            SignatureFormat::Ssh => {
+                let key = self.key.as_deref().ok_or_else(|| {
+                    signing_error("user.signingkey doit être défini pour signer avec SSH")
+                })?;
+                self.sign_ssh(repo, key, buffer)
+            }
+        }
+    }
+
+    /// Signe avec `ssh-keygen -Y sign` (clé privée, ou clé publique de l'agent SSH).
+    fn sign_ssh(&self, repo: &Repository, key: &str, buffer: &str) -> Result<String> {
+        let literal = key
+            .strip_prefix("key::")
+            .or_else(|| key.starts_with("ssh-").then_some(key));
+
+        let Some(public_key) = literal else {
+            let path = expand_home(key);
+            let path = path.to_string_lossy();
+            return run_signer(
+                &self.program,
+                &["-Y", "sign", "-n", "git", "-f", &path],
+                buffer,
+            );
+        };
+
+        // Clé publique littérale : ssh-keygen attend un fichier et signe via l'agent.
+        let key_file = repo.path().join("git_sv_signing_key.pub");
+        std::fs::write(&key_file, format!("{}\n", public_key.trim())).map_err(|e| {
+            GitSvError::Io {
+                source: e,
+                context: "écriture de la clé publique de signature".to_string(),
+            }
+        })?;
+        let path = key_file.to_string_lossy();
+        let result = run_signer(
+            &self.program,
+            &["-Y", "sign", "-n", "git", "-U", "-f", &path],
+            buffer,
+        );
+        let _ = std::fs::remove_file(&key_file);
+        result
+    }
+}
+
+/// Écrit un commit et met à jour `update_ref`, comme `Repository::commit`.
+///
+/// Le commit est signé si `commit.gpgsign` est activé.
+pub fn write_commit(
+    repo: &Repository,
+    update_ref: Option<&str>,
+    author: &Signature,
+    committer: &Signature,
+    message: &str,
+    tree: &Tree,
+    parents: &[&Commit],
+) -> Result<Oid> {
+    let Some(signing) = SigningConfig::from_repo(repo)? else {
+        return Ok(repo.commit(update_ref, author, committer, message, tree, parents)?);
+    };
+
+    let oid = create_signed(repo, &signing, author, committer, message, tree, parents)?;
+    if let Some(refname) = update_ref {
+        let kind = match parents.len() {
+            0 => "commit (initial)",
+            1 => "commit",
+            _ => "commit (merge)",
+        };
+        // Comme `Repository::commit` : la référence doit encore viser le premier parent.
+        let expected = parents.first().map(|parent| parent.id());
+        update_reference(repo, refname, oid, expected, &reflog_message(kind, message))?;
+    }
+    Ok(oid)
+}
+
+/// Crée un commit signé sans mettre à jour de référence.
+fn create_signed(
+    repo: &Repository,
+    signing: &SigningConfig,
+    author: &Signature,
+    committer: &Signature,
+    message: &str,
+    tree: &Tree,
+    parents: &[&Commit],
+) -> Result<Oid> {
+    let buffer = repo.commit_create_buffer(author, committer, message, tree, parents)?;
+    let buffer = buffer
+        .as_str()
+        .ok_or_else(|| signing_error("le contenu du commit n'est pas en UTF-8"))?;
+    let signature = signing.sign(repo, buffer)?;
+    Ok(repo.commit_signed(buffer, &signature, None)?)
+}
+
+/// Fait pointer `refname` (ou la branche de HEAD si `refname` est symbolique) sur `oid`.
+///
+/// La mise à jour n'a lieu que si la référence vise encore `expected`, lu lors de la
+/// création du commit (None : elle ne doit pas encore exister). Sinon elle a bougé
+/// entre-temps et l'opération échoue au lieu d'écraser ce changement.
+pub fn update_reference(
+    repo: &Repository,
+    refname: &str,
+    oid: Oid,
+    expected: Option<Oid>,
+    log_message: &str,
+) -> Result<()> {
+    // La référence symbolique n'est pas résolue : HEAD d'un repository sans
+    // commit pointe sur une branche qui n'existe pas encore.
+    let target = match repo.find_reference(refname) {
+        Ok(reference) => reference.symbolic_target().unwrap_or(refname).to_string(),
+        Err(_) => refname.to_string(),
+    };
+    // Un identifiant nul impose que la référence n'existe pas.
+    let current = expected.unwrap_or_else(Oid::zero);
+    repo.reference_matching(&target, oid, true, current, log_message)?;
+    Ok(())
+}
+
+/// Message de reflog d'un commit (ex: "commit (amend): Corrige le parser").
+pub fn reflog_message(kind: &str, message: &str) -> String {
+    format!("{}: {}", kind, message.lines().next().unwrap_or(""))
+}
+
+/// Exécute le programme de signature : buffer sur stdin, signature sur stdout.
+///
+/// ssh-keygen passe par `SSH_ASKPASS` au lieu du terminal ; sans programme
+/// configuré, la demande de phrase de passe échoue.
+fn run_signer(program: &str, args: &[&str], buffer: &str) -> Result<String> {
+    let mut command = Command::new(program);
+    command.env("SSH_ASKPASS_REQUIRE", "force");
+    if std::env::var_os("SSH_ASKPASS").is_none() {
+        command.env("SSH_ASKPASS", "false");
+    }
+    let mut child = command
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| signing_error(format!("impossible de lancer {}: {}", program, e)))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(buffer.as_bytes())
+            .map_err(|e| signing_error(format!("écriture vers {}: {}", program, e)))?;
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|e| signing_error(format!("exécution de {}: {}", program, e)))?;
+
+    let signature = String::from_utf8_lossy(&output.stdout).to_string();
+    if !output.status.success() || signature.trim().is_empty() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        // Les lignes de statut de gpg ([GNUPG:] ...) n'aident pas l'utilisateur.
+        let details: Vec<&str> = stderr
+            .lines()
+            .filter(|line| !line.starts_with("[GNUPG:]") && !line.trim().is_empty())
+            .collect();
+        if stderr.contains("No pinentry") || stderr.contains("passphrase") {
+            return Err(signing_error(format!(
+                "phrase de passe requise, déverrouillez la clé dans l'agent ({})",
+                program
+            )));
+        }
+        return Err(signing_error(format!(
+            "{} a échoué: {}",
+            program,
+            details.join(" ")
+        )));
+    }
+    Ok(signature)
+}
+
+/// Remplace un `~/` initial par le répertoire personnel.
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+fn signing_error(details: impl Into<String>) -> GitSvError {
+    GitSvError::OperationFailed {
+        operation: "signature du commit",
+        details: details.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::commit::{amend_commit_with_hooks, create_commit_with_hooks};
+    use crate::git::hooks::HookRun;
+    use crate::git::tests::test_utils::*;
+
+    /// Génère une clé SSH de test (None si ssh-keygen est indisponible).
+    fn ssh_key(dir: &std::path::Path, passphrase: &str) -> Option<PathBuf> {
+        let key = dir.join("id_test");
+        let status = Command::new("ssh-keygen")
+            .args(["-q", "-t", "ed25519", "-N", passphrase, "-C", "test", "-f"])
+            .arg(&key)
+            .stdin(Stdio::null())
+            .status()
+            .ok()?;
+        status.success().then_some(key)
+    }
+
+    #[test]
+    fn test_commits_are_signed_with_ssh_key() {
+        let (_dir, repo) = create_test_repo();
+        let keys = tempfile::TempDir::new().unwrap();
+        let Some(key) = ssh_key(keys.path(), "") else {
+            return;
+        };
+        let mut config = repo.config().unwrap();
+        config.set_bool("commit.gpgsign", true).unwrap();
+        config.set_str("gpg.format", "ssh").unwrap();
+        config
+            .set_str("user.signingkey", key.to_str().unwrap())
+            .unwrap();
+
+        create_file(&repo, "a.txt", "v1");
+        crate::git::commit::stage_all(&repo).unwrap();
+        let first =
+            create_commit_with_hooks(&repo, "Premier commit", &mut HookRun::default()).unwrap();
+        let (signature, data) = repo.extract_signature(&first, None).unwrap();
+        assert!(signature
+            .as_str()
+            .unwrap()
+            .starts_with("-----BEGIN SSH SIGNATURE-----"));
+        assert!(data.as_str().unwrap().contains("Premier commit"));
+        assert_eq!(repo.head().unwrap().target(), Some(first));
+
+        // La signature est vérifiable avec la clé publique
+        let allowed = keys.path().join("allowed_signers");
+        let public_key = std::fs::read_to_string(key.with_extension("pub")).unwrap();
+        std::fs::write(&allowed, format!("test@example.com {}", public_key)).unwrap();
+        let signature_file = keys.path().join("commit.sig");
+        std::fs::write(&signature_file, &*signature).unwrap();
+        let mut verify = Command::new("ssh-keygen")
+            .args(["-Y", "verify", "-n", "git", "-I", "test@example.com", "-f"])
+            .arg(&allowed)
+            .arg("-s")
+            .arg(&signature_file)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+        verify.stdin.take().unwrap().write_all(&data).unwrap();
+        assert!(verify.wait().unwrap().success());
+
+        // L'amend est signé et remplace HEAD
+        let amended =
+            amend_commit_with_hooks(&repo, "Premier commit (amendé)", &mut HookRun::default())
+                .unwrap();
+        assert_ne!(amended, first);
+        assert!(repo.extract_signature(&amended, None).is_ok());
+        assert_eq!(repo.head().unwrap().target(), Some(amended));
+    }
+
+    /// Trousseau gpg de test et wrapper de `gpg.program` qui l'utilise
+    /// (None si gpg est indisponible).
+    fn gpg_home(passphrase: &str) -> Option<(tempfile::TempDir, PathBuf)> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let home = tempfile::TempDir::new().unwrap();
+        let generated = Command::new("gpg")
+            .arg("--homedir")
+            .arg(home.path())
+            .args(["--batch", "--pinentry-mode", "loopback"])
+            .args(["--passphrase", passphrase, "--quick-gen-key"])
+            .args(["Test User <test@example.com>", "ed25519", "sign", "never"])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .ok()?;
+        // L'agent est arrêté : la phrase de passe n'est pas en cache.
+        kill_gpg_agent(home.path());
+        if !generated.success() {
+            return None;
+        }
+        let wrapper = home.path().join("gpg-test");
+        std::fs::write(
+            &wrapper,
+            format!(
+                "#!/bin/sh\nexec gpg --homedir '{}' \"$@\"\n",
+                home.path().display()
+            ),
+        )
+        .unwrap();
+        std::fs::set_permissions(&wrapper, std::fs::Permissions::from_mode(0o755)).unwrap();
+        Some((home, wrapper))
+    }
+
+    fn kill_gpg_agent(home: &std::path::Path) {
+        let _ = Command::new("gpgconf")
+            .arg("--homedir")
+            .arg(home)
+            .args(["--kill", "gpg-agent"])
+            .status();
+    }
+
+    #[test]
+    fn test_commits_are_signed_with_gpg() {
+        let (_dir, repo) = create_test_repo();
+        let Some((home, wrapper)) = gpg_home("") else {
+            // gpg indisponible dans cet environnement.
+            return;
+        };
+        let mut config = repo.config().unwrap();
+        config.set_bool("commit.gpgsign", true).unwrap();
+        config
+            .set_str("gpg.program", wrapper.to_str().unwrap())
+            .unwrap();
+
+        // Sans user.signingkey, la clé de l'identité du committer est utilisée
+        create_file(&repo, "a.txt", "v1");
+        crate::git::commit::stage_all(&repo).unwrap();
+        let oid =
+            create_commit_with_hooks(&repo, "Signé avec gpg", &mut HookRun::default()).unwrap();
+        let (signature, _) = repo.extract_signature(&oid, None).unwrap();
+        assert!(signature
+            .as_str()
+            .unwrap()
+            .starts_with("-----BEGIN PGP SIGNATURE-----"));
+
+        kill_gpg_agent(home.path());
+    }
+
+    #[test]
+    fn test_locked_gpg_key_reports_missing_passphrase() {
+        let (_dir, repo) = create_test_repo();
+        let Some((home, wrapper)) = gpg_home("secret") else {
+            return;
+        };
+        let mut config = repo.config().unwrap();
+        config.set_bool("commit.gpgsign", true).unwrap();
+        config
+            .set_str("gpg.program", wrapper.to_str().unwrap())
+            .unwrap();
+
+        // Pas de pinentry : la signature échoue au lieu d'attendre sur le terminal.
+        create_file(&repo, "a.txt", "v1");
+        crate::git::commit::stage_all(&repo).unwrap();
+        let err =
+            create_commit_with_hooks(&repo, "Verrouillé", &mut HookRun::default()).unwrap_err();
+        assert!(
+            err.to_string().contains("phrase de passe requise"),
+            "{}",
+            err
+        );
+
+        kill_gpg_agent(home.path());
+    }
+
+    #[test]
+    fn test_signing_failure_aborts_commit() {
+        let (_dir, repo) = create_test_repo();
+        let first = commit_file(&repo, "a.txt", "v1", "First");
+        let mut config = repo.config().unwrap();
+        config.set_bool("commit.gpgsign", true).unwrap();
+        config
+            .set_str("gpg.program", "git_sv-programme-inexistant")
+            .unwrap();
+
+        create_file(&repo, "a.txt", "v2");
+        crate::git::commit::stage_all(&repo).unwrap();
+        let err = create_commit_with_hooks(&repo, "Second", &mut HookRun::default()).unwrap_err();
+        assert!(
+            err.to_string().contains("git_sv-programme-inexistant"),
+            "{}",
+            err
+        );
+        assert_eq!(repo.head().unwrap().target(), Some(first));
+    }
+
+    #[test]
+    fn test_locked_ssh_key_reports_missing_passphrase() {
+        let (_dir, repo) = create_test_repo();
+        let keys = tempfile::TempDir::new().unwrap();
+        let Some(key) = ssh_key(keys.path(), "secret") else {
+            return;
+        };
+        let mut config = repo.config().unwrap();
+        config.set_bool("commit.gpgsign", true).unwrap();
+        config.set_str("gpg.format", "ssh").unwrap();
+        config
+            .set_str("user.signingkey", key.to_str().unwrap())
+            .unwrap();
+
+        // Aucune invite sur le terminal : la signature échoue aussitôt.
+        create_file(&repo, "a.txt", "v1");
+        crate::git::commit::stage_all(&repo).unwrap();
+        let err =
+            create_commit_with_hooks(&repo, "Verrouillé", &mut HookRun::default()).unwrap_err();
+        assert!(
+            err.to_string().contains("phrase de passe requise"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_update_reference_refuses_a_moved_branch() {
+        let (_dir, repo) = create_test_repo();
+        let first = commit_file(&repo, "a.txt", "v1", "First");
+        let second = commit_file(&repo, "a.txt", "v2", "Second");
+
+        // La branche a avancé depuis la lecture de `first` : rien n'est écrasé
+        assert!(update_reference(&repo, "HEAD", first, Some(first), "test").is_err());
+        assert_eq!(repo.head().unwrap().target(), Some(second));
+
+        update_reference(&repo, "HEAD", first, Some(second), "test").unwrap();
+        assert_eq!(repo.head().unwrap().target(), Some(first));
+
+        // Sans valeur attendue, la référence ne doit pas encore exister
+        update_reference(&repo, "refs/heads/nouvelle", second, None, "test").unwrap();
+        assert!(update_reference(&repo, "refs/heads/nouvelle", first, None, "test").is_err());
+    }
+
+    #[test]
+    fn test_signing_config_from_repo() {
+        let (_dir, repo) = create_test_repo();
+        let mut config = repo.config().unwrap();
+        config.set_bool("commit.gpgsign", false).unwrap();
+        assert_eq!(SigningConfig::from_repo(&repo).unwrap(), None);
+
+        config.set_bool("commit.gpgsign", true).unwrap();
+        config.set_str("gpg.format", "x509").unwrap();
+        config.set_str("user.signingkey", "ABCDEF").unwrap();
+        let signing = SigningConfig::from_repo(&repo).unwrap().unwrap();
+        assert_eq!(signing.format, SignatureFormat::X509);
+        assert_eq!(signing.program, "gpgsm");
+        assert_eq!(signing.key.as_deref(), Some("ABCDEF"));
+
+        config.set_str("gpg.format", "pgp").unwrap();
+        assert!(SigningConfig::from_repo(&repo).is_err());
+    }
+}
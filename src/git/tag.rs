@@ -0,0 +1,171 @@
+//! Création, suppression et listing des tags.
+
+use git2::{ObjectType, Oid, Repository};
+
+use crate::error::Result;
+
+/// Informations sur un tag.
+#[derive(Debug, Clone)]
+pub struct TagInfo {
+    /// Nom du tag (sans `refs/tags/`).
+    pub name: String,
+    /// Commit pointé par le tag.
+    pub target: Oid,
+    /// Résumé du commit pointé.
+    pub summary: String,
+    /// Message du tag (tags annotés uniquement).
+    pub message: Option<String>,
+    /// Auteur du tag (tags annotés uniquement).
+    pub tagger: Option<String>,
+    /// Date du tag en secondes depuis l'epoch (tags annotés uniquement).
+    pub tagger_date: Option<i64>,
+}
+
+impl Default for TagInfo {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            target: Oid::zero(),
+            summary: String::new(),
+            message: None,
+            tagger: None,
+            tagger_date: None,
+        }
+    }
+}
+
+impl TagInfo {
+    /// Le tag est-il annoté?
+    pub fn is_annotated(&self) -> bool {
+        self.tagger_date.is_some() || self.message.is_some()
+    }
+}
+
+/// Liste tous les tags du repository, triés par nom.
+pub fn list_tags(repo: &Repository) -> Result<Vec<TagInfo>> {
+    let mut tags = Vec::new();
+
+    for name in repo.tag_names(None)?.iter().flatten() {
+        let reference = repo.find_reference(&format!("refs/tags/{}", name))?;
+        let object = reference.peel(ObjectType::Any)?;
+
+        // Un tag annoté est un objet à part entière qui pointe vers le commit.
+        let (message, tagger, tagger_date) =
+            match reference.target().and_then(|oid| repo.find_tag(oid).ok()) {
+                Some(tag) => (
+                    tag.message().map(|m| m.trim_end().to_string()),
+                    tag.tagger().and_then(|s| s.name().map(|n| n.to_string())),
+                    tag.tagger().map(|s| s.when().seconds()),
+                ),
+                None => (None, None, None),
+            };
+
+        // Ignorer les tags qui ne pointent pas vers un commit (tags d'arbre ou de blob).
+        let Ok(commit) = object.peel_to_commit() else {
+            continue;
+        };
+
+        tags.push(TagInfo {
+            name: name.to_string(),
+            target: commit.id(),
+            summary: commit.summary().unwrap_or("").to_string(),
+            message,
+            tagger,
+            tagger_date,
+        });
+    }
+
+    tags.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(tags)
+}
+
+/// Crée un tag léger sur le commit donné.
+pub fn create_lightweight_tag(repo: &Repository, name: &str, target: Oid) -> Result<()> {
+    let commit = repo.find_commit(target)?;
+    repo.tag_lightweight(name, commit.as_object(), false)?;
+    Ok(())
+}
+
+/// Crée un tag annoté sur le commit donné.
+pub fn create_annotated_tag(
+    repo: &Repository,
+    name: &str,
+    target: Oid,
+    message: &str,
+) -> Result<()> {
+    let commit = repo.find_commit(target)?;
+    let signature = repo.signature()?;
+    repo.tag(name, commit.as_object(), &signature, message, false)?;
+    Ok(())
+}
+
+/// Supprime un tag local.
+pub fn delete_tag(repo: &Repository, name: &str) -> Result<()> {
+    repo.tag_delete(name)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::tests::test_utils::*;
+
+    #[test]
+    fn test_create_lightweight_tag() {
+        let (_temp_dir, repo) = create_test_repo();
+        let first = commit_file(&repo, "a.txt", "v1", "First");
+        commit_file(&repo, "a.txt", "v2", "Second");
+
+        create_lightweight_tag(&repo, "v0.1", first).unwrap();
+
+        let tags = list_tags(&repo).unwrap();
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].name, "v0.1");
+        assert_eq!(tags[0].target, first);
+        assert_eq!(tags[0].summary, "First");
+        assert!(!tags[0].is_annotated());
+        assert!(tags[0].tagger_date.is_none());
+    }
+
+    #[test]
+    fn test_create_annotated_tag() {
+        let (_temp_dir, repo) = create_test_repo();
+        let oid = commit_file(&repo, "a.txt", "v1", "First");
+
+        create_annotated_tag(&repo, "v1.0", oid, "Release 1.0").unwrap();
+
+        let tags = list_tags(&repo).unwrap();
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].target, oid);
+        assert!(tags[0].is_annotated());
+        assert_eq!(tags[0].message.as_deref(), Some("Release 1.0"));
+        assert_eq!(tags[0].tagger.as_deref(), Some("Test User"));
+        assert!(tags[0].tagger_date.is_some());
+    }
+
+    #[test]
+    fn test_create_existing_tag_fails() {
+        let (_temp_dir, repo) = create_test_repo();
+        let oid = commit_file(&repo, "a.txt", "v1", "First");
+
+        create_lightweight_tag(&repo, "v1.0", oid).unwrap();
+        assert!(create_annotated_tag(&repo, "v1.0", oid, "Again").is_err());
+    }
+
+    #[test]
+    fn test_delete_tag() {
+        let (_temp_dir, repo) = create_test_repo();
+        let oid = commit_file(&repo, "a.txt", "v1", "First");
+        create_lightweight_tag(&repo, "v0.1", oid).unwrap();
+        create_annotated_tag(&repo, "v1.0", oid, "Release").unwrap();
+
+        delete_tag(&repo, "v0.1").unwrap();
+
+        let names: Vec<String> = list_tags(&repo)
+            .unwrap()
+            .into_iter()
+            .map(|t| t.name)
+            .collect();
+        assert_eq!(names, vec!["v1.0".to_string()]);
+    }
+}
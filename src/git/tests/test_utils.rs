@@ -65,3 +65,14 @@ pub fn commit_file(repo: &Repository, path: &str, content: &str, message: &str)
     index.write().expect("Failed to write index");
     commit(repo, message)
 }
+
+/// Installe un hook shell exécutable dans `dir`.
+pub fn install_hook(dir: &Path, name: &str, script: &str) {
+    use std::os::unix::fs::PermissionsExt;
+
+    std::fs::create_dir_all(dir).expect("Failed to create hooks dir");
+    let path = dir.join(name);
+    std::fs::write(&path, format!("#!/bin/sh\n{}\n", script)).expect("Failed to write hook");
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))
+        .expect("Failed to make hook executable");
+}
@@ -0,0 +1,429 @@
+//! Snapshots de l'état du repository pour annuler/rétablir les actions destructives.
+//!
+//! Un snapshot capture HEAD, les branches et tags locaux, l'arbre de l'index,
+//! les modifications du répertoire de travail (sous forme de commit de stash
+//! orphelin, récupérable même hors de git_sv) et la pile des stashes.
+
+use std::collections::BTreeMap;
+
+use git2::{build::CheckoutBuilder, Oid, Repository, RepositoryState};
+
+use crate::error::Result;
+
+/// Nombre maximum de snapshots conservés dans l'historique.
+const MAX_HISTORY: usize = 50;
+
+/// Cible de HEAD au moment du snapshot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HeadTarget {
+    /// HEAD symbolique vers une branche (nom complet, ex: "refs/heads/main").
+    Branch(String),
+    /// HEAD détachée sur un commit.
+    Detached(Oid),
+}
+
+/// État du repository capturé avant une action.
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    /// Description de l'action qui a suivi le snapshot (ex: "commit").
+    pub label: String,
+    /// Cible de HEAD.
+    pub head: HeadTarget,
+    /// Branches et tags locaux (nom complet → oid).
+    pub refs: BTreeMap<String, Oid>,
+    /// Arbre de l'index (None si l'index contient des conflits).
+    pub index_tree: Option<Oid>,
+    /// Arbre du répertoire de travail (fichiers suivis).
+    pub worktree_tree: Option<Oid>,
+    /// Commit de stash orphelin du répertoire de travail, s'il diffère de l'index.
+    pub worktree_commit: Option<Oid>,
+    /// Pile des stashes (oid, message), du plus récent au plus ancien.
+    pub stashes: Vec<(Oid, String)>,
+    /// Une opération (merge, revert, cherry-pick...) était-elle en cours?
+    pub in_progress: bool,
+}
+
+impl Snapshot {
+    /// Capture l'état courant du repository.
+    pub fn take(repo: &Repository, label: &str) -> Result<Self> {
+        let head_ref = repo.find_reference("HEAD")?;
+        let head = match head_ref.symbolic_target() {
+            Some(name) => HeadTarget::Branch(name.to_string()),
+            None => HeadTarget::Detached(head_ref.target().unwrap_or_else(Oid::zero)),
+        };
+
+        let mut refs = BTreeMap::new();
+        for glob in ["refs/heads/*", "refs/tags/*"] {
+            for reference in repo.references_glob(glob)?.flatten() {
+                if let (Some(name), Some(oid)) = (reference.name(), reference.target()) {
+                    refs.insert(name.to_string(), oid);
+                }
+            }
+        }
+
+        let (index_tree, worktree_tree) = capture_trees(repo)?;
+        let worktree_commit = match (index_tree, worktree_tree) {
+            (Some(index_tree), Some(worktree_tree)) if index_tree != worktree_tree => Some(
+                create_worktree_commit(repo, index_tree, worktree_tree, label)?,
+            ),
+            _ => None,
+        };
+
+        let stashes = match repo.reflog("refs/stash") {
+            Ok(reflog) => reflog
+                .iter()
+                .map(|entry| (entry.id_new(), entry.message().unwrap_or("").to_string()))
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+
+        Ok(Self {
+            label: label.to_string(),
+            head,
+            refs,
+            index_tree,
+            worktree_tree,
+            worktree_commit,
+            stashes,
+            in_progress: repo.state() != RepositoryState::Clean,
+        })
+    }
+
+    /// Les deux snapshots décrivent-ils le même état (le libellé est ignoré)?
+    pub fn same_state(&self, other: &Snapshot) -> bool {
+        self.head == other.head
+            && self.refs == other.refs
+            && self.index_tree == other.index_tree
+            && self.worktree_tree == other.worktree_tree
+            && self.stashes == other.stashes
+            && self.in_progress == other.in_progress
+    }
+
+    /// Restaure ce snapshot, `current` étant l'état actuel du repository.
+    ///
+    /// Retourne la liste des éléments restaurés, pour l'affichage.
+    pub fn restore(&self, repo: &Repository, current: &Snapshot) -> Result<Vec<String>> {
+        let mut restored = Vec::new();
+        let message = format!("git_sv: annulation de {}", self.label);
+
+        // Références modifiées ou supprimées depuis le snapshot.
+        let mut changed_refs = 0;
+        for (name, oid) in &self.refs {
+            if current.refs.get(name) != Some(oid) {
+                repo.reference(name, *oid, true, &message)?;
+                changed_refs += 1;
+            }
+        }
+
+        if self.head != current.head {
+            match &self.head {
+                HeadTarget::Branch(name) => repo.set_head(name)?,
+                HeadTarget::Detached(oid) => repo.set_head_detached(*oid)?,
+            }
+            restored.push("HEAD".to_string());
+        }
+
+        // Références créées depuis le snapshot (HEAD n'y pointe plus).
+        for name in current.refs.keys() {
+            if !self.refs.contains_key(name) {
+                repo.find_reference(name)?.delete()?;
+                changed_refs += 1;
+            }
+        }
+        if changed_refs > 0 {
+            restored.push(format!("{} référence(s)", changed_refs));
+        }
+
+        if !self.in_progress && current.in_progress {
+            repo.cleanup_state()?;
+            restored.push("état du merge".to_string());
+        }
+
+        if let Some(index_tree) = self.index_tree {
+            let worktree_changed = self.worktree_tree != current.worktree_tree
+                || self.index_tree != current.index_tree;
+            if worktree_changed {
+                // Checkout forcé du répertoire de travail, puis index remis à son arbre.
+                // Sans commit de stash, le répertoire de travail était identique à l'index.
+                let tree = match self.worktree_commit {
+                    Some(commit) => repo.find_commit(commit)?.tree()?,
+                    None => repo.find_tree(index_tree)?,
+                };
+                let mut checkout = CheckoutBuilder::new();
+                checkout.force();
+                repo.checkout_tree(tree.as_object(), Some(&mut checkout))?;
+
+                let mut index = repo.index()?;
+                index.read_tree(&repo.find_tree(index_tree)?)?;
+                index.write()?;
+
+                if self.index_tree != current.index_tree {
+                    restored.push("index".to_string());
+                }
+                if self.worktree_tree != current.worktree_tree {
+                    restored.push("répertoire de travail".to_string());
+                }
+            }
+        }
+
+        if self.stashes != current.stashes {
+            restore_stashes(repo, &self.stashes)?;
+            restored.push("stashes".to_string());
+        }
+
+        Ok(restored)
+    }
+}
+
+/// Calcule l'arbre de l'index et celui du répertoire de travail (fichiers suivis).
+fn capture_trees(repo: &Repository) -> Result<(Option<Oid>, Option<Oid>)> {
+    let mut index = repo.index()?;
+    if index.has_conflicts() {
+        return Ok((None, None));
+    }
+    let index_tree = index.write_tree()?;
+
+    // Mise à jour en mémoire seulement : l'index sur disque est relu ensuite.
+    let worktree_tree = index
+        .update_all(["*"].iter(), None)
+        .and_then(|_| index.write_tree());
+    index.read(true)?;
+
+    Ok((Some(index_tree), Some(worktree_tree?)))
+}
+
+/// Crée un commit de stash orphelin (parents : HEAD et l'index) pour le répertoire de travail.
+fn create_worktree_commit(
+    repo: &Repository,
+    index_tree: Oid,
+    worktree_tree: Oid,
+    label: &str,
+) -> Result<Oid> {
+    let signature = repo
+        .signature()
+        .or_else(|_| git2::Signature::now("git_sv", "git_sv@local"))?;
+    let head = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+    let head_parents: Vec<&git2::Commit> = head.iter().collect();
+
+    let index_oid = repo.commit(
+        None,
+        &signature,
+        &signature,
+        &format!("index avant {}", label),
+        &repo.find_tree(index_tree)?,
+        &head_parents,
+    )?;
+    let index_commit = repo.find_commit(index_oid)?;
+
+    let mut parents = head_parents;
+    parents.push(&index_commit);
+    let oid = repo.commit(
+        None,
+        &signature,
+        &signature,
+        &format!("git_sv: snapshot avant {}", label),
+        &repo.find_tree(worktree_tree)?,
+        &parents,
+    )?;
+    Ok(oid)
+}
+
+/// Reconstruit `refs/stash` et son reflog à partir de la pile donnée.
+fn restore_stashes(repo: &Repository, stashes: &[(Oid, String)]) -> Result<()> {
+    if let Ok(mut reference) = repo.find_reference("refs/stash") {
+        reference.delete()?;
+    }
+    for (oid, message) in stashes.iter().rev() {
+        // Le reflog de refs/stash n'est écrit que s'il existe déjà.
+        repo.reference_ensure_log("refs/stash")?;
+        repo.reference("refs/stash", *oid, true, message)?;
+    }
+    Ok(())
+}
+
+/// Résultat d'une annulation ou d'un rétablissement.
+#[derive(Debug, Clone)]
+pub struct UndoOutcome {
+    /// Libellé de l'action annulée ou rétablie.
+    pub label: String,
+    /// Éléments restaurés.
+    pub restored: Vec<String>,
+}
+
+/// Historique des snapshots pour annuler/rétablir.
+#[derive(Debug, Clone, Default)]
+pub struct UndoHistory {
+    undo: Vec<Snapshot>,
+    redo: Vec<Snapshot>,
+}
+
+impl UndoHistory {
+    /// Enregistre un snapshot avant une action (vide la pile de rétablissement).
+    pub fn record(&mut self, snapshot: Snapshot) {
+        if self
+            .undo
+            .last()
+            .is_some_and(|last| last.same_state(&snapshot))
+        {
+            return;
+        }
+        self.undo.push(snapshot);
+        if self.undo.len() > MAX_HISTORY {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+    }
+
+    /// Restaure le dernier snapshot qui diffère de l'état courant.
+    pub fn undo(&mut self, repo: &Repository) -> Result<Option<UndoOutcome>> {
+        Self::step(repo, &mut self.undo, &mut self.redo)
+    }
+
+    /// Rétablit le dernier état annulé.
+    pub fn redo(&mut self, repo: &Repository) -> Result<Option<UndoOutcome>> {
+        Self::step(repo, &mut self.redo, &mut self.undo)
+    }
+
+    /// Dépile `from` jusqu'à un snapshot différent de l'état courant, le restaure
+    /// et empile l'état courant sur `to`.
+    fn step(
+        repo: &Repository,
+        from: &mut Vec<Snapshot>,
+        to: &mut Vec<Snapshot>,
+    ) -> Result<Option<UndoOutcome>> {
+        let mut current = Snapshot::take(repo, "")?;
+        while let Some(snapshot) = from.pop() {
+            if snapshot.same_state(&current) {
+                continue;
+            }
+            let restored = match snapshot.restore(repo, &current) {
+                Ok(restored) => restored,
+                Err(e) => {
+                    from.push(snapshot);
+                    return Err(e);
+                }
+            };
+            current.label = snapshot.label.clone();
+            to.push(current);
+            return Ok(Some(UndoOutcome {
+                label: snapshot.label,
+                restored,
+            }));
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::tests::test_utils::*;
+    use std::fs;
+
+    #[test]
+    fn test_undo_restores_discarded_changes() {
+        let (dir, repo) = create_test_repo();
+        commit_file(&repo, "a.txt", "v1", "First");
+        fs::write(dir.path().join("a.txt"), "local changes").unwrap();
+
+        let mut history = UndoHistory::default();
+        history.record(Snapshot::take(&repo, "discard").unwrap());
+        crate::git::discard::discard_all(&repo).unwrap();
+        assert_eq!(fs::read_to_string(dir.path().join("a.txt")).unwrap(), "v1");
+
+        let outcome = history.undo(&repo).unwrap().unwrap();
+        assert_eq!(outcome.label, "discard");
+        assert!(outcome
+            .restored
+            .contains(&"répertoire de travail".to_string()));
+        assert_eq!(
+            fs::read_to_string(dir.path().join("a.txt")).unwrap(),
+            "local changes"
+        );
+
+        // Rétablir refait le discard.
+        history.redo(&repo).unwrap().unwrap();
+        assert_eq!(fs::read_to_string(dir.path().join("a.txt")).unwrap(), "v1");
+    }
+
+    #[test]
+    fn test_undo_restores_deleted_branch_and_head() {
+        let (_dir, repo) = create_test_repo();
+        let first = commit_file(&repo, "a.txt", "v1", "First");
+        let commit = repo.find_commit(first).unwrap();
+        repo.branch("feature", &commit, false).unwrap();
+
+        let mut history = UndoHistory::default();
+        history.record(Snapshot::take(&repo, "delete").unwrap());
+        repo.find_branch("feature", git2::BranchType::Local)
+            .unwrap()
+            .delete()
+            .unwrap();
+        commit_file(&repo, "a.txt", "v2", "Second");
+
+        history.undo(&repo).unwrap().unwrap();
+
+        let feature = repo
+            .find_branch("feature", git2::BranchType::Local)
+            .unwrap();
+        assert_eq!(feature.get().target(), Some(first));
+        assert_eq!(repo.head().unwrap().target(), Some(first));
+        assert_eq!(history.undo.len(), 0);
+        assert_eq!(history.redo.len(), 1);
+    }
+
+    #[test]
+    fn test_undo_restores_index() {
+        let (dir, repo) = create_test_repo();
+        commit_file(&repo, "a.txt", "v1", "First");
+        fs::write(dir.path().join("a.txt"), "v2").unwrap();
+
+        let mut history = UndoHistory::default();
+        history.record(Snapshot::take(&repo, "stage").unwrap());
+        crate::git::commit::stage_file(&repo, "a.txt").unwrap();
+
+        let outcome = history.undo(&repo).unwrap().unwrap();
+        assert_eq!(outcome.restored, vec!["index".to_string()]);
+
+        let statuses = repo.statuses(None).unwrap();
+        let status = statuses.get(0).unwrap().status();
+        assert!(status.is_wt_modified());
+        assert!(!status.is_index_modified());
+        assert_eq!(fs::read_to_string(dir.path().join("a.txt")).unwrap(), "v2");
+    }
+
+    #[test]
+    fn test_undo_restores_dropped_stash() {
+        let (dir, mut repo) = create_test_repo();
+        commit_file(&repo, "a.txt", "v1", "First");
+        fs::write(dir.path().join("a.txt"), "v2").unwrap();
+        crate::git::stash::save_stash(&mut repo, Some("wip")).unwrap();
+
+        let mut history = UndoHistory::default();
+        history.record(Snapshot::take(&repo, "drop").unwrap());
+        crate::git::stash::drop_stash(&mut repo, 0).unwrap();
+        assert!(crate::git::stash::list_stashes(&mut repo)
+            .unwrap()
+            .is_empty());
+
+        history.undo(&repo).unwrap().unwrap();
+
+        let stashes = crate::git::stash::list_stashes(&mut repo).unwrap();
+        assert_eq!(stashes.len(), 1);
+        assert!(stashes[0].message.contains("wip"));
+    }
+
+    #[test]
+    fn test_record_skips_identical_snapshots() {
+        let (_dir, repo) = create_test_repo();
+        commit_file(&repo, "a.txt", "v1", "First");
+
+        let mut history = UndoHistory::default();
+        history.record(Snapshot::take(&repo, "a").unwrap());
+        history.record(Snapshot::take(&repo, "b").unwrap());
+        assert_eq!(history.undo.len(), 1);
+
+        // Rien n'a changé : il n'y a rien à annuler.
+        assert!(history.undo(&repo).unwrap().is_none());
+    }
+}
@@ -2,8 +2,10 @@
 
 use super::traits::{ActionHandler, HandlerContext};
 use crate::error::Result;
+use crate::git::remote::PushRequest;
 use crate::state::action::BranchAction;
-use crate::state::{AppState, BranchesSection, ViewMode};
+use crate::state::{AppState, BranchesFocus, BranchesSection, InputAction, ViewMode};
+use crate::transfer::RemoteOperation;
 
 /// Handler pour les opérations sur les branches.
 pub struct BranchHandler;
@@ -26,6 +28,20 @@ impl ActionHandler for BranchHandler {
             BranchAction::StashDrop => handle_stash_drop(ctx.state),
             BranchAction::WorktreeCreate => handle_worktree_create(ctx.state),
             BranchAction::WorktreeRemove => handle_worktree_remove(ctx.state),
+            BranchAction::TagCreate => handle_tag_create(ctx.state),
+            BranchAction::TagDelete => handle_tag_delete(ctx.state),
+            BranchAction::TagPush => handle_tag_push(ctx.state),
+            BranchAction::TagPushAll => handle_tag_push_all(ctx.state),
+            BranchAction::PushTo => handle_push_to(ctx.state),
+            BranchAction::RemoteAdd => open_input(ctx.state, InputAction::AddRemote, String::new()),
+            BranchAction::RemoteRename => handle_remote_edit(ctx.state, InputAction::RenameRemote),
+            BranchAction::RemoteSetUrl => handle_remote_edit(ctx.state, InputAction::SetRemoteUrl),
+            BranchAction::RemoteSetPushUrl => {
+                handle_remote_edit(ctx.state, InputAction::SetRemotePushUrl)
+            }
+            BranchAction::RemoteRemove => handle_remote_remove(ctx.state),
+            BranchAction::RemoteFetch => handle_remote_fetch(ctx.state, false),
+            BranchAction::RemotePrune => handle_remote_fetch(ctx.state, true),
             BranchAction::NextSection => handle_next_section(ctx.state),
             BranchAction::PrevSection => handle_prev_section(ctx.state),
             BranchAction::ConfirmInput => handle_confirm_input(ctx.state),
@@ -74,6 +90,7 @@ fn handle_checkout(state: &mut AppState) -> Result<()> {
     };
 
     if let Some(branch_name) = branch_name {
+        super::git::record_snapshot(state, "checkout");
         match crate::git::branch::checkout_branch(&state.repo.repo, &branch_name) {
             Ok(_) => {
                 // Fermer le panel si applicable
@@ -190,6 +207,7 @@ fn handle_stash_apply(state: &mut AppState) -> Result<()> {
         let selected = state.branches_view_state.stash_selected();
         if let Some(stash) = state.branches_view_state.stashes.get(selected).cloned() {
             let index = stash.index;
+            super::git::record_snapshot(state, "stash apply");
             match crate::git::stash::apply_stash(&mut state.repo.repo, index) {
                 Ok(_) => {
                     state.mark_dirty();
@@ -209,6 +227,7 @@ fn handle_stash_pop(state: &mut AppState) -> Result<()> {
         let selected = state.branches_view_state.stash_selected();
         if let Some(stash) = state.branches_view_state.stashes.get(selected).cloned() {
             let index = stash.index;
+            super::git::record_snapshot(state, "stash pop");
             match crate::git::stash::pop_stash(&mut state.repo.repo, index) {
                 Ok(_) => {
                     state.mark_dirty();
@@ -252,20 +271,136 @@ fn handle_worktree_remove(state: &mut AppState) -> Result<()> {
     Ok(())
 }
 
+fn handle_tag_create(state: &mut AppState) -> Result<()> {
+    // Depuis le graph : tag sur le commit sélectionné. Sinon : tag sur HEAD.
+    let target = match state.view_mode {
+        ViewMode::Graph => state
+            .graph
+            .get(state.selected_index)
+            .map(|row| row.node.oid),
+        ViewMode::Branches => state.repo.repo.head().ok().and_then(|head| head.target()),
+        _ => None,
+    };
+
+    let Some(target) = target else {
+        state.set_flash_message("Aucun commit à tagger".to_string());
+        return Ok(());
+    };
+
+    state.view_mode = ViewMode::Branches;
+    state.branches_view_state.section = BranchesSection::Tags;
+    state.branches_view_state.tag_target = Some(target);
+    state.branches_view_state.focus = crate::state::BranchesFocus::Input;
+    state.branches_view_state.input_action = Some(crate::state::InputAction::CreateTag);
+    state.branches_view_state.input_text.clear();
+    state.branches_view_state.input_cursor = 0;
+    state.mark_dirty();
+    Ok(())
+}
+
+fn handle_tag_delete(state: &mut AppState) -> Result<()> {
+    use crate::ui::confirm_dialog::ConfirmAction;
+
+    if let Some(tag) = state.branches_view_state.tags.selected_item() {
+        state.pending_confirmation = Some(ConfirmAction::TagDelete(tag.name.clone()));
+    }
+    Ok(())
+}
+
+fn handle_tag_push(state: &mut AppState) -> Result<()> {
+    let Some(name) = state
+        .branches_view_state
+        .tags
+        .selected_item()
+        .map(|t| t.name.clone())
+    else {
+        return Ok(());
+    };
+    super::git::start_transfer(state, RemoteOperation::PushTag(name))
+}
+
+fn handle_tag_push_all(state: &mut AppState) -> Result<()> {
+    super::git::start_transfer(state, RemoteOperation::PushAllTags)
+}
+
+/// Ouvre la saisie de la vue branches, pré-remplie avec `text`.
+fn open_input(state: &mut AppState, action: InputAction, text: String) -> Result<()> {
+    state.branches_view_state.focus = BranchesFocus::Input;
+    state.branches_view_state.input_action = Some(action);
+    state.branches_view_state.input_cursor = text.len();
+    state.branches_view_state.input_text = text;
+    Ok(())
+}
+
+fn handle_push_to(state: &mut AppState) -> Result<()> {
+    let Some(branch) = state.branches_view_state.selected_branch() else {
+        return Ok(());
+    };
+    if state.branches_view_state.show_remote
+        && state.branches_view_state.branch_selected()
+            >= state.branches_view_state.local_branches.len()
+    {
+        state.set_flash_message("Sélectionnez une branche locale à pousser".to_string());
+        return Ok(());
+    }
+
+    let request = PushRequest::for_branch(&state.repo.repo, &branch.name);
+    super::push::open_push_dialog(state, request, None);
+    Ok(())
+}
+
+/// Ouvre la saisie d'une modification du remote sélectionné.
+fn handle_remote_edit(state: &mut AppState, action: InputAction) -> Result<()> {
+    let Some(remote) = state.branches_view_state.remotes.selected_item() else {
+        return Ok(());
+    };
+    let text = match action {
+        InputAction::RenameRemote => remote.name.clone(),
+        InputAction::SetRemoteUrl => remote.fetch_url.clone().unwrap_or_default(),
+        _ => remote.effective_push_url().unwrap_or_default().to_string(),
+    };
+    open_input(state, action, text)
+}
+
+fn handle_remote_remove(state: &mut AppState) -> Result<()> {
+    use crate::ui::confirm_dialog::ConfirmAction;
+
+    if let Some(remote) = state.branches_view_state.remotes.selected_item() {
+        state.pending_confirmation = Some(ConfirmAction::RemoteRemove(remote.name.clone()));
+    }
+    Ok(())
+}
+
+fn handle_remote_fetch(state: &mut AppState, prune: bool) -> Result<()> {
+    let Some(remote) = state
+        .branches_view_state
+        .remotes
+        .selected_item()
+        .map(|r| r.name.clone())
+    else {
+        return Ok(());
+    };
+    super::git::start_transfer(state, RemoteOperation::FetchRemote { remote, prune })
+}
+
 fn handle_next_section(state: &mut AppState) -> Result<()> {
     state.branches_view_state.section = match state.branches_view_state.section {
         BranchesSection::Branches => BranchesSection::Worktrees,
         BranchesSection::Worktrees => BranchesSection::Stashes,
-        BranchesSection::Stashes => BranchesSection::Branches,
+        BranchesSection::Stashes => BranchesSection::Tags,
+        BranchesSection::Tags => BranchesSection::Remotes,
+        BranchesSection::Remotes => BranchesSection::Branches,
     };
     Ok(())
 }
 
 fn handle_prev_section(state: &mut AppState) -> Result<()> {
     state.branches_view_state.section = match state.branches_view_state.section {
-        BranchesSection::Branches => BranchesSection::Stashes,
+        BranchesSection::Branches => BranchesSection::Remotes,
         BranchesSection::Worktrees => BranchesSection::Branches,
         BranchesSection::Stashes => BranchesSection::Worktrees,
+        BranchesSection::Tags => BranchesSection::Stashes,
+        BranchesSection::Remotes => BranchesSection::Tags,
     };
     Ok(())
 }
@@ -280,6 +415,7 @@ fn handle_confirm_input(state: &mut AppState) -> Result<()> {
 
     match state.branches_view_state.input_action {
         Some(crate::state::InputAction::CreateBranch) => {
+            super::git::record_snapshot(state, "création de branche");
             match crate::git::branch::create_branch(&state.repo.repo, &input) {
                 Ok(_) => {
                     state.set_flash_message(format!("Branche '{}' créée ✓", input));
@@ -291,6 +427,7 @@ fn handle_confirm_input(state: &mut AppState) -> Result<()> {
         Some(crate::state::InputAction::RenameBranch) => {
             if let Some(branch) = state.branches_view_state.selected_branch() {
                 let old_name = branch.name.clone();
+                super::git::record_snapshot(state, "renommage de branche");
                 match crate::git::branch::rename_branch(&state.repo.repo, &old_name, &input) {
                     Ok(_) => {
                         state.set_flash_message(format!("Branche renommée → '{}' ✓", input));
@@ -301,6 +438,7 @@ fn handle_confirm_input(state: &mut AppState) -> Result<()> {
             }
         }
         Some(crate::state::InputAction::SaveStash) => {
+            super::git::record_snapshot(state, "stash");
             match crate::git::stash::save_stash(&mut state.repo.repo, Some(&input)) {
                 Ok(_) => {
                     state.set_flash_message(format!("Stash créé: {} ✓", input));
@@ -309,6 +447,83 @@ fn handle_confirm_input(state: &mut AppState) -> Result<()> {
                 Err(e) => state.set_flash_message(format!("Erreur: {}", e)),
             }
         }
+        Some(crate::state::InputAction::CreateTag) => {
+            // Le format attendu est "nom [message]" : un message crée un tag annoté.
+            let (name, message) = match input.split_once(char::is_whitespace) {
+                Some((name, message)) => (name, Some(message.trim())),
+                None => (input.as_str(), None),
+            };
+            let message = message.filter(|m| !m.is_empty());
+            if let Some(target) = state.branches_view_state.tag_target.take() {
+                super::git::record_snapshot(state, "création de tag");
+                let result = match message {
+                    Some(message) => crate::git::tag::create_annotated_tag(
+                        &state.repo.repo,
+                        name,
+                        target,
+                        message,
+                    ),
+                    None => crate::git::tag::create_lightweight_tag(&state.repo.repo, name, target),
+                };
+                match result {
+                    Ok(_) => {
+                        state.set_flash_message(format!("Tag '{}' créé sur {:.7} ✓", name, target));
+                        state.mark_dirty();
+                    }
+                    Err(e) => state.set_flash_message(format!("Erreur: {}", e)),
+                }
+            }
+        }
+        Some(InputAction::AddRemote) => {
+            // Le format attendu est "nom url"
+            match input.split_once(char::is_whitespace) {
+                Some((name, url)) => {
+                    match crate::git::remote::add_remote(&state.repo.repo, name, url.trim()) {
+                        Ok(_) => {
+                            state.set_flash_message(format!("Remote '{}' ajouté ✓", name));
+                            state.mark_dirty();
+                        }
+                        Err(e) => state.set_flash_message(format!("Erreur: {}", e)),
+                    }
+                }
+                None => state.set_flash_message("Format: nom url".to_string()),
+            }
+        }
+        Some(
+            action @ (InputAction::RenameRemote
+            | InputAction::SetRemoteUrl
+            | InputAction::SetRemotePushUrl),
+        ) => {
+            if let Some(name) = state
+                .branches_view_state
+                .remotes
+                .selected_item()
+                .map(|r| r.name.clone())
+            {
+                let repo = &state.repo.repo;
+                let (result, message) = match action {
+                    InputAction::RenameRemote => (
+                        crate::git::remote::rename_remote(repo, &name, &input),
+                        format!("Remote renommé → '{}' ✓", input),
+                    ),
+                    InputAction::SetRemoteUrl => (
+                        crate::git::remote::set_remote_url(repo, &name, &input),
+                        format!("URL de '{}' modifiée ✓", name),
+                    ),
+                    _ => (
+                        crate::git::remote::set_remote_push_url(repo, &name, Some(&input)),
+                        format!("URL de push de '{}' modifiée ✓", name),
+                    ),
+                };
+                match result {
+                    Ok(_) => {
+                        state.set_flash_message(message);
+                        state.mark_dirty();
+                    }
+                    Err(e) => state.set_flash_message(format!("Erreur: {}", e)),
+                }
+            }
+        }
         Some(crate::state::InputAction::CreateWorktree) => {
             // Le format attendu est "nom chemin [branche]"
             let parts: Vec<&str> = input.split_whitespace().collect();
@@ -339,6 +554,7 @@ fn handle_confirm_input(state: &mut AppState) -> Result<()> {
 
 fn handle_cancel_input(state: &mut AppState) -> Result<()> {
     state.branches_view_state.focus = crate::state::BranchesFocus::List;
+    state.branches_view_state.tag_target = None;
     state.branches_view_state.input_action = None;
     state.branches_view_state.input_text.clear();
     state.branches_view_state.input_cursor = 0;
@@ -0,0 +1,287 @@
+//! Handler pour les commandes personnalisées de l'utilisateur.
+
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+
+use super::traits::{ActionHandler, HandlerContext};
+use crate::commands::{self, CommandMode, CommandTask, CustomCommand};
+use crate::error::Result;
+use crate::state::action::CommandAction;
+use crate::state::{AppState, CommandOutputState, StagingFocus, ViewMode};
+use crate::utils::shell;
+
+/// Handler pour les commandes personnalisées.
+///
+/// Les commandes interactives sont seulement préparées ici : la boucle
+/// événementielle les exécute après avoir suspendu la TUI.
+pub struct CommandHandler;
+
+impl ActionHandler for CommandHandler {
+    type Action = CommandAction;
+
+    fn handle(&mut self, ctx: &mut HandlerContext, action: CommandAction) -> Result<()> {
+        match action {
+            CommandAction::Run(index) => {
+                if let Some(command) = commands::current().get(index) {
+                    run_command(ctx.state, command);
+                }
+            }
+            CommandAction::CloseOutput => ctx.state.command_output = None,
+            CommandAction::ScrollDown => {
+                if let Some(ref mut output) = ctx.state.command_output {
+                    output.scroll_down();
+                }
+            }
+            CommandAction::ScrollUp => {
+                if let Some(ref mut output) = ctx.state.command_output {
+                    output.scroll_up();
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Remplit le modèle de la commande et l'exécute selon son mode.
+fn run_command(state: &mut AppState, command: &CustomCommand) {
+    let script = match commands::expand(&command.command, |name| placeholder_value(state, name)) {
+        Ok(script) => script,
+        Err(e) => {
+            state.set_flash_message(format!("Commande '{}' : {}", command.name, e));
+            return;
+        }
+    };
+
+    match command.mode {
+        CommandMode::Interactive => state.interactive_command = Some(script),
+        CommandMode::Popup => {
+            if let Some(ref task) = state.command_task {
+                state.set_flash_message(format!("Commande '{}' déjà en cours", task.name));
+                return;
+            }
+            match CommandTask::spawn(command.name.clone(), script, workdir(state)) {
+                Ok(task) => {
+                    state.set_flash_message(format!("Commande '{}' en cours…", command.name));
+                    state.command_task = Some(task);
+                }
+                Err(e) => {
+                    state.set_flash_message(format!("Commande '{}' : {}", command.name, e));
+                }
+            }
+        }
+    }
+}
+
+/// Affiche la sortie de la commande en mode popup si elle est terminée.
+///
+/// Appelée à chaque tour de la boucle principale.
+pub fn poll_command(state: &mut AppState) {
+    let Some(result) = state
+        .command_task
+        .as_ref()
+        .and_then(|task| task.try_finish())
+    else {
+        return;
+    };
+    let Some(task) = state.command_task.take() else {
+        return;
+    };
+
+    match result {
+        Ok(output) => {
+            state.command_output = Some(CommandOutputState::new(
+                task.name,
+                &String::from_utf8_lossy(&output.stdout),
+                &String::from_utf8_lossy(&output.stderr),
+                output.status.success(),
+            ));
+        }
+        Err(e) => {
+            state.set_flash_message(format!("Commande '{}' : {}", task.name, e));
+        }
+    }
+    // La commande a pu modifier le repository.
+    state.mark_dirty();
+}
+
+/// Exécute une commande interactive dans le terminal (TUI suspendue).
+///
+/// Attend la touche Entrée avant de rendre la main à la TUI.
+pub fn run_interactive(state: &mut AppState, script: &str) {
+    let status = shell::sh(script)
+        .current_dir(workdir(state))
+        .status()
+        .map_err(|e| shell::spawn_error(e, "lancement de sh".to_string()));
+
+    match status {
+        Ok(status) => {
+            print!(
+                "\n[git_sv] Commande terminée ({}). Entrée pour revenir…",
+                status
+            );
+            let _ = io::stdout().flush();
+            let _ = io::stdin().lock().read_line(&mut String::new());
+            if status.success() {
+                state.set_flash_message("Commande terminée");
+            } else {
+                state.set_flash_message(format!("Commande échouée ({})", status));
+            }
+        }
+        Err(e) => state.set_flash_message(format!("Erreur commande: {}", e)),
+    }
+    state.mark_dirty();
+}
+
+/// Répertoire de travail du repository (répertoire `.git` pour un repo bare).
+fn workdir(state: &AppState) -> PathBuf {
+    state
+        .repo
+        .repo
+        .workdir()
+        .unwrap_or_else(|| state.repo.repo.path())
+        .to_path_buf()
+}
+
+/// Valeur d'un placeholder d'après la sélection de la vue courante.
+fn placeholder_value(state: &AppState, name: &str) -> Option<String> {
+    match name {
+        "commit" => selected_commit(state),
+        "branch" => selected_branch(state),
+        "file" => selected_file(state),
+        "repo" => Some(workdir(state).to_string_lossy().into_owned()),
+        _ => None,
+    }
+}
+
+/// Commit sélectionné (graph, reflog), HEAD ailleurs.
+fn selected_commit(state: &AppState) -> Option<String> {
+    let oid = match state.view_mode {
+        ViewMode::Graph => state
+            .graph
+            .get(state.selected_index)
+            .map(|row| row.node.oid),
+        ViewMode::Reflog => state
+            .reflog_state
+            .as_ref()
+            .and_then(|r| r.selected_entry())
+            .map(|e| e.new_oid),
+        _ => None,
+    };
+    oid.or_else(|| state.repo.repo.head().ok().and_then(|head| head.target()))
+        .map(|oid| oid.to_string())
+}
+
+/// Branche sélectionnée dans la vue branches, branche courante ailleurs.
+fn selected_branch(state: &AppState) -> Option<String> {
+    if state.view_mode == ViewMode::Branches {
+        if let Some(branch) = state.branches_view_state.selected_branch() {
+            return Some(branch.name.clone());
+        }
+    }
+    state.current_branch.clone()
+}
+
+/// Fichier sélectionné dans la vue courante.
+fn selected_file(state: &AppState) -> Option<String> {
+    match state.view_mode {
+        ViewMode::Staging => match state.staging_state.focus {
+            StagingFocus::Diff => state
+                .staging_state
+                .current_diff
+                .as_ref()
+                .map(|d| d.path.clone()),
+            _ => state.staging_state.selected_file().map(|f| f.path.clone()),
+        },
+        ViewMode::Graph => state
+            .commit_files
+            .get(state.file_selected_index)
+            .map(|f| f.path.clone()),
+        ViewMode::Blame => state.blame_state.as_ref().map(|b| b.file_path.clone()),
+        ViewMode::Conflicts => state
+            .conflicts_state
+            .as_ref()
+            .and_then(|c| c.selected_file())
+            .map(|f| f.path.clone()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::repo::GitRepo;
+    use git2::Repository;
+    use std::time::{Duration, Instant};
+    use tempfile::TempDir;
+
+    fn setup_state() -> (TempDir, AppState) {
+        let dir = TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let sig = git2::Signature::now("Test", "test@test.com").unwrap();
+        let tree_oid = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_oid).unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "Initial commit", &tree, &[])
+            .unwrap();
+
+        let git_repo = GitRepo::open(dir.path().to_str().unwrap()).unwrap();
+        let state = AppState::new(git_repo, dir.path().to_string_lossy().to_string()).unwrap();
+        (dir, state)
+    }
+
+    fn command(template: &str, mode: CommandMode) -> CustomCommand {
+        CustomCommand {
+            name: "test".to_string(),
+            key: "X".to_string(),
+            context: "global".to_string(),
+            command: template.to_string(),
+            mode,
+            description: None,
+        }
+    }
+
+    #[test]
+    fn test_popup_command_captures_output() {
+        let (_dir, mut state) = setup_state();
+        let head = state.repo.repo.head().unwrap().target().unwrap();
+
+        run_command(
+            &mut state,
+            &command("echo {commit}; echo erreur >&2", CommandMode::Popup),
+        );
+        // La commande s'exécute en arrière-plan : la sortie arrive plus tard.
+        assert!(state.command_task.is_some());
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while state.command_task.is_some() {
+            assert!(Instant::now() < deadline, "commande non terminée");
+            std::thread::sleep(Duration::from_millis(10));
+            poll_command(&mut state);
+        }
+
+        let output = state.command_output.expect("popup de sortie");
+        assert!(output.success);
+        assert_eq!(output.lines, vec![head.to_string(), "erreur".to_string()]);
+    }
+
+    #[test]
+    fn test_interactive_command_is_deferred() {
+        let (_dir, mut state) = setup_state();
+
+        run_command(
+            &mut state,
+            &command("tig {branch}", CommandMode::Interactive),
+        );
+        let branch = state.current_branch.clone().unwrap();
+        assert_eq!(state.interactive_command, Some(format!("tig {}", branch)));
+        assert!(state.command_output.is_none());
+    }
+
+    #[test]
+    fn test_missing_placeholder_is_reported() {
+        let (_dir, mut state) = setup_state();
+
+        run_command(&mut state, &command("cat {file}", CommandMode::Popup));
+        assert!(state.command_output.is_none());
+        let (message, _) = state.flash_message.expect("message d'erreur");
+        assert!(message.contains("{file} indisponible"), "{}", message);
+    }
+}
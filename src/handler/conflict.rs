@@ -30,6 +30,9 @@ impl ActionHandler for ConflictHandler {
             ConflictAction::MarkResolved => handle_mark_resolved(ctx.state),
             ConflictAction::FinalizeMerge => handle_finalize_merge(ctx.state),
             ConflictAction::AbortMerge => handle_abort_merge(ctx.state),
+            ConflictAction::ContinueRebase => super::rebase::handle_continue(ctx.state),
+            ConflictAction::SkipRebase => super::rebase::handle_skip(ctx.state),
+            ConflictAction::AbortRebase => super::rebase::handle_abort(ctx.state),
             ConflictAction::SetModeFile => handle_set_mode_file(ctx.state),
             ConflictAction::SetModeBlock => handle_set_mode_block(ctx.state),
             ConflictAction::SetModeLine => handle_set_mode_line(ctx.state),
@@ -542,13 +545,30 @@ fn handle_mark_resolved(state: &mut AppState) -> Result<()> {
 }
 
 fn handle_finalize_merge(state: &mut AppState) -> Result<()> {
+    // Pendant un rebase interactif, finaliser revient à continuer le rebase.
+    if state.rebase_session.is_some() {
+        return super::rebase::handle_continue(state);
+    }
     if state.view_mode == ViewMode::Conflicts {
-        match crate::git::conflict::finalize_merge(&state.repo.repo, "Merge finalisé") {
+        // Un revert conserve son message standard préparé dans MERGE_MSG
+        let is_revert = matches!(
+            state.repo.repo.state(),
+            git2::RepositoryState::Revert | git2::RepositoryState::RevertSequence
+        );
+        let (message, label) = if is_revert {
+            let message = crate::git::conflict::prepared_message(&state.repo.repo)
+                .unwrap_or_else(|| "Revert".to_string());
+            (message, "Revert")
+        } else {
+            ("Merge finalisé".to_string(), "Merge")
+        };
+
+        match crate::git::conflict::finalize_merge(&state.repo.repo, &message) {
             Ok(_) => {
                 state.conflicts_state = None;
                 state.view_mode = ViewMode::Graph;
                 state.mark_dirty();
-                state.set_flash_message("Merge finalisé ✓".to_string());
+                state.set_flash_message(format!("{} finalisé ✓", label));
             }
             Err(e) => {
                 state.set_flash_message(format!("Erreur: {}", e));
@@ -559,6 +579,9 @@ fn handle_finalize_merge(state: &mut AppState) -> Result<()> {
 }
 
 fn handle_abort_merge(state: &mut AppState) -> Result<()> {
+    if state.rebase_session.is_some() {
+        return super::rebase::handle_abort(state);
+    }
     if state.view_mode == ViewMode::Conflicts {
         match crate::git::conflict::abort_merge(&state.repo.repo) {
             Ok(_) => {
@@ -6,16 +6,21 @@
 use crate::error::Result;
 use crate::state::action::{
     BranchAction, ConflictAction, EditAction, FilterAction, GitAction, NavigationAction,
-    SearchAction, StagingAction,
+    PaletteAction, SearchAction, StagingAction,
 };
 use crate::state::{AppAction, AppState, BranchesFocus, FocusPanel, ViewMode};
 
 use super::branch::BranchHandler;
+use super::command::CommandHandler;
 use super::conflict::ConflictHandler;
 use super::edit::EditHandler;
 use super::filter::FilterHandler;
 use super::git::GitHandler;
 use super::navigation::NavigationHandler;
+use super::palette::PaletteHandler;
+use super::push::PushHandler;
+use super::rebase::RebaseHandler;
+use super::reflog::ReflogHandler;
 use super::search::SearchHandler;
 use super::staging::StagingHandler;
 use super::traits::{ActionHandler, HandlerContext};
@@ -30,6 +35,11 @@ pub struct ActionDispatcher {
     search: SearchHandler,
     edit: EditHandler,
     filter: FilterHandler,
+    rebase: RebaseHandler,
+    reflog: ReflogHandler,
+    palette: PaletteHandler,
+    push: PushHandler,
+    command: CommandHandler,
 }
 
 impl ActionDispatcher {
@@ -44,6 +54,11 @@ impl ActionDispatcher {
             search: SearchHandler,
             edit: EditHandler,
             filter: FilterHandler,
+            rebase: RebaseHandler,
+            reflog: ReflogHandler,
+            palette: PaletteHandler,
+            push: PushHandler,
+            command: CommandHandler,
         }
     }
 
@@ -61,6 +76,23 @@ impl ActionDispatcher {
             AppAction::Search(search) => self.search.handle(&mut ctx, search),
             AppAction::Edit(edit) => self.edit.handle(&mut ctx, edit),
             AppAction::Filter(filter) => self.filter.handle(&mut ctx, filter),
+            AppAction::Rebase(rebase) => self.rebase.handle(&mut ctx, rebase),
+            AppAction::Reflog(reflog) => self.reflog.handle(&mut ctx, reflog),
+            AppAction::Palette(PaletteAction::Confirm) => {
+                // Exécuter l'action choisie comme si son raccourci avait été pressé
+                let action = ctx
+                    .state
+                    .command_palette
+                    .take()
+                    .and_then(|p| p.selected_entry().map(|e| e.action.clone()));
+                match action {
+                    Some(action) => self.dispatch(ctx.state, action),
+                    None => Ok(()),
+                }
+            }
+            AppAction::Palette(palette) => self.palette.handle(&mut ctx, palette),
+            AppAction::Push(push) => self.push.handle(&mut ctx, push),
+            AppAction::Command(command) => self.command.handle(&mut ctx, command),
 
             // Actions simples
             AppAction::Quit => {
@@ -157,6 +189,43 @@ impl ActionDispatcher {
                 Ok(())
             }
 
+            // Reset picker actions
+            AppAction::ResetPickerUp => {
+                if let Some(ref mut picker) = ctx.state.reset_picker {
+                    picker.modes.select_previous();
+                }
+                Ok(())
+            }
+
+            AppAction::ResetPickerDown => {
+                if let Some(ref mut picker) = ctx.state.reset_picker {
+                    picker.modes.select_next();
+                }
+                Ok(())
+            }
+
+            AppAction::ResetPickerConfirm => {
+                use crate::git::reset::ResetMode;
+                use crate::ui::confirm_dialog::ConfirmAction;
+
+                if let Some(picker) = ctx.state.reset_picker.take() {
+                    match picker.selected_mode() {
+                        // Le reset --hard perd les modifications : demander confirmation
+                        ResetMode::Hard => {
+                            ctx.state.pending_confirmation =
+                                Some(ConfirmAction::ResetHard(picker.target));
+                        }
+                        mode => super::git::apply_reset(ctx.state, picker.target, mode),
+                    }
+                }
+                Ok(())
+            }
+
+            AppAction::ResetPickerCancel => {
+                ctx.state.reset_picker = None;
+                Ok(())
+            }
+
             // Confirmations
             AppAction::ConfirmAction => self.handle_confirm_action(&mut ctx),
             AppAction::CancelAction => {
@@ -326,8 +395,10 @@ impl ActionDispatcher {
                 Ok(())
             }
             AppAction::SwitchToConflicts => {
-                ctx.state.view_mode = ViewMode::Conflicts;
-                ctx.state.dirty = true;
+                if ctx.state.conflicts_state.is_some() {
+                    ctx.state.view_mode = ViewMode::Conflicts;
+                    ctx.state.dirty = true;
+                }
                 Ok(())
             }
 
@@ -535,7 +606,7 @@ impl ActionDispatcher {
                         .unwrap_or_default();
                 }
                 StagingFocus::CommitMessage => {
-                    text_to_copy = ctx.state.staging_state.commit_message.clone();
+                    text_to_copy = ctx.state.staging_state.commit_editor.text();
                 }
             },
             ViewMode::Branches => match ctx.state.branches_view_state.section {
@@ -571,6 +642,24 @@ impl ActionDispatcher {
                         })
                         .unwrap_or_default();
                 }
+                BranchesSection::Tags => {
+                    text_to_copy = ctx
+                        .state
+                        .branches_view_state
+                        .tags
+                        .selected_item()
+                        .map(|t| t.name.clone())
+                        .unwrap_or_default();
+                }
+                BranchesSection::Remotes => {
+                    text_to_copy = ctx
+                        .state
+                        .branches_view_state
+                        .remotes
+                        .selected_item()
+                        .and_then(|r| r.fetch_url.clone())
+                        .unwrap_or_default();
+                }
             },
             ViewMode::Conflicts => {
                 if let Some(ref conflicts_state) = ctx.state.conflicts_state {
@@ -592,6 +681,16 @@ impl ActionDispatcher {
                     }
                 }
             }
+            ViewMode::Reflog => {
+                if let Some(entry) = ctx
+                    .state
+                    .reflog_state
+                    .as_ref()
+                    .and_then(|r| r.selected_entry())
+                {
+                    text_to_copy = format!("{} {}", entry.new_oid, entry.message);
+                }
+            }
             ViewMode::Help => {
                 // Pas de contenu à copier en mode aide
             }
@@ -623,6 +722,7 @@ impl ActionDispatcher {
             .cloned();
 
         if let Some(branch_name) = branch_to_merge {
+            super::git::record_snapshot(ctx.state, "merge");
             match crate::git::merge::merge_branch_with_result(&ctx.state.repo.repo, &branch_name) {
                 Ok(MergeResult::UpToDate) => {
                     ctx.state
@@ -673,6 +773,9 @@ impl ActionDispatcher {
         use crate::ui::confirm_dialog::ConfirmAction;
 
         if let Some(confirm_action) = ctx.state.pending_confirmation.clone() {
+            if let Some(label) = confirm_action.undo_label() {
+                super::git::record_snapshot(ctx.state, label);
+            }
             match confirm_action {
                 ConfirmAction::DiscardAll => {
                     ctx.state.pending_confirmation = None;
@@ -693,6 +796,30 @@ impl ActionDispatcher {
                     }
                     ctx.state.mark_dirty();
                 }
+                ConfirmAction::DiscardHunk(path, hunk) => {
+                    ctx.state.pending_confirmation = None;
+                    // Le fichier a pu changer pendant la confirmation : le hunk affiché
+                    // doit toujours être celui que l'on va annuler.
+                    let result = if super::staging::displayed_hunk_matches(
+                        ctx.state,
+                        &path,
+                        crate::state::DiffSource::Unstaged,
+                        hunk,
+                    ) {
+                        super::git::record_snapshot(ctx.state, "discard du hunk");
+                        crate::git::patch::discard_hunk(&ctx.state.repo.repo, &path, hunk)
+                    } else {
+                        Err(super::staging::stale_hunk_error())
+                    };
+                    if let Err(e) = result {
+                        ctx.state.set_flash_message(format!("Erreur: {}", e));
+                    } else {
+                        ctx.state
+                            .set_flash_message(format!("Hunk {} ignoré ✓", hunk + 1));
+                    }
+                    ctx.state.mark_dirty();
+                    super::staging::refresh_staging(ctx.state)?;
+                }
                 ConfirmAction::BranchDelete(name) => {
                     ctx.state.pending_confirmation = None;
                     if let Err(e) = crate::git::branch::delete_branch(&ctx.state.repo.repo, &name) {
@@ -703,6 +830,63 @@ impl ActionDispatcher {
                     }
                     ctx.state.mark_dirty();
                 }
+                ConfirmAction::TagDelete(name) => {
+                    ctx.state.pending_confirmation = None;
+                    if let Err(e) = crate::git::tag::delete_tag(&ctx.state.repo.repo, &name) {
+                        ctx.state.set_flash_message(format!("Erreur: {}", e));
+                    } else {
+                        ctx.state
+                            .set_flash_message(format!("Tag {} supprimé ✓", name));
+                    }
+                    ctx.state.mark_dirty();
+                }
+                ConfirmAction::RemoteRemove(name) => {
+                    ctx.state.pending_confirmation = None;
+                    if let Err(e) = crate::git::remote::remove_remote(&ctx.state.repo.repo, &name) {
+                        ctx.state.set_flash_message(format!("Erreur: {}", e));
+                    } else {
+                        ctx.state
+                            .set_flash_message(format!("Remote {} supprimé ✓", name));
+                    }
+                    ctx.state.mark_dirty();
+                }
+                ConfirmAction::Revert(oid, mainline) => {
+                    use crate::git::conflict::MergeResult;
+
+                    ctx.state.pending_confirmation = None;
+                    match crate::git::commit::revert_with_result(
+                        &ctx.state.repo.repo,
+                        oid,
+                        mainline,
+                    ) {
+                        Ok(MergeResult::Conflicts(files)) => {
+                            let ours_name =
+                                crate::git::conflict::get_current_branch_name(&ctx.state.repo.repo);
+                            ctx.state.conflicts_state = Some(crate::state::ConflictsState::new(
+                                files,
+                                format!("Revert de {:.7}", oid),
+                                ours_name,
+                                format!("revert {:.7}", oid),
+                            ));
+                            ctx.state.view_mode = ViewMode::Conflicts;
+                            ctx.state.set_flash_message(
+                                "Conflits lors du revert - résolution requise".to_string(),
+                            );
+                        }
+                        Ok(_) => {
+                            ctx.state
+                                .set_flash_message(format!("Commit {:.7} revert ✓", oid));
+                        }
+                        Err(e) => {
+                            ctx.state.set_flash_message(format!("Erreur: {}", e));
+                        }
+                    }
+                    ctx.state.mark_dirty();
+                }
+                ConfirmAction::ResetHard(oid) => {
+                    ctx.state.pending_confirmation = None;
+                    super::git::apply_reset(ctx.state, oid, crate::git::reset::ResetMode::Hard);
+                }
                 ConfirmAction::AbortMerge => {
                     ctx.state.pending_confirmation = None;
                     if let Err(e) = crate::git::conflict::abort_merge(&ctx.state.repo.repo) {
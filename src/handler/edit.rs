@@ -3,7 +3,7 @@
 use super::traits::{ActionHandler, HandlerContext};
 use crate::error::Result;
 use crate::state::action::EditAction;
-use crate::state::AppState;
+use crate::state::{AppState, CommitEditor};
 
 /// Handler pour les opérations d'édition de texte.
 pub struct EditHandler;
@@ -13,88 +13,37 @@ impl ActionHandler for EditHandler {
 
     fn handle(&mut self, ctx: &mut HandlerContext, action: EditAction) -> Result<()> {
         match action {
-            EditAction::InsertChar(c) => handle_insert_char(ctx.state, c),
-            EditAction::DeleteCharBefore => handle_delete_char_before(ctx.state),
-            EditAction::DeleteCharAfter => handle_delete_char_after(ctx.state),
-            EditAction::CursorLeft => handle_cursor_left(ctx.state),
-            EditAction::CursorRight => handle_cursor_right(ctx.state),
-            EditAction::CursorHome => handle_cursor_home(ctx.state),
-            EditAction::CursorEnd => handle_cursor_end(ctx.state),
-            EditAction::NewLine => handle_new_line(ctx.state),
+            EditAction::InsertChar(c) => with_editor(ctx.state, |e| e.insert_char(c)),
+            EditAction::DeleteCharBefore => with_editor(ctx.state, CommitEditor::backspace),
+            EditAction::DeleteCharAfter => with_editor(ctx.state, CommitEditor::delete),
+            EditAction::DeleteWordBefore => {
+                with_editor(ctx.state, CommitEditor::delete_word_before)
+            }
+            EditAction::CursorLeft => with_editor(ctx.state, CommitEditor::move_left),
+            EditAction::CursorRight => with_editor(ctx.state, CommitEditor::move_right),
+            EditAction::CursorUp => with_editor(ctx.state, CommitEditor::move_up),
+            EditAction::CursorDown => with_editor(ctx.state, CommitEditor::move_down),
+            EditAction::WordLeft => with_editor(ctx.state, CommitEditor::move_word_left),
+            EditAction::WordRight => with_editor(ctx.state, CommitEditor::move_word_right),
+            EditAction::CursorHome => with_editor(ctx.state, CommitEditor::move_home),
+            EditAction::CursorEnd => with_editor(ctx.state, CommitEditor::move_end),
+            EditAction::NewLine => with_editor(ctx.state, CommitEditor::new_line),
+            EditAction::Paste(text) => with_editor(ctx.state, |e| e.insert_str(&text)),
         }
     }
 }
 
-fn handle_insert_char(state: &mut AppState, c: char) -> Result<()> {
-    if state.staging_state.is_committing {
-        let pos = state.staging_state.cursor_position;
-        if pos <= state.staging_state.commit_message.len() {
-            state.staging_state.commit_message.insert(pos, c);
-            state.staging_state.cursor_position += 1;
-        }
-    }
-    Ok(())
-}
-
-fn handle_delete_char_before(state: &mut AppState) -> Result<()> {
-    if state.staging_state.is_committing {
-        let pos = state.staging_state.cursor_position;
-        if pos > 0 && pos <= state.staging_state.commit_message.len() {
-            state.staging_state.commit_message.remove(pos - 1);
-            state.staging_state.cursor_position -= 1;
-        }
-    }
-    Ok(())
-}
-
-fn handle_delete_char_after(state: &mut AppState) -> Result<()> {
-    if state.staging_state.is_committing {
-        let pos = state.staging_state.cursor_position;
-        if pos < state.staging_state.commit_message.len() {
-            state.staging_state.commit_message.remove(pos);
-        }
-    }
-    Ok(())
-}
-
-fn handle_cursor_left(state: &mut AppState) -> Result<()> {
-    if state.staging_state.is_committing && state.staging_state.cursor_position > 0 {
-        state.staging_state.cursor_position -= 1;
-    }
-    Ok(())
-}
-
-fn handle_cursor_right(state: &mut AppState) -> Result<()> {
-    if state.staging_state.is_committing {
-        let len = state.staging_state.commit_message.len();
-        if state.staging_state.cursor_position < len {
-            state.staging_state.cursor_position += 1;
-        }
-    }
-    Ok(())
-}
-
-fn handle_cursor_home(state: &mut AppState) -> Result<()> {
-    if state.staging_state.is_committing {
-        state.staging_state.cursor_position = 0;
-    }
-    Ok(())
-}
-
-fn handle_cursor_end(state: &mut AppState) -> Result<()> {
-    if state.staging_state.is_committing {
-        state.staging_state.cursor_position = state.staging_state.commit_message.len();
-    }
-    Ok(())
-}
-
-fn handle_new_line(state: &mut AppState) -> Result<()> {
-    if state.staging_state.is_committing {
-        let pos = state.staging_state.cursor_position;
-        if pos <= state.staging_state.commit_message.len() {
-            state.staging_state.commit_message.insert(pos, '\n');
-            state.staging_state.cursor_position += 1;
-        }
+/// Applique l'opération à l'éditeur de message actif : le reword du rebase
+/// (au premier plan), sinon le message de commit.
+fn with_editor(state: &mut AppState, f: impl FnOnce(&mut CommitEditor)) -> Result<()> {
+    let reword = state
+        .rebase_editor
+        .as_mut()
+        .and_then(|editor| editor.reword_input.as_mut());
+    if let Some(editor) = reword {
+        f(editor);
+    } else if state.staging_state.is_committing {
+        f(&mut state.staging_state.commit_editor);
     }
     Ok(())
 }
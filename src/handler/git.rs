@@ -1,9 +1,12 @@
 //! Handler pour les actions Git (remote, blame, cherry-pick, etc.).
 
 use super::traits::{ActionHandler, HandlerContext};
-use crate::error::Result;
-use crate::state::action::GitAction;
-use crate::state::{AppState, BlameState, FocusPanel, StagingFocus, ViewMode};
+use crate::error::{GitSvError, Result};
+use crate::git::remote::{PullMode, PullOutcome, PushRequest};
+use crate::state::action::{AppAction, GitAction};
+use crate::state::{AppState, BlameState, FocusPanel, PullPickerState, StagingFocus, ViewMode};
+use crate::transfer::{RemoteOperation, RemoteOutcome, RemoteTask};
+use crate::ui::keymap::{self, KeyContext};
 
 /// Handler pour les opérations Git.
 pub struct GitHandler;
@@ -13,10 +16,39 @@ impl ActionHandler for GitHandler {
 
     fn handle(&mut self, ctx: &mut HandlerContext, action: GitAction) -> Result<()> {
         match action {
-            GitAction::Push => handle_push(ctx.state),
-            GitAction::Pull => handle_pull(ctx.state),
-            GitAction::Fetch => handle_fetch(ctx.state),
+            GitAction::Push => start_transfer(ctx.state, RemoteOperation::Push),
+            GitAction::Pull => {
+                let mode = PullMode::for_current_branch(&ctx.state.repo.repo);
+                start_transfer(ctx.state, RemoteOperation::Pull(mode))
+            }
+            GitAction::PullPrompt => handle_pull_prompt(ctx.state),
+            GitAction::PullPickerNext => {
+                if let Some(ref mut picker) = ctx.state.pull_picker {
+                    picker.modes.select_next();
+                }
+                Ok(())
+            }
+            GitAction::PullPickerPrevious => {
+                if let Some(ref mut picker) = ctx.state.pull_picker {
+                    picker.modes.select_previous();
+                }
+                Ok(())
+            }
+            GitAction::PullPickerConfirm => match ctx.state.pull_picker.take() {
+                Some(picker) => {
+                    start_transfer(ctx.state, RemoteOperation::Pull(picker.selected_mode()))
+                }
+                None => Ok(()),
+            },
+            GitAction::PullPickerCancel => {
+                ctx.state.pull_picker = None;
+                Ok(())
+            }
+            GitAction::Fetch => start_transfer(ctx.state, RemoteOperation::Fetch),
+            GitAction::CancelTransfer => handle_cancel_transfer(ctx.state),
             GitAction::CherryPick => handle_cherry_pick(ctx.state),
+            GitAction::Revert => handle_revert(ctx.state),
+            GitAction::ResetPrompt => handle_reset_prompt(ctx.state),
             GitAction::AmendCommit => handle_amend_commit(ctx.state),
             GitAction::OpenBlame => handle_open_blame(ctx.state),
             GitAction::CloseBlame => handle_close_blame(ctx.state),
@@ -25,18 +57,26 @@ impl ActionHandler for GitHandler {
             GitAction::StashPrompt => handle_stash_prompt(ctx.state),
             GitAction::MergePrompt => handle_merge_prompt(ctx.state),
             GitAction::BranchList => handle_branch_list(ctx.state),
+            GitAction::Undo => handle_undo(ctx.state, false),
+            GitAction::Redo => handle_undo(ctx.state, true),
         }
     }
 }
 
-fn handle_push(state: &mut AppState) -> Result<()> {
+/// Démarre une opération distante en arrière-plan.
+///
+/// Le résultat est appliqué par [`poll_transfer`] une fois l'opération terminée.
+pub fn start_transfer(state: &mut AppState, operation: RemoteOperation) -> Result<()> {
+    if let Some(ref task) = state.remote_task {
+        state.set_flash_message(format!("{} déjà en cours", task.operation.label()));
+        return Ok(());
+    }
+
     match crate::git::remote::has_remote(&state.repo.repo) {
-        Ok(true) => match crate::git::remote::push_current_branch(&state.repo.repo) {
-            Ok(msg) => {
-                state.set_flash_message(format!("{} ✓", msg));
-            }
+        Ok(true) => match RemoteTask::spawn(&state.repo_path, operation) {
+            Ok(task) => state.remote_task = Some(task),
             Err(e) => {
-                state.set_flash_message(format!("Erreur lors du push: {}", e));
+                state.set_flash_message(format!("Erreur: {}", e));
             }
         },
         Ok(false) => {
@@ -49,74 +89,167 @@ fn handle_push(state: &mut AppState) -> Result<()> {
     Ok(())
 }
 
-fn handle_pull(state: &mut AppState) -> Result<()> {
-    use crate::git::conflict::MergeResult;
-    use crate::state::ConflictsState;
+fn handle_cancel_transfer(state: &mut AppState) -> Result<()> {
+    if let Some(ref task) = state.remote_task {
+        task.cancel();
+        state.set_flash_message(format!("Annulation du {}…", task.operation.label()));
+    }
+    Ok(())
+}
 
-    match crate::git::remote::has_remote(&state.repo.repo) {
-        Ok(true) => match crate::git::remote::pull_current_branch_with_result(&state.repo.repo) {
-            Ok(MergeResult::UpToDate) => {
-                state.set_flash_message("Déjà à jour ✓".to_string());
-            }
-            Ok(MergeResult::FastForward) => {
-                state.set_flash_message("Pull (fast-forward) réussi ✓".to_string());
-                state.mark_dirty();
-            }
-            Ok(MergeResult::Success) => {
-                state.set_flash_message("Pull réussi ✓".to_string());
-                state.mark_dirty();
-            }
-            Ok(MergeResult::Conflicts(files)) => {
-                let ours_name = crate::git::conflict::get_current_branch_name(&state.repo.repo);
-                let theirs_name = format!(
-                    "origin/{}",
-                    state
-                        .current_branch
-                        .clone()
-                        .unwrap_or_else(|| "HEAD".to_string())
-                );
-                state.conflicts_state = Some(ConflictsState::new(
-                    files,
-                    "Pull depuis origin".to_string(),
-                    ours_name,
-                    theirs_name,
-                ));
-                state.view_mode = ViewMode::Conflicts;
-                state.set_flash_message("Conflits lors du pull - résolution requise".to_string());
-            }
-            Err(e) => {
-                state.set_flash_message(format!("Erreur lors du pull: {}", e));
-            }
-        },
-        Ok(false) => {
-            state.set_flash_message("Aucun remote configuré".to_string());
+/// Applique le résultat de l'opération distante si elle est terminée.
+///
+/// Appelée à chaque tour de la boucle principale.
+pub fn poll_transfer(state: &mut AppState) {
+    let Some(result) = state
+        .remote_task
+        .as_ref()
+        .and_then(|task| task.try_finish())
+    else {
+        return;
+    };
+    let Some(task) = state.remote_task.take() else {
+        return;
+    };
+    let label = task.operation.label();
+
+    // Un pull annulé après son fetch n'est pas intégré.
+    let fetched_pull = matches!(result, Ok(RemoteOutcome::PullFetched(_)));
+    if task.progress.is_cancelled() && (result.is_err() || fetched_pull) {
+        state.set_flash_message(format!("{} annulé", label));
+        // Un fetch interrompu a pu mettre à jour une partie des références.
+        state.mark_dirty();
+        return;
+    }
+
+    match result {
+        Ok(RemoteOutcome::Pushed(msg)) => {
+            state.set_flash_message(format!("{} ✓", msg));
+            state.mark_dirty();
+        }
+        Ok(RemoteOutcome::Fetched(msg)) => {
+            state.set_flash_message(format!("{} ✓", msg));
+            state.mark_dirty();
+        }
+        Ok(RemoteOutcome::PullFetched(mode)) => integrate_pull(state, mode),
+        Err(GitSvError::PushRejected { branch, reason, .. }) => {
+            // Rouvrir le push dans le dialogue, avec l'explication du refus
+            let request = match task.operation {
+                RemoteOperation::PushBranch(request) => request,
+                _ => PushRequest::for_branch(&state.repo.repo, &branch),
+            };
+            super::push::open_push_dialog(state, request, Some(reason));
+        }
+        Err(GitSvError::HookFailed {
+            hook,
+            status,
+            output,
+        }) => {
+            state.command_output = Some(crate::state::CommandOutputState::new(
+                format!("Hook {} ({})", hook, status),
+                &output,
+                "",
+                false,
+            ));
+            state.set_flash_message(format!("{} annulé : hook {} en échec", label, hook));
         }
         Err(e) => {
-            state.set_flash_message(format!("Erreur: {}", e));
+            state.set_flash_message(format!("Erreur lors du {}: {}", label.to_lowercase(), e));
         }
     }
+}
+
+/// Ouvre le sélecteur de stratégie de pull, la stratégie configurée présélectionnée.
+fn handle_pull_prompt(state: &mut AppState) -> Result<()> {
+    if let Some(ref task) = state.remote_task {
+        state.set_flash_message(format!("{} déjà en cours", task.operation.label()));
+        return Ok(());
+    }
+    let configured = PullMode::for_current_branch(&state.repo.repo);
+    state.pull_picker = Some(PullPickerState::new(configured));
     Ok(())
 }
 
-fn handle_fetch(state: &mut AppState) -> Result<()> {
-    match crate::git::remote::has_remote(&state.repo.repo) {
-        Ok(true) => match crate::git::remote::fetch_all(&state.repo.repo) {
-            Ok(_) => {
-                state.set_flash_message("Fetch réussi ✓".to_string());
-                state.mark_dirty();
-            }
-            Err(e) => {
-                state.set_flash_message(format!("Erreur lors du fetch: {}", e));
-            }
-        },
-        Ok(false) => {
-            state.set_flash_message("Aucun remote configuré".to_string());
-        }
+/// Intègre l'upstream fetché par un pull, sur le repository de l'interface.
+///
+/// Le snapshot d'undo est pris ici plutôt qu'au lancement : l'utilisateur a pu
+/// modifier le repository pendant le fetch.
+fn integrate_pull(state: &mut AppState, mode: PullMode) {
+    if state.rebase_session.is_some() || state.conflicts_state.is_some() {
+        state.set_flash_message(
+            "Fetch effectué, pull non intégré : résolution de conflits en cours".to_string(),
+        );
+        state.mark_dirty();
+        return;
+    }
+
+    record_snapshot(state, "pull");
+    match crate::git::remote::integrate_upstream(&state.repo.repo, mode) {
+        Ok(PullOutcome::Merged(merge)) => apply_pull_result(state, merge),
+        Ok(PullOutcome::Rebased(session, progress)) => apply_pull_rebase(state, *session, progress),
         Err(e) => {
-            state.set_flash_message(format!("Erreur: {}", e));
+            state.set_flash_message(format!("Erreur lors du pull: {}", e));
+            // Le fetch a pu mettre à jour les branches distantes.
+            state.mark_dirty();
+        }
+    }
+}
+
+/// Applique le résultat d'un pull en mode rebase.
+///
+/// En cas de conflit, la session reste ouverte : la vue des conflits propose
+/// alors de continuer ou d'abandonner le rebase.
+fn apply_pull_rebase(
+    state: &mut AppState,
+    session: crate::git::rebase::RebaseSession,
+    progress: crate::git::rebase::RebaseProgress,
+) {
+    use crate::git::rebase::RebaseProgress;
+
+    if let RebaseProgress::Completed = progress {
+        state.set_flash_message(format!(
+            "Pull (rebase) réussi ✓ - {} commit(s) rejoué(s)",
+            session.total
+        ));
+        state.mark_dirty();
+        return;
+    }
+    state.rebase_session = Some(session);
+    super::rebase::apply_progress(state, progress);
+}
+
+fn apply_pull_result(state: &mut AppState, result: crate::git::conflict::MergeResult) {
+    use crate::git::conflict::MergeResult;
+    use crate::state::ConflictsState;
+
+    match result {
+        MergeResult::UpToDate => {
+            state.set_flash_message("Déjà à jour ✓".to_string());
+            // Le fetch a pu mettre à jour les branches distantes.
+            state.mark_dirty();
+        }
+        MergeResult::FastForward => {
+            state.set_flash_message("Pull (fast-forward) réussi ✓".to_string());
+            state.mark_dirty();
+        }
+        MergeResult::Success => {
+            state.set_flash_message("Pull réussi ✓".to_string());
+            state.mark_dirty();
+        }
+        MergeResult::Conflicts(files) => {
+            let ours_name = crate::git::conflict::get_current_branch_name(&state.repo.repo);
+            let theirs_name = crate::git::remote::current_upstream(&state.repo.repo)
+                .unwrap_or_else(|| "upstream".to_string());
+            state.conflicts_state = Some(ConflictsState::new(
+                files,
+                format!("Pull depuis {}", theirs_name),
+                ours_name,
+                theirs_name,
+            ));
+            state.view_mode = ViewMode::Conflicts;
+            state.set_flash_message("Conflits lors du pull - résolution requise".to_string());
         }
     }
-    Ok(())
 }
 
 fn handle_cherry_pick(state: &mut AppState) -> Result<()> {
@@ -138,6 +271,47 @@ fn handle_cherry_pick(state: &mut AppState) -> Result<()> {
     Ok(())
 }
 
+fn handle_revert(state: &mut AppState) -> Result<()> {
+    use crate::state::BottomLeftMode;
+    use crate::ui::confirm_dialog::ConfirmAction;
+
+    if !matches!(state.view_mode, ViewMode::Graph) {
+        return Ok(());
+    }
+
+    let (commit_oid, parent_count) = if let Some(node) = state.selected_commit() {
+        (node.oid, node.parents.len())
+    } else {
+        state.set_flash_message("Aucun commit sélectionné".to_string());
+        return Ok(());
+    };
+
+    // Un commit de merge nécessite de choisir le parent principal (mainline)
+    let mainline = if parent_count > 1 {
+        if state.bottom_left_mode != BottomLeftMode::Parents {
+            state.bottom_left_mode = BottomLeftMode::Parents;
+            state.focus = FocusPanel::BottomLeft;
+            state.parent_selected_index = 0;
+            let revert = AppAction::Git(GitAction::Revert);
+            let next = keymap::current()
+                .action_keys(&[KeyContext::Graph], &revert)
+                .unwrap_or_else(|| "relancez le revert".to_string());
+            state.set_flash_message(format!(
+                "Commit de merge : choisissez le parent principal puis {}",
+                next
+            ));
+            return Ok(());
+        }
+        Some(state.parent_selected_index.min(parent_count - 1) as u32 + 1)
+    } else {
+        None
+    };
+
+    state.pending_confirmation = Some(ConfirmAction::Revert(commit_oid, mainline));
+
+    Ok(())
+}
+
 fn handle_amend_commit(state: &mut AppState) -> Result<()> {
     use crate::state::StagingFocus;
 
@@ -150,8 +324,8 @@ fn handle_amend_commit(state: &mut AppState) -> Result<()> {
         head_commit.message().unwrap_or("").to_string()
     };
 
-    state.staging_state.commit_message = commit_message;
-    state.staging_state.cursor_position = state.staging_state.commit_message.len();
+    state.staging_state.commit_editor.set_text(&commit_message);
+    state.staging_state.comment_char = None;
     state.staging_state.is_committing = true;
     state.staging_state.is_amending = true;
     state.staging_state.focus = StagingFocus::CommitMessage;
@@ -255,8 +429,7 @@ fn handle_commit_prompt(state: &mut AppState) -> Result<()> {
     state.view_mode = ViewMode::Staging;
     state.staging_state.is_committing = true;
     state.staging_state.focus = StagingFocus::CommitMessage;
-    state.staging_state.commit_message.clear();
-    state.staging_state.cursor_position = 0;
+    crate::handler::staging::reset_commit_message(state);
     state.mark_dirty();
     // Charger le diff du premier fichier sélectionné
     crate::handler::staging::load_staging_diff(state);
@@ -277,6 +450,45 @@ fn handle_stash_prompt(state: &mut AppState) -> Result<()> {
     Ok(())
 }
 
+fn handle_reset_prompt(state: &mut AppState) -> Result<()> {
+    if !matches!(state.view_mode, ViewMode::Graph) {
+        return Ok(());
+    }
+
+    match state.selected_commit() {
+        Some(node) => {
+            state.reset_picker = Some(crate::state::ResetPickerState::new(node.oid));
+        }
+        None => {
+            state.set_flash_message("Aucun commit sélectionné".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Reset la branche courante et affiche l'ancien HEAD pour pouvoir annuler.
+pub(crate) fn apply_reset(
+    state: &mut AppState,
+    target: git2::Oid,
+    mode: crate::git::reset::ResetMode,
+) {
+    record_snapshot(state, &format!("reset {}", mode.label()));
+    match crate::git::reset::reset_to(&state.repo.repo, target, mode) {
+        Ok(previous) => {
+            state.set_flash_message(format!(
+                "Reset {} sur {:.7} ✓ (ancien HEAD : {:.7})",
+                mode.label(),
+                target,
+                previous
+            ));
+        }
+        Err(e) => {
+            state.set_flash_message(format!("Erreur lors du reset: {}", e));
+        }
+    }
+    state.mark_dirty();
+}
+
 fn handle_merge_prompt(state: &mut AppState) -> Result<()> {
     // Charger la liste des branches pour le merge picker
     match crate::git::branch::list_all_branches(&state.repo.repo) {
@@ -314,3 +526,50 @@ fn handle_branch_list(state: &mut AppState) -> Result<()> {
     // Cette fonction nécessite une logique UI
     Ok(())
 }
+
+/// Enregistre un snapshot du repository avant une action qui le modifie.
+///
+/// Un échec de capture n'empêche pas l'action : elle ne sera simplement pas annulable.
+pub(crate) fn record_snapshot(state: &mut AppState, label: &str) {
+    match crate::git::undo::Snapshot::take(&state.repo.repo, label) {
+        Ok(snapshot) => state.undo_history.record(snapshot),
+        Err(e) => state.set_flash_message(format!("Snapshot impossible ({}): {}", label, e)),
+    }
+}
+
+/// Annule (ou rétablit) la dernière action enregistrée et indique ce qui a été restauré.
+fn handle_undo(state: &mut AppState, redo: bool) -> Result<()> {
+    let result = if redo {
+        state.undo_history.redo(&state.repo.repo)
+    } else {
+        state.undo_history.undo(&state.repo.repo)
+    };
+    let verb = if redo { "Rétabli" } else { "Annulé" };
+
+    match result {
+        Ok(Some(outcome)) => {
+            let restored = if outcome.restored.is_empty() {
+                String::new()
+            } else {
+                format!(" — restauré : {}", outcome.restored.join(", "))
+            };
+            state.set_flash_message(format!("{} : {}{} ✓", verb, outcome.label, restored));
+
+            // Un merge annulé ne laisse plus de conflits à résoudre.
+            if state.repo.repo.state() == git2::RepositoryState::Clean {
+                state.conflicts_state = None;
+                if state.view_mode == ViewMode::Conflicts {
+                    state.view_mode = ViewMode::Graph;
+                }
+            }
+            state.mark_dirty();
+            if state.view_mode == ViewMode::Staging {
+                super::staging::refresh_staging(state)?;
+            }
+        }
+        Ok(None) if redo => state.set_flash_message("Rien à rétablir".to_string()),
+        Ok(None) => state.set_flash_message("Rien à annuler".to_string()),
+        Err(e) => state.set_flash_message(format!("Erreur lors de l'annulation: {}", e)),
+    }
+    Ok(())
+}
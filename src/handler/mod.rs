@@ -4,12 +4,17 @@
 //! Chaque handler spécialisé gère un domaine fonctionnel spécifique.
 
 pub mod branch;
+pub mod command;
 pub mod conflict;
 pub mod dispatcher;
 pub mod edit;
 pub mod filter;
 pub mod git;
 pub mod navigation;
+pub mod palette;
+pub mod push;
+pub mod rebase;
+pub mod reflog;
 pub mod search;
 pub mod staging;
 pub mod traits;
@@ -22,10 +27,15 @@ use ratatui::{backend::CrosstermBackend, Terminal};
 use std::io::Stdout;
 
 use crate::error::Result;
+use crate::git::repo::StatusEntry;
+use crate::loader::{GraphLoad, LoaderResult, RefreshRequest, RefreshResult, RepoLoader};
 use crate::state::{AppState, ViewMode};
+use crate::terminal::{resume_terminal, suspend_terminal};
 use crate::ui;
 use crate::ui::input::handle_input_with_timeout;
-use crate::watcher::GitWatcher;
+use crate::ui::keymap::PendingKeys;
+use crate::ui::loading::LoadingSpinner;
+use crate::watcher::{GitWatcher, RepoChanges};
 
 /// Gestionnaire principal de la boucle événementielle.
 ///
@@ -35,16 +45,28 @@ pub struct EventHandler {
     state: AppState,
     dispatcher: ActionDispatcher,
     watcher: GitWatcher,
+    loader: RepoLoader,
+    /// Touches d'une séquence de raccourci en cours de saisie.
+    pending_keys: PendingKeys,
+    /// Catégories modifiées détectées par le watcher, à recharger.
+    pending_changes: RepoChanges,
+    /// Une page du graphe est-elle en cours de chargement?
+    page_in_flight: bool,
 }
 
 impl EventHandler {
     /// Crée un nouveau gestionnaire d'événements.
     pub fn new(state: AppState) -> Result<Self> {
         let watcher = GitWatcher::new(&state.repo_path)?;
+        let loader = RepoLoader::new(&state.repo_path)?;
         Ok(Self {
             state,
             dispatcher: ActionDispatcher::new(),
             watcher,
+            loader,
+            pending_keys: PendingKeys::default(),
+            pending_changes: RepoChanges::default(),
+            page_in_flight: false,
         })
     }
 
@@ -56,27 +78,58 @@ impl EventHandler {
         }
 
         loop {
+            // Appliquer les résultats du chargement en arrière-plan
+            while let Some(result) = self.loader.try_recv() {
+                match result {
+                    LoaderResult::Refresh(result) => self.apply_refresh(*result),
+                    LoaderResult::Page(page) => {
+                        self.page_in_flight = false;
+                        self.state.apply_graph_page(page);
+                    }
+                }
+            }
+            if !self.loader.is_loading() {
+                self.state.loading_spinner = None;
+            }
+
+            // Appliquer le résultat de l'opération distante terminée
+            git::poll_transfer(&mut self.state);
+            // Afficher la sortie de la commande personnalisée terminée
+            command::poll_command(&mut self.state);
+
             // Rendu
             terminal.draw(|frame| {
                 ui::render(frame, &mut self.state);
             })?;
 
-            // Vérifier les changements dans le repository git (auto-refresh)
-            if self.watcher.check_changed()? {
-                self.state.dirty = true;
-            }
+            // Vérifier les changements dans le repository git (auto-refresh ciblé)
+            self.pending_changes |= self.watcher.check_changed()?;
 
-            // Input avec timeout adaptatif
-            let timeout_ms = if self.state.flash_message.is_some() {
-                100
-            } else {
-                250
-            };
+            // Input avec timeout adaptatif (plus court pour animer le spinner)
+            let animated = self.state.flash_message.is_some()
+                || self.state.loading_spinner.is_some()
+                || self.state.remote_task.is_some();
+            let timeout_ms = if animated { 100 } else { 250 };
 
-            if let Some(action) = handle_input_with_timeout(&self.state, timeout_ms)? {
+            let input = handle_input_with_timeout(&self.state, &mut self.pending_keys, timeout_ms)?;
+            for action in input {
                 self.dispatcher.dispatch(&mut self.state, action)?;
             }
 
+            // Commande personnalisée interactive : la TUI est suspendue le temps de l'exécution
+            if let Some(script) = self.state.interactive_command.take() {
+                suspend_terminal(terminal)?;
+                command::run_interactive(&mut self.state, &script);
+                resume_terminal(terminal)?;
+            }
+
+            // Message de commit dans l'éditeur externe : idem
+            if std::mem::take(&mut self.state.external_editor_requested) {
+                suspend_terminal(terminal)?;
+                staging::run_external_editor(&mut self.state);
+                resume_terminal(terminal)?;
+            }
+
             if self.state.should_quit {
                 break;
             }
@@ -85,89 +138,98 @@ impl EventHandler {
             self.state.check_flash_expired();
 
             // Rafraîchissement conditionnel
-            if self.state.dirty {
+            if self.state.dirty || !self.pending_changes.is_empty() {
                 self.refresh()?;
             }
+
+            // Page suivante de l'historique demandée par la navigation, une fois le
+            // graphe rafraîchi : la page reprend après les rangées affichées.
+            if self.state.graph_view.page_requested
+                && !self.page_in_flight
+                && !self.loader.is_loading()
+            {
+                self.loader.request_page(
+                    crate::config::current().commit_limit,
+                    self.state.graph.len(),
+                )?;
+                self.page_in_flight = true;
+            }
         }
         Ok(())
     }
 
-    /// Rafraîchit les données depuis le repository.
+    /// Demande un rafraîchissement des données au thread de chargement.
+    ///
+    /// Le flag dirty (action de l'utilisateur) recharge tout ; sinon seules les
+    /// catégories signalées par le watcher sont rechargées.
     fn refresh(&mut self) -> Result<()> {
-        // Mise à jour des données de base
-        self.state.current_branch = self.state.repo.current_branch().ok();
-
-        // Construire le graphe avec ou sans filtres
-        self.state.graph = if self.state.graph_filter.is_active() {
-            self.state
-                .repo
-                .build_graph_filtered(crate::state::MAX_COMMITS, &self.state.graph_filter)
-                .unwrap_or_default()
+        let changes = if self.state.dirty {
+            RepoChanges::ALL
         } else {
-            self.state
-                .repo
-                .build_graph(crate::state::MAX_COMMITS)
-                .unwrap_or_default()
+            self.pending_changes
         };
+        self.pending_changes = RepoChanges::default();
 
-        self.state.status_entries = self.state.repo.status().unwrap_or_default();
-
-        // Synchroniser graph_view.rows avec le graphe reconstruit
-        self.state.graph_view.rows.set_items(self.state.graph.clone());
+        let filter = self
+            .state
+            .graph_filter
+            .is_active()
+            .then(|| self.state.graph_filter.clone());
+        let commit_limit = crate::config::current().commit_limit;
 
-        // Synchronisation de la sélection - ne pas dépasser les bornes
-        if self.state.selected_index >= self.state.graph.len() && !self.state.graph.is_empty() {
-            self.state.selected_index = self.state.graph.len() - 1;
+        // Un changement de l'arbre de travail ou de l'index invalide les diffs non commités.
+        if changes.index {
+            self.state.diff_cache.clear_working_directory();
+        } else if changes.worktree {
+            self.state.diff_cache.clear_unstaged();
         }
-        if self.state.graph.is_empty() {
-            self.state.selected_index = 0;
+
+        self.loader.request(RefreshRequest {
+            changes,
+            // Seule la première page est reconstruite si les références n'ont pas bougé
+            graph_count: commit_limit,
+            loaded_rows: self.state.graph.len(),
+            filter,
+            include_branches: self.state.view_mode == ViewMode::Branches,
+            selected_oid: self.state.selected_commit().map(|node| node.oid),
+            selected_index: self.state.selected_index,
+        })?;
+
+        // Seul le rechargement du graphe est assez long pour afficher le spinner.
+        if changes.refs && self.state.loading_spinner.is_none() {
+            self.state.loading_spinner = Some(LoadingSpinner::new("Chargement…"));
         }
 
-        // Synchroniser graph_view avec la nouvelle sélection
-        self.state.graph_view.rows.select(self.state.selected_index);
+        // Réinitialiser le flag dirty
+        self.state.dirty = false;
 
-        // Synchroniser graph_state (ListState de ratatui) avec la sélection
-        // Le graphe contient 2 items par commit (ligne + connexion)
-        self.state
-            .graph_state
-            .select(Some(self.state.selected_index * 2));
+        // Réinitialiser le watcher pour éviter de redétecter les mêmes changements
+        self.watcher.reset()?;
 
-        // Clamper file_selected_index pour éviter les index hors limites
-        if self.state.file_selected_index >= self.state.commit_files.len() {
-            self.state.file_selected_index = self.state.commit_files.len().saturating_sub(1);
-        }
+        Ok(())
+    }
 
-        // Mise à jour des fichiers du commit sélectionné
-        if let Some(row) = self.state.graph.get(self.state.selected_index) {
-            self.state.commit_files = self
-                .state
-                .repo
-                .commit_diff(row.node.oid)
-                .unwrap_or_default();
-        } else {
-            self.state.commit_files.clear();
+    /// Applique à l'état les données chargées en arrière-plan.
+    ///
+    /// Seules les parties rechargées sont remplacées : la sélection, le scroll
+    /// et le cache des diffs des commits inchangés sont conservés.
+    fn apply_refresh(&mut self, result: RefreshResult) {
+        // Mise à jour des données de base
+        self.state.current_branch = result.current_branch;
+
+        let graph_reloaded = result.graph.is_some();
+        if let Some(graph) = result.graph {
+            self.apply_graph(graph);
         }
 
-        // Mise à jour du staging
-        let all_entries = self.state.repo.status().unwrap_or_default();
-        self.state.staging_state.set_staged_files(
-            all_entries
-                .iter()
-                .filter(|e| e.is_staged())
-                .cloned()
-                .collect(),
-        );
-        self.state.staging_state.set_unstaged_files(
-            all_entries
-                .iter()
-                .filter(|e| e.is_unstaged())
-                .cloned()
-                .collect(),
-        );
+        let status_reloaded = result.status.is_some();
+        if let Some(status) = result.status {
+            self.apply_status(status);
+        }
 
         // Charger les données de la vue branches
-        if self.state.view_mode == ViewMode::Branches {
-            match crate::git::branch::list_all_branches(&self.state.repo.repo) {
+        if let Some(branches) = result.branches {
+            match branches.branches {
                 Ok((local, remote)) => {
                     self.state
                         .branches_view_state
@@ -184,37 +246,115 @@ impl EventHandler {
                 }
             }
 
-            // Charger les worktrees
-            match crate::git::worktree::list_worktrees(&self.state.repo.repo) {
-                Ok(worktrees) => {
-                    self.state
-                        .branches_view_state
-                        .worktrees
-                        .set_items(worktrees);
-                }
-                Err(_) => {}
+            if let Some(worktrees) = branches.worktrees {
+                self.state
+                    .branches_view_state
+                    .worktrees
+                    .set_items(worktrees);
             }
 
-            // Charger les stashes
-            match crate::git::stash::list_stashes(&mut self.state.repo.repo) {
-                Ok(stashes) => {
-                    self.state.branches_view_state.stashes.set_items(stashes);
-                }
-                Err(_) => {}
+            if let Some(stashes) = branches.stashes {
+                self.state.branches_view_state.stashes.set_items(stashes);
+            }
+
+            if let Some(tags) = branches.tags {
+                self.state.branches_view_state.tags.set_items(tags);
+            }
+            if let Some(remotes) = branches.remotes {
+                self.state.branches_view_state.remotes.set_items(remotes);
             }
         }
 
+        // Recharger le reflog affiché (les mouvements de HEAD y sont ajoutés)
+        if graph_reloaded && self.state.reflog_state.is_some() {
+            reflog::reload_reflog(&mut self.state);
+        }
+
         // Charger le diff si on est en mode Staging
-        if self.state.view_mode == ViewMode::Staging {
+        if status_reloaded && self.state.view_mode == ViewMode::Staging {
             staging::load_staging_diff(&mut self.state);
         }
+    }
 
-        // Réinitialiser le flag dirty
-        self.state.dirty = false;
+    /// Remplace le graphe en gardant la sélection sur le même commit.
+    fn apply_graph(&mut self, mut graph: GraphLoad) {
+        let selected_oid = self
+            .state
+            .graph
+            .get(self.state.selected_index)
+            .map(|row| row.node.oid);
 
-        // Réinitialiser le watcher pour éviter de redétecter les mêmes changements
-        self.watcher.reset()?;
+        // Remplacer le graphe (avec ou sans filtres)
+        let selected_files = graph.selected_files.take();
+        self.state.apply_graph_load(graph);
 
-        Ok(())
+        // Suivre le commit sélectionné s'il a été déplacé (ex: nouveaux commits au-dessus)
+        let moved = selected_oid
+            .and_then(|oid| self.state.graph.iter().position(|row| row.node.oid == oid));
+        if let Some(index) = moved {
+            self.state.selected_index = index;
+        }
+
+        // Synchronisation de la sélection - ne pas dépasser les bornes
+        if self.state.selected_index >= self.state.graph.len() && !self.state.graph.is_empty() {
+            self.state.selected_index = self.state.graph.len() - 1;
+        }
+        if self.state.graph.is_empty() {
+            self.state.selected_index = 0;
+        }
+
+        // Synchroniser graph_view avec la nouvelle sélection
+        self.state.graph_view.rows.select(self.state.selected_index);
+
+        // Synchroniser graph_state (ListState de ratatui) avec la sélection
+        // Le graphe contient 2 items par commit (ligne + connexion)
+        self.state
+            .graph_state
+            .select(Some(self.state.selected_index * 2));
+
+        // Mise à jour des fichiers du commit sélectionné, s'il a changé : ils ont
+        // été chargés en arrière-plan, sauf si la sélection a bougé entre-temps.
+        let new_oid = self
+            .state
+            .graph
+            .get(self.state.selected_index)
+            .map(|row| row.node.oid);
+        match (new_oid, selected_files) {
+            (Some(_), _) if new_oid == selected_oid => {}
+            (Some(oid), Some((files_oid, files))) if files_oid == oid => {
+                self.state.commit_files = files;
+            }
+            (Some(oid), _) => {
+                self.state.commit_files = self.state.repo.commit_diff(oid).unwrap_or_default();
+            }
+            (None, _) => self.state.commit_files.clear(),
+        }
+
+        // Clamper file_selected_index pour éviter les index hors limites
+        if self.state.file_selected_index >= self.state.commit_files.len() {
+            self.state.file_selected_index = self.state.commit_files.len().saturating_sub(1);
+        }
+    }
+
+    /// Remplace le status et les listes de la vue staging (sélections conservées).
+    fn apply_status(&mut self, status: Vec<StatusEntry>) {
+        self.state.status_entries = status;
+
+        self.state.staging_state.set_staged_files(
+            self.state
+                .status_entries
+                .iter()
+                .filter(|e| e.is_staged())
+                .cloned()
+                .collect(),
+        );
+        self.state.staging_state.set_unstaged_files(
+            self.state
+                .status_entries
+                .iter()
+                .filter(|e| e.is_unstaged())
+                .cloned()
+                .collect(),
+        );
     }
 }
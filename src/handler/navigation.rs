@@ -3,7 +3,7 @@
 use super::traits::{ActionHandler, HandlerContext};
 use crate::error::Result;
 use crate::state::action::NavigationAction;
-use crate::state::{AppState, BranchesSection, FocusPanel, StagingFocus, ViewMode};
+use crate::state::{AppState, BottomLeftMode, BranchesSection, FocusPanel, StagingFocus, ViewMode};
 
 /// Handler pour la navigation dans les listes.
 pub struct NavigationHandler;
@@ -72,6 +72,7 @@ fn handle_move_down(state: &mut AppState) {
                 state.graph_view.rows.select(new_index);
                 state.sync_graph_selection();
                 state.sync_legacy_selection();
+                state.request_next_graph_page_if_needed();
             }
         }
         ViewMode::Staging => {
@@ -118,6 +119,7 @@ fn handle_page_down(state: &mut AppState) {
                 state.graph_view.rows.select(new_index);
                 state.sync_graph_selection();
                 state.sync_legacy_selection();
+                state.request_next_graph_page_if_needed();
             }
         }
     }
@@ -151,6 +153,7 @@ fn handle_go_bottom(state: &mut AppState) {
                 state.graph_view.rows.select(new_index);
                 state.sync_graph_selection();
                 state.sync_legacy_selection();
+                state.request_next_graph_page_if_needed();
             }
         }
     }
@@ -192,6 +195,11 @@ fn handle_scroll_diff_down(state: &mut AppState) {
 }
 
 fn handle_file_up(state: &mut AppState) {
+    // Panneau Parents d'un commit de merge : choisir la mainline
+    if state.bottom_left_mode == BottomLeftMode::Parents && is_merge_selected(state) {
+        state.parent_selected_index = state.parent_selected_index.saturating_sub(1);
+        return;
+    }
     if state.file_selected_index > 0 {
         state.file_selected_index -= 1;
         state.graph_view.file_selected_index = state.file_selected_index;
@@ -201,6 +209,13 @@ fn handle_file_up(state: &mut AppState) {
 }
 
 fn handle_file_down(state: &mut AppState) {
+    if state.bottom_left_mode == BottomLeftMode::Parents && is_merge_selected(state) {
+        let parent_count = state.selected_commit().map_or(0, |c| c.parents.len());
+        if state.parent_selected_index + 1 < parent_count {
+            state.parent_selected_index += 1;
+        }
+        return;
+    }
     if state.file_selected_index + 1 < state.commit_files.len() {
         state.file_selected_index += 1;
         state.graph_view.file_selected_index = state.file_selected_index;
@@ -209,6 +224,11 @@ fn handle_file_down(state: &mut AppState) {
     }
 }
 
+/// Retourne true si le commit sélectionné est un commit de merge.
+fn is_merge_selected(state: &AppState) -> bool {
+    state.selected_commit().is_some_and(|c| c.parents.len() > 1)
+}
+
 fn handle_back_to_graph(state: &mut AppState) {
     // Retourner au focus Graph (utilisé par Esc depuis BottomLeft/Files)
     if state.view_mode == ViewMode::Graph {
@@ -245,11 +265,7 @@ fn handle_staging_navigation(state: &mut AppState, direction: i32) {
             }
         }
         StagingFocus::Diff => {
-            if direction > 0 {
-                state.staging_state.diff_scroll += 1;
-            } else if state.staging_state.diff_scroll > 0 {
-                state.staging_state.diff_scroll -= 1;
-            }
+            state.staging_state.move_diff_cursor(direction as isize);
         }
         _ => {}
     }
@@ -305,6 +321,31 @@ fn handle_branches_navigation(state: &mut AppState, direction: i32) {
                 state.branches_view_state.set_stash_selected(new_idx);
             }
         }
+        BranchesSection::Tags => {
+            let max = state.branches_view_state.tags.len();
+            if max > 0 {
+                let new_idx = if direction > 0 {
+                    (state.branches_view_state.tag_selected() + 1).min(max - 1)
+                } else {
+                    state.branches_view_state.tag_selected().saturating_sub(1)
+                };
+                state.branches_view_state.set_tag_selected(new_idx);
+            }
+        }
+        BranchesSection::Remotes => {
+            let max = state.branches_view_state.remotes.len();
+            if max > 0 {
+                let new_idx = if direction > 0 {
+                    (state.branches_view_state.remote_selected() + 1).min(max - 1)
+                } else {
+                    state
+                        .branches_view_state
+                        .remote_selected()
+                        .saturating_sub(1)
+                };
+                state.branches_view_state.set_remote_selected(new_idx);
+            }
+        }
     }
 }
 
@@ -0,0 +1,86 @@
+//! Handler pour la palette de commandes.
+
+use super::traits::{ActionHandler, HandlerContext};
+use crate::error::Result;
+use crate::state::action::PaletteAction;
+use crate::state::{AppAction, AppState, CommandPaletteState, PaletteEntry};
+use crate::ui::keymap::{self, KeyContext};
+
+/// Handler pour la palette de commandes.
+///
+/// L'exécution de l'action choisie (`PaletteAction::Confirm`) est faite par le
+/// dispatcher, qui la redistribue comme une touche pressée.
+pub struct PaletteHandler;
+
+impl ActionHandler for PaletteHandler {
+    type Action = PaletteAction;
+
+    fn handle(&mut self, ctx: &mut HandlerContext, action: PaletteAction) -> Result<()> {
+        match action {
+            PaletteAction::Open => handle_open(ctx.state),
+            PaletteAction::Close => ctx.state.command_palette = None,
+            PaletteAction::InsertChar(c) => with_palette(ctx.state, |p| {
+                p.query.push(c);
+                p.update_matches();
+            }),
+            PaletteAction::Backspace => with_palette(ctx.state, |p| {
+                p.query.pop();
+                p.update_matches();
+            }),
+            PaletteAction::SelectNext => with_palette(ctx.state, |p| p.matches.select_next()),
+            PaletteAction::SelectPrevious => {
+                with_palette(ctx.state, |p| p.matches.select_previous())
+            }
+            // Géré par le dispatcher.
+            PaletteAction::Confirm => {}
+        }
+        Ok(())
+    }
+}
+
+/// Applique une modification à la palette si elle est ouverte.
+fn with_palette(state: &mut AppState, f: impl FnOnce(&mut CommandPaletteState)) {
+    if let Some(ref mut palette) = state.command_palette {
+        f(palette);
+    }
+}
+
+/// Ouvre la palette avec les actions disponibles dans le contexte courant.
+fn handle_open(state: &mut AppState) {
+    let contexts = keymap::active_contexts(state);
+    state.command_palette = Some(CommandPaletteState::new(palette_entries(&contexts)));
+}
+
+/// Actions proposées pour les contextes donnés, avec leur raccourci actuel.
+fn palette_entries(contexts: &[KeyContext]) -> Vec<PaletteEntry> {
+    keymap::current()
+        .available(contexts)
+        .into_iter()
+        .filter(|b| !matches!(b.action, AppAction::Palette(_)))
+        .map(|b| PaletteEntry {
+            action: b.action.clone(),
+            description: b.description,
+            keys: b.keys_label(),
+            context: b.context.title(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::FocusPanel;
+
+    #[test]
+    fn test_entries_follow_context() {
+        let graph = palette_entries(&KeyContext::graph(FocusPanel::Graph));
+        assert!(graph.iter().any(|e| e.action == AppAction::GitPush));
+        assert!(graph.iter().any(|e| e.action == AppAction::SwitchToStaging));
+        assert!(!graph
+            .iter()
+            .any(|e| matches!(e.action, AppAction::Palette(_))));
+
+        let reflog = palette_entries(&[KeyContext::Reflog]);
+        assert!(!reflog.iter().any(|e| e.action == AppAction::GitPush));
+    }
+}
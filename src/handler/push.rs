@@ -0,0 +1,77 @@
+//! Handler pour le dialogue de push avec options.
+
+use super::traits::{ActionHandler, HandlerContext};
+use crate::error::Result;
+use crate::git::remote::{self, PushRequest};
+use crate::state::action::PushAction;
+use crate::state::{AppState, PushDialogState};
+use crate::transfer::RemoteOperation;
+
+/// Handler pour le dialogue de push.
+pub struct PushHandler;
+
+impl ActionHandler for PushHandler {
+    type Action = PushAction;
+
+    fn handle(&mut self, ctx: &mut HandlerContext, action: PushAction) -> Result<()> {
+        match action {
+            PushAction::Open => handle_open(ctx.state),
+            PushAction::Close => ctx.state.push_dialog = None,
+            PushAction::NextField => with_dialog(ctx.state, |d| d.move_field(1)),
+            PushAction::PreviousField => with_dialog(ctx.state, |d| d.move_field(-1)),
+            PushAction::Toggle => with_dialog(ctx.state, PushDialogState::toggle),
+            PushAction::NextRemote => with_dialog(ctx.state, |d| d.cycle_remote(1)),
+            PushAction::PreviousRemote => with_dialog(ctx.state, |d| d.cycle_remote(-1)),
+            PushAction::InsertChar(c) => with_dialog(ctx.state, |d| d.insert_char(c)),
+            PushAction::Backspace => with_dialog(ctx.state, PushDialogState::backspace),
+            PushAction::Confirm => return handle_confirm(ctx.state),
+        }
+        Ok(())
+    }
+}
+
+/// Applique une modification au dialogue s'il est ouvert.
+fn with_dialog(state: &mut AppState, f: impl FnOnce(&mut PushDialogState)) {
+    if let Some(ref mut dialog) = state.push_dialog {
+        f(dialog);
+    }
+}
+
+/// Ouvre le dialogue de push, avec la raison du refus précédent le cas échéant.
+pub fn open_push_dialog(state: &mut AppState, request: PushRequest, rejection: Option<String>) {
+    let remotes = remote::list_remotes(&state.repo.repo)
+        .map(|remotes| remotes.into_iter().map(|r| r.name).collect())
+        .unwrap_or_default();
+    let mut dialog = PushDialogState::new(request, remotes);
+    dialog.rejection = rejection;
+    state.push_dialog = Some(dialog);
+}
+
+fn handle_open(state: &mut AppState) {
+    match state.current_branch.clone() {
+        Some(branch) => {
+            let request = PushRequest::for_branch(&state.repo.repo, &branch);
+            open_push_dialog(state, request, None);
+        }
+        None => state.set_flash_message("HEAD détachée, impossible de pousser".to_string()),
+    }
+}
+
+fn handle_confirm(state: &mut AppState) -> Result<()> {
+    let Some(dialog) = state.push_dialog.as_ref() else {
+        return Ok(());
+    };
+    let request = dialog.request.clone();
+
+    if request.remote_branch.is_empty() {
+        state.set_flash_message("Branche distante vide".to_string());
+        return Ok(());
+    }
+    if state.repo.repo.find_remote(&request.remote).is_err() {
+        state.set_flash_message(format!("Remote '{}' introuvable", request.remote));
+        return Ok(());
+    }
+
+    state.push_dialog = None;
+    super::git::start_transfer(state, RemoteOperation::PushBranch(request))
+}
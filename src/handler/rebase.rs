@@ -0,0 +1,243 @@
+//! Handler pour le rebase interactif.
+
+use super::traits::{ActionHandler, HandlerContext};
+use crate::error::Result;
+use crate::git::rebase::{rebase_todo, RebaseProgress, RebaseSession};
+use crate::state::action::{AppAction, RebaseAction};
+use crate::state::{AppState, ConflictsState, RebaseEditorState, ViewMode};
+use crate::ui::keymap::{self, KeyContext};
+
+/// Handler pour les opérations de rebase interactif.
+pub struct RebaseHandler;
+
+impl ActionHandler for RebaseHandler {
+    type Action = RebaseAction;
+
+    fn handle(&mut self, ctx: &mut HandlerContext, action: RebaseAction) -> Result<()> {
+        match action {
+            RebaseAction::Open => handle_open(ctx.state),
+            RebaseAction::Close => handle_close(ctx.state),
+            RebaseAction::SelectNext => with_editor(ctx.state, |e| e.entries.select_next()),
+            RebaseAction::SelectPrevious => with_editor(ctx.state, |e| e.entries.select_previous()),
+            RebaseAction::MoveEntryUp => with_editor(ctx.state, |e| e.move_selected(true)),
+            RebaseAction::MoveEntryDown => with_editor(ctx.state, |e| e.move_selected(false)),
+            RebaseAction::SetAction(action) => with_editor(ctx.state, |e| e.set_action(action)),
+            RebaseAction::StartReword => handle_start_reword(ctx.state),
+            RebaseAction::ConfirmReword => with_editor(ctx.state, |e| e.confirm_reword()),
+            RebaseAction::CancelReword => with_editor(ctx.state, |e| e.reword_input = None),
+            RebaseAction::Execute => handle_execute(ctx.state),
+            RebaseAction::Continue => handle_continue(ctx.state),
+            RebaseAction::Skip => handle_skip(ctx.state),
+            RebaseAction::Abort => handle_abort(ctx.state),
+        }
+    }
+}
+
+/// Applique une modification à l'éditeur s'il est ouvert.
+fn with_editor(state: &mut AppState, f: impl FnOnce(&mut RebaseEditorState)) -> Result<()> {
+    if let Some(ref mut editor) = state.rebase_editor {
+        f(editor);
+    }
+    Ok(())
+}
+
+fn handle_open(state: &mut AppState) -> Result<()> {
+    // Un rebase est déjà en cours : afficher sa progression.
+    if let Some(ref session) = state.rebase_session {
+        state.rebase_editor = Some(RebaseEditorState::new(
+            session.onto,
+            session.todo.iter().cloned().collect(),
+        ));
+        return Ok(());
+    }
+
+    if state.view_mode != ViewMode::Graph {
+        return Ok(());
+    }
+
+    let base = if let Some(row) = state.graph.get(state.selected_index) {
+        row.node.oid
+    } else {
+        state.set_flash_message("Aucun commit sélectionné".to_string());
+        return Ok(());
+    };
+
+    match rebase_todo(&state.repo.repo, base) {
+        Ok(entries) => state.rebase_editor = Some(RebaseEditorState::new(base, entries)),
+        Err(e) => state.set_flash_message(format!("Rebase impossible: {}", e)),
+    }
+    Ok(())
+}
+
+/// Ouvre l'éditeur du message de l'entrée sélectionnée, pré-rempli avec le message complet.
+fn handle_start_reword(state: &mut AppState) -> Result<()> {
+    let Some(ref mut editor) = state.rebase_editor else {
+        return Ok(());
+    };
+    let Some(oid) = editor.entries.selected_item().map(|entry| entry.oid) else {
+        return Ok(());
+    };
+    let commit = state.repo.repo.find_commit(oid)?;
+    editor.start_reword(commit.message().unwrap_or(""));
+    Ok(())
+}
+
+fn handle_close(state: &mut AppState) -> Result<()> {
+    state.rebase_editor = None;
+    Ok(())
+}
+
+fn handle_execute(state: &mut AppState) -> Result<()> {
+    if state.rebase_session.is_some() {
+        return Ok(());
+    }
+    let Some((base, todo)) = state.rebase_editor.as_ref().map(|e| (e.base, e.todo())) else {
+        return Ok(());
+    };
+
+    super::git::record_snapshot(state, "rebase interactif");
+    match RebaseSession::start(&state.repo.repo, base, todo) {
+        Ok((session, progress)) => {
+            state.rebase_session = Some(session);
+            state.rebase_editor = None;
+            apply_progress(state, progress);
+        }
+        Err(e) => {
+            state.set_flash_message(format!("Erreur lors du rebase: {}", e));
+        }
+    }
+    Ok(())
+}
+
+/// Continue le rebase en cours (après résolution des conflits ou un arrêt sur edit).
+pub(crate) fn handle_continue(state: &mut AppState) -> Result<()> {
+    let Some(mut session) = state.rebase_session.take() else {
+        state.set_flash_message("Aucun rebase en cours".to_string());
+        return Ok(());
+    };
+
+    super::git::record_snapshot(state, "suite du rebase");
+    let result = session.continue_rebase(&state.repo.repo);
+    state.rebase_session = Some(session);
+    match result {
+        Ok(progress) => apply_progress(state, progress),
+        Err(e) => state.set_flash_message(format!("Erreur: {}", e)),
+    }
+    Ok(())
+}
+
+/// Passe le commit courant du rebase.
+pub(crate) fn handle_skip(state: &mut AppState) -> Result<()> {
+    let Some(mut session) = state.rebase_session.take() else {
+        state.set_flash_message("Aucun rebase en cours".to_string());
+        return Ok(());
+    };
+
+    super::git::record_snapshot(state, "commit passé du rebase");
+    let result = session.skip(&state.repo.repo);
+    state.rebase_session = Some(session);
+    match result {
+        Ok(progress) => apply_progress(state, progress),
+        Err(e) => state.set_flash_message(format!("Erreur: {}", e)),
+    }
+    Ok(())
+}
+
+/// Abandonne le rebase et restaure la branche d'origine.
+pub(crate) fn handle_abort(state: &mut AppState) -> Result<()> {
+    let Some(session) = state.rebase_session.take() else {
+        state.set_flash_message("Aucun rebase en cours".to_string());
+        return Ok(());
+    };
+
+    match session.abort(&state.repo.repo) {
+        Ok(()) => {
+            state.rebase_editor = None;
+            state.conflicts_state = None;
+            if state.view_mode == ViewMode::Conflicts {
+                state.view_mode = ViewMode::Graph;
+            }
+            state.mark_dirty();
+            state.set_flash_message("Rebase annulé".to_string());
+        }
+        Err(e) => {
+            state.rebase_session = Some(session);
+            state.set_flash_message(format!("Erreur: {}", e));
+        }
+    }
+    Ok(())
+}
+
+/// Reprend le rebase laissé en cours par un lancement précédent (état sauvegardé dans `.git`).
+pub fn restore_session(state: &mut AppState) {
+    match RebaseSession::load(&state.repo.repo) {
+        Ok(Some((session, progress))) => {
+            state.rebase_session = Some(session);
+            apply_progress(state, progress);
+        }
+        Ok(None) => {}
+        Err(e) => state.set_flash_message(format!("Reprise du rebase impossible: {}", e)),
+    }
+}
+
+/// Met à jour l'interface selon l'état d'avancement du rebase.
+pub(crate) fn apply_progress(state: &mut AppState, progress: RebaseProgress) {
+    state.rebase_editor = None;
+    state.mark_dirty();
+
+    match progress {
+        RebaseProgress::Completed => {
+            state.rebase_session = None;
+            state.conflicts_state = None;
+            if state.view_mode == ViewMode::Conflicts {
+                state.view_mode = ViewMode::Graph;
+            }
+            state.set_flash_message("Rebase terminé ✓".to_string());
+        }
+        RebaseProgress::Conflicts(files) => {
+            let (description, theirs_name) = match state.rebase_session {
+                Some(ref session) => {
+                    let theirs = session
+                        .current
+                        .as_ref()
+                        .map(|e| format!("{:.7} {}", e.oid, e.summary))
+                        .unwrap_or_default();
+                    (
+                        format!("Rebase ({}/{})", session.done_count(), session.total),
+                        theirs,
+                    )
+                }
+                None => ("Rebase".to_string(), String::new()),
+            };
+            state.conflicts_state = Some(ConflictsState::new(
+                files,
+                description,
+                "HEAD".to_string(),
+                theirs_name,
+            ));
+            state.view_mode = ViewMode::Conflicts;
+            state.set_flash_message(format!(
+                "Conflits lors du rebase - résolvez puis {}",
+                keymap::current().bar_text(&[KeyContext::ConflictsRebase])
+            ));
+        }
+        RebaseProgress::Stopped(oid) => {
+            state.conflicts_state = None;
+            if state.view_mode == ViewMode::Conflicts {
+                state.view_mode = ViewMode::Graph;
+            }
+            let open = AppAction::Rebase(RebaseAction::Open);
+            let message = match keymap::current().action_keys(&[KeyContext::Graph], &open) {
+                Some(keys) => format!(
+                    "Rebase arrêté sur {:.7} - modifiez puis {} pour continuer",
+                    oid, keys
+                ),
+                None => format!(
+                    "Rebase arrêté sur {:.7} - modifiez puis continuez le rebase",
+                    oid
+                ),
+            };
+            state.set_flash_message(message);
+        }
+    }
+}
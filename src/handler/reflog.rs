@@ -0,0 +1,263 @@
+//! Handler pour la vue reflog.
+
+use super::traits::{ActionHandler, HandlerContext};
+use crate::error::Result;
+use crate::git::graph::CommitNode;
+use crate::git::reflog::read_reflog;
+use crate::state::action::ReflogAction;
+use crate::state::{AppState, ReflogFocus, ReflogState, ResetPickerState, ViewMode};
+
+/// Handler pour les opérations de la vue reflog.
+pub struct ReflogHandler;
+
+impl ActionHandler for ReflogHandler {
+    type Action = ReflogAction;
+
+    fn handle(&mut self, ctx: &mut HandlerContext, action: ReflogAction) -> Result<()> {
+        match action {
+            ReflogAction::Open => handle_open(ctx.state),
+            ReflogAction::Close => handle_close(ctx.state),
+            ReflogAction::SelectNext => handle_select(ctx.state, true),
+            ReflogAction::SelectPrevious => handle_select(ctx.state, false),
+            ReflogAction::ToggleFocus => handle_toggle_focus(ctx.state),
+            ReflogAction::Checkout => handle_checkout(ctx.state),
+            ReflogAction::StartBranch => with_reflog(ctx.state, |r| {
+                r.branch_input = Some(String::new());
+            }),
+            ReflogAction::BranchInsertChar(c) => with_reflog(ctx.state, |r| {
+                if let Some(ref mut input) = r.branch_input {
+                    input.push(c);
+                }
+            }),
+            ReflogAction::BranchBackspace => with_reflog(ctx.state, |r| {
+                if let Some(ref mut input) = r.branch_input {
+                    input.pop();
+                }
+            }),
+            ReflogAction::ConfirmBranch => handle_confirm_branch(ctx.state),
+            ReflogAction::CancelBranch => with_reflog(ctx.state, |r| r.branch_input = None),
+            ReflogAction::Reset => handle_reset(ctx.state),
+        }
+    }
+}
+
+/// Applique une modification à l'état du reflog s'il est ouvert.
+fn with_reflog(state: &mut AppState, f: impl FnOnce(&mut ReflogState)) -> Result<()> {
+    if let Some(ref mut reflog) = state.reflog_state {
+        f(reflog);
+    }
+    Ok(())
+}
+
+fn handle_open(state: &mut AppState) -> Result<()> {
+    // Depuis la vue branches : reflog de la branche sélectionnée. Sinon : HEAD.
+    let refname = if state.view_mode == ViewMode::Branches {
+        match state.branches_view_state.selected_branch() {
+            Some(branch) if branch.is_remote => format!("refs/remotes/{}", branch.name),
+            Some(branch) => format!("refs/heads/{}", branch.name),
+            None => return Ok(()),
+        }
+    } else {
+        "HEAD".to_string()
+    };
+
+    match read_reflog(&state.repo.repo, &refname) {
+        Ok(entries) if entries.is_empty() => {
+            state.set_flash_message(format!("Reflog vide pour {}", refname));
+        }
+        Ok(entries) => {
+            let mut reflog = ReflogState::new(refname, entries);
+            reflog.return_view = state.view_mode;
+            state.reflog_state = Some(reflog);
+            state.view_mode = ViewMode::Reflog;
+            load_preview(state);
+        }
+        Err(e) => state.set_flash_message(format!("Erreur lecture du reflog: {}", e)),
+    }
+    Ok(())
+}
+
+fn handle_close(state: &mut AppState) -> Result<()> {
+    if let Some(reflog) = state.reflog_state.take() {
+        state.view_mode = reflog.return_view;
+    } else if state.view_mode == ViewMode::Reflog {
+        state.view_mode = ViewMode::Graph;
+    }
+    Ok(())
+}
+
+fn handle_select(state: &mut AppState, next: bool) -> Result<()> {
+    let Some(ref mut reflog) = state.reflog_state else {
+        return Ok(());
+    };
+
+    match reflog.focus {
+        ReflogFocus::Entries => {
+            if next {
+                reflog.entries.select_next();
+            } else {
+                reflog.entries.select_previous();
+            }
+            load_preview(state);
+        }
+        ReflogFocus::Files => {
+            if next {
+                reflog.files.select_next();
+            } else {
+                reflog.files.select_previous();
+            }
+            load_file_diff(state);
+        }
+    }
+    Ok(())
+}
+
+fn handle_toggle_focus(state: &mut AppState) -> Result<()> {
+    with_reflog(state, |r| {
+        r.focus = match r.focus {
+            ReflogFocus::Entries if !r.files.is_empty() => ReflogFocus::Files,
+            _ => ReflogFocus::Entries,
+        };
+    })
+}
+
+/// Oid du commit de l'entrée sélectionnée (None si l'entrée supprime la référence).
+fn selected_target(state: &AppState) -> Option<git2::Oid> {
+    state
+        .reflog_state
+        .as_ref()
+        .and_then(|r| r.selected_entry())
+        .map(|e| e.new_oid)
+        .filter(|oid| !oid.is_zero())
+}
+
+fn handle_checkout(state: &mut AppState) -> Result<()> {
+    let Some(target) = selected_target(state) else {
+        return Ok(());
+    };
+
+    super::git::record_snapshot(state, "checkout");
+    match crate::git::reflog::checkout_commit(&state.repo.repo, target) {
+        Ok(()) => {
+            state.mark_dirty();
+            state.set_flash_message(format!("Checkout de {:.7} (HEAD détachée) ✓", target));
+        }
+        Err(e) => state.set_flash_message(format!("Erreur checkout: {}", e)),
+    }
+    Ok(())
+}
+
+fn handle_confirm_branch(state: &mut AppState) -> Result<()> {
+    let name = state
+        .reflog_state
+        .as_mut()
+        .and_then(|r| r.branch_input.take())
+        .map(|s| s.trim().to_string())
+        .unwrap_or_default();
+    let Some(target) = selected_target(state) else {
+        return Ok(());
+    };
+    if name.is_empty() {
+        return Ok(());
+    }
+
+    super::git::record_snapshot(state, "création de branche");
+    match crate::git::branch::create_branch_at(&state.repo.repo, &name, target) {
+        Ok(()) => {
+            state.mark_dirty();
+            state.set_flash_message(format!("Branche '{}' créée sur {:.7} ✓", name, target));
+        }
+        Err(e) => state.set_flash_message(format!("Erreur: {}", e)),
+    }
+    Ok(())
+}
+
+fn handle_reset(state: &mut AppState) -> Result<()> {
+    if let Some(target) = selected_target(state) {
+        state.reset_picker = Some(ResetPickerState::new(target));
+    }
+    Ok(())
+}
+
+/// Recharge le reflog affiché (après un checkout, un reset, un commit...).
+pub(crate) fn reload_reflog(state: &mut AppState) {
+    let Some(ref mut reflog) = state.reflog_state else {
+        return;
+    };
+    if let Ok(entries) = read_reflog(&state.repo.repo, &reflog.refname) {
+        reflog.entries.set_items(entries);
+    }
+    load_preview(state);
+}
+
+/// Charge l'aperçu (commit, fichiers, diff) de l'entrée sélectionnée.
+fn load_preview(state: &mut AppState) {
+    let target = selected_target(state);
+    let commit = target
+        .and_then(|oid| state.repo.repo.find_commit(oid).ok())
+        .map(|commit| CommitNode {
+            oid: commit.id(),
+            message: commit.message().unwrap_or("").trim_end().to_string(),
+            author: commit.author().name().unwrap_or("Inconnu").to_string(),
+            timestamp: commit.time().seconds(),
+            parents: commit.parent_ids().collect(),
+            ..CommitNode::default()
+        });
+    let files = target
+        .and_then(|oid| state.repo.commit_diff(oid).ok())
+        .unwrap_or_default();
+
+    let Some(ref mut reflog) = state.reflog_state else {
+        return;
+    };
+    reflog.commit = commit;
+    reflog.files.set_items(files);
+    if reflog.files.is_empty() {
+        reflog.focus = ReflogFocus::Entries;
+    }
+    load_file_diff(state);
+}
+
+/// Charge le diff du fichier sélectionné dans l'aperçu.
+fn load_file_diff(state: &mut AppState) {
+    let target = selected_target(state);
+    let Some(ref mut reflog) = state.reflog_state else {
+        return;
+    };
+
+    reflog.file_diff = match (target, reflog.files.selected_item()) {
+        (Some(oid), Some(file)) => state.repo.file_diff(oid, &file.path).ok(),
+        _ => None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::repo::GitRepo;
+    use crate::git::tests::test_utils::*;
+
+    #[test]
+    fn test_reflog_checkout_can_be_undone() {
+        let (dir, repo) = create_test_repo();
+        let first = commit_file(&repo, "a.txt", "v1", "First");
+        commit_file(&repo, "a.txt", "v2", "Second");
+        let path = dir.path().to_string_lossy().to_string();
+        let mut state = AppState::new(GitRepo::open(&path).unwrap(), path).unwrap();
+        let mut ctx = HandlerContext { state: &mut state };
+
+        for action in [
+            ReflogAction::Open,
+            ReflogAction::SelectNext,
+            ReflogAction::Checkout,
+        ] {
+            ReflogHandler.handle(&mut ctx, action).unwrap();
+        }
+        assert_eq!(repo.head().unwrap().target(), Some(first));
+        assert!(repo.head_detached().unwrap());
+
+        let outcome = state.undo_history.undo(&repo).unwrap().unwrap();
+        assert_eq!(outcome.label, "checkout");
+        assert!(!repo.head_detached().unwrap());
+    }
+}
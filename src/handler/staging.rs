@@ -1,10 +1,14 @@
 //! Handler pour les actions de staging.
 
 use super::traits::{ActionHandler, HandlerContext};
-use crate::error::Result;
+use crate::error::{GitSvError, Result};
+use crate::git::hooks::HookRun;
 use crate::state::action::StagingAction;
 use crate::state::cache::DiffCacheKey;
-use crate::state::{AppState, StagingFocus, ViewMode};
+use crate::state::{
+    AppState, CommandOutputState, CommitEditor, ConventionalPickerState, DiffSource, StagingFocus,
+    ViewMode,
+};
 
 /// Handler pour les opérations de staging.
 pub struct StagingHandler;
@@ -23,9 +27,39 @@ impl ActionHandler for StagingHandler {
             StagingAction::StartCommitMessage => handle_start_commit(ctx.state),
             StagingAction::ConfirmCommit => handle_confirm_commit(ctx.state),
             StagingAction::CancelCommit => handle_cancel_commit(ctx.state),
+            StagingAction::ToggleSkipHooks => handle_toggle_skip_hooks(ctx.state),
+            StagingAction::OpenExternalEditor => handle_open_external_editor(ctx.state),
+            StagingAction::ConventionalOpen => handle_conventional_open(ctx.state),
+            StagingAction::ConventionalInsertChar(c) => with_conventional(ctx.state, |p| {
+                p.query.push(c);
+                p.update_matches();
+            }),
+            StagingAction::ConventionalBackspace => with_conventional(ctx.state, |p| {
+                p.query.pop();
+                p.update_matches();
+            }),
+            StagingAction::ConventionalNext => {
+                with_conventional(ctx.state, |p| p.matches.select_next())
+            }
+            StagingAction::ConventionalPrevious => {
+                with_conventional(ctx.state, |p| p.matches.select_previous())
+            }
+            StagingAction::ConventionalConfirm => handle_conventional_confirm(ctx.state),
+            StagingAction::ConventionalCancel => {
+                ctx.state.staging_state.conventional_picker = None;
+                Ok(())
+            }
             StagingAction::SwitchFocus => handle_switch_focus(ctx.state),
             StagingAction::StashSelectedFile => handle_stash_selected_file(ctx.state),
             StagingAction::StashUnstagedFiles => handle_stash_unstaged_files(ctx.state),
+            StagingAction::StageHunk => handle_stage_hunk(ctx.state),
+            StagingAction::UnstageHunk => handle_unstage_hunk(ctx.state),
+            StagingAction::DiscardHunk => handle_discard_hunk(ctx.state),
+            StagingAction::NextHunk => handle_jump_hunk(ctx.state, true),
+            StagingAction::PrevHunk => handle_jump_hunk(ctx.state, false),
+            StagingAction::ToggleLineSelection => handle_toggle_line_selection(ctx.state),
+            StagingAction::StageSelectedLines => handle_stage_selected_lines(ctx.state),
+            StagingAction::UnstageSelectedLines => handle_unstage_selected_lines(ctx.state),
         }
     }
 }
@@ -37,7 +71,9 @@ fn handle_stage_file(state: &mut AppState) -> Result<()> {
             .unstaged_files()
             .get(state.staging_state.unstaged_selected())
         {
-            crate::git::commit::stage_file(&state.repo.repo, &file.path)?;
+            let path = file.path.clone();
+            super::git::record_snapshot(state, "stage du fichier");
+            crate::git::commit::stage_file(&state.repo.repo, &path)?;
             state.mark_dirty();
             refresh_staging(state)?;
         }
@@ -52,7 +88,9 @@ fn handle_unstage_file(state: &mut AppState) -> Result<()> {
             .staged_files()
             .get(state.staging_state.staged_selected())
         {
-            crate::git::commit::unstage_file(&state.repo.repo, &file.path)?;
+            let path = file.path.clone();
+            super::git::record_snapshot(state, "unstage du fichier");
+            crate::git::commit::unstage_file(&state.repo.repo, &path)?;
             state.mark_dirty();
             refresh_staging(state)?;
         }
@@ -62,6 +100,7 @@ fn handle_unstage_file(state: &mut AppState) -> Result<()> {
 
 fn handle_stage_all(state: &mut AppState) -> Result<()> {
     if state.view_mode == ViewMode::Staging {
+        super::git::record_snapshot(state, "stage de tous les fichiers");
         crate::git::commit::stage_all(&state.repo.repo)?;
         state.mark_dirty();
         refresh_staging(state)?;
@@ -71,6 +110,7 @@ fn handle_stage_all(state: &mut AppState) -> Result<()> {
 
 fn handle_unstage_all(state: &mut AppState) -> Result<()> {
     if state.view_mode == ViewMode::Staging {
+        super::git::record_snapshot(state, "unstage de tous les fichiers");
         crate::git::commit::unstage_all(&state.repo.repo)?;
         state.mark_dirty();
         refresh_staging(state)?;
@@ -105,28 +145,103 @@ fn handle_discard_all(state: &mut AppState) -> Result<()> {
 
 fn handle_start_commit(state: &mut AppState) -> Result<()> {
     if state.view_mode == ViewMode::Staging {
+        if !state.staging_state.is_committing {
+            reset_commit_message(state);
+        }
         state.staging_state.is_committing = true;
         state.staging_state.focus = StagingFocus::CommitMessage;
     }
     Ok(())
 }
 
+/// Prépare un nouveau message de commit, pré-rempli avec `commit.template` s'il existe.
+pub(crate) fn reset_commit_message(state: &mut AppState) {
+    let template = crate::git::commit::commit_template(&state.repo.repo);
+    state.staging_state.comment_char = template
+        .is_some()
+        .then(|| crate::git::commit::comment_char(&state.repo.repo));
+    state.staging_state.commit_editor = template
+        .map(|template| CommitEditor::from_text(&template))
+        .unwrap_or_default();
+}
+
 fn handle_confirm_commit(state: &mut AppState) -> Result<()> {
-    if state.view_mode == ViewMode::Staging && !state.staging_state.commit_message.is_empty() {
-        let message = state.staging_state.commit_message.clone();
+    if state.view_mode == ViewMode::Staging && !state.staging_state.commit_editor.is_empty() {
+        // Comme git, les commentaires du modèle ne font pas partie du message ; sans
+        // modèle, seuls les blancs sont nettoyés (comme `git commit -m`).
+        let message = state.staging_state.cleaned_message()?;
+        if message.is_empty() {
+            state.set_flash_message("Message de commit vide".to_string());
+            return Ok(());
+        }
+        // Règles Conventional Commits : elles s'appliquent même si les hooks sont ignorés.
+        let rules = &crate::config::current().conventional_commits;
+        if rules.enabled {
+            if let Some(violation) = crate::conventional::check(&message, rules).first() {
+                state.set_flash_message(format!("Message non conforme : {}", violation));
+                return Ok(());
+            }
+        }
+        let amending = state.staging_state.is_amending;
 
-        if state.staging_state.is_amending {
-            crate::git::commit::amend_commit(&state.repo.repo, &message)?;
+        super::git::record_snapshot(state, "commit");
+        let mut hooks = HookRun::new(state.staging_state.skip_hooks);
+        let result = if amending {
+            crate::git::commit::amend_commit_with_hooks(&state.repo.repo, &message, &mut hooks)
+        } else {
+            crate::git::commit::create_commit_with_hooks(&state.repo.repo, &message, &mut hooks)
+        };
+        // En cas d'échec, le message est conservé : corriger puis valider à nouveau.
+        match result {
+            Ok(_) => {}
+            Err(GitSvError::HookFailed {
+                hook,
+                status,
+                output,
+            }) => {
+                state.command_output = Some(CommandOutputState::new(
+                    format!("Hook {} ({})", hook, status),
+                    &output,
+                    "",
+                    false,
+                ));
+                state.set_flash_message(format!("Commit annulé : hook {} en échec", hook));
+                return Ok(());
+            }
+            Err(GitSvError::OperationFailed {
+                operation: "signature du commit",
+                details,
+            }) => {
+                state.set_flash_message(format!("Signature du commit impossible : {}", details));
+                return Ok(());
+            }
+            Err(e) => {
+                state.set_flash_message(format!("Commit impossible : {}", e));
+                return Ok(());
+            }
+        }
+
+        if amending {
             state.set_flash_message("Commit amendé ✓".to_string());
         } else {
-            crate::git::commit::create_commit(&state.repo.repo, &message)?;
             state.set_flash_message("Commit créé ✓".to_string());
         }
+        let output = hooks.text();
+        if !output.is_empty() {
+            state.command_output = Some(CommandOutputState::new(
+                "Hooks".to_string(),
+                &output,
+                "",
+                true,
+            ));
+        }
 
         // Réinitialiser l'état du commit
         state.staging_state.is_committing = false;
         state.staging_state.is_amending = false;
-        state.staging_state.commit_message.clear();
+        state.staging_state.skip_hooks = false;
+        state.staging_state.comment_char = None;
+        state.staging_state.commit_editor.clear();
         state.staging_state.focus = StagingFocus::Unstaged;
 
         state.mark_dirty();
@@ -139,12 +254,139 @@ fn handle_cancel_commit(state: &mut AppState) -> Result<()> {
     if state.view_mode == ViewMode::Staging {
         state.staging_state.is_committing = false;
         state.staging_state.is_amending = false;
-        state.staging_state.commit_message.clear();
+        state.staging_state.skip_hooks = false;
+        state.staging_state.comment_char = None;
+        state.staging_state.commit_editor.clear();
         state.staging_state.focus = StagingFocus::Unstaged;
     }
     Ok(())
 }
 
+/// Active/désactive les hooks de vérification pour le commit en cours (`--no-verify`).
+fn handle_toggle_skip_hooks(state: &mut AppState) -> Result<()> {
+    if state.view_mode == ViewMode::Staging {
+        state.staging_state.skip_hooks = !state.staging_state.skip_hooks;
+        state.set_flash_message(if state.staging_state.skip_hooks {
+            "Hooks pre-commit et commit-msg ignorés pour ce commit".to_string()
+        } else {
+            "Hooks réactivés".to_string()
+        });
+    }
+    Ok(())
+}
+
+/// Demande l'écriture du message dans l'éditeur externe (exécutée TUI suspendue).
+fn handle_open_external_editor(state: &mut AppState) -> Result<()> {
+    if state.view_mode != ViewMode::Staging {
+        return Ok(());
+    }
+    // Hors du panneau, le message saisi est commité directement.
+    if !state.staging_state.is_committing && state.staging_state.staged_files().is_empty() {
+        state.set_flash_message("Aucun fichier stagé à commiter".to_string());
+        return Ok(());
+    }
+    state.external_editor_requested = true;
+    Ok(())
+}
+
+/// Écrit le message de commit dans l'éditeur externe, sur `COMMIT_EDITMSG`.
+///
+/// Le terminal doit être libéré. Depuis le panneau du message, le résultat y
+/// est replacé ; sinon le commit est créé directement. Un message vide annule.
+pub fn run_external_editor(state: &mut AppState) {
+    let editor = crate::git::commit::editor_command(&state.repo.repo);
+    edit_in_editor(state, &editor);
+}
+
+/// Corps de [`run_external_editor`] avec une commande d'éditeur donnée.
+fn edit_in_editor(state: &mut AppState, editor: &str) {
+    let back_to_panel = state.staging_state.is_committing;
+    if !back_to_panel {
+        reset_commit_message(state);
+    }
+
+    let repo = &state.repo.repo;
+    let contents = crate::git::commit::commit_editmsg(
+        &state.staging_state.commit_editor.text(),
+        state.current_branch.as_deref(),
+        state.staging_state.staged_files(),
+        crate::git::commit::comment_char(repo),
+    );
+    let message = match crate::git::commit::edit_message(repo, editor, &contents) {
+        Ok(message) => message,
+        Err(e) => {
+            state.set_flash_message(format!("Erreur éditeur: {}", e));
+            return;
+        }
+    };
+    if message.is_empty() {
+        state.set_flash_message("Message vide : commit annulé".to_string());
+        return;
+    }
+
+    // Le message revient de l'éditeur déjà débarrassé de ses commentaires.
+    state.staging_state.commit_editor.set_text(&message);
+    state.staging_state.comment_char = None;
+    state.staging_state.is_committing = true;
+    state.staging_state.focus = StagingFocus::CommitMessage;
+    if back_to_panel {
+        state.set_flash_message("Message repris de l'éditeur".to_string());
+    } else {
+        // Passer par le handler : même validation (et même instantané d'undo) que Ctrl+S.
+        let mut ctx = HandlerContext { state };
+        if let Err(e) = StagingHandler.handle(&mut ctx, StagingAction::ConfirmCommit) {
+            ctx.state.set_flash_message(format!("Erreur: {}", e));
+        }
+    }
+}
+
+/// Applique une modification à l'assistant Conventional Commits s'il est ouvert.
+fn with_conventional(
+    state: &mut AppState,
+    f: impl FnOnce(&mut ConventionalPickerState),
+) -> Result<()> {
+    if let Some(ref mut picker) = state.staging_state.conventional_picker {
+        f(picker);
+    }
+    Ok(())
+}
+
+/// Ouvre l'assistant Conventional Commits sur le message en cours.
+///
+/// Les scopes proposés sont ceux de la configuration puis ceux des sujets
+/// des commits chargés dans le graphe.
+fn handle_conventional_open(state: &mut AppState) -> Result<()> {
+    if state.view_mode != ViewMode::Staging {
+        return Ok(());
+    }
+    // Conserve le message en cours et donne le focus au champ de message.
+    handle_start_commit(state)?;
+    let rules = &crate::config::current().conventional_commits;
+    let scopes = crate::conventional::learn_scopes(
+        state.graph.iter().map(|row| row.node.message.as_str()),
+        &rules.scopes,
+    );
+    state.staging_state.conventional_picker =
+        Some(ConventionalPickerState::new(rules.types.clone(), scopes));
+    Ok(())
+}
+
+/// Valide l'étape de l'assistant ; après le scope, réécrit l'en-tête du message.
+fn handle_conventional_confirm(state: &mut AppState) -> Result<()> {
+    let Some(ref mut picker) = state.staging_state.conventional_picker else {
+        return Ok(());
+    };
+    let Some((kind, scope)) = picker.confirm() else {
+        return Ok(());
+    };
+    state.staging_state.conventional_picker = None;
+
+    let editor = &mut state.staging_state.commit_editor;
+    let subject = crate::conventional::apply_header(&editor.lines()[0], &kind, scope.as_deref());
+    editor.set_subject(&subject);
+    Ok(())
+}
+
 fn handle_switch_focus(state: &mut AppState) -> Result<()> {
     if state.view_mode == ViewMode::Staging {
         state.staging_state.focus = match state.staging_state.focus {
@@ -168,6 +410,206 @@ fn handle_stash_unstaged_files(state: &mut AppState) -> Result<()> {
     Ok(())
 }
 
+/// Hunk sous le curseur du panneau diff, si le diff provient de `source`.
+fn selected_hunk(state: &AppState, source: DiffSource) -> Option<(String, usize)> {
+    let staging = &state.staging_state;
+    if state.view_mode != ViewMode::Staging
+        || staging.focus != StagingFocus::Diff
+        || staging.diff_source != source
+    {
+        return None;
+    }
+    let diff = staging.current_diff.as_ref()?;
+    Some((diff.path.clone(), staging.current_hunk_index()?))
+}
+
+fn handle_stage_hunk(state: &mut AppState) -> Result<()> {
+    if let Some((path, hunk)) = selected_hunk(state, DiffSource::Unstaged) {
+        // Un échec (hunk périmé, fichier binaire, contexte divergent) ne doit pas
+        // quitter l'application : il est signalé et le diff est rechargé.
+        let result = if displayed_hunk_matches(state, &path, DiffSource::Unstaged, hunk) {
+            super::git::record_snapshot(state, "stage du hunk");
+            crate::git::patch::stage_hunk(&state.repo.repo, &path, hunk)
+        } else {
+            Err(stale_hunk_error())
+        };
+        match result {
+            Ok(()) => state.set_flash_message(format!("Hunk {} stagé ✓", hunk + 1)),
+            Err(e) => state.set_flash_message(format!("Erreur: {}", e)),
+        }
+        state.mark_dirty();
+        refresh_staging(state)?;
+    }
+    Ok(())
+}
+
+fn handle_unstage_hunk(state: &mut AppState) -> Result<()> {
+    if let Some((path, hunk)) = selected_hunk(state, DiffSource::Staged) {
+        let result = if displayed_hunk_matches(state, &path, DiffSource::Staged, hunk) {
+            super::git::record_snapshot(state, "unstage du hunk");
+            crate::git::patch::unstage_hunk(&state.repo.repo, &path, hunk)
+        } else {
+            Err(stale_hunk_error())
+        };
+        match result {
+            Ok(()) => state.set_flash_message(format!("Hunk {} retiré du staging ✓", hunk + 1)),
+            Err(e) => state.set_flash_message(format!("Erreur: {}", e)),
+        }
+        state.mark_dirty();
+        refresh_staging(state)?;
+    }
+    Ok(())
+}
+
+fn handle_discard_hunk(state: &mut AppState) -> Result<()> {
+    use crate::ui::confirm_dialog::ConfirmAction;
+
+    if let Some((path, hunk)) = selected_hunk(state, DiffSource::Unstaged) {
+        if displayed_hunk_matches(state, &path, DiffSource::Unstaged, hunk) {
+            state.pending_confirmation = Some(ConfirmAction::DiscardHunk(path, hunk));
+        } else {
+            state.set_flash_message(format!("Erreur: {}", stale_hunk_error()));
+            state.mark_dirty();
+            refresh_staging(state)?;
+        }
+    }
+    Ok(())
+}
+
+fn handle_jump_hunk(state: &mut AppState, forward: bool) -> Result<()> {
+    if state.view_mode == ViewMode::Staging && state.staging_state.focus == StagingFocus::Diff {
+        state.staging_state.jump_to_hunk(forward);
+    }
+    Ok(())
+}
+
+fn handle_toggle_line_selection(state: &mut AppState) -> Result<()> {
+    if state.view_mode == ViewMode::Staging && state.staging_state.focus == StagingFocus::Diff {
+        state.staging_state.toggle_diff_selection();
+    }
+    Ok(())
+}
+
+/// Lignes sélectionnées dans le panneau diff, si le diff provient de `source`.
+fn selected_lines(state: &AppState, source: DiffSource) -> Option<(String, usize, usize)> {
+    let staging = &state.staging_state;
+    if state.view_mode != ViewMode::Staging
+        || staging.focus != StagingFocus::Diff
+        || staging.diff_source != source
+    {
+        return None;
+    }
+    let diff = staging.current_diff.as_ref()?;
+    let (start, end) = staging.diff_selection()?;
+    Some((diff.path.clone(), start, end))
+}
+
+/// Le diff affiché correspond-il encore au fichier jusqu'à la ligne `end` incluse?
+///
+/// La sélection porte sur le diff mis en cache à l'affichage : si le fichier a changé
+/// depuis, les mêmes indices désigneraient d'autres lignes du diff recalculé.
+fn displayed_lines_match(state: &AppState, path: &str, source: DiffSource, end: usize) -> bool {
+    let current = match source {
+        DiffSource::Unstaged => crate::git::diff::unstaged_file_diff(&state.repo.repo, path),
+        DiffSource::Staged => crate::git::diff::staged_file_diff(&state.repo.repo, path),
+    };
+    match (current, state.staging_state.current_diff.as_ref()) {
+        (Ok(current), Some(displayed)) => {
+            displayed.lines.len() > end
+                && current.lines.len() > end
+                && current.lines[..=end] == displayed.lines[..=end]
+        }
+        _ => false,
+    }
+}
+
+/// Le hunk `hunk` du diff affiché pour `path` correspond-il encore au fichier?
+///
+/// Même vérification que pour les lignes, jusqu'à la fin du hunk : son en-tête porte
+/// le nombre de lignes, un hunk qui a grandi depuis l'affichage est donc refusé.
+pub(crate) fn displayed_hunk_matches(
+    state: &AppState,
+    path: &str,
+    source: DiffSource,
+    hunk: usize,
+) -> bool {
+    let staging = &state.staging_state;
+    let Some(diff) = staging
+        .current_diff
+        .as_ref()
+        .filter(|diff| staging.diff_source == source && diff.path == path)
+    else {
+        return false;
+    };
+    let mut headers = diff
+        .lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.line_type == crate::git::diff::DiffLineType::HunkHeader)
+        .map(|(i, _)| i)
+        .skip(hunk);
+    if headers.next().is_none() {
+        return false;
+    }
+    let end = headers.next().map_or(diff.lines.len() - 1, |next| next - 1);
+    displayed_lines_match(state, path, source, end)
+}
+
+/// Erreur signalée quand le hunk sélectionné ne correspond plus au fichier.
+pub(crate) fn stale_hunk_error() -> GitSvError {
+    GitSvError::Other("Le fichier a changé depuis l'affichage du diff, hunk annulé".to_string())
+}
+
+/// Applique la sélection de lignes au diff de `source`, puis recharge le staging.
+///
+/// `label` nomme l'instantané d'undo pris juste avant l'application.
+fn apply_selected_lines(
+    state: &mut AppState,
+    source: DiffSource,
+    apply: fn(&git2::Repository, &str, usize, usize) -> Result<()>,
+    label: &str,
+    success: &str,
+) -> Result<()> {
+    if let Some((path, start, end)) = selected_lines(state, source) {
+        let result = if displayed_lines_match(state, &path, source, end) {
+            super::git::record_snapshot(state, label);
+            apply(&state.repo.repo, &path, start, end)
+        } else {
+            Err(GitSvError::Other(
+                "Le fichier a changé depuis l'affichage du diff, sélection annulée".to_string(),
+            ))
+        };
+        state.staging_state.diff_selection_anchor = None;
+        match result {
+            Ok(()) => state.set_flash_message(success.to_string()),
+            Err(e) => state.set_flash_message(format!("Erreur: {}", e)),
+        }
+        state.mark_dirty();
+        refresh_staging(state)?;
+    }
+    Ok(())
+}
+
+fn handle_stage_selected_lines(state: &mut AppState) -> Result<()> {
+    apply_selected_lines(
+        state,
+        DiffSource::Unstaged,
+        crate::git::patch::stage_lines,
+        "stage des lignes",
+        "Lignes stagées ✓",
+    )
+}
+
+fn handle_unstage_selected_lines(state: &mut AppState) -> Result<()> {
+    apply_selected_lines(
+        state,
+        DiffSource::Staged,
+        crate::git::patch::unstage_lines,
+        "unstage des lignes",
+        "Lignes retirées du staging ✓",
+    )
+}
+
 /// Rafraîchit l'état du staging depuis le repository.
 pub fn refresh_staging(state: &mut AppState) -> Result<()> {
     let all_entries = state.repo.status()?;
@@ -210,40 +652,63 @@ pub fn refresh_staging_with_entries(
 }
 
 /// Charge le diff pour le fichier sélectionné dans le staging.
+///
+/// Le diff affiché correspond à la liste d'origine du fichier : modifications
+/// non stagées (index → working directory) ou stagées (HEAD → index).
 pub fn load_staging_diff(state: &mut AppState) {
-    let selected_file = match state.staging_state.focus {
-        StagingFocus::Unstaged => state
+    let source = match state.staging_state.focus {
+        StagingFocus::Unstaged => DiffSource::Unstaged,
+        StagingFocus::Staged => DiffSource::Staged,
+        // Le panneau diff conserve la liste du dernier fichier sélectionné.
+        StagingFocus::Diff => state.staging_state.diff_source,
+        StagingFocus::CommitMessage => {
+            state.staging_state.current_diff = None;
+            return;
+        }
+    };
+
+    let selected_file = match source {
+        DiffSource::Unstaged => state
             .staging_state
             .unstaged_files()
             .get(state.staging_state.unstaged_selected()),
-        StagingFocus::Staged => state
+        DiffSource::Staged => state
             .staging_state
             .staged_files()
             .get(state.staging_state.staged_selected()),
-        _ => None,
     };
+    let path = selected_file.map(|file| file.path.clone());
+
+    // Conserver le curseur si le même diff est rechargé (ex: après un stage de hunk).
+    let same_diff = state.staging_state.diff_source == source
+        && state.staging_state.current_diff.as_ref().map(|d| &d.path) == path.as_ref();
+    state.staging_state.diff_source = source;
 
-    if let Some(file) = selected_file {
-        // Pour le working directory, on utilise DiffCacheKey::working_dir()
-        let cache_key = DiffCacheKey::working_dir(&file.path);
+    state.staging_state.current_diff = path.and_then(|path| {
+        let cache_key = match source {
+            DiffSource::Unstaged => DiffCacheKey::working_dir(&path),
+            DiffSource::Staged => DiffCacheKey::staged(&path),
+        };
 
         // Essayer de récupérer du cache
         if let Some(cached_diff) = state.diff_cache.get(&cache_key) {
-            state.staging_state.current_diff = Some(cached_diff.clone());
-        } else {
-            // Calculer et mettre en cache
-            match crate::git::diff::working_dir_file_diff(&state.repo.repo, &file.path) {
-                Ok(diff) => {
-                    state.diff_cache.put(cache_key, diff.clone());
-                    state.staging_state.current_diff = Some(diff);
-                }
-                Err(_) => {
-                    state.staging_state.current_diff = None;
-                }
-            }
+            return Some(cached_diff.clone());
         }
+
+        // Calculer et mettre en cache
+        let diff = match source {
+            DiffSource::Unstaged => crate::git::diff::unstaged_file_diff(&state.repo.repo, &path),
+            DiffSource::Staged => crate::git::diff::staged_file_diff(&state.repo.repo, &path),
+        }
+        .ok()?;
+        state.diff_cache.put(cache_key, diff.clone());
+        Some(diff)
+    });
+
+    if same_diff {
+        state.staging_state.move_diff_cursor(0);
     } else {
-        state.staging_state.current_diff = None;
+        state.staging_state.reset_diff_cursor();
     }
 }
 
@@ -252,6 +717,7 @@ mod tests {
     use super::*;
     use crate::git::repo::GitRepo;
     use crate::git::repo::StatusEntry;
+    use crate::git::tests::test_utils::commit_file;
     use tempfile::TempDir;
 
     /// Setup un repo temporaire pour les tests.
@@ -336,13 +802,45 @@ mod tests {
         assert_eq!(state.staging_state.focus, StagingFocus::CommitMessage);
     }
 
+    #[test]
+    fn test_start_commit_loads_template() {
+        let (dir, repo) = setup_test_repo();
+        create_test_file(&dir, ".gitmessage", "\n\n; Pourquoi ?\n");
+        let mut config = repo.repo.config().unwrap();
+        config.set_str("commit.template", ".gitmessage").unwrap();
+        config.set_str("core.commentChar", ";").unwrap();
+        let mut state = AppState::new(repo, dir.path().to_string_lossy().to_string()).unwrap();
+        state.view_mode = ViewMode::Staging;
+
+        let mut handler = StagingHandler;
+        let mut ctx = HandlerContext { state: &mut state };
+        handler
+            .handle(&mut ctx, StagingAction::StartCommitMessage)
+            .unwrap();
+
+        // Le modèle est chargé, curseur sur le sujet
+        let editor = &state.staging_state.commit_editor;
+        assert_eq!(editor.lines(), ["", "", "; Pourquoi ?"]);
+        assert_eq!(editor.cursor(), (0, 0));
+
+        // Le message vérifié et enregistré perd les commentaires du modèle
+        state
+            .staging_state
+            .commit_editor
+            .insert_str("feat: ajoute le lint");
+        assert_eq!(
+            state.staging_state.cleaned_message().unwrap(),
+            "feat: ajoute le lint\n"
+        );
+    }
+
     #[test]
     fn test_cancel_commit() {
         let (dir, repo) = setup_test_repo();
         let mut state = AppState::new(repo, dir.path().to_string_lossy().to_string()).unwrap();
         state.view_mode = ViewMode::Staging;
         state.staging_state.is_committing = true;
-        state.staging_state.commit_message = "Test message".to_string();
+        state.staging_state.commit_editor = CommitEditor::from_text("Test message");
         state.staging_state.focus = StagingFocus::CommitMessage;
 
         let mut handler = StagingHandler;
@@ -354,7 +852,7 @@ mod tests {
 
         drop(ctx);
         assert!(!state.staging_state.is_committing);
-        assert!(state.staging_state.commit_message.is_empty());
+        assert!(state.staging_state.commit_editor.is_empty());
         assert_eq!(state.staging_state.focus, StagingFocus::Unstaged);
     }
 
@@ -364,7 +862,7 @@ mod tests {
         let mut state = AppState::new(repo, dir.path().to_string_lossy().to_string()).unwrap();
         state.view_mode = ViewMode::Staging;
         state.staging_state.is_committing = true;
-        state.staging_state.commit_message = "".to_string();
+        state.staging_state.commit_editor = CommitEditor::new();
 
         let mut handler = StagingHandler;
         let mut ctx = HandlerContext { state: &mut state };
@@ -378,6 +876,57 @@ mod tests {
         assert!(state.staging_state.is_committing);
     }
 
+    #[test]
+    fn test_conventional_picker_rewrites_subject() {
+        let (dir, repo) = setup_test_repo();
+        {
+            let sig = git2::Signature::now("Test", "test@test.com").unwrap();
+            let head = repo.repo.head().unwrap().peel_to_commit().unwrap();
+            repo.repo
+                .commit(
+                    Some("HEAD"),
+                    &sig,
+                    &sig,
+                    "fix(ui): corrige le rendu",
+                    &head.tree().unwrap(),
+                    &[&head],
+                )
+                .unwrap();
+        }
+        let mut state = AppState::new(repo, dir.path().to_string_lossy().to_string()).unwrap();
+        state.graph = state
+            .repo
+            .graph_walk(None)
+            .unwrap()
+            .next_page(10)
+            .unwrap()
+            .0;
+        state.view_mode = ViewMode::Staging;
+        state.staging_state.is_committing = true;
+        state.staging_state.commit_editor = CommitEditor::from_text("Corrige le picker\n\nCorps");
+
+        let mut handler = StagingHandler;
+        let mut ctx = HandlerContext { state: &mut state };
+        for action in [
+            StagingAction::ConventionalOpen,
+            StagingAction::ConventionalInsertChar('f'),
+            StagingAction::ConventionalInsertChar('i'),
+            StagingAction::ConventionalConfirm,
+            // "(sans scope)" puis le scope appris de l'historique
+            StagingAction::ConventionalNext,
+            StagingAction::ConventionalConfirm,
+        ] {
+            handler.handle(&mut ctx, action).unwrap();
+        }
+
+        // Le type et le scope sont insérés, le corps conservé
+        assert!(state.staging_state.conventional_picker.is_none());
+        assert_eq!(
+            state.staging_state.commit_editor.text(),
+            "fix(ui): Corrige le picker\n\nCorps"
+        );
+    }
+
     #[test]
     fn test_stage_all_moves_files_to_staged() {
         let (dir, repo) = setup_test_repo();
@@ -521,7 +1070,7 @@ mod tests {
         let mut state = AppState::new(repo, dir.path().to_string_lossy().to_string()).unwrap();
         state.view_mode = ViewMode::Staging;
         state.staging_state.is_committing = true;
-        state.staging_state.commit_message = "Test commit".to_string();
+        state.staging_state.commit_editor = CommitEditor::from_text("Test commit");
 
         // Rafraîchir pour voir les fichiers stagés
         refresh_staging(&mut state).unwrap();
@@ -542,6 +1091,320 @@ mod tests {
 
         // L'état devrait être réinitialisé
         assert!(!state.staging_state.is_committing);
-        assert!(state.staging_state.commit_message.is_empty());
+        assert!(state.staging_state.commit_editor.is_empty());
+    }
+
+    #[test]
+    fn test_external_editor_commit_can_be_undone() {
+        let (dir, repo) = setup_test_repo();
+        let mut state = AppState::new(repo, dir.path().to_string_lossy().to_string()).unwrap();
+        state.view_mode = ViewMode::Staging;
+        let head = state.repo.repo.head().unwrap().target().unwrap();
+
+        edit_in_editor(
+            &mut state,
+            r#"sh -c 'printf "Depuis l editeur\n" > "$1"' editor"#,
+        );
+        let commit = state.repo.repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(commit.summary(), Some("Depuis l editeur"));
+
+        let outcome = state
+            .undo_history
+            .undo(&state.repo.repo)
+            .unwrap()
+            .expect("le commit devrait être annulable");
+        assert_eq!(outcome.label, "commit");
+        assert_eq!(state.repo.repo.head().unwrap().target(), Some(head));
+    }
+
+    #[test]
+    fn test_rejected_commit_records_no_snapshot() {
+        let (dir, repo) = setup_test_repo();
+        let mut state = AppState::new(repo, dir.path().to_string_lossy().to_string()).unwrap();
+        state.view_mode = ViewMode::Staging;
+        edit_in_editor(
+            &mut state,
+            r#"sh -c 'printf "Depuis l editeur\n" > "$1"' editor"#,
+        );
+        state.undo_history.undo(&state.repo.repo).unwrap().unwrap();
+
+        // Le message vide est refusé avant toute écriture : aucun snapshot n'est pris,
+        // et le commit annulé peut toujours être rétabli.
+        state.staging_state.is_committing = true;
+        state.staging_state.commit_editor.set_text("   \n\n");
+        let mut ctx = HandlerContext { state: &mut state };
+        StagingHandler
+            .handle(&mut ctx, StagingAction::ConfirmCommit)
+            .unwrap();
+
+        let outcome = state
+            .undo_history
+            .redo(&state.repo.repo)
+            .unwrap()
+            .expect("le commit annulé devrait pouvoir être rétabli");
+        assert_eq!(outcome.label, "commit");
+    }
+
+    #[test]
+    fn test_confirm_commit_keeps_hash_lines_without_template() {
+        let (dir, repo) = setup_test_repo();
+        let mut state = AppState::new(repo, dir.path().to_string_lossy().to_string()).unwrap();
+        state.view_mode = ViewMode::Staging;
+        state.staging_state.is_committing = true;
+        state.staging_state.commit_editor =
+            CommitEditor::from_text("#123 Corrige le crash\n\n# Cause : index périmé");
+
+        let mut handler = StagingHandler;
+        let mut ctx = HandlerContext { state: &mut state };
+        handler
+            .handle(&mut ctx, StagingAction::ConfirmCommit)
+            .unwrap();
+
+        let commit = state.repo.repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(
+            commit.message().unwrap(),
+            "#123 Corrige le crash\n\n# Cause : index périmé\n"
+        );
+    }
+
+    #[test]
+    fn test_failing_hook_keeps_commit_message() {
+        let (dir, repo) = setup_test_repo();
+        crate::git::tests::test_utils::install_hook(
+            &repo.repo.path().join("hooks"),
+            "pre-commit",
+            "echo 'clippy: 1 warning'; exit 1",
+        );
+        create_test_file(&dir, "new_file.txt", "new content");
+        crate::git::commit::stage_all(&repo.repo).unwrap();
+
+        let mut state = AppState::new(repo, dir.path().to_string_lossy().to_string()).unwrap();
+        state.view_mode = ViewMode::Staging;
+        state.staging_state.is_committing = true;
+        state.staging_state.focus = StagingFocus::CommitMessage;
+        state.staging_state.commit_editor = CommitEditor::from_text("Test commit");
+        let head = state.repo.repo.head().unwrap().target();
+
+        let mut handler = StagingHandler;
+        let mut ctx = HandlerContext { state: &mut state };
+        handler
+            .handle(&mut ctx, StagingAction::ConfirmCommit)
+            .unwrap();
+
+        // Commit annulé : la sortie du hook est affichée, le message conservé
+        assert_eq!(state.repo.repo.head().unwrap().target(), head);
+        let output = state.command_output.take().expect("popup du hook");
+        assert!(!output.success);
+        assert_eq!(output.lines, vec!["clippy: 1 warning".to_string()]);
+        assert_eq!(state.staging_state.commit_editor.text(), "Test commit");
+
+        // En ignorant les hooks, le commit est créé
+        let mut ctx = HandlerContext { state: &mut state };
+        handler
+            .handle(&mut ctx, StagingAction::ToggleSkipHooks)
+            .unwrap();
+        handler
+            .handle(&mut ctx, StagingAction::ConfirmCommit)
+            .unwrap();
+        assert_ne!(state.repo.repo.head().unwrap().target(), head);
+        assert!(state.command_output.is_none());
+        assert!(!state.staging_state.skip_hooks);
+    }
+
+    #[test]
+    fn test_signing_failure_keeps_commit_message() {
+        let (dir, repo) = setup_test_repo();
+        let mut config = repo.repo.config().unwrap();
+        config.set_bool("commit.gpgsign", true).unwrap();
+        config.set_str("gpg.format", "ssh").unwrap();
+        create_test_file(&dir, "new_file.txt", "new content");
+        crate::git::commit::stage_all(&repo.repo).unwrap();
+
+        let mut state = AppState::new(repo, dir.path().to_string_lossy().to_string()).unwrap();
+        state.view_mode = ViewMode::Staging;
+        state.staging_state.is_committing = true;
+        state.staging_state.focus = StagingFocus::CommitMessage;
+        state.staging_state.commit_editor = CommitEditor::from_text("Test commit");
+        let head = state.repo.repo.head().unwrap().target();
+
+        let mut ctx = HandlerContext { state: &mut state };
+        StagingHandler
+            .handle(&mut ctx, StagingAction::ConfirmCommit)
+            .unwrap();
+
+        assert_eq!(state.repo.repo.head().unwrap().target(), head);
+        let (flash, _) = state.flash_message.as_ref().unwrap();
+        assert!(
+            flash.starts_with("Signature du commit impossible"),
+            "{}",
+            flash
+        );
+        assert!(state.staging_state.is_committing);
+        assert_eq!(state.staging_state.commit_editor.text(), "Test commit");
+    }
+
+    #[test]
+    fn test_stage_hunk_from_diff_panel() {
+        let (dir, repo) = setup_test_repo();
+        let lines: Vec<String> = (1..=20).map(|i| format!("line {}", i)).collect();
+        commit_file(
+            &repo.repo,
+            "file.txt",
+            &(lines.join("\n") + "\n"),
+            "Add file",
+        );
+
+        let mut modified = lines.clone();
+        modified[1] = "line 2 modifiée".to_string();
+        modified[17] = "line 18 modifiée".to_string();
+        create_test_file(&dir, "file.txt", &(modified.join("\n") + "\n"));
+
+        let mut state = AppState::new(repo, dir.path().to_string_lossy().to_string()).unwrap();
+        state.view_mode = ViewMode::Staging;
+        refresh_staging(&mut state).unwrap();
+        state.staging_state.focus = StagingFocus::Diff;
+        load_staging_diff(&mut state);
+        assert_eq!(state.staging_state.diff_source, DiffSource::Unstaged);
+
+        let mut handler = StagingHandler;
+        let mut ctx = HandlerContext { state: &mut state };
+        handler.handle(&mut ctx, StagingAction::NextHunk).unwrap();
+        handler.handle(&mut ctx, StagingAction::StageHunk).unwrap();
+
+        // Le fichier a des modifications stagées et non stagées
+        assert_eq!(state.staging_state.staged_files().len(), 1);
+        assert_eq!(state.staging_state.unstaged_files().len(), 1);
+        let diff = state.staging_state.current_diff.as_ref().unwrap();
+        assert!(diff
+            .lines
+            .iter()
+            .all(|l| !l.content.contains("line 18 modifiée")));
+    }
+
+    #[test]
+    fn test_stage_stale_hunk_reports_error() {
+        let (dir, repo) = setup_test_repo();
+        commit_file(&repo.repo, "file.txt", "a\n", "Add file");
+        create_test_file(&dir, "file.txt", "a\nb\n");
+
+        let mut state = AppState::new(repo, dir.path().to_string_lossy().to_string()).unwrap();
+        state.view_mode = ViewMode::Staging;
+        refresh_staging(&mut state).unwrap();
+        state.staging_state.focus = StagingFocus::Diff;
+        load_staging_diff(&mut state);
+
+        // Le fichier revient à son état commité : le hunk affiché n'existe plus
+        create_test_file(&dir, "file.txt", "a\n");
+
+        let mut handler = StagingHandler;
+        let mut ctx = HandlerContext { state: &mut state };
+        handler.handle(&mut ctx, StagingAction::StageHunk).unwrap();
+
+        assert!(state
+            .current_flash_message()
+            .is_some_and(|msg| msg.starts_with("Erreur")));
+        assert!(state.staging_state.staged_files().is_empty());
+    }
+
+    #[test]
+    fn test_discard_hunk_rejects_changed_file() {
+        let (dir, repo) = setup_test_repo();
+        let lines: Vec<String> = (1..=20).map(|i| format!("line {}", i)).collect();
+        commit_file(
+            &repo.repo,
+            "file.txt",
+            &(lines.join("\n") + "\n"),
+            "Add file",
+        );
+        let mut modified = lines.clone();
+        modified[17] = "line 18 modifiée".to_string();
+        create_test_file(&dir, "file.txt", &(modified.join("\n") + "\n"));
+
+        let mut state = AppState::new(repo, dir.path().to_string_lossy().to_string()).unwrap();
+        state.view_mode = ViewMode::Staging;
+        refresh_staging(&mut state).unwrap();
+        state.staging_state.focus = StagingFocus::Diff;
+        load_staging_diff(&mut state);
+        {
+            let mut ctx = HandlerContext { state: &mut state };
+            StagingHandler
+                .handle(&mut ctx, StagingAction::DiscardHunk)
+                .unwrap();
+        }
+        assert!(state.pending_confirmation.is_some());
+
+        // Une modification plus haut dans le fichier devient le hunk 0 pendant la confirmation
+        modified[1] = "line 2 modifiée".to_string();
+        create_test_file(&dir, "file.txt", &(modified.join("\n") + "\n"));
+        crate::handler::ActionDispatcher::new()
+            .dispatch(&mut state, crate::state::action::AppAction::ConfirmAction)
+            .unwrap();
+
+        assert!(state
+            .current_flash_message()
+            .is_some_and(|msg| msg.starts_with("Erreur")));
+        let content = std::fs::read_to_string(dir.path().join("file.txt")).unwrap();
+        assert!(content.contains("line 2 modifiée"));
+        assert!(content.contains("line 18 modifiée"));
+    }
+
+    #[test]
+    fn test_stage_selected_lines_rejects_changed_file() {
+        let (dir, repo) = setup_test_repo();
+        commit_file(&repo.repo, "file.txt", "a\nb\n", "Add file");
+        create_test_file(&dir, "file.txt", "a\nb\nc\nd\n");
+
+        let mut state = AppState::new(repo, dir.path().to_string_lossy().to_string()).unwrap();
+        state.view_mode = ViewMode::Staging;
+        refresh_staging(&mut state).unwrap();
+        state.staging_state.focus = StagingFocus::Diff;
+        load_staging_diff(&mut state);
+        state.staging_state.move_diff_cursor(3);
+
+        // Une ligne est insérée avant la sélection : la ligne 3 n'est plus "+c"
+        create_test_file(&dir, "file.txt", "a\nb\nx\nc\nd\n");
+
+        let mut handler = StagingHandler;
+        let mut ctx = HandlerContext { state: &mut state };
+        handler
+            .handle(&mut ctx, StagingAction::ToggleLineSelection)
+            .unwrap();
+        handler
+            .handle(&mut ctx, StagingAction::StageSelectedLines)
+            .unwrap();
+
+        assert!(state
+            .current_flash_message()
+            .is_some_and(|msg| msg.starts_with("Erreur")));
+        assert!(state.staging_state.staged_files().is_empty());
+    }
+
+    #[test]
+    fn test_stage_selected_lines_from_visual_selection() {
+        let (dir, repo) = setup_test_repo();
+        commit_file(&repo.repo, "file.txt", "a\nb\n", "Add file");
+        create_test_file(&dir, "file.txt", "a\nb\nc\nd\n");
+
+        let mut state = AppState::new(repo, dir.path().to_string_lossy().to_string()).unwrap();
+        state.view_mode = ViewMode::Staging;
+        refresh_staging(&mut state).unwrap();
+        state.staging_state.focus = StagingFocus::Diff;
+        load_staging_diff(&mut state);
+
+        // Lignes : 0 en-tête, 1 " a", 2 " b", 3 "+c", 4 "+d"
+        state.staging_state.move_diff_cursor(3);
+        let mut handler = StagingHandler;
+        let mut ctx = HandlerContext { state: &mut state };
+        handler
+            .handle(&mut ctx, StagingAction::ToggleLineSelection)
+            .unwrap();
+        handler
+            .handle(&mut ctx, StagingAction::StageSelectedLines)
+            .unwrap();
+
+        assert!(state.staging_state.diff_selection_anchor.is_none());
+        let staged = crate::git::diff::staged_file_diff(&state.repo.repo, "file.txt").unwrap();
+        assert_eq!(staged.additions, 1);
+        assert!(staged.lines.iter().any(|l| l.content == "c"));
     }
 }
@@ -0,0 +1,701 @@
+//! Chargement des données du repository en arrière-plan.
+//!
+//! Le graphe, le status et les branches sont calculés dans un thread dédié
+//! qui possède sa propre instance de `GitRepo`. Les résultats sont renvoyés
+//! à la boucle principale via un channel, ce qui garde l'interface réactive
+//! pendant les chargements longs.
+//!
+//! Chaque demande précise les catégories de changements à recharger. Seule la
+//! demande la plus récente est traitée : si plusieurs rafraîchissements sont
+//! demandés pendant un chargement, leurs catégories sont fusionnées.
+//!
+//! Le thread conserve aussi le parcours de l'historique du dernier graphe
+//! chargé : les pages suivantes le reprennent là où il s'était arrêté. Les
+//! demandes étant traitées dans l'ordre, une page s'applique toujours au
+//! graphe qui la précède dans le flux de résultats.
+//!
+//! Si les références n'ont pas bougé, un rafraîchissement ne reconstruit que la
+//! première page et l'interface la raccorde aux rangées déjà chargées. Sinon toutes
+//! les rangées affichées sont reconstruites : les commits apparus plus bas dans
+//! l'historique y figurent et les colonnes restent continues.
+
+use git2::{Oid, Repository};
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+use crate::error::{GitSvError, Result};
+use crate::git::branch::BranchInfo;
+use crate::git::diff::DiffFile;
+use crate::git::graph::{GraphRow, GraphWalk, RefInfo};
+use crate::git::remote::RemoteInfo;
+use crate::git::repo::{GitRepo, StatusEntry};
+use crate::git::stash::StashEntry;
+use crate::git::tag::TagInfo;
+use crate::git::worktree::WorktreeInfo;
+use crate::state::GraphFilter;
+use crate::watcher::RepoChanges;
+
+/// Demande de rafraîchissement envoyée au thread de chargement.
+#[derive(Debug, Clone)]
+pub struct RefreshRequest {
+    /// Catégories de données à recharger.
+    pub changes: RepoChanges,
+    /// Taille de la première page du graphe.
+    pub graph_count: usize,
+    /// Nombre de rangées du graphe affichées au moment de la demande.
+    pub loaded_rows: usize,
+    /// Filtres actifs sur le graphe (s'il y en a).
+    pub filter: Option<GraphFilter>,
+    /// Charger aussi les branches, worktrees, stashes, tags et remotes.
+    pub include_branches: bool,
+    /// Commit sélectionné dans le graphe au moment de la demande.
+    pub selected_oid: Option<Oid>,
+    /// Position de la sélection dans le graphe au moment de la demande.
+    pub selected_index: usize,
+}
+
+impl RefreshRequest {
+    /// Fusionne une demande plus ancienne, non traitée, dans celle-ci.
+    fn absorb(&mut self, older: &RefreshRequest) {
+        self.changes |= older.changes;
+        self.include_branches |= older.include_branches;
+    }
+}
+
+/// Graphe chargé (première page ou page suivante de l'historique).
+#[derive(Debug, Default)]
+pub struct GraphLoad {
+    /// Rangées du graphe.
+    pub rows: Vec<GraphRow>,
+    /// Reste-t-il des commits à charger?
+    pub has_more: bool,
+    /// Références de chaque commit, si les rangées déjà chargées au-delà de cette
+    /// première page restent valables (seules leurs références sont à mettre à jour,
+    /// HEAD ayant pu changer de branche).
+    pub tail_refs: Option<HashMap<Oid, Vec<RefInfo>>>,
+    /// Fichiers du commit qui sera sélectionné, s'il diffère de la sélection demandée.
+    pub selected_files: Option<(Oid, Vec<DiffFile>)>,
+}
+
+/// Données de la vue branches.
+#[derive(Debug)]
+pub struct BranchesLoad {
+    /// Branches locales et distantes.
+    pub branches: Result<(Vec<BranchInfo>, Vec<BranchInfo>)>,
+    /// Worktrees (None en cas d'erreur).
+    pub worktrees: Option<Vec<WorktreeInfo>>,
+    /// Stashes (None en cas d'erreur).
+    pub stashes: Option<Vec<StashEntry>>,
+    /// Tags (None en cas d'erreur).
+    pub tags: Option<Vec<TagInfo>>,
+    /// Remotes (None en cas d'erreur).
+    pub remotes: Option<Vec<RemoteInfo>>,
+}
+
+/// Résultat d'un rafraîchissement.
+#[derive(Debug)]
+pub struct RefreshResult {
+    /// Numéro de la demande ayant produit ce résultat.
+    pub generation: u64,
+    /// Branche courante.
+    pub current_branch: Option<String>,
+    /// Graphe de commits (si les références ont changé).
+    pub graph: Option<GraphLoad>,
+    /// Status du working directory (si l'index ou l'arbre de travail ont changé).
+    pub status: Option<Vec<StatusEntry>>,
+    /// Données de la vue branches (si demandées).
+    pub branches: Option<BranchesLoad>,
+}
+
+/// Demande envoyée au thread de chargement.
+#[derive(Debug)]
+enum LoaderRequest {
+    /// Rafraîchissement numéroté.
+    Refresh(u64, RefreshRequest),
+    /// Page suivante du graphe courant, de la taille donnée, après les rangées affichées.
+    NextPage { page_size: usize, loaded: usize },
+}
+
+/// Résultat renvoyé par le thread de chargement.
+#[derive(Debug)]
+pub enum LoaderResult {
+    /// Résultat d'un rafraîchissement.
+    Refresh(Box<RefreshResult>),
+    /// Page suivante du graphe, à ajouter à la fin des rangées actuelles.
+    Page(Result<GraphLoad>),
+}
+
+/// Chargeur asynchrone des données du repository.
+pub struct RepoLoader {
+    /// Canal d'envoi des demandes vers le thread de chargement.
+    requests: Sender<LoaderRequest>,
+    /// Canal de réception des résultats.
+    results: Receiver<LoaderResult>,
+    /// Numéro de la dernière demande envoyée.
+    requested: u64,
+    /// Numéro du dernier résultat reçu.
+    received: u64,
+}
+
+impl RepoLoader {
+    /// Démarre le thread de chargement pour le repository donné.
+    pub fn new(repo_path: &str) -> Result<Self> {
+        // Le thread possède ses propres instances : git2::Repository n'est pas Sync.
+        // La seconde sert au parcours paginé de l'historique, qui l'emprunte.
+        let repo = GitRepo::open(repo_path)?;
+        let walk_repo = GitRepo::open(repo_path)?;
+        let (request_tx, request_rx) = mpsc::channel();
+        let (result_tx, result_rx) = mpsc::channel();
+
+        thread::Builder::new()
+            .name("git_sv-loader".to_string())
+            .spawn(move || run_worker(repo, &walk_repo, request_rx, result_tx))
+            .map_err(|e| GitSvError::Io {
+                source: e,
+                context: "démarrage du thread de chargement".to_string(),
+            })?;
+
+        Ok(Self {
+            requests: request_tx,
+            results: result_rx,
+            requested: 0,
+            received: 0,
+        })
+    }
+
+    /// Envoie une demande de rafraîchissement.
+    pub fn request(&mut self, request: RefreshRequest) -> Result<()> {
+        self.requested += 1;
+        self.send(LoaderRequest::Refresh(self.requested, request))
+    }
+
+    /// Demande la page suivante du dernier graphe chargé, qui compte `loaded` rangées.
+    pub fn request_page(&mut self, page_size: usize, loaded: usize) -> Result<()> {
+        self.send(LoaderRequest::NextPage { page_size, loaded })
+    }
+
+    fn send(&self, request: LoaderRequest) -> Result<()> {
+        self.requests
+            .send(request)
+            .map_err(|_| GitSvError::Other("Le thread de chargement est arrêté".to_string()))
+    }
+
+    /// Récupère le prochain résultat disponible, sans bloquer.
+    ///
+    /// Les résultats sont partiels : ils doivent tous être appliqués, dans l'ordre.
+    pub fn try_recv(&mut self) -> Option<LoaderResult> {
+        let result = self.results.try_recv().ok()?;
+        if let LoaderResult::Refresh(ref refresh) = result {
+            self.received = refresh.generation;
+        }
+        Some(result)
+    }
+
+    /// Un chargement est-il en cours?
+    pub fn is_loading(&self) -> bool {
+        self.received < self.requested
+    }
+}
+
+/// Boucle du thread de chargement.
+fn run_worker(
+    mut repo: GitRepo,
+    walk_repo: &GitRepo,
+    requests: Receiver<LoaderRequest>,
+    results: Sender<LoaderResult>,
+) {
+    // Parcours de l'historique du dernier graphe chargé.
+    let mut walk: Option<GraphWalk> = None;
+    let mut history: Option<LoadedHistory> = None;
+
+    while let Ok(first) = requests.recv() {
+        let pending: Vec<LoaderRequest> =
+            std::iter::once(first).chain(requests.try_iter()).collect();
+
+        // Ne traiter que le rafraîchissement le plus récent, avec les catégories des
+        // précédents. Les pages sont servies dans l'ordre de leur demande.
+        let is_refresh = |r: &LoaderRequest| matches!(r, LoaderRequest::Refresh(..));
+        let pages_before = pending
+            .iter()
+            .rposition(is_refresh)
+            .map(|last| pending[..last].iter().filter(|r| !is_refresh(r)).count());
+        let mut merged: Option<(u64, RefreshRequest)> = None;
+        let mut ordered = Vec::with_capacity(pending.len());
+        for request in pending {
+            match request {
+                LoaderRequest::Refresh(generation, mut request) => {
+                    if let Some((_, older)) = merged.take() {
+                        request.absorb(&older);
+                    }
+                    merged = Some((generation, request));
+                }
+                page => ordered.push(page),
+            }
+        }
+        if let (Some(index), Some((generation, request))) = (pages_before, merged) {
+            ordered.insert(index, LoaderRequest::Refresh(generation, request));
+        }
+        let pending = ordered;
+
+        for request in pending {
+            let result = match request {
+                LoaderRequest::Refresh(generation, request) => {
+                    let mut graph = GraphSource {
+                        walk_repo,
+                        walk: &mut walk,
+                        history: &mut history,
+                    };
+                    LoaderResult::Refresh(Box::new(load(
+                        &mut repo, &mut graph, generation, &request,
+                    )))
+                }
+                LoaderRequest::NextPage { page_size, loaded } => {
+                    LoaderResult::Page(load_page(walk.as_mut(), page_size, loaded))
+                }
+            };
+            if results.send(result).is_err() {
+                // L'interface a été fermée.
+                return;
+            }
+        }
+    }
+}
+
+/// Parcours de l'historique conservé par le thread entre deux demandes.
+struct GraphSource<'a, 'repo> {
+    /// Instance du repository empruntée par le parcours.
+    walk_repo: &'repo GitRepo,
+    /// Parcours du dernier graphe chargé.
+    walk: &'a mut Option<GraphWalk<'repo>>,
+    /// Origine de l'historique du dernier graphe chargé.
+    history: &'a mut Option<LoadedHistory>,
+}
+
+/// Origine de l'historique du dernier graphe chargé.
+#[derive(Debug, PartialEq)]
+struct LoadedHistory {
+    /// Commit visé par chaque référence de départ du parcours.
+    refs: HashMap<String, Oid>,
+    /// Filtres appliqués.
+    filter: Option<GraphFilter>,
+}
+
+impl LoadedHistory {
+    /// Relève les références dont part le parcours de l'historique.
+    fn read(repo: &Repository, filter: Option<GraphFilter>) -> Result<Self> {
+        let mut refs = HashMap::new();
+        for reference in repo.references()? {
+            let reference = reference?;
+            if let (Some(name), Some(oid)) = (reference.name(), reference.target()) {
+                refs.insert(name.to_string(), oid);
+            }
+        }
+        Ok(Self { refs, filter })
+    }
+}
+
+/// Charge toutes les données demandées depuis le repository.
+fn load(
+    repo: &mut GitRepo,
+    source: &mut GraphSource,
+    generation: u64,
+    request: &RefreshRequest,
+) -> RefreshResult {
+    let changes = request.changes;
+    let current_branch = repo.current_branch().ok();
+    let graph = changes.refs.then(|| {
+        let mut graph = load_graph(source, request).unwrap_or_default();
+        graph.selected_files = load_selected_files(repo, &graph, request);
+        graph
+    });
+    let status = changes
+        .affects_status()
+        .then(|| repo.status().unwrap_or_default());
+
+    let branches = if request.include_branches && (changes.refs || changes.stash) {
+        Some(BranchesLoad {
+            branches: crate::git::branch::list_all_branches(&repo.repo),
+            worktrees: crate::git::worktree::list_worktrees(&repo.repo).ok(),
+            stashes: crate::git::stash::list_stashes(&mut repo.repo).ok(),
+            tags: crate::git::tag::list_tags(&repo.repo).ok(),
+            remotes: crate::git::remote::list_remotes(&repo.repo).ok(),
+        })
+    } else {
+        None
+    };
+
+    RefreshResult {
+        generation,
+        current_branch,
+        graph,
+        status,
+        branches,
+    }
+}
+
+/// Construit la première page du graphe, avec ou sans filtres.
+///
+/// Si les références et les filtres n'ont pas changé depuis le chargement précédent,
+/// les rangées suivantes déjà chargées restent valables : seule la première page est
+/// reconstruite. Sinon toutes les rangées affichées (et au moins jusqu'au commit
+/// sélectionné) sont reconstruites, les nouveaux commits pouvant s'y intercaler et
+/// décaler les colonnes.
+fn load_graph(source: &mut GraphSource, request: &RefreshRequest) -> Result<GraphLoad> {
+    *source.walk = None;
+    let history = LoadedHistory::read(&source.walk_repo.repo, request.filter.clone())?;
+    let keeps_tail = source.history.as_ref() == Some(&history);
+    *source.history = Some(history);
+
+    let count = if keeps_tail {
+        request.graph_count
+    } else {
+        request
+            .graph_count
+            .max(request.loaded_rows)
+            .max(request.selected_index + 1)
+    };
+    let walk = source
+        .walk
+        .insert(source.walk_repo.graph_walk(request.filter.clone())?);
+    let (rows, has_more) = walk.next_page(count)?;
+    Ok(GraphLoad {
+        rows,
+        has_more,
+        tail_refs: (keeps_tail && has_more).then(|| walk.refs().clone()),
+        selected_files: None,
+    })
+}
+
+/// Charge la page suivante du parcours en cours, après les `loaded` rangées affichées.
+fn load_page(walk: Option<&mut GraphWalk>, page_size: usize, loaded: usize) -> Result<GraphLoad> {
+    match walk {
+        Some(walk) => {
+            // Rangées conservées lors du dernier rafraîchissement : le parcours les saute.
+            while walk.loaded() < loaded {
+                let (_, has_more) = walk.next_page(loaded - walk.loaded())?;
+                if !has_more {
+                    break;
+                }
+            }
+            let (rows, has_more) = walk.next_page(page_size)?;
+            Ok(GraphLoad {
+                rows,
+                has_more,
+                ..GraphLoad::default()
+            })
+        }
+        None => Ok(GraphLoad::default()),
+    }
+}
+
+/// Charge les fichiers du commit qui sera sélectionné dans le nouveau graphe.
+///
+/// Reprend la règle de l'interface : la sélection suit son commit, sinon elle est
+/// bornée au graphe. Rien n'est chargé si le commit sélectionné ne change pas.
+fn load_selected_files(
+    repo: &GitRepo,
+    graph: &GraphLoad,
+    request: &RefreshRequest,
+) -> Option<(Oid, Vec<DiffFile>)> {
+    let rows = &graph.rows;
+    let in_rows = request
+        .selected_oid
+        .is_some_and(|oid| rows.iter().any(|row| row.node.oid == oid));
+    // La sélection se trouve dans les rangées conservées après la première page.
+    if graph.tail_refs.is_some() && !in_rows && request.selected_index >= rows.len() {
+        return None;
+    }
+    let index = request
+        .selected_oid
+        .and_then(|oid| rows.iter().position(|row| row.node.oid == oid))
+        .unwrap_or_else(|| request.selected_index.min(rows.len().saturating_sub(1)));
+    let oid = rows.get(index)?.node.oid;
+    if Some(oid) == request.selected_oid {
+        return None;
+    }
+    Some((oid, repo.commit_diff(oid).unwrap_or_default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::tests::test_utils::*;
+    use std::time::{Duration, Instant};
+
+    /// Attend un résultat du chargeur (avec timeout).
+    fn wait_any(loader: &mut RepoLoader) -> LoaderResult {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            if let Some(result) = loader.try_recv() {
+                return result;
+            }
+            assert!(Instant::now() < deadline, "Aucun résultat du chargeur");
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    /// Attend le résultat d'un rafraîchissement.
+    fn wait_result(loader: &mut RepoLoader) -> RefreshResult {
+        match wait_any(loader) {
+            LoaderResult::Refresh(result) => *result,
+            other => panic!("Rafraîchissement attendu, reçu {:?}", other),
+        }
+    }
+
+    /// Attend une page du graphe.
+    fn wait_page(loader: &mut RepoLoader) -> GraphLoad {
+        match wait_any(loader) {
+            LoaderResult::Page(page) => page.unwrap(),
+            other => panic!("Page attendue, reçu {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_loader_loads_graph_and_status() {
+        let (_temp_dir, repo) = create_test_repo();
+        commit_file(&repo, "a.txt", "A", "First");
+        commit_file(&repo, "a.txt", "B", "Second");
+        create_file(&repo, "b.txt", "untracked");
+
+        let mut loader = RepoLoader::new(repo.workdir().unwrap().to_str().unwrap()).unwrap();
+        assert!(!loader.is_loading());
+
+        loader
+            .request(RefreshRequest {
+                changes: RepoChanges::ALL,
+                graph_count: 10,
+                loaded_rows: 0,
+                filter: None,
+                include_branches: true,
+                selected_oid: None,
+                selected_index: 0,
+            })
+            .unwrap();
+        assert!(loader.is_loading());
+
+        let result = wait_result(&mut loader);
+        assert!(!loader.is_loading());
+        assert_eq!(result.current_branch.as_deref(), Some("main"));
+        let graph = result.graph.expect("Le graphe devrait être chargé");
+        assert_eq!(graph.rows.len(), 2);
+        assert!(!graph.has_more);
+        assert_eq!(result.status.map(|s| s.len()), Some(1));
+        let (oid, files) = graph.selected_files.expect("Fichiers du premier commit");
+        assert_eq!(oid, graph.rows[0].node.oid);
+        assert_eq!(files.len(), 1);
+
+        let branches = result
+            .branches
+            .expect("Les branches devraient être chargées");
+        let (local, _remote) = branches.branches.unwrap();
+        assert_eq!(local.len(), 1);
+    }
+
+    #[test]
+    fn test_loader_paginates_graph() {
+        let (_temp_dir, repo) = create_test_repo();
+        for i in 0..3 {
+            commit_file(&repo, "a.txt", &i.to_string(), &format!("Commit {}", i));
+        }
+
+        let mut loader = RepoLoader::new(repo.workdir().unwrap().to_str().unwrap()).unwrap();
+        loader
+            .request(RefreshRequest {
+                changes: RepoChanges::ALL,
+                graph_count: 2,
+                loaded_rows: 0,
+                filter: None,
+                include_branches: false,
+                selected_oid: None,
+                selected_index: 0,
+            })
+            .unwrap();
+
+        let result = wait_result(&mut loader);
+        let graph = result.graph.expect("Le graphe devrait être chargé");
+        assert_eq!(graph.rows.len(), 2);
+        assert!(graph.has_more);
+        assert!(result.branches.is_none());
+
+        // La page suivante reprend le parcours après les commits déjà chargés
+        loader.request_page(2, 2).unwrap();
+        let page = wait_page(&mut loader);
+        assert_eq!(page.rows.len(), 1);
+        assert_eq!(page.rows[0].node.message, "Commit 0");
+        assert!(!page.has_more);
+        assert!(page.rows[0].connection.is_none());
+    }
+
+    /// Demande de rafraîchissement complet du graphe, `loaded_rows` rangées étant affichées.
+    fn graph_request(
+        graph_count: usize,
+        loaded_rows: usize,
+        selected_index: usize,
+    ) -> RefreshRequest {
+        RefreshRequest {
+            changes: RepoChanges::ALL,
+            graph_count,
+            loaded_rows,
+            filter: None,
+            include_branches: false,
+            selected_oid: None,
+            selected_index,
+        }
+    }
+
+    fn messages(rows: &[GraphRow]) -> Vec<&str> {
+        rows.iter().map(|row| row.node.message.as_str()).collect()
+    }
+
+    #[test]
+    fn test_refresh_reloads_only_the_first_page() {
+        let (_temp_dir, repo) = create_test_repo();
+        for i in 0..5 {
+            commit_file(&repo, "a.txt", &i.to_string(), &format!("Commit {}", i));
+        }
+
+        let mut loader = RepoLoader::new(repo.workdir().unwrap().to_str().unwrap()).unwrap();
+        loader.request(graph_request(2, 0, 0)).unwrap();
+        let graph = wait_result(&mut loader).graph.unwrap();
+        assert!(graph.tail_refs.is_none());
+        loader.request_page(2, 2).unwrap();
+        assert_eq!(
+            messages(&wait_page(&mut loader).rows),
+            ["Commit 2", "Commit 1"]
+        );
+
+        // Références inchangées : seule la première page est reconstruite, avec les
+        // références des rangées suivantes
+        loader.request(graph_request(2, 4, 3)).unwrap();
+        let graph = wait_result(&mut loader).graph.unwrap();
+        assert_eq!(messages(&graph.rows), ["Commit 4", "Commit 3"]);
+        assert!(graph.selected_files.is_none());
+        assert!(graph.tail_refs.is_some());
+
+        // Le graphe affiché compte 4 rangées : la page suivante reprend après
+        loader.request_page(2, 4).unwrap();
+        let page = wait_page(&mut loader);
+        assert_eq!(messages(&page.rows), ["Commit 0"]);
+        assert!(!page.has_more);
+    }
+
+    #[test]
+    fn test_new_refs_reload_all_loaded_rows() {
+        let (_temp_dir, repo) = create_test_repo();
+        let first = commit_file(&repo, "a.txt", "0", "Commit 0");
+        for i in 1..5 {
+            commit_file(&repo, "a.txt", &i.to_string(), &format!("Commit {}", i));
+        }
+
+        let mut loader = RepoLoader::new(repo.workdir().unwrap().to_str().unwrap()).unwrap();
+        loader.request(graph_request(2, 0, 0)).unwrap();
+        wait_result(&mut loader);
+        loader.request_page(2, 2).unwrap();
+        wait_page(&mut loader);
+
+        // Branche partant d'un commit sous la première page, et tag sur un commit chargé
+        let base = repo.find_commit(first).unwrap();
+        let sig = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let side = repo
+            .commit(None, &sig, &sig, "Side", &base.tree().unwrap(), &[&base])
+            .unwrap();
+        repo.reference("refs/heads/side", side, false, "test")
+            .unwrap();
+        crate::git::tag::create_lightweight_tag(&repo, "v0", first).unwrap();
+
+        // Les 4 rangées affichées sont reconstruites : la branche y figure
+        loader.request(graph_request(2, 4, 3)).unwrap();
+        let graph = wait_result(&mut loader).graph.unwrap();
+        assert!(graph.tail_refs.is_none());
+        assert_eq!(graph.rows.len(), 4);
+        assert!(messages(&graph.rows).contains(&"Side"));
+        assert!(graph.has_more);
+
+        loader.request_page(10, 4).unwrap();
+        let page = wait_page(&mut loader);
+        let rows: Vec<_> = graph.rows.iter().chain(&page.rows).collect();
+        assert_eq!(rows.len(), 6);
+        let tagged = rows.iter().find(|row| row.node.oid == first).unwrap();
+        assert_eq!(tagged.node.refs[0].name, "v0");
+    }
+
+    #[test]
+    fn test_rewritten_history_is_reloaded_up_to_the_selection() {
+        let (_temp_dir, repo) = create_test_repo();
+        for i in 0..5 {
+            commit_file(&repo, "a.txt", &i.to_string(), &format!("Commit {}", i));
+        }
+
+        let mut loader = RepoLoader::new(repo.workdir().unwrap().to_str().unwrap()).unwrap();
+        loader.request(graph_request(2, 0, 0)).unwrap();
+        wait_result(&mut loader);
+
+        // La branche recule : les rangées chargées ne sont plus valables
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        let parent = head.parent(0).unwrap();
+        repo.reset(parent.as_object(), git2::ResetType::Hard, None)
+            .unwrap();
+        loader.request(graph_request(2, 2, 2)).unwrap();
+        let graph = wait_result(&mut loader).graph.unwrap();
+        assert!(graph.tail_refs.is_none());
+        assert_eq!(messages(&graph.rows), ["Commit 3", "Commit 2", "Commit 1"]);
+        assert!(graph.has_more);
+    }
+
+    #[test]
+    fn test_filtered_graph_paginates() {
+        let (_temp_dir, repo) = create_test_repo();
+        for i in 0..5 {
+            let kind = if i % 2 == 0 { "fix" } else { "feat" };
+            commit_file(&repo, "a.txt", &i.to_string(), &format!("{} {}", kind, i));
+        }
+
+        let mut loader = RepoLoader::new(repo.workdir().unwrap().to_str().unwrap()).unwrap();
+        loader
+            .request(RefreshRequest {
+                filter: Some(GraphFilter {
+                    message: Some("fix".to_string()),
+                    ..GraphFilter::default()
+                }),
+                ..graph_request(2, 0, 0)
+            })
+            .unwrap();
+        let graph = wait_result(&mut loader).graph.unwrap();
+        assert_eq!(messages(&graph.rows), ["fix 4", "fix 2"]);
+        assert!(graph.has_more);
+
+        loader.request_page(2, 2).unwrap();
+        let page = wait_page(&mut loader);
+        assert_eq!(messages(&page.rows), ["fix 0"]);
+        assert!(!page.has_more);
+    }
+
+    #[test]
+    fn test_loader_reloads_only_changed_categories() {
+        let (_temp_dir, repo) = create_test_repo();
+        commit_file(&repo, "a.txt", "A", "First");
+        create_file(&repo, "b.txt", "untracked");
+
+        let mut loader = RepoLoader::new(repo.workdir().unwrap().to_str().unwrap()).unwrap();
+        loader
+            .request(RefreshRequest {
+                changes: RepoChanges {
+                    worktree: true,
+                    ..Default::default()
+                },
+                graph_count: 10,
+                loaded_rows: 0,
+                filter: None,
+                include_branches: true,
+                selected_oid: None,
+                selected_index: 0,
+            })
+            .unwrap();
+
+        let result = wait_result(&mut loader);
+        assert!(result.graph.is_none());
+        assert!(result.branches.is_none());
+        assert_eq!(result.status.map(|s| s.len()), Some(1));
+    }
+}
@@ -1,11 +1,16 @@
 mod app;
+mod commands;
+mod config;
+mod conventional;
 mod error;
 mod error_display;
 mod git;
 mod handler;
+mod loader;
 mod state;
 mod terminal;
 mod test_utils;
+mod transfer;
 mod ui;
 mod utils;
 mod watcher;
@@ -42,6 +47,8 @@ fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
 
     let repo = GitRepo::open(&cli.path)?;
+    config::init(config::Config::load(repo.repo.path())?);
+    commands::init(commands::CommandsFile::load()?);
 
     match cli.command {
         Some(Commands::Log { max_count }) => {
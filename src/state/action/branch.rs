@@ -1,4 +1,4 @@
-//! Actions sur les branches, worktrees et stashes.
+//! Actions sur les branches, worktrees, stashes, tags et remotes.
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum BranchAction {
@@ -28,6 +28,30 @@ pub enum BranchAction {
     WorktreeCreate,
     /// Supprimer un worktree
     WorktreeRemove,
+    /// Créer un tag (sur le commit sélectionné dans le graph, sinon sur HEAD)
+    TagCreate,
+    /// Supprimer un tag
+    TagDelete,
+    /// Pousser le tag sélectionné
+    TagPush,
+    /// Pousser tous les tags
+    TagPushAll,
+    /// Pousser la branche sélectionnée vers un remote à choisir
+    PushTo,
+    /// Ajouter un remote
+    RemoteAdd,
+    /// Renommer le remote sélectionné
+    RemoteRename,
+    /// Changer l'URL de fetch du remote sélectionné
+    RemoteSetUrl,
+    /// Changer l'URL de push du remote sélectionné
+    RemoteSetPushUrl,
+    /// Supprimer le remote sélectionné
+    RemoteRemove,
+    /// Fetch du remote sélectionné
+    RemoteFetch,
+    /// Fetch du remote sélectionné en supprimant les branches disparues
+    RemotePrune,
     /// Basculer vers la section suivante
     NextSection,
     /// Basculer vers la section précédente
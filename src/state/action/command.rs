@@ -0,0 +1,14 @@
+//! Actions des commandes personnalisées.
+
+/// Actions des commandes personnalisées.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CommandAction {
+    /// Exécuter la commande personnalisée d'indice donné
+    Run(usize),
+    /// Fermer le popup de sortie
+    CloseOutput,
+    /// Défiler la sortie vers le bas
+    ScrollDown,
+    /// Défiler la sortie vers le haut
+    ScrollUp,
+}
@@ -34,6 +34,12 @@ pub enum ConflictAction {
     FinalizeMerge,
     /// Abandonner le merge
     AbortMerge,
+    /// Continuer le rebase interactif après résolution
+    ContinueRebase,
+    /// Passer le commit en conflit du rebase interactif
+    SkipRebase,
+    /// Abandonner le rebase interactif
+    AbortRebase,
     /// Définir le mode de résolution à Fichier
     SetModeFile,
     /// Définir le mode de résolution à Bloc
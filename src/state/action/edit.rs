@@ -8,14 +8,26 @@ pub enum EditAction {
     DeleteCharBefore,
     /// Supprimer le caractère après le curseur
     DeleteCharAfter,
+    /// Supprimer le mot avant le curseur
+    DeleteWordBefore,
     /// Déplacer le curseur à gauche
     CursorLeft,
     /// Déplacer le curseur à droite
     CursorRight,
+    /// Déplacer le curseur à la ligne précédente
+    CursorUp,
+    /// Déplacer le curseur à la ligne suivante
+    CursorDown,
+    /// Aller au début du mot précédent
+    WordLeft,
+    /// Aller à la fin du mot suivant
+    WordRight,
     /// Aller au début de la ligne
     CursorHome,
     /// Aller à la fin de la ligne
     CursorEnd,
     /// Nouvelle ligne
     NewLine,
+    /// Coller un texte (éventuellement multi-ligne)
+    Paste(String),
 }
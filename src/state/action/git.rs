@@ -4,12 +4,28 @@
 pub enum GitAction {
     /// Push vers le remote
     Push,
-    /// Pull depuis le remote
+    /// Pull depuis le remote (stratégie configurée)
     Pull,
+    /// Ouvrir le sélecteur de stratégie de pull (merge, rebase, ff-only)
+    PullPrompt,
+    /// Stratégie suivante dans le sélecteur de pull
+    PullPickerNext,
+    /// Stratégie précédente dans le sélecteur de pull
+    PullPickerPrevious,
+    /// Lancer le pull avec la stratégie sélectionnée
+    PullPickerConfirm,
+    /// Fermer le sélecteur de pull
+    PullPickerCancel,
     /// Fetch depuis le remote
     Fetch,
+    /// Annuler l'opération distante en cours
+    CancelTransfer,
     /// Cherry-pick un commit
     CherryPick,
+    /// Revert le commit sélectionné
+    Revert,
+    /// Ouvrir le picker de reset sur le commit sélectionné
+    ResetPrompt,
     /// Amender le dernier commit
     AmendCommit,
     /// Ouvrir le blame d'un fichier
@@ -26,4 +42,8 @@ pub enum GitAction {
     MergePrompt,
     /// Lister les branches
     BranchList,
+    /// Annuler la dernière action (restaure le dernier snapshot)
+    Undo,
+    /// Rétablir la dernière action annulée
+    Redo,
 }
@@ -1,20 +1,30 @@
 //! Actions de l'application organisées par domaine.
 
 mod branch;
+mod command;
 mod conflict;
 mod edit;
 mod filter;
 mod git;
 mod navigation;
+mod palette;
+mod push;
+mod rebase;
+mod reflog;
 mod search;
 mod staging;
 
 pub use branch::BranchAction;
+pub use command::CommandAction;
 pub use conflict::ConflictAction;
 pub use edit::EditAction;
 pub use filter::FilterAction;
 pub use git::GitAction;
 pub use navigation::NavigationAction;
+pub use palette::PaletteAction;
+pub use push::PushAction;
+pub use rebase::RebaseAction;
+pub use reflog::ReflogAction;
 pub use search::SearchAction;
 pub use staging::StagingAction;
 
@@ -58,6 +68,21 @@ pub enum AppAction {
     /// Actions de filtrage du graph (nouvelle structure)
     Filter(FilterAction),
 
+    /// Actions du rebase interactif
+    Rebase(RebaseAction),
+
+    /// Actions de la vue reflog
+    Reflog(ReflogAction),
+
+    /// Actions de la palette de commandes
+    Palette(PaletteAction),
+
+    /// Actions du dialogue de push
+    Push(PushAction),
+
+    /// Actions des commandes personnalisées
+    Command(CommandAction),
+
     /// Changer de mode de vue
     SwitchView(ViewMode),
 
@@ -90,6 +115,12 @@ pub enum AppAction {
     MergePickerConfirm,
     MergePickerCancel,
 
+    /// Navigation dans le reset picker
+    ResetPickerUp,
+    ResetPickerDown,
+    ResetPickerConfirm,
+    ResetPickerCancel,
+
     // ═══════════════════════════════════════════════════
     // Variantes legacy pour compatibilité ascendante
     // TODO: Migrer vers les sous-enums et supprimer ces variantes
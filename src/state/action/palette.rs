@@ -0,0 +1,20 @@
+//! Actions de la palette de commandes.
+
+/// Actions de la palette de commandes.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PaletteAction {
+    /// Ouvrir la palette avec les actions du contexte courant
+    Open,
+    /// Fermer la palette sans rien exécuter
+    Close,
+    /// Insérer un caractère dans la recherche
+    InsertChar(char),
+    /// Supprimer le dernier caractère de la recherche
+    Backspace,
+    /// Sélectionner l'action suivante
+    SelectNext,
+    /// Sélectionner l'action précédente
+    SelectPrevious,
+    /// Exécuter l'action sélectionnée
+    Confirm,
+}
@@ -0,0 +1,26 @@
+//! Actions du dialogue de push.
+
+/// Actions du dialogue de push avec options.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PushAction {
+    /// Ouvrir le dialogue pour la branche courante
+    Open,
+    /// Fermer le dialogue sans pousser
+    Close,
+    /// Passer au champ suivant
+    NextField,
+    /// Passer au champ précédent
+    PreviousField,
+    /// Cocher/décocher la case sélectionnée
+    Toggle,
+    /// Sélectionner le remote suivant
+    NextRemote,
+    /// Sélectionner le remote précédent
+    PreviousRemote,
+    /// Insérer un caractère dans la branche distante
+    InsertChar(char),
+    /// Supprimer le dernier caractère de la branche distante
+    Backspace,
+    /// Lancer le push
+    Confirm,
+}
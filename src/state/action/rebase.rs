@@ -0,0 +1,37 @@
+//! Actions du rebase interactif.
+
+use crate::git::rebase::TodoAction;
+
+/// Actions de l'éditeur de rebase interactif.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RebaseAction {
+    /// Ouvrir l'éditeur avec le commit sélectionné comme base
+    /// (ou l'état du rebase en cours).
+    Open,
+    /// Fermer l'éditeur sans lancer le rebase
+    Close,
+    /// Sélectionner l'entrée suivante
+    SelectNext,
+    /// Sélectionner l'entrée précédente
+    SelectPrevious,
+    /// Déplacer l'entrée sélectionnée vers le haut
+    MoveEntryUp,
+    /// Déplacer l'entrée sélectionnée vers le bas
+    MoveEntryDown,
+    /// Changer l'action de l'entrée sélectionnée
+    SetAction(TodoAction),
+    /// Ouvrir l'éditeur du nouveau message (reword), édité par les `EditAction`
+    StartReword,
+    /// Valider le nouveau message
+    ConfirmReword,
+    /// Annuler la saisie du message
+    CancelReword,
+    /// Lancer le rebase avec la todo list
+    Execute,
+    /// Continuer le rebase en cours
+    Continue,
+    /// Passer le commit courant
+    Skip,
+    /// Abandonner le rebase en cours
+    Abort,
+}
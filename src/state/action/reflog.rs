@@ -0,0 +1,30 @@
+//! Actions de la vue reflog.
+
+/// Actions de la vue reflog.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReflogAction {
+    /// Ouvrir le reflog de la branche sélectionnée (vue branches) ou de HEAD
+    Open,
+    /// Fermer la vue et revenir à la vue précédente
+    Close,
+    /// Sélectionner l'élément suivant du panneau actif
+    SelectNext,
+    /// Sélectionner l'élément précédent du panneau actif
+    SelectPrevious,
+    /// Basculer le focus entre les entrées et les fichiers
+    ToggleFocus,
+    /// Checkout du commit de l'entrée (HEAD détachée)
+    Checkout,
+    /// Ouvrir la saisie d'une branche sur le commit de l'entrée
+    StartBranch,
+    /// Insérer un caractère dans le nom de branche
+    BranchInsertChar(char),
+    /// Supprimer le dernier caractère du nom de branche
+    BranchBackspace,
+    /// Créer la branche
+    ConfirmBranch,
+    /// Annuler la saisie du nom de branche
+    CancelBranch,
+    /// Ouvrir le sélecteur de reset sur le commit de l'entrée
+    Reset,
+}
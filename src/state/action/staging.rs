@@ -16,6 +16,24 @@ pub enum StagingAction {
     ConfirmCommit,
     /// Annuler le commit
     CancelCommit,
+    /// Activer/désactiver les hooks de vérification pour le commit en cours
+    ToggleSkipHooks,
+    /// Écrire le message de commit dans l'éditeur externe ($EDITOR)
+    OpenExternalEditor,
+    /// Ouvrir l'assistant Conventional Commits (type puis scope)
+    ConventionalOpen,
+    /// Insérer un caractère dans la recherche de l'assistant
+    ConventionalInsertChar(char),
+    /// Supprimer le dernier caractère de la recherche de l'assistant
+    ConventionalBackspace,
+    /// Option suivante de l'assistant
+    ConventionalNext,
+    /// Option précédente de l'assistant
+    ConventionalPrevious,
+    /// Valider l'option choisie dans l'assistant
+    ConventionalConfirm,
+    /// Fermer l'assistant
+    ConventionalCancel,
     /// Discard les modifications d'un fichier
     DiscardFile,
     /// Discard toutes les modifications
@@ -26,4 +44,20 @@ pub enum StagingAction {
     StashSelectedFile,
     /// Stash tous les fichiers non stagés
     StashUnstagedFiles,
+    /// Ajouter le hunk sous le curseur au staging
+    StageHunk,
+    /// Retirer le hunk sous le curseur du staging
+    UnstageHunk,
+    /// Discard le hunk sous le curseur
+    DiscardHunk,
+    /// Aller au hunk suivant dans le diff
+    NextHunk,
+    /// Aller au hunk précédent dans le diff
+    PrevHunk,
+    /// Activer/désactiver la sélection visuelle de lignes dans le diff
+    ToggleLineSelection,
+    /// Ajouter les lignes sélectionnées au staging
+    StageSelectedLines,
+    /// Retirer les lignes sélectionnées du staging
+    UnstageSelectedLines,
 }
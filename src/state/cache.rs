@@ -167,6 +167,8 @@ pub struct DiffCacheKey {
     pub commit_oid: Oid,
     /// Chemin du fichier
     pub file_path: String,
+    /// Diff des modifications stagées (HEAD → index) plutôt que non stagées.
+    pub staged: bool,
 }
 
 impl DiffCacheKey {
@@ -174,6 +176,7 @@ impl DiffCacheKey {
         Self {
             commit_oid,
             file_path: file_path.into(),
+            staged: false,
         }
     }
 
@@ -182,6 +185,16 @@ impl DiffCacheKey {
         Self {
             commit_oid: Oid::zero(),
             file_path: file_path.into(),
+            staged: false,
+        }
+    }
+
+    /// Clé pour les modifications stagées d'un fichier.
+    pub fn staged(file_path: impl Into<String>) -> Self {
+        Self {
+            commit_oid: Oid::zero(),
+            file_path: file_path.into(),
+            staged: true,
         }
     }
 
@@ -225,24 +238,34 @@ impl DiffCache {
         self.cache.clear();
     }
 
+    /// Invalide les diffs non stagés (index → working directory).
+    ///
+    /// Appelé quand seuls des fichiers de l'arbre de travail ont changé :
+    /// les diffs stagés et ceux des commits restent valides.
+    pub fn clear_unstaged(&mut self) {
+        self.retain(|key| !key.is_working_dir() || key.staged);
+    }
+
     /// Invalide toutes les entrées du working directory.
     ///
     /// Appelé après stage/unstage/commit pour s'assurer que
     /// les diffs du working directory sont rechargés.
     pub fn clear_working_directory(&mut self) {
-        // LruCache ne permet pas de supprimer par prédicat facilement,
-        // donc on reconstruit le cache sans les entrées WD
-        let capacity = self.cache.cap();
-        let entries: Vec<_> = self
+        self.retain(|key| !key.is_working_dir());
+    }
+
+    /// Ne conserve que les entrées dont la clé satisfait `keep` (ordre LRU préservé).
+    fn retain(&mut self, keep: impl Fn(&DiffCacheKey) -> bool) {
+        // LruCache ne permet pas de supprimer par prédicat : on retire les clés une à une.
+        let removed: Vec<DiffCacheKey> = self
             .cache
             .iter()
-            .filter(|(k, _)| !k.is_working_dir())
-            .map(|(k, v)| (k.clone(), v.clone()))
+            .map(|(key, _)| key)
+            .filter(|key| !keep(key))
+            .cloned()
             .collect();
-
-        self.cache.clear();
-        for (k, v) in entries {
-            self.cache.put(k, v);
+        for key in removed {
+            self.cache.pop(&key);
         }
     }
 
@@ -338,6 +361,28 @@ mod tests {
         assert!(cache.contains(&commit_key));
     }
 
+    #[test]
+    fn test_clear_unstaged_keeps_staged_diffs() {
+        let mut cache = DiffCache::new(10);
+        let diff = FileDiff {
+            path: String::new(),
+            status: crate::git::diff::DiffStatus::Modified,
+            lines: Vec::new(),
+            additions: 0,
+            deletions: 0,
+        };
+
+        cache.put(DiffCacheKey::working_dir("file.rs"), diff.clone());
+        cache.put(DiffCacheKey::staged("file.rs"), diff.clone());
+        cache.put(DiffCacheKey::new(make_oid(1), "file.rs"), diff);
+
+        cache.clear_unstaged();
+
+        assert_eq!(cache.len(), 2);
+        assert!(!cache.contains(&DiffCacheKey::working_dir("file.rs")));
+        assert!(cache.contains(&DiffCacheKey::staged("file.rs")));
+    }
+
     #[test]
     fn test_cache_key_working_dir() {
         let wd_key = DiffCacheKey::working_dir("test.rs");
@@ -41,21 +41,8 @@ impl GraphFilter {
         self.message = None;
     }
 
-    /// Filtre une liste de commits selon les critères actifs.
-    pub fn filter_commits(&self, commits: &[CommitInfo]) -> Vec<CommitInfo> {
-        if !self.is_active() {
-            return commits.to_vec();
-        }
-
-        commits
-            .iter()
-            .filter(|commit| self.matches(commit))
-            .cloned()
-            .collect()
-    }
-
     /// Vérifie si un commit correspond aux critères de filtre.
-    fn matches(&self, commit: &CommitInfo) -> bool {
+    pub fn matches(&self, commit: &CommitInfo) -> bool {
         // Filtre par auteur
         if let Some(ref author_filter) = self.author {
             if !commit
@@ -264,6 +251,15 @@ mod tests {
         }
     }
 
+    /// Commits retenus par le filtre.
+    fn apply(filter: &GraphFilter, commits: &[CommitInfo]) -> Vec<CommitInfo> {
+        commits
+            .iter()
+            .filter(|commit| filter.matches(commit))
+            .cloned()
+            .collect()
+    }
+
     #[test]
     fn test_filter_is_active() {
         let mut filter = GraphFilter::new();
@@ -287,7 +283,7 @@ mod tests {
         let mut filter = GraphFilter::new();
         filter.author = Some("ali".to_string());
 
-        let filtered = filter.filter_commits(&commits);
+        let filtered = apply(&filter, &commits);
         assert_eq!(filtered.len(), 1);
         assert_eq!(filtered[0].author, "Alice");
     }
@@ -303,7 +299,7 @@ mod tests {
         let mut filter = GraphFilter::new();
         filter.message = Some("fix".to_string());
 
-        let filtered = filter.filter_commits(&commits);
+        let filtered = apply(&filter, &commits);
         assert_eq!(filtered.len(), 2);
     }
 
@@ -319,7 +315,7 @@ mod tests {
         filter.date_from = Some(2000);
         filter.date_to = Some(8000);
 
-        let filtered = filter.filter_commits(&commits);
+        let filtered = apply(&filter, &commits);
         assert_eq!(filtered.len(), 1);
         assert_eq!(filtered[0].author, "Bob");
     }
@@ -336,7 +332,7 @@ mod tests {
         filter.author = Some("alice".to_string());
         filter.message = Some("fix".to_string());
 
-        let filtered = filter.filter_commits(&commits);
+        let filtered = apply(&filter, &commits);
         assert_eq!(filtered.len(), 1);
         assert_eq!(filtered[0].author, "Alice");
         assert_eq!(filtered[0].message, "Fix bug");
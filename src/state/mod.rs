@@ -19,9 +19,12 @@ use crate::git::repo::{GitRepo, StatusEntry};
 use ratatui::widgets::ListState;
 use std::time::{Duration, Instant};
 
-/// Nombre maximum de commits à charger.
+/// Nombre de commits chargés par page dans le graphe (défaut de `commit_limit`).
 pub const MAX_COMMITS: usize = 200;
 
+/// Distance (en commits) à la fin du graphe à partir de laquelle la page suivante est chargée.
+pub const GRAPH_PREFETCH_MARGIN: usize = 20;
+
 /// État principal de l'application.
 pub struct AppState {
     // ═══════════════════════════════════════════════════
@@ -60,6 +63,9 @@ pub struct AppState {
     /// Mode d'affichage du panneau bottom-left.
     pub bottom_left_mode: BottomLeftMode,
 
+    /// Parent sélectionné dans le panneau Parents (mainline d'un commit de merge).
+    pub parent_selected_index: usize,
+
     /// Panneau avec focus.
     pub focus: FocusPanel,
 
@@ -105,6 +111,9 @@ pub struct AppState {
     /// État du blame (si actif).
     pub blame_state: Option<BlameState>,
 
+    /// État de la vue reflog (si active).
+    pub reflog_state: Option<ReflogState>,
+
     /// État de résolution de conflits (si actif).
     pub conflicts_state: Option<ConflictsState>,
 
@@ -114,6 +123,42 @@ pub struct AppState {
     /// Picker de merge (si actif).
     pub merge_picker: Option<MergePickerState>,
 
+    /// Picker de mode de reset (si actif).
+    pub reset_picker: Option<ResetPickerState>,
+
+    /// Picker de stratégie de pull (si actif).
+    pub pull_picker: Option<PullPickerState>,
+
+    /// Palette de commandes (si ouverte).
+    pub command_palette: Option<CommandPaletteState>,
+
+    /// Sortie de la dernière commande personnalisée (si affichée).
+    pub command_output: Option<CommandOutputState>,
+
+    /// Commande personnalisée interactive à exécuter, TUI suspendue.
+    pub interactive_command: Option<String>,
+
+    /// Commande personnalisée en mode popup en cours d'exécution.
+    pub command_task: Option<crate::commands::CommandTask>,
+
+    /// Message de commit à écrire dans l'éditeur externe, TUI suspendue.
+    pub external_editor_requested: bool,
+
+    /// Éditeur de rebase interactif (si ouvert).
+    pub rebase_editor: Option<RebaseEditorState>,
+
+    /// Rebase interactif en cours (si démarré).
+    pub rebase_session: Option<crate::git::rebase::RebaseSession>,
+
+    /// Opération distante (push, pull, fetch) en cours d'exécution.
+    pub remote_task: Option<crate::transfer::RemoteTask>,
+
+    /// Dialogue de push avec options (si ouvert).
+    pub push_dialog: Option<PushDialogState>,
+
+    /// Historique des snapshots pour annuler/rétablir les actions.
+    pub undo_history: crate::git::undo::UndoHistory,
+
     // ═══════════════════════════════════════════════════
     // UI transient
     // ═══════════════════════════════════════════════════
@@ -169,6 +214,7 @@ impl AppState {
             graph: Vec::new(),
             graph_view: GraphViewState::new(),
             bottom_left_mode: BottomLeftMode::Files,
+            parent_selected_index: 0,
             focus: FocusPanel::Graph,
             selected_index: 0,
             graph_state,
@@ -182,16 +228,29 @@ impl AppState {
             staging_state: StagingState::new(),
             branches_view_state: BranchesViewState::new(),
             blame_state: None,
+            reflog_state: None,
             conflicts_state: None,
             search_state: SearchState::default(),
             merge_picker: None,
+            reset_picker: None,
+            pull_picker: None,
+            command_palette: None,
+            command_output: None,
+            interactive_command: None,
+            command_task: None,
+            external_editor_requested: false,
+            rebase_editor: None,
+            rebase_session: None,
+            remote_task: None,
+            push_dialog: None,
+            undo_history: crate::git::undo::UndoHistory::default(),
             flash_message: None,
             pending_confirmation: None,
             loading_spinner: None,
             show_branch_panel: false,
             branch_selected: 0,
             should_quit: false,
-            diff_cache: DiffCache::new(50),
+            diff_cache: DiffCache::new(crate::config::current().diff_cache_size),
             graph_filter: GraphFilter::new(),
             filter_popup: FilterPopupState::new(),
         };
@@ -206,31 +265,11 @@ impl AppState {
         self.diff_cache.clear_working_directory();
     }
 
-    /// L'état nécessite-t-il un refresh?
-    pub fn is_dirty(&self) -> bool {
-        self.dirty
-    }
-
-    /// Marque l'état comme propre.
-    pub fn mark_clean(&mut self) {
-        self.dirty = false;
-    }
-
-    /// Alias de mark_clean pour compatibilité.
-    pub fn clear_dirty(&mut self) {
-        self.mark_clean();
-    }
-
     /// Définit un message flash.
     pub fn set_flash_message(&mut self, message: impl Into<String>) {
         self.flash_message = Some((message.into(), Instant::now()));
     }
 
-    /// Efface le message flash.
-    pub fn clear_flash_message(&mut self) {
-        self.flash_message = None;
-    }
-
     /// Vérifie si le message flash a expiré et le supprime le cas échéant.
     pub fn check_flash_expired(&mut self) {
         if let Some((_, timestamp)) = &self.flash_message {
@@ -250,9 +289,88 @@ impl AppState {
         self.graph.get(self.selected_index).map(|row| &row.node)
     }
 
-    /// Accès au cache de diff.
-    pub fn diff_cache_mut(&mut self) -> &mut DiffCache {
-        &mut self.diff_cache
+    /// Parents du commit sélectionné s'il s'agit d'un merge (vide sinon).
+    pub fn selected_merge_parents(&self) -> Vec<crate::git::commit::CommitInfo> {
+        match self.selected_commit() {
+            Some(node) if node.parents.len() > 1 => node
+                .parents
+                .iter()
+                .filter_map(|oid| self.repo.repo.find_commit(*oid).ok())
+                .map(|commit| crate::git::commit::CommitInfo::from_git2_commit(&commit))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Remplace le graphe par un graphe fraîchement chargé.
+    ///
+    /// Si le chargement ne contient que la première page d'un historique inchangé,
+    /// les rangées déjà chargées au-delà sont conservées à la suite.
+    pub fn apply_graph_load(&mut self, load: crate::loader::GraphLoad) {
+        let tail = load
+            .tail_refs
+            .and_then(|refs| Some((self.graph_tail_start(&load.rows)?, refs)));
+        match tail {
+            Some((start, refs)) => {
+                let mut rows = load.rows;
+                rows.extend(self.graph.drain(start..).map(|mut row| {
+                    row.node.refs = refs.get(&row.node.oid).cloned().unwrap_or_default();
+                    row
+                }));
+                self.graph = rows;
+                // La fin du graphe est inchangée : has_more aussi.
+            }
+            None => {
+                self.graph = load.rows;
+                self.graph_view.has_more = load.has_more;
+            }
+        }
+
+        // Synchroniser graph_view.rows avec le graphe reconstruit
+        self.graph_view.rows.set_items(self.graph.clone());
+    }
+
+    /// Début des rangées actuelles qui prolongent la première page `rows`.
+    ///
+    /// None si la page ne se raccorde pas au graphe : son dernier commit doit y
+    /// figurer, précédé uniquement de commits également présents dans la page.
+    fn graph_tail_start(&self, rows: &[GraphRow]) -> Option<usize> {
+        let last = rows.last()?.node.oid;
+        let join = self.graph.iter().position(|row| row.node.oid == last)?;
+        let reloaded: std::collections::HashSet<git2::Oid> =
+            rows.iter().map(|row| row.node.oid).collect();
+        self.graph[..join]
+            .iter()
+            .all(|row| reloaded.contains(&row.node.oid))
+            .then_some(join + 1)
+    }
+
+    /// Demande la page suivante de l'historique si la sélection approche de la fin du graphe.
+    ///
+    /// La page est chargée par le thread de chargement puis ajoutée par `apply_graph_page`.
+    pub fn request_next_graph_page_if_needed(&mut self) {
+        if self
+            .graph_view
+            .needs_next_page(self.selected_index, GRAPH_PREFETCH_MARGIN)
+        {
+            self.graph_view.page_requested = true;
+        }
+    }
+
+    /// Ajoute au graphe la page suivante chargée en arrière-plan.
+    pub fn apply_graph_page(&mut self, page: crate::error::Result<crate::loader::GraphLoad>) {
+        self.graph_view.page_requested = false;
+        match page {
+            Ok(page) => {
+                self.graph.extend(page.rows.iter().cloned());
+                self.graph_view.rows.extend(page.rows);
+                self.graph_view.has_more = page.has_more;
+            }
+            Err(e) => {
+                self.graph_view.has_more = false;
+                self.set_flash_message(format!("Erreur chargement de l'historique: {}", e));
+            }
+        }
     }
 
     /// Met à jour la sélection du graph à partir de graph_view.
@@ -274,6 +392,7 @@ impl AppState {
     /// Recharge également les fichiers du commit sélectionné.
     pub fn sync_legacy_selection(&mut self) {
         self.graph_view.rows.select(self.selected_index);
+        self.parent_selected_index = 0;
         // Recharger les fichiers du commit sélectionné
         if let Some(row) = self.graph.get(self.selected_index) {
             self.commit_files = self.repo.commit_diff(row.node.oid).unwrap_or_default();
@@ -363,6 +482,49 @@ mod tests {
         state
     }
 
+    #[test]
+    fn test_graph_load_keeps_rows_after_the_first_page() {
+        use crate::git::graph::{RefInfo, RefType};
+        use crate::loader::GraphLoad;
+
+        let mut state = create_test_state_with_graph(6);
+        state.graph_view.has_more = true;
+        let mut new_commit = state.graph[0].clone();
+        new_commit.node.oid = Oid::from_bytes(&[0xaa; 20]).unwrap();
+        let tagged = state.graph[4].node.oid;
+        let refs = [(tagged, vec![RefInfo::new("v1.0", RefType::Tag)])].into();
+
+        // Nouvelle première page : un nouveau commit puis les deux premiers
+        state.apply_graph_load(GraphLoad {
+            rows: vec![
+                new_commit.clone(),
+                state.graph[0].clone(),
+                state.graph[1].clone(),
+            ],
+            has_more: true,
+            tail_refs: Some(refs),
+            selected_files: None,
+        });
+        assert_eq!(state.graph.len(), 7);
+        assert_eq!(state.graph[0].node.oid, new_commit.node.oid);
+        assert_eq!(state.graph[5].node.oid, tagged);
+        assert_eq!(state.graph[5].node.refs[0].name, "v1.0");
+        assert_eq!(state.graph_view.rows.len(), 7);
+        assert!(state.graph_view.has_more);
+
+        // Page sans raccord avec le graphe : il est remplacé
+        let mut other = new_commit;
+        other.node.oid = Oid::from_bytes(&[0xbb; 20]).unwrap();
+        state.apply_graph_load(GraphLoad {
+            rows: vec![other],
+            has_more: false,
+            tail_refs: Some(Default::default()),
+            selected_files: None,
+        });
+        assert_eq!(state.graph.len(), 1);
+        assert!(!state.graph_view.has_more);
+    }
+
     #[test]
     fn test_visual_index_last_commit() {
         // Créer un graph avec 2 commits
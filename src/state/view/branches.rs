@@ -1,7 +1,11 @@
-//! État de la vue branches/worktrees/stashes.
+//! État de la vue branches/worktrees/stashes/tags/remotes.
+
+use git2::Oid;
 
 use crate::git::branch::BranchInfo;
+use crate::git::remote::RemoteInfo;
 use crate::git::stash::StashEntry;
+use crate::git::tag::TagInfo;
 use crate::git::worktree::WorktreeInfo;
 use crate::state::selection::ListSelection;
 
@@ -12,6 +16,8 @@ pub enum BranchesSection {
     Branches,
     Worktrees,
     Stashes,
+    Tags,
+    Remotes,
 }
 
 /// Panneau focalisé dans la vue branches.
@@ -30,6 +36,13 @@ pub enum InputAction {
     CreateWorktree,
     RenameBranch,
     SaveStash,
+    /// Format "nom [message]" : un message crée un tag annoté.
+    CreateTag,
+    /// Format "nom url".
+    AddRemote,
+    RenameRemote,
+    SetRemoteUrl,
+    SetRemotePushUrl,
 }
 
 /// État de la vue branches/worktree/stash.
@@ -44,6 +57,10 @@ pub struct BranchesViewState {
     pub stashes: ListSelection<StashEntry>,
     pub stash_file_selected: usize,
     pub stash_file_diff: Option<Vec<String>>,
+    pub tags: ListSelection<TagInfo>,
+    /// Commit cible du tag en cours de création.
+    pub tag_target: Option<Oid>,
+    pub remotes: ListSelection<RemoteInfo>,
     pub input_text: String,
     pub input_cursor: usize,
     pub input_action: Option<InputAction>,
@@ -105,4 +122,24 @@ impl BranchesViewState {
     pub fn set_worktree_selected(&mut self, index: usize) {
         self.worktrees.select(index);
     }
+
+    /// Index du tag sélectionné.
+    pub fn tag_selected(&self) -> usize {
+        self.tags.selected_index()
+    }
+
+    /// Définit l'index du tag sélectionné.
+    pub fn set_tag_selected(&mut self, index: usize) {
+        self.tags.select(index);
+    }
+
+    /// Index du remote sélectionné.
+    pub fn remote_selected(&self) -> usize {
+        self.remotes.selected_index()
+    }
+
+    /// Définit l'index du remote sélectionné.
+    pub fn set_remote_selected(&mut self, index: usize) {
+        self.remotes.select(index);
+    }
 }
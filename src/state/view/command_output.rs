@@ -0,0 +1,46 @@
+//! État du popup de sortie d'une commande personnalisée.
+
+/// Sortie capturée d'une commande personnalisée.
+#[derive(Debug, Clone)]
+pub struct CommandOutputState {
+    /// Nom de la commande (titre du popup).
+    pub title: String,
+    /// Lignes de stdout puis stderr.
+    pub lines: Vec<String>,
+    /// Première ligne affichée.
+    pub scroll: usize,
+    /// La commande s'est terminée avec succès.
+    pub success: bool,
+}
+
+impl CommandOutputState {
+    /// Crée le popup à partir de la sortie d'une commande.
+    pub fn new(title: String, stdout: &str, stderr: &str, success: bool) -> Self {
+        let mut lines: Vec<String> = stdout
+            .lines()
+            .chain(stderr.lines())
+            .map(|line| line.replace('\t', "    "))
+            .collect();
+        if lines.is_empty() {
+            lines.push("(aucune sortie)".to_string());
+        }
+        Self {
+            title,
+            lines,
+            scroll: 0,
+            success,
+        }
+    }
+
+    /// Défile vers le bas sans dépasser la dernière ligne.
+    pub fn scroll_down(&mut self) {
+        if self.scroll + 1 < self.lines.len() {
+            self.scroll += 1;
+        }
+    }
+
+    /// Défile vers le haut.
+    pub fn scroll_up(&mut self) {
+        self.scroll = self.scroll.saturating_sub(1);
+    }
+}
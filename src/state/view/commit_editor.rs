@@ -0,0 +1,359 @@
+//! Éditeur multi-ligne du message de commit.
+
+/// Longueur conseillée de la ligne de sujet.
+pub const SUBJECT_RULER: usize = 50;
+
+/// Longueur conseillée des lignes du corps.
+pub const BODY_RULER: usize = 72;
+
+/// Message de commit en cours d'édition, ligne par ligne.
+///
+/// Le curseur est exprimé en (ligne, colonne), la colonne comptée en caractères.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitEditor {
+    /// Lignes du message (au moins une).
+    lines: Vec<String>,
+    /// Ligne du curseur.
+    line: usize,
+    /// Colonne du curseur (en caractères).
+    col: usize,
+}
+
+impl Default for CommitEditor {
+    fn default() -> Self {
+        Self {
+            lines: vec![String::new()],
+            line: 0,
+            col: 0,
+        }
+    }
+}
+
+impl CommitEditor {
+    /// Crée un éditeur vide.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Crée un éditeur contenant le texte, curseur au début.
+    pub fn from_text(text: &str) -> Self {
+        let mut editor = Self::new();
+        editor.set_text(text);
+        editor.line = 0;
+        editor.col = 0;
+        editor
+    }
+
+    /// Remplace le contenu, curseur à la fin (retours à la ligne finaux retirés).
+    pub fn set_text(&mut self, text: &str) {
+        let text = normalize_newlines(text);
+        self.lines = text
+            .trim_end_matches('\n')
+            .split('\n')
+            .map(str::to_string)
+            .collect();
+        self.line = self.lines.len() - 1;
+        self.col = self.line_len(self.line);
+    }
+
+    /// Vide le message.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Message complet (lignes séparées par `\n`).
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    /// Vrai si le message ne contient que des blancs.
+    pub fn is_empty(&self) -> bool {
+        self.lines.iter().all(|l| l.trim().is_empty())
+    }
+
+    /// Lignes du message.
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// Position du curseur (ligne, colonne en caractères).
+    pub fn cursor(&self) -> (usize, usize) {
+        (self.line, self.col)
+    }
+
+    /// Longueur conseillée de la ligne donnée (sujet ou corps).
+    pub fn ruler(line: usize) -> usize {
+        if line == 0 {
+            SUBJECT_RULER
+        } else {
+            BODY_RULER
+        }
+    }
+
+    /// Longueur du sujet (en caractères).
+    pub fn subject_len(&self) -> usize {
+        self.line_len(0)
+    }
+
+    /// Vrai si un corps suit le sujet sans ligne vide de séparation.
+    ///
+    /// Les lignes commençant par `comment_char`, retirées au commit, ne comptent pas.
+    pub fn missing_blank_line(&self, comment_char: Option<char>) -> bool {
+        self.lines.get(1).is_some_and(|l| {
+            !l.trim().is_empty() && !comment_char.is_some_and(|c| l.starts_with(c))
+        })
+    }
+
+    /// Remplace la ligne de sujet, curseur à sa fin.
+    pub fn set_subject(&mut self, subject: &str) {
+        self.lines[0] = subject.to_string();
+        self.line = 0;
+        self.col = self.line_len(0);
+    }
+
+    /// Insère un caractère au curseur (`\n` coupe la ligne).
+    pub fn insert_char(&mut self, c: char) {
+        match c {
+            '\n' => self.new_line(),
+            '\r' => {}
+            c => {
+                let index = self.byte_index(self.col);
+                self.lines[self.line].insert(index, c);
+                self.col += 1;
+            }
+        }
+    }
+
+    /// Insère un texte collé, éventuellement sur plusieurs lignes.
+    pub fn insert_str(&mut self, text: &str) {
+        for c in normalize_newlines(text).chars() {
+            self.insert_char(c);
+        }
+    }
+
+    /// Coupe la ligne au curseur.
+    pub fn new_line(&mut self) {
+        let index = self.byte_index(self.col);
+        let rest = self.lines[self.line].split_off(index);
+        self.line += 1;
+        self.col = 0;
+        self.lines.insert(self.line, rest);
+    }
+
+    /// Supprime le caractère avant le curseur (joint les lignes en début de ligne).
+    pub fn backspace(&mut self) {
+        if self.col > 0 {
+            self.col -= 1;
+            let index = self.byte_index(self.col);
+            self.lines[self.line].remove(index);
+        } else if self.line > 0 {
+            let current = self.lines.remove(self.line);
+            self.line -= 1;
+            self.col = self.line_len(self.line);
+            self.lines[self.line].push_str(&current);
+        }
+    }
+
+    /// Supprime le caractère sous le curseur (joint la ligne suivante en fin de ligne).
+    pub fn delete(&mut self) {
+        if self.col < self.line_len(self.line) {
+            let index = self.byte_index(self.col);
+            self.lines[self.line].remove(index);
+        } else if self.line + 1 < self.lines.len() {
+            let next = self.lines.remove(self.line + 1);
+            self.lines[self.line].push_str(&next);
+        }
+    }
+
+    /// Supprime le mot avant le curseur.
+    pub fn delete_word_before(&mut self) {
+        let target = self.word_left_position();
+        while (self.line, self.col) > target {
+            self.backspace();
+        }
+    }
+
+    /// Déplace le curseur d'un caractère à gauche (passe à la ligne précédente).
+    pub fn move_left(&mut self) {
+        if self.col > 0 {
+            self.col -= 1;
+        } else if self.line > 0 {
+            self.line -= 1;
+            self.col = self.line_len(self.line);
+        }
+    }
+
+    /// Déplace le curseur d'un caractère à droite (passe à la ligne suivante).
+    pub fn move_right(&mut self) {
+        if self.col < self.line_len(self.line) {
+            self.col += 1;
+        } else if self.line + 1 < self.lines.len() {
+            self.line += 1;
+            self.col = 0;
+        }
+    }
+
+    /// Remonte d'une ligne (colonne limitée à la longueur de la ligne).
+    pub fn move_up(&mut self) {
+        if self.line > 0 {
+            self.line -= 1;
+            self.col = self.col.min(self.line_len(self.line));
+        }
+    }
+
+    /// Descend d'une ligne (colonne limitée à la longueur de la ligne).
+    pub fn move_down(&mut self) {
+        if self.line + 1 < self.lines.len() {
+            self.line += 1;
+            self.col = self.col.min(self.line_len(self.line));
+        }
+    }
+
+    /// Début de la ligne.
+    pub fn move_home(&mut self) {
+        self.col = 0;
+    }
+
+    /// Fin de la ligne.
+    pub fn move_end(&mut self) {
+        self.col = self.line_len(self.line);
+    }
+
+    /// Début du mot précédent.
+    pub fn move_word_left(&mut self) {
+        (self.line, self.col) = self.word_left_position();
+    }
+
+    /// Fin du mot suivant.
+    pub fn move_word_right(&mut self) {
+        if self.col == self.line_len(self.line) {
+            self.move_right();
+            return;
+        }
+        let chars: Vec<char> = self.lines[self.line].chars().collect();
+        let mut col = self.col;
+        while col < chars.len() && !is_word_char(chars[col]) {
+            col += 1;
+        }
+        while col < chars.len() && is_word_char(chars[col]) {
+            col += 1;
+        }
+        self.col = col;
+    }
+
+    /// Position du début du mot précédent (fin de la ligne précédente en début de ligne).
+    fn word_left_position(&self) -> (usize, usize) {
+        if self.col == 0 {
+            return match self.line {
+                0 => (0, 0),
+                line => (line - 1, self.line_len(line - 1)),
+            };
+        }
+        let chars: Vec<char> = self.lines[self.line].chars().collect();
+        let mut col = self.col;
+        while col > 0 && !is_word_char(chars[col - 1]) {
+            col -= 1;
+        }
+        while col > 0 && is_word_char(chars[col - 1]) {
+            col -= 1;
+        }
+        (self.line, col)
+    }
+
+    /// Longueur de la ligne (en caractères).
+    fn line_len(&self, line: usize) -> usize {
+        self.lines[line].chars().count()
+    }
+
+    /// Index en octets de la colonne sur la ligne du curseur.
+    fn byte_index(&self, col: usize) -> usize {
+        let line = &self.lines[self.line];
+        line.char_indices().nth(col).map_or(line.len(), |(i, _)| i)
+    }
+}
+
+/// Caractère faisant partie d'un mot (pour les déplacements par mot).
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Remplace les fins de ligne `\r\n` et `\r` par `\n`.
+fn normalize_newlines(text: &str) -> String {
+    text.replace("\r\n", "\n").replace('\r', "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_across_lines() {
+        let mut editor = CommitEditor::new();
+        editor.insert_str("Ajoute é");
+        editor.new_line();
+        editor.new_line();
+        editor.insert_str("Corps");
+        assert_eq!(editor.text(), "Ajoute é\n\nCorps");
+        assert_eq!(editor.cursor(), (2, 5));
+
+        // Monter sur une ligne plus courte limite la colonne à sa longueur
+        editor.insert_str("   suite");
+        editor.move_up();
+        assert_eq!(editor.cursor(), (1, 0));
+        editor.move_up();
+        editor.move_end();
+        assert_eq!(editor.cursor(), (0, 8));
+        editor.backspace();
+        assert_eq!(editor.lines()[0], "Ajoute ");
+
+        // Backspace en début de ligne joint les lignes, Delete en fin aussi
+        editor.move_down();
+        editor.backspace();
+        assert_eq!(editor.cursor(), (0, 7));
+        editor.delete();
+        assert_eq!(editor.text(), "Ajoute Corps   suite");
+    }
+
+    #[test]
+    fn test_word_motions() {
+        let mut editor = CommitEditor::from_text("fix(ui): corrige le_rendu\nsuite");
+        editor.move_word_right();
+        assert_eq!(editor.cursor(), (0, 3));
+        editor.move_word_right();
+        assert_eq!(editor.cursor(), (0, 6));
+        editor.move_end();
+        editor.move_word_left();
+        assert_eq!(editor.cursor(), (0, 17));
+        editor.delete_word_before();
+        assert_eq!(editor.lines()[0], "fix(ui): le_rendu");
+
+        // Les mots traversent les lignes
+        editor.move_end();
+        editor.move_word_right();
+        assert_eq!(editor.cursor(), (1, 0));
+        editor.move_word_left();
+        assert_eq!(editor.cursor(), (0, 17));
+    }
+
+    #[test]
+    fn test_paste_and_subject_checks() {
+        let mut editor = CommitEditor::new();
+        editor.insert_str("Sujet\r\nCorps sans ligne vide\r\n");
+        assert_eq!(editor.lines(), ["Sujet", "Corps sans ligne vide", ""]);
+        assert!(editor.missing_blank_line(None));
+
+        editor.set_text("Sujet\n\nCorps\n");
+        assert!(!editor.missing_blank_line(None));
+        assert_eq!(editor.subject_len(), 5);
+        assert_eq!(editor.cursor(), (2, 5));
+        assert!(CommitEditor::from_text(" \n\n").is_empty());
+    }
+
+    #[test]
+    fn test_missing_blank_line_ignores_comments() {
+        let editor = CommitEditor::from_text("Sujet\n; commentaire\n");
+        assert!(!editor.missing_blank_line(Some(';')));
+        assert!(editor.missing_blank_line(Some('#')));
+        assert!(editor.missing_blank_line(None));
+    }
+}
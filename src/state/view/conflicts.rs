@@ -93,12 +93,4 @@ impl ConflictsState {
     pub fn selected_file(&self) -> Option<&MergeFile> {
         self.all_files.get(self.file_selected)
     }
-
-    /// Nombre de fichiers avec des conflits restants.
-    pub fn remaining_conflicts(&self) -> usize {
-        self.all_files
-            .iter()
-            .filter(|f| f.has_conflicts && !f.is_resolved)
-            .count()
-    }
 }
@@ -0,0 +1,138 @@
+//! État de l'assistant Conventional Commits : choix du type, puis du scope.
+
+use crate::state::selection::ListSelection;
+use crate::utils::fuzzy::fuzzy_score;
+
+/// Étape de l'assistant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConventionalStep {
+    /// Choix du type.
+    Type,
+    /// Choix du scope pour le type choisi.
+    Scope { kind: String },
+}
+
+/// État de l'assistant Conventional Commits.
+#[derive(Debug, Clone)]
+pub struct ConventionalPickerState {
+    /// Étape en cours.
+    pub step: ConventionalStep,
+    /// Texte de recherche (ou nouveau scope).
+    pub query: String,
+    /// Options de l'étape : types, ou scopes ("" pour aucun scope).
+    pub options: Vec<String>,
+    /// Indices des options correspondant à la recherche.
+    pub matches: ListSelection<usize>,
+    /// Scopes proposés à la seconde étape.
+    scopes: Vec<String>,
+}
+
+impl ConventionalPickerState {
+    /// Crée l'assistant sur le choix du type.
+    pub fn new(types: Vec<String>, scopes: Vec<String>) -> Self {
+        let mut picker = Self {
+            step: ConventionalStep::Type,
+            query: String::new(),
+            options: types,
+            matches: ListSelection::new(),
+            scopes,
+        };
+        picker.update_matches();
+        picker
+    }
+
+    /// Recalcule les options correspondant à la recherche, les meilleures d'abord.
+    pub fn update_matches(&mut self) {
+        let mut scored: Vec<(i64, usize)> = self
+            .options
+            .iter()
+            .enumerate()
+            .filter_map(|(i, option)| match option.as_str() {
+                // "Aucun scope" n'est proposé que sans recherche.
+                "" => self.query.is_empty().then_some((0, i)),
+                option => fuzzy_score(&self.query, option).map(|score| (score, i)),
+            })
+            .collect();
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        self.matches
+            .set_items(scored.into_iter().map(|(_, i)| i).collect());
+        self.matches.select(0);
+    }
+
+    /// Option sélectionnée.
+    pub fn selected_option(&self) -> Option<&str> {
+        self.matches
+            .selected_item()
+            .and_then(|&i| self.options.get(i))
+            .map(String::as_str)
+    }
+
+    /// Options correspondant à la recherche, dans l'ordre d'affichage.
+    pub fn matching_options(&self) -> impl Iterator<Item = &str> {
+        self.matches
+            .iter()
+            .filter_map(|&i| self.options.get(i))
+            .map(String::as_str)
+    }
+
+    /// Valide l'étape en cours.
+    ///
+    /// Le choix du type passe au scope ; le choix du scope retourne le type et
+    /// le scope retenus. Un scope absent de la liste est pris dans la recherche.
+    pub fn confirm(&mut self) -> Option<(String, Option<String>)> {
+        match &self.step {
+            ConventionalStep::Type => {
+                let kind = self.selected_option()?.to_string();
+                self.step = ConventionalStep::Scope { kind };
+                self.options = std::iter::once(String::new())
+                    .chain(self.scopes.iter().cloned())
+                    .collect();
+                self.query.clear();
+                self.update_matches();
+                None
+            }
+            ConventionalStep::Scope { kind } => {
+                let scope = match self.selected_option() {
+                    Some(scope) => scope.to_string(),
+                    None => self.query.trim().to_string(),
+                };
+                Some((kind.clone(), Some(scope).filter(|s| !s.is_empty())))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pick_type_then_scope() {
+        let types = vec!["feat".to_string(), "fix".to_string()];
+        let mut picker = ConventionalPickerState::new(types, vec!["ui".to_string()]);
+
+        picker.query = "fi".to_string();
+        picker.update_matches();
+        assert_eq!(picker.selected_option(), Some("fix"));
+        assert_eq!(picker.confirm(), None);
+        assert_eq!(
+            picker.step,
+            ConventionalStep::Scope {
+                kind: "fix".to_string()
+            }
+        );
+
+        // Sans recherche : aucun scope, puis les scopes appris
+        assert_eq!(picker.selected_option(), Some(""));
+        picker.matches.select_next();
+        assert_eq!(picker.confirm(), Some(("fix".into(), Some("ui".into()))));
+
+        // Un scope inconnu est pris tel que saisi
+        picker.query = "config".to_string();
+        picker.update_matches();
+        assert_eq!(
+            picker.confirm(),
+            Some(("fix".into(), Some("config".into())))
+        );
+    }
+}
@@ -12,6 +12,10 @@ pub struct GraphViewState {
     pub file_selected_index: usize,
     /// Offset de scroll dans le diff.
     pub diff_scroll_offset: usize,
+    /// Reste-t-il des commits à charger au-delà des rangées actuelles?
+    pub has_more: bool,
+    /// La page suivante a-t-elle été demandée au thread de chargement?
+    pub page_requested: bool,
 }
 
 impl GraphViewState {
@@ -24,4 +28,9 @@ impl GraphViewState {
     pub fn selected_commit(&self) -> Option<&crate::git::graph::CommitNode> {
         self.rows.selected_item().map(|row| &row.node)
     }
+
+    /// La sélection est-elle assez proche de la fin pour charger la page suivante?
+    pub fn needs_next_page(&self, selected_index: usize, margin: usize) -> bool {
+        self.has_more && !self.page_requested && selected_index + margin >= self.rows.len()
+    }
 }
@@ -2,19 +2,37 @@
 
 mod blame;
 mod branches;
+mod command_output;
+mod commit_editor;
 mod conflicts;
+mod conventional_picker;
 mod graph;
 mod merge_picker;
+mod palette;
+mod pull_picker;
+mod push_dialog;
+mod rebase;
+mod reflog;
+mod reset_picker;
 mod search;
 mod staging;
 
 pub use blame::BlameState;
 pub use branches::{BranchesFocus, BranchesSection, BranchesViewState, InputAction};
+pub use command_output::CommandOutputState;
+pub use commit_editor::{CommitEditor, SUBJECT_RULER};
 pub use conflicts::{ConflictPanelFocus, ConflictsState};
+pub use conventional_picker::{ConventionalPickerState, ConventionalStep};
 pub use graph::GraphViewState;
 pub use merge_picker::MergePickerState;
+pub use palette::{CommandPaletteState, PaletteEntry};
+pub use pull_picker::PullPickerState;
+pub use push_dialog::{PushDialogState, PushField};
+pub use rebase::RebaseEditorState;
+pub use reflog::{ReflogFocus, ReflogState};
+pub use reset_picker::ResetPickerState;
 pub use search::SearchState;
-pub use staging::{StagingFocus, StagingState};
+pub use staging::{DiffSource, StagingFocus, StagingState};
 
 /// Mode de vue actif.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -25,6 +43,7 @@ pub enum ViewMode {
     Branches,
     Conflicts,
     Blame,
+    Reflog,
     Help,
 }
 
@@ -0,0 +1,108 @@
+//! État de la palette de commandes.
+
+use crate::state::selection::ListSelection;
+use crate::state::AppAction;
+use crate::utils::fuzzy::fuzzy_score;
+
+/// Action proposée par la palette.
+#[derive(Debug, Clone)]
+pub struct PaletteEntry {
+    /// Action déclenchée.
+    pub action: AppAction,
+    /// Description de l'action.
+    pub description: &'static str,
+    /// Raccourci actuel (vide si l'action n'a plus de touche).
+    pub keys: String,
+    /// Contexte d'origine du raccourci (titre de la section d'aide).
+    pub context: &'static str,
+}
+
+/// État de la palette de commandes.
+#[derive(Debug, Clone)]
+pub struct CommandPaletteState {
+    /// Texte de recherche.
+    pub query: String,
+    /// Actions disponibles dans le contexte où la palette a été ouverte.
+    pub entries: Vec<PaletteEntry>,
+    /// Indices des entrées correspondant à la recherche, les meilleures d'abord.
+    pub matches: ListSelection<usize>,
+}
+
+impl CommandPaletteState {
+    /// Crée une palette proposant les entrées données.
+    pub fn new(entries: Vec<PaletteEntry>) -> Self {
+        let mut palette = Self {
+            query: String::new(),
+            entries,
+            matches: ListSelection::new(),
+        };
+        palette.update_matches();
+        palette
+    }
+
+    /// Recalcule les entrées correspondant à la recherche.
+    pub fn update_matches(&mut self) {
+        let mut scored: Vec<(i64, usize)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(i, entry)| {
+                let text = format!("{} {}", entry.description, entry.context);
+                fuzzy_score(&self.query, &text).map(|score| (score, i))
+            })
+            .collect();
+        // Tri stable : à score égal, l'ordre de la table des raccourcis est conservé.
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        self.matches
+            .set_items(scored.into_iter().map(|(_, i)| i).collect());
+        self.matches.select(0);
+    }
+
+    /// Entrée sélectionnée.
+    pub fn selected_entry(&self) -> Option<&PaletteEntry> {
+        self.matches
+            .selected_item()
+            .and_then(|&i| self.entries.get(i))
+    }
+
+    /// Entrées correspondant à la recherche, dans l'ordre d'affichage.
+    pub fn matching_entries(&self) -> impl Iterator<Item = &PaletteEntry> {
+        self.matches.iter().filter_map(|&i| self.entries.get(i))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(description: &'static str, action: AppAction) -> PaletteEntry {
+        PaletteEntry {
+            action,
+            description,
+            keys: String::new(),
+            context: "Graph",
+        }
+    }
+
+    #[test]
+    fn test_query_filters_and_ranks_entries() {
+        let mut palette = CommandPaletteState::new(vec![
+            entry("Nouveau commit", AppAction::CommitPrompt),
+            entry("Push", AppAction::GitPush),
+            entry("Pull", AppAction::GitPull),
+        ]);
+        assert_eq!(palette.matches.len(), 3);
+
+        palette.query = "pus".to_string();
+        palette.update_matches();
+        assert_eq!(palette.matches.len(), 1);
+        assert_eq!(
+            palette.selected_entry().map(|e| &e.action),
+            Some(&AppAction::GitPush)
+        );
+
+        palette.query = "xyz".to_string();
+        palette.update_matches();
+        assert!(palette.selected_entry().is_none());
+    }
+}
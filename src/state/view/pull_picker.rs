@@ -0,0 +1,35 @@
+//! État du sélecteur de stratégie pour le pull.
+
+use crate::git::remote::PullMode;
+use crate::state::selection::ListSelection;
+
+/// État du sélecteur de stratégie pour le pull.
+#[derive(Debug, Clone)]
+pub struct PullPickerState {
+    /// Stratégies disponibles.
+    pub modes: ListSelection<PullMode>,
+    /// Stratégie configurée (`pull.rebase`, `pull.ff`).
+    pub configured: PullMode,
+    /// Actif ou non.
+    pub is_active: bool,
+}
+
+impl PullPickerState {
+    /// Crée un nouveau pull picker, la stratégie configurée présélectionnée.
+    pub fn new(configured: PullMode) -> Self {
+        let mut modes = ListSelection::with_items(PullMode::ALL.to_vec());
+        if let Some(index) = PullMode::ALL.iter().position(|m| *m == configured) {
+            modes.select(index);
+        }
+        Self {
+            modes,
+            configured,
+            is_active: true,
+        }
+    }
+
+    /// Stratégie actuellement sélectionnée.
+    pub fn selected_mode(&self) -> PullMode {
+        self.modes.selected_item().copied().unwrap_or_default()
+    }
+}
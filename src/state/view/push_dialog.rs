@@ -0,0 +1,170 @@
+//! État du dialogue de push avec options.
+
+use crate::git::remote::PushRequest;
+
+/// Champ du dialogue de push.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PushField {
+    #[default]
+    Remote,
+    RemoteBranch,
+    ForceWithLease,
+    SetUpstream,
+    IncludeTags,
+}
+
+impl PushField {
+    /// Champs dans l'ordre d'affichage.
+    pub const ALL: [PushField; 5] = [
+        PushField::Remote,
+        PushField::RemoteBranch,
+        PushField::ForceWithLease,
+        PushField::SetUpstream,
+        PushField::IncludeTags,
+    ];
+
+    /// Libellé du champ.
+    pub fn label(&self) -> &'static str {
+        match self {
+            PushField::Remote => "Remote",
+            PushField::RemoteBranch => "Branche distante",
+            PushField::ForceWithLease => "Force-with-lease",
+            PushField::SetUpstream => "Configurer l'upstream",
+            PushField::IncludeTags => "Inclure les tags",
+        }
+    }
+}
+
+/// État du dialogue de push.
+#[derive(Debug, Clone)]
+pub struct PushDialogState {
+    /// Push à effectuer.
+    pub request: PushRequest,
+    /// Remotes configurés (choix du champ remote).
+    pub remotes: Vec<String>,
+    /// Champ sélectionné.
+    pub field: PushField,
+    /// Raison du refus du push précédent (affichée en tête du dialogue).
+    pub rejection: Option<String>,
+}
+
+impl PushDialogState {
+    /// Crée le dialogue pour le push donné.
+    pub fn new(request: PushRequest, remotes: Vec<String>) -> Self {
+        Self {
+            request,
+            remotes,
+            field: PushField::default(),
+            rejection: None,
+        }
+    }
+
+    /// Passe au champ suivant (ou précédent si `delta` est négatif).
+    pub fn move_field(&mut self, delta: isize) {
+        let count = PushField::ALL.len() as isize;
+        let index = PushField::ALL
+            .iter()
+            .position(|f| *f == self.field)
+            .unwrap_or(0) as isize;
+        self.field = PushField::ALL[(index + delta).rem_euclid(count) as usize];
+    }
+
+    /// Passe au remote suivant (ou précédent si `delta` est négatif) sur le champ remote.
+    pub fn cycle_remote(&mut self, delta: isize) {
+        if self.field != PushField::Remote || self.remotes.is_empty() {
+            return;
+        }
+        let count = self.remotes.len() as isize;
+        let index = self
+            .remotes
+            .iter()
+            .position(|r| *r == self.request.remote)
+            .map_or(0, |i| (i as isize + delta).rem_euclid(count));
+        self.request.remote = self.remotes[index as usize].clone();
+    }
+
+    /// Coche ou décoche la case sélectionnée (change de remote sur le champ remote).
+    pub fn toggle(&mut self) {
+        match self.field {
+            PushField::Remote => self.cycle_remote(1),
+            PushField::RemoteBranch => {}
+            PushField::ForceWithLease => {
+                self.request.force_with_lease = !self.request.force_with_lease
+            }
+            PushField::SetUpstream => self.request.set_upstream = !self.request.set_upstream,
+            PushField::IncludeTags => self.request.include_tags = !self.request.include_tags,
+        }
+    }
+
+    /// État d'une case à cocher.
+    pub fn is_checked(&self, field: PushField) -> bool {
+        match field {
+            PushField::ForceWithLease => self.request.force_with_lease,
+            PushField::SetUpstream => self.request.set_upstream,
+            PushField::IncludeTags => self.request.include_tags,
+            PushField::Remote | PushField::RemoteBranch => false,
+        }
+    }
+
+    /// Ajoute un caractère au nom de la branche distante.
+    pub fn insert_char(&mut self, c: char) {
+        if self.field == PushField::RemoteBranch && !c.is_whitespace() {
+            self.request.remote_branch.push(c);
+        }
+    }
+
+    /// Supprime le dernier caractère du nom de la branche distante.
+    pub fn backspace(&mut self) {
+        if self.field == PushField::RemoteBranch {
+            self.request.remote_branch.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dialog() -> PushDialogState {
+        let request = PushRequest {
+            branch: "feature".to_string(),
+            remote: "origin".to_string(),
+            remote_branch: "feature".to_string(),
+            force_with_lease: false,
+            set_upstream: false,
+            include_tags: false,
+        };
+        PushDialogState::new(request, vec!["origin".to_string(), "upstream".to_string()])
+    }
+
+    #[test]
+    fn test_push_dialog_editing() {
+        let mut dialog = dialog();
+
+        dialog.toggle();
+        assert_eq!(dialog.request.remote, "upstream");
+        dialog.cycle_remote(1);
+        assert_eq!(dialog.request.remote, "origin");
+
+        // La saisie ne modifie que la branche distante
+        dialog.insert_char('x');
+        assert_eq!(dialog.request.remote_branch, "feature");
+        dialog.move_field(1);
+        dialog.backspace();
+        dialog.insert_char('e');
+        dialog.insert_char('s');
+        assert_eq!(dialog.request.remote_branch, "features");
+
+        // Le remote ne change que sur son champ
+        dialog.cycle_remote(1);
+        assert_eq!(dialog.request.remote, "origin");
+
+        dialog.move_field(1);
+        dialog.toggle();
+        assert!(dialog.request.force_with_lease);
+        assert!(dialog.is_checked(PushField::ForceWithLease));
+
+        dialog.move_field(-3);
+        assert_eq!(dialog.field, PushField::IncludeTags);
+    }
+}
@@ -0,0 +1,160 @@
+//! État de l'éditeur de rebase interactif.
+
+use git2::Oid;
+
+use super::CommitEditor;
+use crate::git::rebase::{RebaseTodoEntry, TodoAction};
+use crate::state::selection::ListSelection;
+
+/// État de l'éditeur de todo list du rebase interactif.
+#[derive(Debug, Clone)]
+pub struct RebaseEditorState {
+    /// Commit de base (exclu de la todo list).
+    pub base: Oid,
+    /// Entrées de la todo list, du plus ancien au plus récent.
+    pub entries: ListSelection<RebaseTodoEntry>,
+    /// Message en cours d'édition pour un reword (si actif).
+    pub reword_input: Option<CommitEditor>,
+}
+
+impl RebaseEditorState {
+    /// Crée un éditeur pour les commits situés au-dessus de `base`.
+    pub fn new(base: Oid, entries: Vec<RebaseTodoEntry>) -> Self {
+        Self {
+            base,
+            entries: ListSelection::with_items(entries),
+            reword_input: None,
+        }
+    }
+
+    /// Change l'action de l'entrée sélectionnée.
+    pub fn set_action(&mut self, action: TodoAction) {
+        if let Some(entry) = self.entries.selected_item_mut() {
+            entry.action = action;
+            if action != TodoAction::Reword {
+                entry.message = None;
+            }
+        }
+    }
+
+    /// Déplace l'entrée sélectionnée d'un cran (vers le haut si `up`).
+    pub fn move_selected(&mut self, up: bool) {
+        let index = self.entries.selected_index();
+        let target = if up {
+            match index.checked_sub(1) {
+                Some(target) => target,
+                None => return,
+            }
+        } else if index + 1 < self.entries.len() {
+            index + 1
+        } else {
+            return;
+        };
+
+        self.entries.items.swap(index, target);
+        self.entries.select(target);
+    }
+
+    /// Ouvre l'éditeur du nouveau message pour l'entrée sélectionnée.
+    ///
+    /// Il est pré-rempli avec le message déjà choisi, sinon avec `original` (message
+    /// complet du commit) : le corps et les trailers sont conservés.
+    pub fn start_reword(&mut self, original: &str) {
+        if let Some(entry) = self.entries.selected_item() {
+            let message = entry.message.as_deref().unwrap_or(original);
+            self.reword_input = Some(CommitEditor::from_text(message));
+        }
+    }
+
+    /// Valide le message édité et passe l'entrée en reword.
+    pub fn confirm_reword(&mut self) {
+        let Some(input) = self.reword_input.take() else {
+            return;
+        };
+        if input.is_empty() {
+            return;
+        }
+        if let Some(entry) = self.entries.selected_item_mut() {
+            entry.action = TodoAction::Reword;
+            entry.message = Some(format!("{}\n", input.text().trim_end()));
+        }
+    }
+
+    /// Entrées dans l'ordre d'application.
+    pub fn todo(&self) -> Vec<RebaseTodoEntry> {
+        self.entries.items.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(summary: &str) -> RebaseTodoEntry {
+        RebaseTodoEntry {
+            oid: Oid::zero(),
+            summary: summary.to_string(),
+            action: TodoAction::Pick,
+            message: None,
+        }
+    }
+
+    #[test]
+    fn test_move_selected_keeps_selection_on_entry() {
+        let mut editor = RebaseEditorState::new(Oid::zero(), vec![entry("a"), entry("b")]);
+        editor.move_selected(false);
+        assert_eq!(editor.entries.selected_index(), 1);
+        assert_eq!(editor.entries.items[1].summary, "a");
+
+        // Déjà en bas : rien ne bouge.
+        editor.move_selected(false);
+        assert_eq!(editor.entries.items[1].summary, "a");
+    }
+
+    #[test]
+    fn test_reword_sets_message_and_action() {
+        let mut editor = RebaseEditorState::new(Oid::zero(), vec![entry("a")]);
+        editor.start_reword("a\n");
+        assert_eq!(
+            editor.reword_input.as_ref().map(CommitEditor::text),
+            Some("a".to_string())
+        );
+
+        editor.reword_input = Some(CommitEditor::from_text("nouveau"));
+        editor.confirm_reword();
+        assert_eq!(editor.entries.items[0].action, TodoAction::Reword);
+        assert_eq!(
+            editor.entries.items[0].message.as_deref(),
+            Some("nouveau\n")
+        );
+
+        editor.set_action(TodoAction::Pick);
+        assert_eq!(editor.entries.items[0].message, None);
+    }
+
+    #[test]
+    fn test_reword_keeps_message_body() {
+        let original = "Sujet\n\nCorps du message.\n\nSigned-off-by: Test <test@test.com>\n";
+        let mut editor = RebaseEditorState::new(Oid::zero(), vec![entry("Sujet")]);
+        editor.start_reword(original);
+
+        // Seule la ligne de sujet est réécrite.
+        let input = editor.reword_input.as_mut().unwrap();
+        input.move_end();
+        input.insert_str(" modifié");
+        editor.confirm_reword();
+
+        assert_eq!(
+            editor.entries.items[0].message.as_deref(),
+            Some("Sujet modifié\n\nCorps du message.\n\nSigned-off-by: Test <test@test.com>\n")
+        );
+
+        // Rouvrir l'éditeur reprend le message choisi, pas l'original.
+        editor.start_reword(original);
+        assert!(editor
+            .reword_input
+            .unwrap()
+            .text()
+            .starts_with("Sujet modifié"));
+    }
+}
@@ -0,0 +1,81 @@
+//! État de la vue reflog.
+
+use crate::git::diff::{DiffFile, FileDiff};
+use crate::git::graph::CommitNode;
+use crate::git::reflog::ReflogEntry;
+use crate::state::selection::ListSelection;
+use crate::state::ViewMode;
+
+/// Panneau focalisé dans la vue reflog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReflogFocus {
+    /// Liste des entrées du reflog.
+    #[default]
+    Entries,
+    /// Fichiers modifiés par le commit de l'entrée sélectionnée.
+    Files,
+}
+
+/// État de la vue reflog.
+#[derive(Debug, Clone)]
+pub struct ReflogState {
+    /// Référence dont on affiche le reflog (ex: "HEAD", "refs/heads/main").
+    pub refname: String,
+    /// Entrées du reflog, de la plus récente à la plus ancienne.
+    pub entries: ListSelection<ReflogEntry>,
+    /// Panneau actif.
+    pub focus: ReflogFocus,
+    /// Commit pointé par l'entrée sélectionnée (aperçu dans le panneau de détail).
+    pub commit: Option<CommitNode>,
+    /// Fichiers modifiés par ce commit.
+    pub files: ListSelection<DiffFile>,
+    /// Diff du fichier sélectionné.
+    pub file_diff: Option<FileDiff>,
+    /// Nom de branche en cours de saisie (création depuis l'entrée), si actif.
+    pub branch_input: Option<String>,
+    /// Vue à restaurer à la fermeture.
+    pub return_view: ViewMode,
+}
+
+impl ReflogState {
+    /// Crée l'état de la vue pour le reflog de `refname`.
+    pub fn new(refname: String, entries: Vec<ReflogEntry>) -> Self {
+        Self {
+            refname,
+            entries: ListSelection::with_items(entries),
+            focus: ReflogFocus::default(),
+            commit: None,
+            files: ListSelection::new(),
+            file_diff: None,
+            branch_input: None,
+            return_view: ViewMode::Graph,
+        }
+    }
+
+    /// Nom court de la référence pour l'affichage (ex: "main").
+    pub fn display_name(&self) -> &str {
+        self.refname
+            .strip_prefix("refs/heads/")
+            .or_else(|| self.refname.strip_prefix("refs/remotes/"))
+            .unwrap_or(&self.refname)
+    }
+
+    /// Entrée actuellement sélectionnée.
+    pub fn selected_entry(&self) -> Option<&ReflogEntry> {
+        self.entries.selected_item()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_name_strips_ref_prefix() {
+        let state = ReflogState::new("refs/heads/main".to_string(), Vec::new());
+        assert_eq!(state.display_name(), "main");
+
+        let state = ReflogState::new("HEAD".to_string(), Vec::new());
+        assert_eq!(state.display_name(), "HEAD");
+    }
+}
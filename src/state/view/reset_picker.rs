@@ -0,0 +1,40 @@
+//! État du sélecteur de mode pour le reset.
+
+use git2::Oid;
+
+use crate::git::reset::ResetMode;
+use crate::state::selection::ListSelection;
+
+/// État du sélecteur de mode pour le reset.
+#[derive(Debug, Clone)]
+pub struct ResetPickerState {
+    /// Commit cible du reset.
+    pub target: Oid,
+    /// Modes de reset disponibles.
+    pub modes: ListSelection<ResetMode>,
+    /// Actif ou non.
+    pub is_active: bool,
+}
+
+impl ResetPickerState {
+    /// Crée un nouveau reset picker pour le commit donné (mixed présélectionné).
+    pub fn new(target: Oid) -> Self {
+        let mut modes = ListSelection::with_items(ResetMode::ALL.to_vec());
+        if let Some(index) = ResetMode::ALL
+            .iter()
+            .position(|m| *m == ResetMode::default())
+        {
+            modes.select(index);
+        }
+        Self {
+            target,
+            modes,
+            is_active: true,
+        }
+    }
+
+    /// Mode actuellement sélectionné.
+    pub fn selected_mode(&self) -> ResetMode {
+        self.modes.selected_item().copied().unwrap_or_default()
+    }
+}
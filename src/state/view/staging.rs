@@ -1,8 +1,10 @@
 //! État de la vue staging.
 
-use crate::git::diff::{DiffViewMode, FileDiff};
+use crate::error::Result;
+use crate::git::diff::{DiffLineType, DiffViewMode, FileDiff};
 use crate::git::repo::StatusEntry;
 use crate::state::selection::ListSelection;
+use crate::state::view::{CommitEditor, ConventionalPickerState};
 
 /// Focus dans la vue staging.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -14,6 +16,16 @@ pub enum StagingFocus {
     CommitMessage,
 }
 
+/// Liste dont provient le diff affiché dans la vue staging.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DiffSource {
+    /// Modifications non stagées (index → working directory).
+    #[default]
+    Unstaged,
+    /// Modifications stagées (HEAD → index).
+    Staged,
+}
+
 /// État complet de la vue staging.
 #[derive(Debug, Clone, Default)]
 pub struct StagingState {
@@ -23,20 +35,33 @@ pub struct StagingState {
     pub staged: ListSelection<StatusEntry>,
     /// Panneau actif.
     pub focus: StagingFocus,
-    /// Message de commit en cours.
-    pub commit_message: String,
-    /// Position du curseur dans le message.
-    pub cursor_position: usize,
+    /// Message de commit en cours d'édition.
+    pub commit_editor: CommitEditor,
     /// Mode saisie de message activé.
     pub is_committing: bool,
     /// Mode amendement activé.
     pub is_amending: bool,
+    /// Hooks de vérification (pre-commit, commit-msg) ignorés pour le commit en cours.
+    pub skip_hooks: bool,
+    /// Caractère des lignes de commentaire à retirer du message, s'il provient de
+    /// `commit.template` (None : seuls les blancs sont nettoyés).
+    pub comment_char: Option<char>,
+    /// Assistant Conventional Commits (type et scope), si ouvert.
+    pub conventional_picker: Option<ConventionalPickerState>,
     /// Diff du fichier sélectionné.
     pub current_diff: Option<FileDiff>,
     /// Offset de scroll du diff.
     pub diff_scroll: usize,
     /// Mode d'affichage du diff (unifié ou côte à côte).
     pub diff_view_mode: DiffViewMode,
+    /// Liste dont provient le diff affiché.
+    pub diff_source: DiffSource,
+    /// Ligne du diff sous le curseur (panneau Diff).
+    pub diff_cursor: usize,
+    /// Hauteur visible du panneau diff (mise à jour au rendu).
+    pub diff_visible_height: usize,
+    /// Ancre de la sélection visuelle de lignes (None hors mode visuel).
+    pub diff_selection_anchor: Option<usize>,
 }
 
 impl StagingState {
@@ -54,16 +79,94 @@ impl StagingState {
         }
     }
 
-    /// Passe au panneau suivant.
-    pub fn cycle_focus(&mut self) {
-        self.focus = match self.focus {
-            StagingFocus::Unstaged => StagingFocus::Staged,
-            StagingFocus::Staged => StagingFocus::Diff,
-            StagingFocus::Diff => StagingFocus::Unstaged,
-            StagingFocus::CommitMessage => StagingFocus::CommitMessage,
+    /// Message de commit tel qu'il sera enregistré, nettoyé comme par `git commit`.
+    pub fn cleaned_message(&self) -> Result<String> {
+        crate::git::commit::cleanup_message(&self.commit_editor.text(), self.comment_char)
+    }
+
+    /// Index du hunk sous le curseur dans le diff courant.
+    pub fn current_hunk_index(&self) -> Option<usize> {
+        let diff = self.current_diff.as_ref()?;
+        let headers = diff
+            .lines
+            .iter()
+            .take(self.diff_cursor + 1)
+            .filter(|l| l.line_type == DiffLineType::HunkHeader)
+            .count();
+        headers.checked_sub(1)
+    }
+
+    /// Lignes (début, fin incluse) du hunk sous le curseur.
+    pub fn current_hunk_range(&self) -> Option<(usize, usize)> {
+        let diff = self.current_diff.as_ref()?;
+        let cursor = self.diff_cursor.min(diff.lines.len().checked_sub(1)?);
+        let start = (0..=cursor)
+            .rev()
+            .find(|&i| diff.lines[i].line_type == DiffLineType::HunkHeader)?;
+        let end = (cursor + 1..diff.lines.len())
+            .find(|&i| diff.lines[i].line_type == DiffLineType::HunkHeader)
+            .map_or(diff.lines.len() - 1, |next| next - 1);
+        Some((start, end))
+    }
+
+    /// Déplace le curseur du diff et ajuste le scroll pour le garder visible.
+    pub fn move_diff_cursor(&mut self, delta: isize) {
+        let len = self.current_diff.as_ref().map_or(0, |d| d.lines.len());
+        self.set_diff_cursor(
+            self.diff_cursor
+                .saturating_add_signed(delta)
+                .min(len.saturating_sub(1)),
+        );
+    }
+
+    /// Place le curseur sur le hunk suivant (ou précédent).
+    pub fn jump_to_hunk(&mut self, forward: bool) {
+        let Some(diff) = self.current_diff.as_ref() else {
+            return;
+        };
+        let is_header = |i: &usize| diff.lines[*i].line_type == DiffLineType::HunkHeader;
+        let target = if forward {
+            (self.diff_cursor + 1..diff.lines.len()).find(is_header)
+        } else {
+            (0..self.diff_cursor).rev().find(is_header)
+        };
+        if let Some(target) = target {
+            self.set_diff_cursor(target);
+        }
+    }
+
+    /// Positionne le curseur du diff et ajuste le scroll.
+    fn set_diff_cursor(&mut self, cursor: usize) {
+        self.diff_cursor = cursor;
+        let height = self.diff_visible_height.max(1);
+        if self.diff_cursor < self.diff_scroll {
+            self.diff_scroll = self.diff_cursor;
+        } else if self.diff_cursor >= self.diff_scroll + height {
+            self.diff_scroll = self.diff_cursor + 1 - height;
+        }
+    }
+
+    /// Réinitialise le curseur, le scroll et la sélection du diff.
+    pub fn reset_diff_cursor(&mut self) {
+        self.diff_cursor = 0;
+        self.diff_scroll = 0;
+        self.diff_selection_anchor = None;
+    }
+
+    /// Active ou désactive la sélection visuelle de lignes depuis le curseur.
+    pub fn toggle_diff_selection(&mut self) {
+        self.diff_selection_anchor = match self.diff_selection_anchor {
+            Some(_) => None,
+            None => Some(self.diff_cursor),
         };
     }
 
+    /// Plage de lignes (début, fin incluse) de la sélection visuelle.
+    pub fn diff_selection(&self) -> Option<(usize, usize)> {
+        let anchor = self.diff_selection_anchor?;
+        Some((anchor.min(self.diff_cursor), anchor.max(self.diff_cursor)))
+    }
+
     // ═══════════════════════════════════════════════════
     // Compatibilité ascendante - champs legacy
     // ═══════════════════════════════════════════════════
@@ -73,11 +176,6 @@ impl StagingState {
         &self.staged.items
     }
 
-    /// Accès mutable aux fichiers stagés (compatibilité).
-    pub fn staged_files_mut(&mut self) -> &mut Vec<StatusEntry> {
-        &mut self.staged.items
-    }
-
     /// Définit les fichiers stagés (compatibilité).
     pub fn set_staged_files(&mut self, files: Vec<StatusEntry>) {
         self.staged.set_items(files);
@@ -88,11 +186,6 @@ impl StagingState {
         &self.unstaged.items
     }
 
-    /// Accès mutable aux fichiers non stagés (compatibilité).
-    pub fn unstaged_files_mut(&mut self) -> &mut Vec<StatusEntry> {
-        &mut self.unstaged.items
-    }
-
     /// Définit les fichiers non stagés (compatibilité).
     pub fn set_unstaged_files(&mut self, files: Vec<StatusEntry>) {
         self.unstaged.set_items(files);
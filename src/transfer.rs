@@ -0,0 +1,239 @@
+//! Opérations distantes (push, pull, fetch) en arrière-plan.
+//!
+//! Chaque opération s'exécute dans un thread dédié qui ouvre sa propre instance
+//! du repository. L'avancement est partagé via un [`ProgressTracker`], lu par
+//! l'interface pour afficher le popup de progression ; le résultat est renvoyé
+//! par un channel et appliqué par la boucle principale une fois l'opération
+//! terminée. L'interface reste utilisable pendant un transfert long.
+//!
+//! Le thread ne fait que des échanges avec le remote : un pull n'y exécute que
+//! son fetch, l'intégration (merge ou rebase) est faite par la boucle principale.
+
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use git2::Repository;
+
+use crate::error::{GitSvError, Result};
+use crate::git::remote::{self, ProgressTracker, PullMode, PushRequest};
+
+/// Opération distante.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemoteOperation {
+    /// Push de la branche courante vers son remote.
+    Push,
+    /// Push d'une branche avec les options choisies (remote, force-with-lease...).
+    PushBranch(PushRequest),
+    /// Push d'un tag vers le remote de la branche courante.
+    PushTag(String),
+    /// Push de tous les tags locaux.
+    PushAllTags,
+    /// Pull de la branche courante avec la stratégie donnée.
+    Pull(PullMode),
+    /// Fetch du remote de la branche courante.
+    Fetch,
+    /// Fetch d'un remote donné (avec prune des branches supprimées).
+    FetchRemote { remote: String, prune: bool },
+}
+
+impl RemoteOperation {
+    /// Libellé affiché (titre du popup, messages).
+    pub fn label(&self) -> &'static str {
+        match self {
+            RemoteOperation::Push
+            | RemoteOperation::PushBranch(_)
+            | RemoteOperation::PushTag(_)
+            | RemoteOperation::PushAllTags => "Push",
+            RemoteOperation::Pull(_) => "Pull",
+            RemoteOperation::Fetch => "Fetch",
+            RemoteOperation::FetchRemote { prune: false, .. } => "Fetch",
+            RemoteOperation::FetchRemote { prune: true, .. } => "Prune",
+        }
+    }
+}
+
+/// Résultat d'une opération distante réussie.
+#[derive(Debug)]
+pub enum RemoteOutcome {
+    /// Push effectué (message décrivant l'action).
+    Pushed(String),
+    /// Fetch d'un pull effectué : l'intégration avec la stratégie donnée reste à faire.
+    PullFetched(PullMode),
+    /// Fetch effectué (message décrivant l'action).
+    Fetched(String),
+}
+
+/// Opération distante en cours d'exécution.
+#[derive(Debug)]
+pub struct RemoteTask {
+    /// Opération exécutée.
+    pub operation: RemoteOperation,
+    /// Avancement et annulation, partagés avec le thread.
+    pub progress: ProgressTracker,
+    /// Instant de démarrage.
+    started: Instant,
+    /// Canal de réception du résultat.
+    result: Receiver<Result<RemoteOutcome>>,
+}
+
+impl RemoteTask {
+    /// Démarre l'opération dans un thread sur le repository donné.
+    pub fn spawn(repo_path: &str, operation: RemoteOperation) -> Result<Self> {
+        // Le thread possède sa propre instance : git2::Repository n'est pas Sync.
+        let repo = Repository::open(repo_path)?;
+        let progress = ProgressTracker::default();
+        let tracker = progress.clone();
+        let (result_tx, result_rx) = mpsc::channel();
+        let thread_operation = operation.clone();
+
+        thread::Builder::new()
+            .name("git_sv-remote".to_string())
+            .spawn(move || {
+                let _ = result_tx.send(run(&repo, &thread_operation, &tracker));
+            })
+            .map_err(|e| GitSvError::Io {
+                source: e,
+                context: "démarrage du thread de transfert".to_string(),
+            })?;
+
+        Ok(Self {
+            operation,
+            progress,
+            started: Instant::now(),
+            result: result_rx,
+        })
+    }
+
+    /// Récupère le résultat si l'opération est terminée, sans bloquer.
+    pub fn try_finish(&self) -> Option<Result<RemoteOutcome>> {
+        match self.result.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err(GitSvError::Other(
+                "Le thread de transfert s'est arrêté".to_string(),
+            ))),
+        }
+    }
+
+    /// Demande l'annulation de l'opération.
+    pub fn cancel(&self) {
+        self.progress.cancel();
+    }
+
+    /// Durée écoulée depuis le démarrage.
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+}
+
+/// Exécute l'opération (dans le thread de transfert).
+fn run(
+    repo: &Repository,
+    operation: &RemoteOperation,
+    progress: &ProgressTracker,
+) -> Result<RemoteOutcome> {
+    match operation {
+        RemoteOperation::Push => {
+            remote::push_current_branch(repo, progress).map(RemoteOutcome::Pushed)
+        }
+        RemoteOperation::PushBranch(request) => {
+            remote::push_branch(repo, request, progress).map(RemoteOutcome::Pushed)
+        }
+        RemoteOperation::PushTag(name) => {
+            remote::push_tag(repo, name, progress).map(RemoteOutcome::Pushed)
+        }
+        RemoteOperation::PushAllTags => {
+            remote::push_all_tags(repo, progress).map(RemoteOutcome::Pushed)
+        }
+        RemoteOperation::Pull(mode) => {
+            remote::fetch_all(repo, progress).map(|_| RemoteOutcome::PullFetched(*mode))
+        }
+        RemoteOperation::Fetch => remote::fetch_all(repo, progress)
+            .map(|_| RemoteOutcome::Fetched("Fetch réussi".to_string())),
+        RemoteOperation::FetchRemote { remote, prune } => {
+            remote::fetch_remote(repo, remote, *prune, progress).map(|_| {
+                let mode = if *prune { " (prune)" } else { "" };
+                RemoteOutcome::Fetched(format!("Fetch de {}{} réussi", remote, mode))
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::tests::test_utils::*;
+
+    /// Attend la fin de l'opération (au plus 10 secondes).
+    fn wait(task: &RemoteTask) -> Result<RemoteOutcome> {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while Instant::now() < deadline {
+            if let Some(result) = task.try_finish() {
+                return result;
+            }
+            thread::sleep(Duration::from_millis(20));
+        }
+        panic!("l'opération distante ne s'est pas terminée");
+    }
+
+    #[test]
+    fn test_push_then_fetch_in_background() {
+        let (dir, repo) = create_test_repo();
+        let remote_dir = tempfile::TempDir::new().unwrap();
+        let bare = Repository::init_bare(remote_dir.path()).unwrap();
+        repo.remote("origin", remote_dir.path().to_str().unwrap())
+            .unwrap();
+        commit_file(&repo, "a.txt", "v1", "First");
+        let branch = repo.head().unwrap().shorthand().unwrap().to_string();
+        let repo_path = dir.path().to_str().unwrap();
+
+        let push = RemoteTask::spawn(repo_path, RemoteOperation::Push).unwrap();
+        assert!(matches!(wait(&push), Ok(RemoteOutcome::Pushed(_))));
+        assert!(bare
+            .find_reference(&format!("refs/heads/{}", branch))
+            .is_ok());
+
+        let fetch = RemoteTask::spawn(repo_path, RemoteOperation::Fetch).unwrap();
+        assert!(matches!(wait(&fetch), Ok(RemoteOutcome::Fetched(_))));
+    }
+
+    #[test]
+    fn test_pull_only_fetches_in_background() {
+        let (dir, repo) = create_test_repo();
+        let remote_dir = tempfile::TempDir::new().unwrap();
+        Repository::init_bare(remote_dir.path()).unwrap();
+        repo.remote("origin", remote_dir.path().to_str().unwrap())
+            .unwrap();
+        let first = commit_file(&repo, "a.txt", "v1", "First");
+        commit_file(&repo, "a.txt", "v2", "Second");
+        let branch = repo.head().unwrap().shorthand().unwrap().to_string();
+        let repo_path = dir.path().to_str().unwrap();
+        let push = RemoteTask::spawn(repo_path, RemoteOperation::Push).unwrap();
+        wait(&push).unwrap();
+        let mut config = repo.config().unwrap();
+        config
+            .set_str(&format!("branch.{}.remote", branch), "origin")
+            .unwrap();
+        config
+            .set_str(
+                &format!("branch.{}.merge", branch),
+                &format!("refs/heads/{}", branch),
+            )
+            .unwrap();
+        repo.reset(
+            &repo.find_object(first, None).unwrap(),
+            git2::ResetType::Hard,
+            None,
+        )
+        .unwrap();
+
+        // Le thread ne fait que le fetch : la branche locale n'avance pas
+        let pull = RemoteTask::spawn(repo_path, RemoteOperation::Pull(PullMode::Merge)).unwrap();
+        assert!(matches!(
+            wait(&pull),
+            Ok(RemoteOutcome::PullFetched(PullMode::Merge))
+        ));
+        assert_eq!(repo.head().unwrap().target(), Some(first));
+    }
+}
@@ -8,9 +8,10 @@ use ratatui::{
 
 use crate::app::{BranchesFocus, BranchesSection, BranchesViewState, InputAction};
 use crate::ui::common::centered_rect;
+use crate::ui::keymap::{self, KeyContext};
 use crate::utils::time::format_relative_time;
 
-/// Rend la vue complète branches/worktrees/stashes.
+/// Rend la vue complète branches/worktrees/stashes/tags.
 pub fn render(
     frame: &mut Frame,
     state: &BranchesViewState,
@@ -46,6 +47,14 @@ pub fn render(
             render_stashes_list(frame, state, layout.list_panel);
             render_stash_detail(frame, state, layout.detail_panel);
         }
+        BranchesSection::Tags => {
+            render_tags_list(frame, state, layout.list_panel);
+            render_tag_detail(frame, state, layout.detail_panel);
+        }
+        BranchesSection::Remotes => {
+            render_remotes_list(frame, state, layout.list_panel);
+            render_remote_detail(frame, state, layout.detail_panel);
+        }
     }
 
     // Help bar contextuelle.
@@ -96,6 +105,8 @@ fn render_tabs(frame: &mut Frame, active: &BranchesSection, area: Rect) {
         ("Branches", BranchesSection::Branches),
         ("Worktrees", BranchesSection::Worktrees),
         ("Stashes", BranchesSection::Stashes),
+        ("Tags", BranchesSection::Tags),
+        ("Remotes", BranchesSection::Remotes),
     ];
 
     let mut spans = Vec::new();
@@ -531,6 +542,175 @@ fn render_stash_detail(frame: &mut Frame, state: &BranchesViewState, area: Rect)
     frame.render_widget(paragraph, area);
 }
 
+/// Rend la liste des tags.
+fn render_tags_list(frame: &mut Frame, state: &BranchesViewState, area: Rect) {
+    let items: Vec<ListItem> = state
+        .tags
+        .iter()
+        .map(|tag| {
+            let marker = if tag.is_annotated() { "◆ " } else { "◇ " };
+            let line = Line::from(vec![
+                Span::styled(marker, Style::default().fg(Color::Yellow)),
+                Span::styled(&tag.name, Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled(
+                    format!("  {:.7}", tag.target),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]);
+            ListItem::new(line)
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(" Tags ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        );
+
+    let mut list_state = ListState::default();
+    list_state.select(Some(state.tag_selected()));
+    frame.render_stateful_widget(list, area, &mut list_state);
+}
+
+/// Rend le détail d'un tag.
+fn render_tag_detail(frame: &mut Frame, state: &BranchesViewState, area: Rect) {
+    let content = if let Some(tag) = state.tags.get(state.tag_selected()) {
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled("Nom: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(&tag.name),
+            ]),
+            Line::from(vec![
+                Span::styled("Type: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(if tag.is_annotated() {
+                    "annoté"
+                } else {
+                    "léger"
+                }),
+            ]),
+            Line::from(vec![
+                Span::styled("Cible: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled(
+                    format!("{:.7}", tag.target),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::raw(format!(" {}", tag.summary)),
+            ]),
+        ];
+
+        if let Some(ref tagger) = tag.tagger {
+            lines.push(Line::from(vec![
+                Span::styled("Auteur: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(tagger),
+            ]));
+        }
+
+        if let Some(date) = tag.tagger_date {
+            lines.push(Line::from(vec![
+                Span::styled("Date: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled(format_relative_time(date), Style::default().fg(Color::Cyan)),
+            ]));
+        }
+
+        if let Some(ref message) = tag.message {
+            lines.push(Line::from(""));
+            lines.push(Line::from(vec![Span::styled(
+                "Message:",
+                Style::default().add_modifier(Modifier::BOLD),
+            )]));
+            for line in message.lines() {
+                lines.push(Line::from(line));
+            }
+        }
+
+        lines
+    } else {
+        vec![Line::from("Aucun tag sélectionné")]
+    };
+
+    let paragraph =
+        Paragraph::new(content).block(Block::default().title(" Détail ").borders(Borders::ALL));
+    frame.render_widget(paragraph, area);
+}
+
+/// Rend la liste des remotes.
+fn render_remotes_list(frame: &mut Frame, state: &BranchesViewState, area: Rect) {
+    let items: Vec<ListItem> = state
+        .remotes
+        .iter()
+        .map(|remote| {
+            let line = Line::from(vec![
+                Span::styled(&remote.name, Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled(
+                    format!("  {}", remote.fetch_url.as_deref().unwrap_or("")),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]);
+            ListItem::new(line)
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(" Remotes ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        );
+
+    let mut list_state = ListState::default();
+    list_state.select(Some(state.remote_selected()));
+    frame.render_stateful_widget(list, area, &mut list_state);
+}
+
+/// Rend le détail d'un remote.
+fn render_remote_detail(frame: &mut Frame, state: &BranchesViewState, area: Rect) {
+    let content = if let Some(remote) = state.remotes.get(state.remote_selected()) {
+        let push_url = match remote.push_url {
+            Some(ref url) => Span::raw(url),
+            None => Span::styled(
+                format!(
+                    "{} (identique au fetch)",
+                    remote.fetch_url.as_deref().unwrap_or("")
+                ),
+                Style::default().fg(Color::DarkGray),
+            ),
+        };
+        vec![
+            Line::from(vec![
+                Span::styled("Nom: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(&remote.name),
+            ]),
+            Line::from(vec![
+                Span::styled("URL fetch: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(remote.fetch_url.as_deref().unwrap_or("(aucune)")),
+            ]),
+            Line::from(vec![
+                Span::styled("URL push: ", Style::default().add_modifier(Modifier::BOLD)),
+                push_url,
+            ]),
+        ]
+    } else {
+        vec![Line::from("Aucun remote configuré")]
+    };
+
+    let paragraph =
+        Paragraph::new(content).block(Block::default().title(" Détail ").borders(Borders::ALL));
+    frame.render_widget(paragraph, area);
+}
+
 /// Rend la barre d'aide de la vue branches.
 fn render_branches_help(
     frame: &mut Frame,
@@ -539,19 +719,9 @@ fn render_branches_help(
     area: Rect,
 ) {
     let help_text = if *focus == BranchesFocus::Input {
-        "Enter:confirmer  Esc:annuler  ←→:curseur"
+        "Enter:confirmer  Esc:annuler  ←→:curseur".to_string()
     } else {
-        match section {
-            BranchesSection::Branches => {
-                "Tab:section  Enter:checkout  n:new  d:delete  r:rename  m:merge  R:remote  P:push  1:graph  2:staging"
-            }
-            BranchesSection::Worktrees => {
-                "Tab:section  n:new  d:delete  1:graph  2:staging"
-            }
-            BranchesSection::Stashes => {
-                "Tab:section  h/l:fichiers  a:apply  p:pop  d:drop  s:save  1:graph  2:staging"
-            }
-        }
+        keymap::current().bar_text(&KeyContext::branches(*section))
     };
 
     let line = Line::from(vec![Span::styled(
@@ -572,6 +742,11 @@ fn render_input_overlay(frame: &mut Frame, state: &BranchesViewState, area: Rect
         Some(InputAction::RenameBranch) => " Renommer la branche ",
         Some(InputAction::CreateWorktree) => " Nouveau worktree (nom chemin [branche]) ",
         Some(InputAction::SaveStash) => " Message du stash ",
+        Some(InputAction::CreateTag) => " Nouveau tag (nom [message → annoté]) ",
+        Some(InputAction::AddRemote) => " Nouveau remote (nom url) ",
+        Some(InputAction::RenameRemote) => " Renommer le remote ",
+        Some(InputAction::SetRemoteUrl) => " URL du remote ",
+        Some(InputAction::SetRemotePushUrl) => " URL de push du remote ",
         None => " Input ",
     };
 
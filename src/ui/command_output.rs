@@ -0,0 +1,45 @@
+//! Popup affichant la sortie d'une commande personnalisée.
+
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::state::CommandOutputState;
+use crate::ui::common::centered_rect;
+use crate::ui::keymap::{self, KeyContext};
+
+/// Rend la sortie capturée, bordure verte en cas de succès et rouge sinon.
+pub fn render(frame: &mut Frame, state: &CommandOutputState, area: Rect) {
+    let popup_area = centered_rect(70, 70, area);
+    frame.render_widget(Clear, popup_area);
+
+    let color = if state.success {
+        Color::Green
+    } else {
+        Color::Red
+    };
+    let status = if state.success { "succès" } else { "échec" };
+
+    let lines: Vec<Line> = state
+        .lines
+        .iter()
+        .skip(state.scroll)
+        .map(|line| Line::raw(line.as_str()))
+        .collect();
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(format!(" {} ({}) ", state.title, status))
+            .title_bottom(format!(
+                " {} ",
+                keymap::current().bar_text(&[KeyContext::CommandOutput])
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(color)),
+    );
+    frame.render_widget(paragraph, popup_area);
+}
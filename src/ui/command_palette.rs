@@ -0,0 +1,85 @@
+//! Popup de la palette de commandes.
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use crate::state::CommandPaletteState;
+use crate::ui::common::centered_rect;
+
+/// Largeur de la colonne des descriptions.
+const DESCRIPTION_WIDTH: usize = 42;
+
+/// Rend la palette de commandes : la saisie puis les actions correspondantes.
+pub fn render(frame: &mut Frame, state: &CommandPaletteState, area: Rect) {
+    let popup_area = centered_rect(60, 60, area);
+    frame.render_widget(Clear, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(3)])
+        .split(popup_area);
+
+    let input = Paragraph::new(Line::from(vec![
+        Span::styled("> ", Style::default().fg(Color::Yellow)),
+        Span::raw(state.query.as_str()),
+    ]))
+    .block(
+        Block::default()
+            .title(" Palette de commandes ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow)),
+    );
+    frame.render_widget(input, chunks[0]);
+    frame.set_cursor_position((
+        chunks[0].x + state.query.chars().count() as u16 + 3,
+        chunks[0].y + 1,
+    ));
+
+    let items: Vec<ListItem> = state
+        .matching_entries()
+        .map(|entry| {
+            ListItem::new(Line::from(vec![
+                Span::raw(format!(
+                    " {:<width$}",
+                    entry.description,
+                    width = DESCRIPTION_WIDTH
+                )),
+                Span::styled(
+                    format!("{:<16}", entry.keys),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::styled(entry.context, Style::default().fg(Color::DarkGray)),
+            ]))
+        })
+        .collect();
+
+    let title = format!(
+        " {}/{} · Enter:exécuter  ↑↓:naviguer  Esc:fermer ",
+        state.matches.len(),
+        state.entries.len()
+    );
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow)),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        );
+
+    let mut list_state = ListState::default();
+    if !state.matches.is_empty() {
+        list_state.select(Some(state.matches.selected_index()));
+    }
+    frame.render_stateful_widget(list, chunks[1], &mut list_state);
+}
@@ -22,7 +22,7 @@ pub use block::StyledBlock;
 pub use help_bar::{HelpBar, KeyBinding};
 pub use list::{list_item, list_item_styled, StyledList};
 pub use popup::Popup;
-pub use rect::{centered_rect, centered_rect_fixed, is_terminal_size_adequate};
+pub use rect::{centered_rect, centered_rect_fixed};
 pub use style::{
     border_style, diff_add_style, diff_header_style, diff_remove_style, dim_style, error_style,
     highlight_style, success_style, title_style, FOCUS_COLOR, INACTIVE_COLOR,
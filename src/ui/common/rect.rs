@@ -35,8 +35,3 @@ pub fn centered_rect_fixed(width: u16, height: u16, area: Rect) -> Rect {
 
     Rect::new(x, y, width.min(area.width), height.min(area.height))
 }
-
-/// Vérifie si le terminal est suffisamment grand.
-pub fn is_terminal_size_adequate(area: Rect, min_width: u16, min_height: u16) -> bool {
-    area.width >= min_width && area.height >= min_height
-}
@@ -17,16 +17,26 @@ pub enum ConfirmAction {
     WorktreeRemove(String),
     /// Supprimer un stash
     StashDrop(usize),
+    /// Supprimer un tag
+    TagDelete(String),
+    /// Supprimer un remote
+    RemoteRemove(String),
     /// Discard les modifications d'un fichier
     DiscardFile(String),
     /// Discard toutes les modifications
     DiscardAll,
+    /// Discard un hunk d'un fichier (chemin, index du hunk)
+    DiscardHunk(String, usize),
     /// Cherry-pick un commit
     CherryPick(git2::Oid),
+    /// Revert un commit (mainline pour un commit de merge)
+    Revert(git2::Oid, Option<u32>),
     /// Merger une branche (source, cible)
     MergeBranch(String, String),
     /// Avorter le merge en cours
     AbortMerge,
+    /// Reset --hard de la branche courante sur un commit
+    ResetHard(git2::Oid),
 }
 
 impl ConfirmAction {
@@ -48,6 +58,15 @@ impl ConfirmAction {
                     index
                 )
             }
+            ConfirmAction::TagDelete(name) => {
+                format!("Êtes-vous sûr de vouloir supprimer le tag '{}' ?", name)
+            }
+            ConfirmAction::RemoteRemove(name) => {
+                format!(
+                    "Êtes-vous sûr de vouloir supprimer le remote '{}' et ses branches de suivi ?",
+                    name
+                )
+            }
             ConfirmAction::DiscardFile(path) => {
                 format!(
                     "Êtes-vous sûr de vouloir discard les modifications de '{}' ?",
@@ -58,6 +77,13 @@ impl ConfirmAction {
                 "Êtes-vous sûr de vouloir discard TOUTES les modifications non stagées ?"
                     .to_string()
             }
+            ConfirmAction::DiscardHunk(path, hunk) => {
+                format!(
+                    "Êtes-vous sûr de vouloir discard le hunk {} de '{}' ?",
+                    hunk + 1,
+                    path
+                )
+            }
             ConfirmAction::CherryPick(oid) => {
                 format!(
                     "Êtes-vous sûr de vouloir cherry-pick le commit {} ?",
@@ -67,9 +93,22 @@ impl ConfirmAction {
             ConfirmAction::MergeBranch(source, target) => {
                 format!("Merger '{}' dans '{}' ?", source, target)
             }
+            ConfirmAction::Revert(oid, mainline) => match mainline {
+                Some(parent) => format!(
+                    "Revert le merge {:.7} par rapport au parent {} ?",
+                    oid, parent
+                ),
+                None => format!("Êtes-vous sûr de vouloir revert le commit {:.7} ?", oid),
+            },
             ConfirmAction::AbortMerge => {
                 "Êtes-vous sûr de vouloir avorter le merge en cours ?".to_string()
             }
+            ConfirmAction::ResetHard(oid) => {
+                format!(
+                    "Reset --hard sur {:.7} ? Les modifications non commitées seront perdues.",
+                    oid
+                )
+            }
         }
     }
 
@@ -79,11 +118,38 @@ impl ConfirmAction {
             ConfirmAction::BranchDelete(_) => "Confirmer la suppression de branche",
             ConfirmAction::WorktreeRemove(_) => "Confirmer la suppression de worktree",
             ConfirmAction::StashDrop(_) => "Confirmer la suppression de stash",
+            ConfirmAction::TagDelete(_) => "Confirmer la suppression de tag",
+            ConfirmAction::RemoteRemove(_) => "Confirmer la suppression de remote",
             ConfirmAction::DiscardFile(_) => "Confirmer le discard de fichier",
             ConfirmAction::DiscardAll => "Confirmer le discard de tous les fichiers",
+            ConfirmAction::DiscardHunk(_, _) => "Confirmer le discard de hunk",
             ConfirmAction::CherryPick(_) => "Confirmer le cherry-pick",
+            ConfirmAction::Revert(_, _) => "Confirmer le revert",
             ConfirmAction::MergeBranch(_, _) => "Confirmer le merge",
             ConfirmAction::AbortMerge => "Confirmer l'annulation du merge",
+            ConfirmAction::ResetHard(_) => "Confirmer le reset --hard",
+        }
+    }
+
+    /// Libellé du snapshot à enregistrer avant d'exécuter l'action.
+    ///
+    /// `ResetHard` passe par `apply_reset`, qui enregistre son propre snapshot ;
+    /// `DiscardHunk` enregistre le sien une fois le diff affiché vérifié.
+    pub fn undo_label(&self) -> Option<&'static str> {
+        match self {
+            ConfirmAction::BranchDelete(_) => Some("suppression de branche"),
+            ConfirmAction::StashDrop(_) => Some("suppression de stash"),
+            ConfirmAction::TagDelete(_) => Some("suppression de tag"),
+            ConfirmAction::DiscardFile(_) => Some("discard du fichier"),
+            ConfirmAction::DiscardAll => Some("discard de toutes les modifications"),
+            ConfirmAction::CherryPick(_) => Some("cherry-pick"),
+            ConfirmAction::Revert(_, _) => Some("revert"),
+            ConfirmAction::MergeBranch(_, _) => Some("merge"),
+            ConfirmAction::AbortMerge => Some("abandon du merge"),
+            ConfirmAction::WorktreeRemove(_)
+            | ConfirmAction::RemoteRemove(_)
+            | ConfirmAction::DiscardHunk(_, _)
+            | ConfirmAction::ResetHard(_) => None,
         }
     }
 }
@@ -4,12 +4,13 @@ use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
     Frame,
 };
 
 use crate::git::conflict::{ConflictResolution, ConflictResolutionMode, ConflictType};
 use crate::state::{ConflictPanelFocus, ConflictsState};
+use crate::ui::keymap::{self, KeyContext};
 use crate::ui::theme::current_theme;
 
 /// Rend la vue de résolution de conflits.
@@ -19,6 +20,7 @@ pub fn render(
     current_branch: &Option<String>,
     repo_path: &str,
     flash_message: Option<&str>,
+    rebasing: bool,
 ) {
     let area = frame.area();
 
@@ -60,7 +62,7 @@ pub fn render(
     render_result_panel(frame, state, resolution_layout[2]);
 
     // Help bar
-    let help_bar = build_help_bar(state);
+    let help_bar = build_help_bar(state, rebasing);
     frame.render_widget(help_bar, main_layout[2]);
 }
 
@@ -102,7 +104,9 @@ fn build_status_bar<'a>(
 }
 
 /// Construit la help bar avec indication du mode actif.
-fn build_help_bar<'a>(state: &'a ConflictsState) -> Paragraph<'a> {
+///
+/// Pendant un rebase, les raccourcis pour continuer, passer ou abandonner y figurent.
+fn build_help_bar<'a>(state: &'a ConflictsState, rebasing: bool) -> Paragraph<'a> {
     let theme = current_theme();
     let mode_indicator = match state.resolution_mode {
         ConflictResolutionMode::File => "Mode:Fichier",
@@ -111,30 +115,12 @@ fn build_help_bar<'a>(state: &'a ConflictsState) -> Paragraph<'a> {
     };
 
     // Aide contextuelle selon le panneau actif et le mode
+    let keys = keymap::current().bar_text(&KeyContext::conflicts(state, rebasing));
     let help_text = if state.is_editing {
-        // Mode édition : raccourcis d'édition
-        "Esc:Annuler  Ctrl+S:Sauvegarder  ↑↓←→:Curseur  Enter:Nouvelle ligne  Backspace:Suppr"
-            .to_string()
-    } else if state.panel_focus == ConflictPanelFocus::FileList {
-        format!(
-            "o/←:Ours  t/→:Theirs  Tab:Panneau  ↑↓:Nav  r:Résoudre  V:Finaliser  q:Quitter  A:Avorter | {}",
-            mode_indicator
-        )
+        // Mode édition : seuls les raccourcis d'édition sont actifs
+        keys
     } else {
-        // Aide contextuelle selon le mode de résolution
-        let action_help = match state.resolution_mode {
-            ConflictResolutionMode::File => "Enter:Choisir  o:Ours  t:Theirs  Tab:Panneau  ↑↓:Nav",
-            ConflictResolutionMode::Block => {
-                "Espace:Choisir  Enter:Valider  b:Les deux  Tab:Panneau  ↑↓:Nav"
-            }
-            ConflictResolutionMode::Line => {
-                "Espace:Choisir ligne  Enter:Valider  Tab:Panneau  ↑↓:Nav"
-            }
-        };
-        format!(
-            "{}  F/B/L:Mode  i:Éditer  V:Finaliser  q:Quitter  A:Avorter | {}",
-            action_help, mode_indicator
-        )
+        format!("{} | {}", keys, mode_indicator)
     };
 
     Paragraph::new(help_text)
@@ -762,99 +748,3 @@ pub fn render_nav_indicator(has_conflicts: bool) -> Line<'static> {
 
     Line::from(spans)
 }
-
-/// Rend l'overlay d'aide pour la vue conflits.
-pub fn render_help_overlay(frame: &mut Frame, area: Rect) {
-    let theme = current_theme();
-    let popup_area = centered_rect(70, 80, area);
-
-    frame.render_widget(Clear, popup_area);
-
-    let content = vec![
-        Line::from(vec![Span::styled(
-            "Raccourcis de la vue Conflits",
-            Style::default()
-                .fg(theme.primary)
-                .add_modifier(Modifier::BOLD),
-        )]),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "Navigation",
-            Style::default().fg(theme.warning),
-        )]),
-        Line::from("  ↑/↓ ou j/k  - Naviguer (fichiers / sections / lignes selon le panneau)"),
-        Line::from("  Tab         - Panneau suivant (Fichiers → Ours → Theirs → Résultat)"),
-        Line::from("  Shift+Tab   - Panneau précédent"),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "Résolution",
-            Style::default().fg(theme.warning),
-        )]),
-        Line::from("  o           - Garder la version 'ours' (HEAD)"),
-        Line::from("  t           - Garder la version 'theirs' (branche mergée)"),
-        Line::from("  b           - Garder les deux versions (mode Bloc uniquement)"),
-        Line::from("  Espace      - Choisir/déchoisir un bloc ou une ligne"),
-        Line::from("  Enter       - Valider et sauvegarder le fichier courant"),
-        Line::from("  r           - Marquer comme résolu (depuis la liste de fichiers)"),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "Édition du résultat",
-            Style::default().fg(theme.warning),
-        )]),
-        Line::from("  i ou e      - Entrer en mode édition (panneau Résultat)"),
-        Line::from("  Esc         - Quitter le mode édition"),
-        Line::from("  ↑/↓/←/→     - Déplacer le curseur"),
-        Line::from("  Caractères  - Insérer du texte"),
-        Line::from("  Backspace   - Supprimer le caractère avant"),
-        Line::from("  Delete      - Supprimer le caractère sous le curseur"),
-        Line::from("  Enter       - Insérer une nouvelle ligne"),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "Actions globales",
-            Style::default().fg(theme.warning),
-        )]),
-        Line::from("  F/B/L       - Mode Fichier/Bloc/Ligne (touche directe)"),
-        Line::from("  V           - Finaliser le merge (créer le commit)"),
-        Line::from("  q ou Esc    - Annuler le merge et revenir au graph"),
-        Line::from("  1/2/3       - Basculer vers Graph/Staging/Branches"),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "Appuyez sur ? pour fermer cette aide",
-            Style::default().fg(theme.text_secondary),
-        )]),
-    ];
-
-    let paragraph = Paragraph::new(content)
-        .block(
-            Block::default()
-                .title("Aide - Résolution de conflits")
-                .title_alignment(Alignment::Center)
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(theme.primary)),
-        )
-        .alignment(Alignment::Left)
-        .wrap(Wrap { trim: true });
-
-    frame.render_widget(paragraph, popup_area);
-}
-
-/// Calcule un rectangle centré de dimensions données (en pourcentage).
-fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
-    let popup_layout = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Percentage((100 - percent_y) / 2),
-            Constraint::Percentage(percent_y),
-            Constraint::Percentage((100 - percent_y) / 2),
-        ])
-        .split(r);
-
-    Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage((100 - percent_x) / 2),
-            Constraint::Percentage(percent_x),
-            Constraint::Percentage((100 - percent_x) / 2),
-        ])
-        .split(popup_layout[1])[1]
-}
@@ -0,0 +1,91 @@
+//! Popup de l'assistant Conventional Commits (type puis scope).
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use crate::conventional::type_description;
+use crate::state::{ConventionalPickerState, ConventionalStep};
+use crate::ui::common::centered_rect;
+
+/// Largeur de la colonne des types et scopes.
+const OPTION_WIDTH: usize = 12;
+
+/// Rend l'assistant : la recherche puis les types ou scopes correspondants.
+pub fn render(frame: &mut Frame, state: &ConventionalPickerState, area: Rect) {
+    let popup_area = centered_rect(50, 60, area);
+    frame.render_widget(Clear, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(3)])
+        .split(popup_area);
+
+    let title = match &state.step {
+        ConventionalStep::Type => " Type de commit ".to_string(),
+        ConventionalStep::Scope { kind } => format!(" Scope de « {} » ", kind),
+    };
+    let input = Paragraph::new(Line::from(vec![
+        Span::styled("> ", Style::default().fg(Color::Yellow)),
+        Span::raw(state.query.as_str()),
+    ]))
+    .block(
+        Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow)),
+    );
+    frame.render_widget(input, chunks[0]);
+    frame.set_cursor_position((
+        chunks[0].x + state.query.chars().count() as u16 + 3,
+        chunks[0].y + 1,
+    ));
+
+    let is_type_step = state.step == ConventionalStep::Type;
+    let items: Vec<ListItem> = state
+        .matching_options()
+        .map(|option| {
+            let line = match option {
+                "" => Line::styled(" (sans scope)", Style::default().fg(Color::DarkGray)),
+                option if is_type_step => Line::from(vec![
+                    Span::raw(format!(" {:<width$}", option, width = OPTION_WIDTH)),
+                    Span::styled(
+                        type_description(option),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ]),
+                option => Line::raw(format!(" {}", option)),
+            };
+            ListItem::new(line)
+        })
+        .collect();
+
+    let hint = if is_type_step || !state.matches.is_empty() {
+        "Enter:choisir  ↑↓:naviguer  Esc:fermer"
+    } else {
+        "Enter:nouveau scope  Esc:fermer"
+    };
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(format!(" {} ", hint))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow)),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        );
+
+    let mut list_state = ListState::default();
+    if !state.matches.is_empty() {
+        list_state.select(Some(state.matches.selected_index()));
+    }
+    frame.render_stateful_widget(list, chunks[1], &mut list_state);
+}
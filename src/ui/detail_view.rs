@@ -6,7 +6,7 @@ use ratatui::{
     Frame,
 };
 
-use crate::git::graph::GraphRow;
+use crate::git::graph::{CommitNode, GraphRow};
 use crate::ui::theme::current_theme;
 use crate::utils::format_absolute_time;
 
@@ -18,10 +18,15 @@ pub fn render(
     area: Rect,
     is_focused: bool,
 ) {
+    let node = graph.get(selected_index).map(|row| &row.node);
+    render_node(frame, node, area, is_focused);
+}
+
+/// Rend le panneau de détail d'un commit donné.
+pub fn render_node(frame: &mut Frame, node: Option<&CommitNode>, area: Rect, is_focused: bool) {
     let theme = current_theme();
 
-    let content: Vec<Line<'static>> = if let Some(row) = graph.get(selected_index) {
-        let node = &row.node;
+    let content: Vec<Line<'static>> = if let Some(node) = node {
         let date_str = format_absolute_time(node.timestamp);
         let oid_str = node.oid.to_string();
         let author = node.author.clone();
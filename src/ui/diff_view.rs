@@ -11,7 +11,22 @@ use crate::git::diff::{DiffLineType, DiffViewMode, FileDiff};
 /// Largeur minimale pour le mode side-by-side (en caractères par colonne).
 const MIN_SIDE_BY_SIDE_WIDTH: u16 = 60;
 
+/// Lignes du diff à mettre en évidence (curseur et hunk courant).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiffHighlight {
+    /// Ligne sous le curseur.
+    pub cursor: usize,
+    /// Première ligne mise en évidence.
+    pub start: usize,
+    /// Dernière ligne mise en évidence (incluse).
+    pub end: usize,
+    /// La plage est une sélection visuelle (et non le hunk courant).
+    pub visual: bool,
+}
+
 /// Rend le diff d'un fichier avec coloration syntaxique.
+///
+/// La mise en évidence n'est affichée qu'en mode unifié.
 pub fn render(
     frame: &mut Frame,
     diff: Option<&FileDiff>,
@@ -19,6 +34,7 @@ pub fn render(
     area: Rect,
     is_focused: bool,
     view_mode: DiffViewMode,
+    highlight: Option<DiffHighlight>,
 ) {
     // Déterminer si on peut utiliser le mode side-by-side.
     let can_side_by_side = area.width >= MIN_SIDE_BY_SIDE_WIDTH * 2 + 3; // 2 colonnes + séparateur
@@ -29,7 +45,9 @@ pub fn render(
     };
 
     match effective_mode {
-        DiffViewMode::Unified => render_unified(frame, diff, scroll_offset, area, is_focused),
+        DiffViewMode::Unified => {
+            render_unified(frame, diff, scroll_offset, area, is_focused, highlight)
+        }
         DiffViewMode::SideBySide => {
             render_side_by_side(frame, diff, scroll_offset, area, is_focused)
         }
@@ -43,9 +61,16 @@ fn render_unified(
     scroll_offset: usize,
     area: Rect,
     is_focused: bool,
+    highlight: Option<DiffHighlight>,
 ) {
     let content = match diff {
-        Some(d) => build_diff_lines(d),
+        Some(d) => {
+            let lines = build_diff_lines(d);
+            match highlight {
+                Some(h) => add_highlight_gutter(lines, h),
+                None => lines,
+            }
+        }
         None => vec![Line::from("Sélectionnez un fichier pour voir le diff")],
     };
 
@@ -206,6 +231,37 @@ fn build_diff_lines(diff: &FileDiff) -> Vec<Line<'static>> {
         .collect()
 }
 
+/// Ajoute une gouttière marquant le curseur et les lignes mises en évidence.
+fn add_highlight_gutter(lines: Vec<Line<'static>>, highlight: DiffHighlight) -> Vec<Line<'static>> {
+    lines
+        .into_iter()
+        .enumerate()
+        .map(|(idx, line)| {
+            let marker = if idx == highlight.cursor {
+                Span::styled(
+                    "▶",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(ratatui::style::Modifier::BOLD),
+                )
+            } else if (highlight.start..=highlight.end).contains(&idx) {
+                let color = if highlight.visual {
+                    Color::Magenta
+                } else {
+                    Color::Cyan
+                };
+                Span::styled("▌", Style::default().fg(color))
+            } else {
+                Span::raw(" ")
+            };
+
+            let mut spans = vec![marker];
+            spans.extend(line.spans);
+            Line::from(spans)
+        })
+        .collect()
+}
+
 /// Une ligne pour l'affichage side-by-side.
 #[derive(Debug, Clone)]
 struct SideBySideLine {
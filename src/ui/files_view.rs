@@ -7,23 +7,41 @@ use ratatui::{
 };
 
 use crate::app::BottomLeftMode;
+use crate::git::commit::CommitInfo;
 use crate::git::diff::{DiffFile, DiffStatus};
 use crate::git::repo::StatusEntry;
 
+/// Contenu du panneau de fichiers.
+pub struct FilesPanel<'a> {
+    /// Fichiers modifiés par le commit sélectionné.
+    pub commit_files: &'a [DiffFile],
+    /// Status du working directory.
+    pub status_entries: &'a [StatusEntry],
+    /// Parents du commit de merge sélectionné (choix de la mainline).
+    pub parents: &'a [CommitInfo],
+    /// Hash court du commit sélectionné, pour le titre.
+    pub selected_commit_hash: Option<String>,
+    /// Mode d'affichage du panneau.
+    pub mode: BottomLeftMode,
+    /// Index de l'élément sélectionné dans la liste affichée.
+    pub selected_index: usize,
+}
+
 /// Rend le panneau de fichiers dans la zone donnée.
 ///
 /// Affiche soit les fichiers du commit sélectionné, soit le status du working directory
-/// selon le mode actif.
-pub fn render(
-    frame: &mut Frame,
-    commit_files: &[DiffFile],
-    status_entries: &[StatusEntry],
-    selected_commit_hash: Option<String>,
-    mode: BottomLeftMode,
-    area: Rect,
-    is_focused: bool,
-    file_selected_index: usize,
-) {
+/// selon le mode actif. En mode Parents, un commit de merge affiche ses parents
+/// pour choisir la mainline.
+pub fn render(frame: &mut Frame, panel: FilesPanel, area: Rect, is_focused: bool) {
+    let FilesPanel {
+        commit_files,
+        status_entries,
+        parents,
+        selected_commit_hash,
+        mode,
+        selected_index: file_selected_index,
+    } = panel;
+
     let (items, title) = match mode {
         BottomLeftMode::Files => {
             let items = build_commit_file_items(commit_files);
@@ -31,6 +49,10 @@ pub fn render(
             let title = format!(" Fichiers — {} ", hash);
             (items, title)
         }
+        BottomLeftMode::Parents if !parents.is_empty() => {
+            let items = build_parent_items(parents);
+            (items, " Parents (mainline) ".to_string())
+        }
         BottomLeftMode::Parents => {
             let items = build_status_items(status_entries);
             let title = format!(" Status ({} fichiers) ", status_entries.len());
@@ -92,6 +114,25 @@ fn build_commit_file_items(files: &[DiffFile]) -> Vec<ListItem> {
         .collect()
 }
 
+/// Construit les items pour les parents d'un commit de merge.
+fn build_parent_items(parents: &[CommitInfo]) -> Vec<ListItem<'_>> {
+    parents
+        .iter()
+        .enumerate()
+        .map(|(i, parent)| {
+            let line = Line::from(vec![
+                Span::styled(format!(" {} ", i + 1), Style::default().fg(Color::Cyan)),
+                Span::styled(
+                    format!("{} ", parent.short_hash()),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::raw(&parent.message),
+            ]);
+            ListItem::new(line)
+        })
+        .collect()
+}
+
 /// Construit les items pour le status du working directory.
 fn build_status_items(entries: &[StatusEntry]) -> Vec<ListItem> {
     entries
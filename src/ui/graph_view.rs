@@ -13,17 +13,32 @@ use crate::utils::format_relative_time;
 /// Espacement entre les colonnes (en caractères).
 const COL_SPACING: usize = 2;
 
+/// Contenu du panneau du graphe.
+pub struct GraphPanel<'a> {
+    /// Rangées du graphe.
+    pub graph: &'a [GraphRow],
+    /// Branche courante, pour le titre.
+    pub current_branch: Option<&'a str>,
+    /// Index du commit sélectionné.
+    pub selected_index: usize,
+    /// Nombre total de commits (affiché si le graphe est filtré).
+    pub total_commits: usize,
+}
+
 /// Rend le graphe de commits dans la zone donnée.
 pub fn render(
     frame: &mut Frame,
-    graph: &[GraphRow],
-    current_branch: &Option<String>,
-    selected_index: usize,
-    total_commits: usize,
+    panel: GraphPanel,
     area: Rect,
     state: &mut ListState,
     is_focused: bool,
 ) {
+    let GraphPanel {
+        graph,
+        current_branch,
+        selected_index,
+        total_commits,
+    } = panel;
     let theme = current_theme();
 
     // Calculer la largeur disponible pour le contenu (hors bordures).
@@ -32,7 +47,7 @@ pub fn render(
     // Construire les lignes du graphe avec les edges de connexion.
     let items = build_graph_items(graph, selected_index, content_width);
 
-    let branch_name = current_branch.as_deref().unwrap_or("???");
+    let branch_name = current_branch.unwrap_or("???");
     let title = if graph.len() < total_commits {
         // Afficher le compteur filtré
         format!(
@@ -495,10 +510,12 @@ mod tests {
                 let area = frame.area();
                 render(
                     frame,
-                    &graph,
-                    &Some("main".to_string()),
-                    0,
-                    graph.len(),
+                    GraphPanel {
+                        graph: &graph,
+                        current_branch: Some("main"),
+                        selected_index: 0,
+                        total_commits: graph.len(),
+                    },
                     area,
                     &mut state,
                     true,
@@ -527,10 +544,12 @@ mod tests {
                 let area = frame.area();
                 render(
                     frame,
-                    &graph,
-                    &Some("feature".to_string()),
-                    1, // selected_index = 1
-                    graph.len(),
+                    GraphPanel {
+                        graph: &graph,
+                        current_branch: Some("feature"),
+                        selected_index: 1, // selected_index = 1
+                        total_commits: graph.len(),
+                    },
                     area,
                     &mut state,
                     false,
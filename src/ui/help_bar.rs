@@ -6,7 +6,8 @@ use ratatui::{
     Frame,
 };
 
-use crate::app::BottomLeftMode;
+use crate::state::FocusPanel;
+use crate::ui::keymap::{self, KeyContext};
 use crate::ui::theme::current_theme;
 
 /// Rend la barre d'aide persistante en bas de l'écran.
@@ -14,43 +15,26 @@ pub fn render(
     frame: &mut Frame,
     selected_index: usize,
     total_commits: usize,
-    bottom_left_mode: BottomLeftMode,
+    has_more_commits: bool,
+    focus: FocusPanel,
     filter_active: bool,
     area: Rect,
 ) {
     let theme = current_theme();
 
-    // Déterminer les touches à afficher.
-    let mut keys = vec![
-        ("j/k", "naviguer"),
-        ("Enter", "détail"),
-        ("b", "branches"),
-        ("c", "commit"),
-        ("s", "stash"),
-        ("m", "merge"),
-        ("P", "push"),
-    ];
-
-    // Ajouter le contexte du panneau bas.
-    match bottom_left_mode {
-        BottomLeftMode::Files => keys.push(("Tab", "fichiers")),
-        BottomLeftMode::Parents => keys.push(("Tab", "commit")),
-    }
-
-    // Ajouter le raccourci pour effacer les filtres s'ils sont actifs
-    if filter_active {
-        keys.push(("Ctrl+R", "effacer filtres"));
-    }
-
-    keys.extend(vec![("r", "rafraîchir"), ("?", "aide"), ("q", "quitter")]);
+    // Touches du panneau actif, depuis le keymap ("Ctrl+R" seulement si un filtre est actif).
+    let keys = keymap::current().bar_entries(&KeyContext::graph(focus), |binding| {
+        filter_active || binding.action != crate::state::AppAction::ClearFilter
+    });
 
     // Construire la ligne avec les touches formatées.
     let mut spans = build_help_spans(&keys, theme);
 
-    // Ajouter le compteur de commits à droite.
+    // Ajouter le compteur de commits à droite ("+" si l'historique n'est pas entièrement chargé).
+    let more_marker = if has_more_commits { "+" } else { "" };
     spans.push(Span::raw("  "));
     spans.push(Span::styled(
-        format!("{}/{}", selected_index + 1, total_commits),
+        format!("{}/{}{}", selected_index + 1, total_commits, more_marker),
         Style::default().fg(theme.text_secondary),
     ));
 
@@ -67,7 +51,7 @@ pub fn render(
 
 /// Construit les spans pour la barre d'aide.
 fn build_help_spans<'a>(
-    keys: &'a [(&'a str, &'a str)],
+    keys: &'a [(String, &'a str)],
     theme: &crate::ui::theme::Theme,
 ) -> Vec<Span<'a>> {
     let mut spans: Vec<Span<'a>> = Vec::with_capacity(keys.len() * 3);
@@ -79,7 +63,7 @@ fn build_help_spans<'a>(
 
         // Touche en cyan + bold.
         spans.push(Span::styled(
-            *key,
+            key.as_str(),
             Style::default()
                 .fg(theme.primary)
                 .add_modifier(Modifier::BOLD),
@@ -6,10 +6,14 @@ use ratatui::{
     Frame,
 };
 
+use crate::state::ViewMode;
 use crate::ui::common::centered_rect;
+use crate::ui::keymap::{self, KeyContext, Keymap};
 
-/// Rend l'overlay d'aide complet centré sur l'écran.
-pub fn render(frame: &mut Frame, area: Rect) {
+/// Rend l'overlay d'aide de la vue donnée, centré sur l'écran.
+///
+/// Le contenu est généré depuis le keymap : il reflète les remappages de la configuration.
+pub fn render(frame: &mut Frame, area: Rect, view: ViewMode) {
     // Créer une zone centrale pour le popup (70% largeur, 80% hauteur).
     let popup_area = centered_rect(70, 80, area);
 
@@ -17,7 +21,7 @@ pub fn render(frame: &mut Frame, area: Rect) {
     frame.render_widget(Clear, popup_area);
 
     // Construire le contenu de l'aide.
-    let content = build_help_content();
+    let content = build_help_content(keymap::current(), view);
 
     let paragraph = Paragraph::new(content)
         .block(
@@ -32,65 +36,34 @@ pub fn render(frame: &mut Frame, area: Rect) {
     frame.render_widget(paragraph, popup_area);
 }
 
-/// Construit le contenu textuel de l'overlay d'aide.
-fn build_help_content() -> Vec<Line<'static>> {
-    vec![
-        Line::from(""),
-        // ── Navigation ──
-        section_header("Navigation"),
-        separator(),
-        key_line("j / ↓", "Commit suivant"),
-        key_line("k / ↑", "Commit précédent"),
-        key_line("g / Home", "Premier commit"),
-        key_line("G / End", "Dernier commit"),
-        key_line("Ctrl+D / PgDn", "Page suivante"),
-        key_line("Ctrl+U / PgUp", "Page précédente"),
-        key_line("Enter", "Détail / action"),
-        key_line("Tab", "Basculer panneaux"),
-        Line::from(""),
-        // ── Vues ──
-        section_header("Vues"),
-        separator(),
-        key_line("1", "Vue Graph"),
-        key_line("2", "Vue Staging"),
-        key_line("3", "Vue Branches"),
-        key_line("4", "Vue Conflits (si actifs)"),
-        Line::from(""),
-        // ── Actions Git ──
-        section_header("Actions Git"),
-        separator(),
-        key_line("c", "Nouveau commit"),
-        key_line("s", "Stash"),
-        key_line("m", "Merge"),
-        key_line("b", "Panneau branches"),
-        key_line("P", "Push"),
-        key_line("p", "Pull"),
-        key_line("f", "Fetch"),
-        key_line("x", "Cherry-pick"),
-        key_line("B", "Blame du fichier"),
-        Line::from(""),
-        // ── Recherche & Filtre ──
-        section_header("Recherche & Filtre"),
-        separator(),
-        key_line("/", "Ouvrir la recherche"),
-        key_line("n / N", "Résultat suivant / précédent"),
-        key_line("F", "Filtre avancé"),
-        Line::from(""),
-        // ── Interface ──
-        section_header("Interface"),
-        separator(),
-        key_line("v", "Toggle diff (unified/split)"),
-        key_line("r", "Rafraîchir"),
-        key_line("y", "Copier dans le clipboard"),
-        key_line("q", "Quitter"),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "Esc ou ? pour fermer",
-            Style::default()
-                .fg(Color::DarkGray)
-                .add_modifier(Modifier::ITALIC),
-        )]),
-    ]
+/// Construit le contenu textuel de l'overlay d'aide : une section par contexte de la vue.
+fn build_help_content(keymap: &Keymap, view: ViewMode) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::from("")];
+
+    let contexts = KeyContext::ALL
+        .into_iter()
+        .filter(|ctx| ctx.view() == Some(view))
+        .chain([KeyContext::Global]);
+    for context in contexts {
+        let bindings: Vec<_> = keymap.bindings(context).collect();
+        if bindings.is_empty() {
+            continue;
+        }
+        lines.push(section_header(context.title()));
+        lines.push(separator());
+        for binding in bindings {
+            lines.push(key_line(&binding.keys_label(), binding.description));
+        }
+        lines.push(Line::from(""));
+    }
+
+    lines.push(Line::from(vec![Span::styled(
+        "Esc ou ? pour fermer",
+        Style::default()
+            .fg(Color::DarkGray)
+            .add_modifier(Modifier::ITALIC),
+    )]));
+    lines
 }
 
 fn section_header(title: &str) -> Line<'static> {
@@ -107,7 +80,7 @@ fn separator() -> Line<'static> {
 }
 
 fn key_line(key: &str, desc: &str) -> Line<'static> {
-    let padding = 16usize.saturating_sub(key.len());
+    let padding = 16usize.saturating_sub(key.chars().count());
     Line::from(vec![
         Span::styled(key.to_string(), Style::default().fg(Color::Cyan)),
         Span::raw(format!("{}{}", " ".repeat(padding), desc)),
@@ -1,64 +1,105 @@
 use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
 use std::time::Duration;
 
-use crate::state::action::SearchAction;
-use crate::state::{
-    AppAction, AppState, BranchesFocus, BranchesSection, ConflictPanelFocus, FocusPanel,
-    StagingFocus, ViewMode,
-};
-
-/// Poll un événement clavier et retourne l'action correspondante.
-pub fn handle_input(state: &AppState) -> std::io::Result<Option<AppAction>> {
-    handle_input_with_timeout(state, 100)
-}
-
-/// Poll un événement avec un timeout configurable (clavier + souris).
+use crate::state::action::{EditAction, SearchAction};
+use crate::state::{AppAction, AppState, BranchesFocus, FocusPanel, ViewMode};
+use crate::ui::keymap::{self, KeyContext, PendingKeys};
+
+/// Poll un événement avec un timeout configurable (clavier + souris + collage).
+///
+/// `pending` conserve les touches d'une séquence en cours (ex: "g g") entre deux appels.
+/// Un collage peut produire plusieurs actions (un caractère par action hors éditeur).
 pub fn handle_input_with_timeout(
     state: &AppState,
+    pending: &mut PendingKeys,
     timeout_ms: u64,
-) -> std::io::Result<Option<AppAction>> {
+) -> std::io::Result<Vec<AppAction>> {
     if event::poll(Duration::from_millis(timeout_ms))? {
         match event::read()? {
-            Event::Key(key) => Ok(map_key(key, state)),
-            Event::Mouse(mouse) => Ok(map_mouse(mouse, state)),
-            _ => Ok(None),
+            Event::Key(key) => Ok(map_key(key, state, pending).into_iter().collect()),
+            Event::Mouse(mouse) => Ok(map_mouse(mouse, state).into_iter().collect()),
+            Event::Paste(text) => {
+                pending.clear();
+                Ok(map_paste(&text, state))
+            }
+            _ => Ok(Vec::new()),
         }
     } else {
-        Ok(None)
+        Ok(Vec::new())
     }
 }
 
+/// Mappe un texte collé.
+///
+/// Les éditeurs de message (commit, reword) le reçoivent en entier ; les autres
+/// saisies reçoivent ses caractères (sans retours à la ligne) comme s'ils étaient tapés.
+fn map_paste(text: &str, state: &AppState) -> Vec<AppAction> {
+    let as_key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+    let contexts = keymap::active_contexts(state);
+    if contexts.contains(&KeyContext::MessageEditor) {
+        return vec![AppAction::Edit(EditAction::Paste(text.to_string()))];
+    }
+    text.chars()
+        .filter(|c| !c.is_control())
+        .filter_map(|c| match map_text_input(as_key(c), state) {
+            Some(action) => action,
+            None => contexts.iter().find_map(|ctx| ctx.insert_action(c)),
+        })
+        .collect()
+}
+
 /// Mappe un événement clavier à une action de l'application.
-fn map_key(key: KeyEvent, state: &AppState) -> Option<AppAction> {
+fn map_key(key: KeyEvent, state: &AppState, pending: &mut PendingKeys) -> Option<AppAction> {
     // Ctrl+C quitte toujours.
     if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
         return Some(AppAction::Quit);
     }
 
-    // Si le merge picker est actif, gérer ses keybindings
-    if state.merge_picker.as_ref().map_or(false, |p| p.is_active) {
-        return match key.code {
-            KeyCode::Char('j') | KeyCode::Down => Some(AppAction::MergePickerDown),
-            KeyCode::Char('k') | KeyCode::Up => Some(AppAction::MergePickerUp),
-            KeyCode::Enter => Some(AppAction::MergePickerConfirm),
-            KeyCode::Esc => Some(AppAction::MergePickerCancel),
-            _ => None,
-        };
+    // Les saisies hors keymap capturent toutes les touches (permet de taper "1", "q"...).
+    if let Some(action) = map_text_input(key, state) {
+        pending.clear();
+        return action;
     }
 
-    // Si une confirmation est en attente, gérer y/n/ESC
-    if state.pending_confirmation.is_some() {
-        return match key.code {
-            KeyCode::Char('y') | KeyCode::Char('Y') => Some(AppAction::ConfirmAction),
-            KeyCode::Char('n') | KeyCode::Char('N') => Some(AppAction::CancelAction),
-            KeyCode::Esc => Some(AppAction::CancelAction),
-            _ => None,
-        };
+    let contexts = keymap::active_contexts(state);
+    let action = keymap::current().resolve(&contexts, pending, &key);
+    if action.is_some() || !pending.is_empty() {
+        return action;
+    }
+    // Dans une saisie, un caractère sans raccourci est inséré tel quel.
+    match key.code {
+        KeyCode::Char(c)
+            if !key
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+        {
+            contexts.iter().find_map(|ctx| ctx.insert_action(c))
+        }
+        _ => None,
+    }
+}
+
+/// Mappe les touches d'une saisie de texte qui ne passe pas par le keymap.
+///
+/// Retourne None si aucune saisie de ce type n'est active : la touche passe alors
+/// par le keymap.
+fn map_text_input(key: KeyEvent, state: &AppState) -> Option<Option<AppAction>> {
+    // Les popups et l'éditeur de rebase passent avant les saisies.
+    if state.command_output.is_some()
+        || state.command_palette.is_some()
+        || state.push_dialog.is_some()
+        || state.merge_picker.as_ref().is_some_and(|p| p.is_active)
+        || state.reset_picker.as_ref().is_some_and(|p| p.is_active)
+        || state.pull_picker.as_ref().is_some_and(|p| p.is_active)
+        || state.pending_confirmation.is_some()
+        || state.rebase_editor.is_some()
+    {
+        return None;
     }
 
     // Si la recherche est active, gérer les inputs de recherche
     if state.search_state.is_active {
-        return match key.code {
+        return Some(match key.code {
             KeyCode::Esc => Some(AppAction::Search(SearchAction::Close)),
             KeyCode::Enter => Some(AppAction::Search(SearchAction::Execute)),
             KeyCode::Down | KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => {
@@ -71,12 +112,12 @@ fn map_key(key: KeyEvent, state: &AppState) -> Option<AppAction> {
             KeyCode::Char(c) => Some(AppAction::Search(SearchAction::InsertChar(c))),
             KeyCode::Backspace => Some(AppAction::Search(SearchAction::DeleteChar)),
             _ => None,
-        };
+        });
     }
 
     // Si le popup de filtre est ouvert, gérer ses inputs
     if state.filter_popup.is_open {
-        return match key.code {
+        return Some(match key.code {
             KeyCode::Esc => Some(AppAction::CloseFilter),
             KeyCode::Enter => Some(AppAction::ApplyFilter),
             KeyCode::Tab | KeyCode::Down => Some(AppAction::FilterNextField),
@@ -87,502 +128,22 @@ fn map_key(key: KeyEvent, state: &AppState) -> Option<AppAction> {
             KeyCode::Char(c) => Some(AppAction::FilterInsertChar(c)),
             KeyCode::Backspace => Some(AppAction::FilterDeleteChar),
             _ => None,
-        };
+        });
     }
 
-    // Si on est en mode Staging avec focus sur CommitMessage, dispatcher immédiatement
-    // sans intercepter les raccourcis globaux (permet de taper "1", "2", "3" dans le message)
-    if state.view_mode == ViewMode::Staging
-        && state.staging_state.focus == StagingFocus::CommitMessage
-    {
-        return map_staging_key(key, state);
-    }
-
-    // Si on est en mode Branches avec focus sur Input, dispatcher immédiatement
-    // sans intercepter les raccourcis globaux (permet de taper "1", "2", "3" dans le nom)
-    if state.view_mode == ViewMode::Branches
-        && state.branches_view_state.focus == BranchesFocus::Input
-    {
-        return map_branches_key(key, state);
-    }
-
-    // Navigation entre les vues principales (toujours disponible, sauf en mode saisie)
-    match key.code {
-        KeyCode::Char('1') => return Some(AppAction::SwitchToGraph),
-        KeyCode::Char('2') => return Some(AppAction::SwitchToStaging),
-        KeyCode::Char('3') => return Some(AppAction::SwitchToBranches),
-        KeyCode::Char('4') => {
-            if state.conflicts_state.is_some() {
-                return Some(AppAction::SwitchToConflicts);
-            }
-        }
-        _ => {}
-    }
-
-    // Si on est en mode Conflicts, utiliser les keybindings spécifiques
-    if state.view_mode == ViewMode::Conflicts {
-        return map_conflicts_key(key, state);
-    }
-
-    // Si on est en mode Staging, utiliser les keybindings spécifiques
-    if state.view_mode == ViewMode::Staging {
-        return map_staging_key(key, state);
-    }
-
-    // Si on est en mode Branches, utiliser les keybindings spécifiques
-    if state.view_mode == ViewMode::Branches {
-        return map_branches_key(key, state);
-    }
-
-    // Si on est en mode Blame, utiliser les keybindings spécifiques
-    if state.view_mode == ViewMode::Blame {
-        return map_blame_key(key, state);
-    }
-
-    // Ctrl+d / Ctrl+u pour page down/up
-    // Ctrl+R pour effacer les filtres si actifs
-    if key.modifiers.contains(KeyModifiers::CONTROL) {
-        match key.code {
-            KeyCode::Char('d') => {
-                if state.focus == FocusPanel::BottomRight {
-                    return Some(AppAction::DiffScrollDown);
-                }
-                return Some(AppAction::PageDown);
-            }
-            KeyCode::Char('u') => {
-                if state.focus == FocusPanel::BottomRight {
-                    return Some(AppAction::DiffScrollUp);
-                }
-                return Some(AppAction::PageUp);
-            }
-            KeyCode::Char('r') => {
-                if state.graph_filter.is_active() {
-                    return Some(AppAction::ClearFilter);
-                }
-            }
-            _ => {}
-        }
-    }
-
-    // Escape ferme l'overlay d'aide si actif.
-    if key.code == KeyCode::Esc && state.view_mode == ViewMode::Help {
-        return Some(AppAction::ToggleHelp);
-    }
-
-    // Escape pour revenir au panneau précédent quand on est dans BottomLeft ou BottomRight.
-    if key.code == KeyCode::Esc {
-        match state.focus {
-            FocusPanel::BottomRight => {
-                return Some(AppAction::SwitchBottomMode);
-            }
-            FocusPanel::BottomLeft => {
-                // Retourner au focus Graph depuis le panneau fichiers
-                return Some(AppAction::Navigation(
-                    crate::state::action::NavigationAction::BackToGraph,
-                ));
-            }
-            _ => {}
-        }
-    }
-
-    // Contexte: panneau de branches ouvert
-    if state.show_branch_panel {
-        return match key.code {
-            KeyCode::Esc | KeyCode::Char('b') => Some(AppAction::CloseBranchPanel),
-            KeyCode::Char('j') | KeyCode::Down => Some(AppAction::MoveDown),
-            KeyCode::Char('k') | KeyCode::Up => Some(AppAction::MoveUp),
-            KeyCode::Enter => Some(AppAction::BranchCheckout),
-            KeyCode::Char('n') => Some(AppAction::BranchCreate),
-            KeyCode::Char('d') => Some(AppAction::BranchDelete),
-            _ => None,
-        };
-    }
-
-    // Navigation contextuelle selon le focus.
-    match state.focus {
-        FocusPanel::BottomLeft => {
-            // Quand focus sur BottomLeft, j/k naviguent dans la liste des fichiers.
-            match key.code {
-                KeyCode::Char('j') | KeyCode::Down => return Some(AppAction::FileDown),
-                KeyCode::Char('k') | KeyCode::Up => return Some(AppAction::FileUp),
-                KeyCode::Enter => return Some(AppAction::SwitchBottomMode), // Passer au diff
-                _ => {}
-            }
-        }
-        FocusPanel::BottomRight => {
-            // Quand focus sur BottomRight, j/k scrollent le diff.
-            match key.code {
-                KeyCode::Char('j') | KeyCode::Down => return Some(AppAction::DiffScrollDown),
-                KeyCode::Char('k') | KeyCode::Up => return Some(AppAction::DiffScrollUp),
-                KeyCode::Char('v') => return Some(AppAction::ToggleDiffViewMode),
-                _ => {}
-            }
-        }
-        _ => {}
-    }
-
-    match key.code {
-        // Navigation
-        KeyCode::Char('q') => Some(AppAction::Quit),
-        KeyCode::Char('j') | KeyCode::Down => Some(AppAction::MoveDown),
-        KeyCode::Char('k') | KeyCode::Up => Some(AppAction::MoveUp),
-        KeyCode::Char('g') | KeyCode::Home => Some(AppAction::GoTop),
-        KeyCode::Char('G') | KeyCode::End => Some(AppAction::GoBottom),
-        KeyCode::PageUp => Some(AppAction::PageUp),
-        KeyCode::PageDown => Some(AppAction::PageDown),
-        KeyCode::Enter => Some(AppAction::Select),
-
-        // Actions git
-        KeyCode::Char('c') => Some(AppAction::CommitPrompt),
-        KeyCode::Char('s') => Some(AppAction::StashPrompt),
-        KeyCode::Char('m') => Some(AppAction::MergePrompt),
-        KeyCode::Char('b') => Some(AppAction::BranchList),
-        KeyCode::Char('P') => Some(AppAction::GitPush),
-        KeyCode::Char('p') => Some(AppAction::GitPull),
-        KeyCode::Char('f') => Some(AppAction::GitFetch),
-
-        // Recherche
-        KeyCode::Char('/') => Some(AppAction::OpenSearch),
-        KeyCode::Char('n') => Some(AppAction::NextSearchResult),
-        KeyCode::Char('N') => Some(AppAction::PrevSearchResult),
-
-        // Filtre
-        KeyCode::Char('F') => Some(AppAction::OpenFilter),
-
-        // Vue blame
-        KeyCode::Char('B') => Some(AppAction::OpenBlame),
-
-        // Cherry-pick
-        KeyCode::Char('x') => Some(AppAction::CherryPick),
-
-        // Aide
-        KeyCode::Char('?') => Some(AppAction::ToggleHelp),
-
-        // Rafraîchir
-        KeyCode::Char('r') => Some(AppAction::Refresh),
-
-        // Copier le contenu du panneau actif dans le clipboard
-        KeyCode::Char('y') => Some(AppAction::CopyPanelContent),
-
-        // Changer le focus entre les panneaux (Graph -> BottomLeft -> BottomRight -> Graph)
-        KeyCode::Tab => Some(AppAction::Navigation(
-            crate::state::action::NavigationAction::SwitchPanel,
-        )),
-
-        // Basculer entre les modes du panneau bas-gauche (Files / Parents)
-        KeyCode::Char('M') => Some(AppAction::SwitchBottomMode),
-
-        _ => None,
-    }
-}
-
-/// Mappe les touches pour la vue branches.
-fn map_branches_key(key: KeyEvent, state: &AppState) -> Option<AppAction> {
-    // Si on est en mode Input.
-    if state.branches_view_state.focus == BranchesFocus::Input {
-        return match key.code {
-            KeyCode::Enter => Some(AppAction::ConfirmInput),
-            KeyCode::Esc => Some(AppAction::CancelInput),
-            KeyCode::Char(c) => Some(AppAction::InsertChar(c)),
-            KeyCode::Backspace => Some(AppAction::DeleteChar),
-            KeyCode::Left => Some(AppAction::MoveCursorLeft),
-            KeyCode::Right => Some(AppAction::MoveCursorRight),
-            _ => None,
-        };
-    }
-
-    // Navigation globale.
-    match key.code {
-        KeyCode::Char('1') => return Some(AppAction::SwitchToGraph),
-        KeyCode::Char('2') => return Some(AppAction::SwitchToStaging),
-        KeyCode::Tab => return Some(AppAction::NextSection),
-        KeyCode::BackTab => return Some(AppAction::PrevSection),
-        KeyCode::Char('q') => return Some(AppAction::Quit),
-        KeyCode::Char('y') => return Some(AppAction::CopyPanelContent),
-        KeyCode::Char('?') => return Some(AppAction::ToggleHelp),
-        KeyCode::Char('P') => return Some(AppAction::GitPush),
-        _ => {}
-    }
-
-    // Actions par section.
-    match state.branches_view_state.section {
-        BranchesSection::Branches => match key.code {
-            KeyCode::Char('j') | KeyCode::Down => Some(AppAction::MoveDown),
-            KeyCode::Char('k') | KeyCode::Up => Some(AppAction::MoveUp),
-            KeyCode::Enter => Some(AppAction::BranchCheckout),
-            KeyCode::Char('n') => Some(AppAction::BranchCreate),
-            KeyCode::Char('d') => Some(AppAction::BranchDelete),
-            KeyCode::Char('r') => Some(AppAction::BranchRename),
-            KeyCode::Char('R') => Some(AppAction::ToggleRemoteBranches),
-            KeyCode::Char('m') => Some(AppAction::MergePrompt),
-            _ => None,
-        },
-        BranchesSection::Worktrees => match key.code {
-            KeyCode::Char('j') | KeyCode::Down => Some(AppAction::MoveDown),
-            KeyCode::Char('k') | KeyCode::Up => Some(AppAction::MoveUp),
-            KeyCode::Char('n') => Some(AppAction::WorktreeCreate),
-            KeyCode::Char('d') => Some(AppAction::WorktreeRemove),
-            _ => None,
-        },
-        BranchesSection::Stashes => match key.code {
-            KeyCode::Char('j') | KeyCode::Down => Some(AppAction::MoveDown),
-            KeyCode::Char('k') | KeyCode::Up => Some(AppAction::MoveUp),
-            KeyCode::Char('l') | KeyCode::Right => Some(AppAction::FileDown),
-            KeyCode::Char('h') | KeyCode::Left => Some(AppAction::FileUp),
-            KeyCode::Char('a') => Some(AppAction::StashApply),
-            KeyCode::Char('p') => Some(AppAction::StashPop),
-            KeyCode::Char('d') => Some(AppAction::StashDrop),
-            KeyCode::Char('s') => Some(AppAction::StashSave),
-            _ => None,
-        },
-    }
-}
-
-/// Mappe les touches pour la vue staging.
-fn map_staging_key(key: KeyEvent, state: &AppState) -> Option<AppAction> {
-    // Vérifier d'abord si on est en mode saisie de commit
-    if state.staging_state.focus == StagingFocus::CommitMessage {
-        return match key.code {
-            KeyCode::Enter => Some(AppAction::ConfirmCommit),
-            KeyCode::Esc => Some(AppAction::CancelCommitMessage),
-            KeyCode::Char(c) => Some(AppAction::InsertChar(c)),
-            KeyCode::Backspace => Some(AppAction::DeleteChar),
-            KeyCode::Left => Some(AppAction::MoveCursorLeft),
-            KeyCode::Right => Some(AppAction::MoveCursorRight),
-            _ => None,
-        };
-    }
-
-    // Touches globales de la vue staging
-    match key.code {
-        KeyCode::Char('q') => return Some(AppAction::Quit),
-        KeyCode::Char('r') => return Some(AppAction::Refresh),
-        KeyCode::Char('y') => return Some(AppAction::CopyPanelContent),
-        KeyCode::Char('?') => return Some(AppAction::ToggleHelp),
-        KeyCode::Char('P') => return Some(AppAction::GitPush),
-        _ => {}
-    }
-
-    // Navigation selon le focus dans la vue staging
-    match state.staging_state.focus {
-        StagingFocus::Unstaged => match key.code {
-            KeyCode::Char('j') | KeyCode::Down => Some(AppAction::MoveDown),
-            KeyCode::Char('k') | KeyCode::Up => Some(AppAction::MoveUp),
-            KeyCode::Char('s') | KeyCode::Enter => Some(AppAction::StageFile),
-            KeyCode::Char('S') => Some(AppAction::StashSelectedFile),
-            KeyCode::Char('a') => Some(AppAction::StageAll),
-            KeyCode::Char('d') => Some(AppAction::DiscardFile),
-            KeyCode::Char('D') => Some(AppAction::DiscardAll),
-            KeyCode::Tab => Some(AppAction::SwitchStagingFocus),
-            KeyCode::Char('c') => Some(AppAction::StartCommitMessage),
-            _ if key.modifiers.contains(KeyModifiers::CONTROL)
-                && key.code == KeyCode::Char('S') =>
-            {
-                Some(AppAction::StashUnstagedFiles)
-            }
-            _ => None,
-        },
-        StagingFocus::Staged => match key.code {
-            KeyCode::Char('j') | KeyCode::Down => Some(AppAction::MoveDown),
-            KeyCode::Char('k') | KeyCode::Up => Some(AppAction::MoveUp),
-            KeyCode::Char('u') | KeyCode::Enter => Some(AppAction::UnstageFile),
-            KeyCode::Char('U') => Some(AppAction::UnstageAll),
-            KeyCode::Tab => Some(AppAction::SwitchStagingFocus),
-            KeyCode::Char('c') => Some(AppAction::StartCommitMessage),
-            KeyCode::Char('A') => Some(AppAction::AmendCommit),
-            _ => None,
-        },
-        StagingFocus::Diff => match key.code {
-            KeyCode::Char('j') | KeyCode::Down => Some(AppAction::DiffScrollDown),
-            KeyCode::Char('k') | KeyCode::Up => Some(AppAction::DiffScrollUp),
-            KeyCode::Tab | KeyCode::Esc => Some(AppAction::SwitchStagingFocus),
-            KeyCode::Char('c') => Some(AppAction::StartCommitMessage),
-            KeyCode::Char('v') => Some(AppAction::ToggleDiffViewMode),
-            _ => None,
-        },
-        // StagingFocus::CommitMessage est géré en priorité au début de la fonction
-        StagingFocus::CommitMessage => unreachable!(),
-    }
-}
-
-/// Mappe les keybindings pour la vue Blame.
-fn map_blame_key(key: KeyEvent, _state: &AppState) -> Option<AppAction> {
-    match key.code {
-        KeyCode::Char('q') | KeyCode::Esc => Some(AppAction::CloseBlame),
-        KeyCode::Char('j') | KeyCode::Down => Some(AppAction::MoveDown),
-        KeyCode::Char('k') | KeyCode::Up => Some(AppAction::MoveUp),
-        KeyCode::Char('g') | KeyCode::Home => Some(AppAction::GoTop),
-        KeyCode::Char('G') | KeyCode::End => Some(AppAction::GoBottom),
-        KeyCode::PageUp => Some(AppAction::PageUp),
-        KeyCode::PageDown => Some(AppAction::PageDown),
-        KeyCode::Enter => Some(AppAction::JumpToBlameCommit),
-        KeyCode::Char('y') => Some(AppAction::CopyPanelContent),
-        _ => None,
-    }
-}
-
-/// Mappe les keybindings pour la vue de résolution de conflits.
-fn map_conflicts_key(key: KeyEvent, state: &AppState) -> Option<AppAction> {
-    use crate::git::conflict::ConflictResolutionMode;
-
-    // Si une confirmation est en attente (pour ConflictValidateMerge)
-    if state.pending_confirmation.is_some() {
-        return match key.code {
-            KeyCode::Char('y') | KeyCode::Char('Y') => Some(AppAction::ConfirmAction),
-            KeyCode::Char('n') | KeyCode::Char('N') => Some(AppAction::CancelAction),
-            KeyCode::Esc => Some(AppAction::CancelAction),
-            _ => None,
-        };
-    }
-
-    // Récupérer le panneau actif, le mode de résolution et l'état d'édition
-    let conflicts_state = state.conflicts_state.as_ref();
-    let panel_focus = conflicts_state.map(|s| s.panel_focus);
-    let is_editing = conflicts_state.map_or(false, |s| s.is_editing);
-    let resolution_mode =
-        conflicts_state.map_or(ConflictResolutionMode::Block, |s| s.resolution_mode);
-
-    // Si en mode édition dans le panneau résultat, capturer toutes les touches comme du texte
-    if is_editing {
-        return match key.code {
-            KeyCode::Esc => Some(AppAction::ConflictStopEditing),
-            // Sauvegarder et quitter l'édition
-            KeyCode::Enter if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                Some(AppAction::ConflictConfirmEdit)
-            }
-            KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                Some(AppAction::ConflictConfirmEdit)
-            }
-            KeyCode::Char(c) => Some(AppAction::ConflictEditInsertChar(c)),
-            KeyCode::Backspace => Some(AppAction::ConflictEditBackspace),
-            KeyCode::Delete => Some(AppAction::ConflictEditDelete),
-            KeyCode::Enter => Some(AppAction::ConflictEditNewline),
-            KeyCode::Up => Some(AppAction::ConflictEditCursorUp),
-            KeyCode::Down => Some(AppAction::ConflictEditCursorDown),
-            KeyCode::Left => Some(AppAction::ConflictEditCursorLeft),
-            KeyCode::Right => Some(AppAction::ConflictEditCursorRight),
-            _ => None,
-        };
-    }
-
-    match key.code {
-        // Tab et Shift+Tab : basculer entre les panneaux
-        KeyCode::Tab => Some(AppAction::ConflictSwitchPanelForward),
-        KeyCode::BackTab => Some(AppAction::ConflictSwitchPanelReverse),
-
-        // Navigation flèches/j/k : dépend du panneau actif et du mode de résolution
-        KeyCode::Char('j') | KeyCode::Down => match panel_focus {
-            Some(ConflictPanelFocus::FileList) => Some(AppAction::ConflictNextFile),
-            Some(ConflictPanelFocus::OursPanel | ConflictPanelFocus::TheirsPanel) => {
-                match resolution_mode {
-                    // En mode Fichier, naviguer entre les fichiers (pas entre sections)
-                    ConflictResolutionMode::File => Some(AppAction::ConflictNextFile),
-                    ConflictResolutionMode::Line => Some(AppAction::ConflictLineDown),
-                    ConflictResolutionMode::Block => Some(AppAction::ConflictNextSection),
-                }
-            }
-            Some(ConflictPanelFocus::ResultPanel) => Some(AppAction::ConflictResultScrollDown),
-            _ => None,
-        },
-        KeyCode::Char('k') | KeyCode::Up => match panel_focus {
-            Some(ConflictPanelFocus::FileList) => Some(AppAction::ConflictPrevFile),
-            Some(ConflictPanelFocus::OursPanel | ConflictPanelFocus::TheirsPanel) => {
-                match resolution_mode {
-                    // En mode Fichier, naviguer entre les fichiers (pas entre sections)
-                    ConflictResolutionMode::File => Some(AppAction::ConflictPrevFile),
-                    ConflictResolutionMode::Line => Some(AppAction::ConflictLineUp),
-                    ConflictResolutionMode::Block => Some(AppAction::ConflictPrevSection),
-                }
-            }
-            Some(ConflictPanelFocus::ResultPanel) => Some(AppAction::ConflictResultScrollUp),
-            _ => None,
-        },
-
-        // Résolution rapide depuis le panneau FileList
-        KeyCode::Char('o') | KeyCode::Left => match panel_focus {
-            Some(ConflictPanelFocus::FileList) => Some(AppAction::ConflictFileChooseOurs),
-            _ => None,
-        },
-        KeyCode::Char('t') | KeyCode::Right => match panel_focus {
-            Some(ConflictPanelFocus::FileList) => Some(AppAction::ConflictFileChooseTheirs),
-            _ => None,
-        },
-        // Marquer comme résolu depuis le panneau FileList
-        KeyCode::Char('r') => match panel_focus {
-            Some(ConflictPanelFocus::FileList) => Some(AppAction::ConflictResolveFile),
-            _ => None,
-        },
-
-        // Résolution "Both" uniquement en mode Bloc (depuis les panneaux Ours/Theirs)
-        KeyCode::Char('b') => {
-            if matches!(
-                panel_focus,
-                Some(ConflictPanelFocus::OursPanel | ConflictPanelFocus::TheirsPanel)
-            ) && resolution_mode == ConflictResolutionMode::Block
-            {
-                Some(AppAction::ConflictChooseBoth)
-            } else {
-                None
-            }
-        }
-
-        // Mode édition (panneau résultat uniquement)
-        KeyCode::Char('i') | KeyCode::Char('e') => {
-            if panel_focus == Some(ConflictPanelFocus::ResultPanel) {
-                Some(AppAction::ConflictStartEditing)
-            } else {
-                None
-            }
-        }
-
-        // Changement de mode de résolution (mapping direct)
-        KeyCode::Char('F') => Some(AppAction::ConflictSetModeFile),
-        KeyCode::Char('B') => Some(AppAction::ConflictSetModeBlock),
-        KeyCode::Char('L') => Some(AppAction::ConflictSetModeLine),
-
-        // Espace: toggle la sélection en mode Block ou Ligne
-        KeyCode::Char(' ') => match panel_focus {
-            Some(ConflictPanelFocus::OursPanel | ConflictPanelFocus::TheirsPanel) => {
-                match resolution_mode {
-                    ConflictResolutionMode::Block => Some(AppAction::ConflictEnterResolve),
-                    ConflictResolutionMode::Line => Some(AppAction::ConflictToggleLine),
-                    _ => None,
-                }
-            }
-            _ => None,
-        },
-
-        // Enter: validation contextuelle selon le panneau et le mode
-        KeyCode::Enter => match panel_focus {
-            Some(ConflictPanelFocus::OursPanel | ConflictPanelFocus::TheirsPanel) => {
-                match resolution_mode {
-                    // En mode Fichier, Enter résout selon le panneau actif
-                    ConflictResolutionMode::File => Some(AppAction::ConflictEnterResolve),
-                    // En mode Block/Ligne, Enter valide le fichier (écrit sur disque)
-                    ConflictResolutionMode::Block | ConflictResolutionMode::Line => {
-                        Some(AppAction::ConflictResolveFile)
-                    }
-                }
-            }
-            _ => None,
-        },
-        KeyCode::Char('V') => Some(AppAction::ConflictValidateMerge),
-        KeyCode::Char('q') | KeyCode::Esc => {
-            if is_editing {
-                Some(AppAction::ConflictStopEditing)
-            } else {
-                Some(AppAction::ConflictLeaveView)
-            }
+    match state.view_mode {
+        // Saisie d'un nom (branche, worktree, stash, tag)
+        ViewMode::Branches if state.branches_view_state.focus == BranchesFocus::Input => {
+            Some(match key.code {
+                KeyCode::Enter => Some(AppAction::ConfirmInput),
+                KeyCode::Esc => Some(AppAction::CancelInput),
+                KeyCode::Char(c) => Some(AppAction::InsertChar(c)),
+                KeyCode::Backspace => Some(AppAction::DeleteChar),
+                KeyCode::Left => Some(AppAction::MoveCursorLeft),
+                KeyCode::Right => Some(AppAction::MoveCursorRight),
+                _ => None,
+            })
         }
-        KeyCode::Char('A') => Some(AppAction::ConflictAbort),
-
-        // Vues
-        KeyCode::Char('?') => Some(AppAction::ToggleHelp),
-
-        // Navigation entre vues
-        KeyCode::Char('1') => return Some(AppAction::SwitchToGraph),
-        KeyCode::Char('2') => return Some(AppAction::SwitchToStaging),
-        KeyCode::Char('3') => return Some(AppAction::SwitchToBranches),
         _ => None,
     }
 }
@@ -594,8 +155,9 @@ fn map_mouse(mouse: MouseEvent, state: &AppState) -> Option<AppAction> {
         return None;
     }
 
-    // Ignorer les événements de souris si le popup de filtre est ouvert
-    if state.filter_popup.is_open {
+    // Ignorer les événements de souris si un popup de saisie est ouvert
+    if state.filter_popup.is_open || state.command_palette.is_some() || state.push_dialog.is_some()
+    {
         return None;
     }
 
@@ -0,0 +1,3299 @@
+//! Table déclarative des raccourcis clavier.
+//!
+//! Chaque raccourci associe, dans un contexte (vue, panneau, popup), une ou
+//! plusieurs séquences de touches à une action. La même table sert à résoudre
+//! les touches pressées et à générer l'overlay d'aide et les barres d'aide.
+//!
+//! Les raccourcis se redéfinissent dans la section `[keys]` de la configuration :
+//!
+//! ```toml
+//! [keys.graph]
+//! n = "move_down"      # n déplace la sélection vers le bas
+//! j = "none"           # j ne fait plus rien dans le graphe
+//! "g t" = "go_top"     # séquence de deux touches
+//! ```
+
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::commands::CustomCommand;
+use crate::git::conflict::ConflictResolutionMode;
+use crate::git::rebase::TodoAction;
+use crate::state::action::{
+    BranchAction, CommandAction, ConflictAction, EditAction, GitAction, NavigationAction,
+    PaletteAction, PushAction, RebaseAction, ReflogAction, StagingAction,
+};
+use crate::state::{
+    AppAction, AppState, BranchesSection, ConflictPanelFocus, ConflictsState, DiffSource,
+    FocusPanel, StagingFocus, StagingState, ViewMode,
+};
+
+/// Délai au-delà duquel une séquence de touches commencée est abandonnée.
+const SEQUENCE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Valeur de configuration qui retire une touche d'un contexte.
+const UNBIND: &str = "none";
+
+/// Combinaison de touches (ex: "j", "ctrl-d", "shift-tab").
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    /// Combinaison correspondant à un événement clavier.
+    ///
+    /// Shift est ignoré pour les caractères (il est déjà porté par la casse)
+    /// et pour Shift+Tab (BackTab).
+    pub fn from_event(key: &KeyEvent) -> Self {
+        let modifiers = match key.code {
+            KeyCode::Char(_) | KeyCode::BackTab => key.modifiers - KeyModifiers::SHIFT,
+            _ => key.modifiers,
+        };
+        Self {
+            code: key.code,
+            modifiers,
+        }
+    }
+
+    /// Libellé affiché dans l'aide (ex: "Ctrl+D", "↓", "Enter").
+    pub fn label(&self) -> String {
+        let key = match self.code {
+            KeyCode::Char(' ') => "Espace".to_string(),
+            KeyCode::Char(c) if self.modifiers.contains(KeyModifiers::CONTROL) => {
+                if c.is_uppercase() {
+                    format!("Shift+{}", c)
+                } else {
+                    c.to_uppercase().to_string()
+                }
+            }
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::Enter => "Enter".to_string(),
+            KeyCode::Esc => "Esc".to_string(),
+            KeyCode::Tab => "Tab".to_string(),
+            KeyCode::BackTab => "Shift+Tab".to_string(),
+            KeyCode::Backspace => "Backspace".to_string(),
+            KeyCode::Delete => "Suppr".to_string(),
+            KeyCode::Up => "↑".to_string(),
+            KeyCode::Down => "↓".to_string(),
+            KeyCode::Left => "←".to_string(),
+            KeyCode::Right => "→".to_string(),
+            KeyCode::Home => "Home".to_string(),
+            KeyCode::End => "End".to_string(),
+            KeyCode::PageUp => "PgUp".to_string(),
+            KeyCode::PageDown => "PgDn".to_string(),
+            KeyCode::F(n) => format!("F{}", n),
+            other => format!("{:?}", other),
+        };
+
+        let mut label = String::new();
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            label.push_str("Ctrl+");
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            label.push_str("Alt+");
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            label.push_str("Shift+");
+        }
+        label.push_str(&key);
+        label
+    }
+
+    /// Touche de navigation doublant une touche principale (flèches, Home, PgUp...).
+    fn is_navigation(&self) -> bool {
+        matches!(
+            self.code,
+            KeyCode::Up
+                | KeyCode::Down
+                | KeyCode::Left
+                | KeyCode::Right
+                | KeyCode::Home
+                | KeyCode::End
+                | KeyCode::PageUp
+                | KeyCode::PageDown
+        )
+    }
+}
+
+impl TryFrom<&str> for KeyChord {
+    type Error = String;
+
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        let invalid = || format!("touche invalide '{}'", value);
+
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = value;
+        // "-" seul est une touche : on ne découpe que les préfixes connus.
+        loop {
+            let lower = rest.to_ascii_lowercase();
+            if let Some(prefix) = ["ctrl-", "alt-", "shift-"]
+                .into_iter()
+                .find(|p| lower.starts_with(p) && rest.len() > p.len())
+            {
+                modifiers |= match prefix {
+                    "ctrl-" => KeyModifiers::CONTROL,
+                    "alt-" => KeyModifiers::ALT,
+                    _ => KeyModifiers::SHIFT,
+                };
+                rest = &rest[prefix.len()..];
+            } else {
+                break;
+            }
+        }
+
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match rest.to_ascii_lowercase().as_str() {
+                "enter" => KeyCode::Enter,
+                "esc" => KeyCode::Esc,
+                "tab" if modifiers.contains(KeyModifiers::SHIFT) => {
+                    modifiers -= KeyModifiers::SHIFT;
+                    KeyCode::BackTab
+                }
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "space" => KeyCode::Char(' '),
+                "backspace" => KeyCode::Backspace,
+                "delete" => KeyCode::Delete,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                name => match name.strip_prefix('f').map(str::parse::<u8>) {
+                    Some(Ok(n)) if (1..=12).contains(&n) => KeyCode::F(n),
+                    _ => return Err(invalid()),
+                },
+            },
+        };
+
+        // Shift sur un caractère est porté par la casse.
+        if let KeyCode::Char(_) = code {
+            modifiers -= KeyModifiers::SHIFT;
+        }
+
+        Ok(Self { code, modifiers })
+    }
+}
+
+/// Séquence de touches (une seule touche dans la plupart des cas, ex: "g g").
+pub type KeySequence = Vec<KeyChord>;
+
+/// Parse une séquence de touches séparées par des espaces.
+pub fn parse_sequence(value: &str) -> std::result::Result<KeySequence, String> {
+    let sequence = value
+        .split_whitespace()
+        .map(KeyChord::try_from)
+        .collect::<std::result::Result<KeySequence, String>>()?;
+    if sequence.is_empty() {
+        return Err(format!("touche invalide '{}'", value));
+    }
+    Ok(sequence)
+}
+
+/// Libellé d'une séquence de touches.
+fn sequence_label(sequence: &[KeyChord]) -> String {
+    sequence
+        .iter()
+        .map(KeyChord::label)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Contexte dans lequel un raccourci est actif.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyContext {
+    Global,
+    Graph,
+    GraphFiles,
+    GraphDiff,
+    BranchPanel,
+    Help,
+    Staging,
+    StagingUnstaged,
+    StagingStaged,
+    StagingDiff,
+    StagingDiffUnstaged,
+    StagingDiffStaged,
+    StagingLines,
+    StagingLinesUnstaged,
+    StagingLinesStaged,
+    CommitMessage,
+    MessageEditor,
+    ConventionalPicker,
+    Branches,
+    BranchesList,
+    BranchesWorktrees,
+    BranchesStashes,
+    BranchesTags,
+    BranchesRemotes,
+    Blame,
+    Reflog,
+    ReflogBranch,
+    Conflicts,
+    ConflictsFiles,
+    ConflictsFileMode,
+    ConflictsBlock,
+    ConflictsLine,
+    ConflictsResult,
+    ConflictsRebase,
+    ConflictsEdit,
+    Palette,
+    PushDialog,
+    MergePicker,
+    ResetPicker,
+    PullPicker,
+    Confirm,
+    CommandOutput,
+    RebaseEditor,
+    RebaseProgress,
+    Reword,
+    Transfer,
+}
+
+impl KeyContext {
+    /// Tous les contexts, dans l'ordre d'affichage de l'aide.
+    pub const ALL: [KeyContext; 46] = [
+        KeyContext::Global,
+        KeyContext::Graph,
+        KeyContext::GraphFiles,
+        KeyContext::GraphDiff,
+        KeyContext::BranchPanel,
+        KeyContext::Help,
+        KeyContext::Staging,
+        KeyContext::StagingUnstaged,
+        KeyContext::StagingStaged,
+        KeyContext::StagingDiff,
+        KeyContext::StagingDiffUnstaged,
+        KeyContext::StagingDiffStaged,
+        KeyContext::StagingLines,
+        KeyContext::StagingLinesUnstaged,
+        KeyContext::StagingLinesStaged,
+        KeyContext::CommitMessage,
+        KeyContext::MessageEditor,
+        KeyContext::ConventionalPicker,
+        KeyContext::Branches,
+        KeyContext::BranchesList,
+        KeyContext::BranchesWorktrees,
+        KeyContext::BranchesStashes,
+        KeyContext::BranchesTags,
+        KeyContext::BranchesRemotes,
+        KeyContext::Blame,
+        KeyContext::Reflog,
+        KeyContext::ReflogBranch,
+        KeyContext::Conflicts,
+        KeyContext::ConflictsFiles,
+        KeyContext::ConflictsFileMode,
+        KeyContext::ConflictsBlock,
+        KeyContext::ConflictsLine,
+        KeyContext::ConflictsResult,
+        KeyContext::ConflictsRebase,
+        KeyContext::ConflictsEdit,
+        KeyContext::Palette,
+        KeyContext::PushDialog,
+        KeyContext::MergePicker,
+        KeyContext::ResetPicker,
+        KeyContext::PullPicker,
+        KeyContext::Confirm,
+        KeyContext::CommandOutput,
+        KeyContext::RebaseEditor,
+        KeyContext::RebaseProgress,
+        KeyContext::Reword,
+        KeyContext::Transfer,
+    ];
+
+    /// Nom utilisé dans la section `[keys]` de la configuration.
+    pub fn name(self) -> &'static str {
+        match self {
+            KeyContext::Global => "global",
+            KeyContext::Graph => "graph",
+            KeyContext::GraphFiles => "graph_files",
+            KeyContext::GraphDiff => "graph_diff",
+            KeyContext::BranchPanel => "branch_panel",
+            KeyContext::Help => "help",
+            KeyContext::Staging => "staging",
+            KeyContext::StagingUnstaged => "staging_unstaged",
+            KeyContext::StagingStaged => "staging_staged",
+            KeyContext::StagingDiff => "staging_diff",
+            KeyContext::StagingDiffUnstaged => "staging_diff_unstaged",
+            KeyContext::StagingDiffStaged => "staging_diff_staged",
+            KeyContext::StagingLines => "staging_lines",
+            KeyContext::StagingLinesUnstaged => "staging_lines_unstaged",
+            KeyContext::StagingLinesStaged => "staging_lines_staged",
+            KeyContext::CommitMessage => "commit_message",
+            KeyContext::MessageEditor => "message_editor",
+            KeyContext::ConventionalPicker => "conventional_picker",
+            KeyContext::Branches => "branches",
+            KeyContext::BranchesList => "branches_list",
+            KeyContext::BranchesWorktrees => "branches_worktrees",
+            KeyContext::BranchesStashes => "branches_stashes",
+            KeyContext::BranchesTags => "branches_tags",
+            KeyContext::BranchesRemotes => "branches_remotes",
+            KeyContext::Blame => "blame",
+            KeyContext::Reflog => "reflog",
+            KeyContext::ReflogBranch => "reflog_branch",
+            KeyContext::Conflicts => "conflicts",
+            KeyContext::ConflictsFiles => "conflicts_files",
+            KeyContext::ConflictsFileMode => "conflicts_file_mode",
+            KeyContext::ConflictsBlock => "conflicts_block",
+            KeyContext::ConflictsLine => "conflicts_line",
+            KeyContext::ConflictsResult => "conflicts_result",
+            KeyContext::ConflictsRebase => "conflicts_rebase",
+            KeyContext::ConflictsEdit => "conflicts_edit",
+            KeyContext::Palette => "palette",
+            KeyContext::PushDialog => "push_dialog",
+            KeyContext::MergePicker => "merge_picker",
+            KeyContext::ResetPicker => "reset_picker",
+            KeyContext::PullPicker => "pull_picker",
+            KeyContext::Confirm => "confirm",
+            KeyContext::CommandOutput => "command_output",
+            KeyContext::RebaseEditor => "rebase_editor",
+            KeyContext::RebaseProgress => "rebase_progress",
+            KeyContext::Reword => "reword",
+            KeyContext::Transfer => "transfer",
+        }
+    }
+
+    /// Titre de la section dans l'overlay d'aide.
+    pub fn title(self) -> &'static str {
+        match self {
+            KeyContext::Global => "Vues",
+            KeyContext::Graph => "Graph",
+            KeyContext::GraphFiles => "Graph — fichiers",
+            KeyContext::GraphDiff => "Graph — diff",
+            KeyContext::BranchPanel => "Panneau branches",
+            KeyContext::Help => "Aide",
+            KeyContext::Staging => "Staging",
+            KeyContext::StagingUnstaged => "Fichiers non stagés",
+            KeyContext::StagingStaged => "Fichiers stagés",
+            KeyContext::StagingDiff => "Diff",
+            KeyContext::StagingDiffUnstaged => "Diff non stagé",
+            KeyContext::StagingDiffStaged => "Diff stagé",
+            KeyContext::StagingLines => "Sélection de lignes",
+            KeyContext::StagingLinesUnstaged => "Sélection de lignes non stagées",
+            KeyContext::StagingLinesStaged => "Sélection de lignes stagées",
+            KeyContext::CommitMessage => "Message de commit",
+            KeyContext::MessageEditor => "Édition du message",
+            KeyContext::ConventionalPicker => "Assistant Conventional Commits",
+            KeyContext::Branches => "Branches",
+            KeyContext::BranchesList => "Section branches",
+            KeyContext::BranchesWorktrees => "Section worktrees",
+            KeyContext::BranchesStashes => "Section stashes",
+            KeyContext::BranchesTags => "Section tags",
+            KeyContext::BranchesRemotes => "Section remotes",
+            KeyContext::Blame => "Blame",
+            KeyContext::Reflog => "Reflog",
+            KeyContext::ReflogBranch => "Nouvelle branche sur l'entrée",
+            KeyContext::Conflicts => "Conflits",
+            KeyContext::ConflictsFiles => "Liste des fichiers",
+            KeyContext::ConflictsFileMode => "Panneaux Ours/Theirs — mode Fichier",
+            KeyContext::ConflictsBlock => "Panneaux Ours/Theirs — mode Bloc",
+            KeyContext::ConflictsLine => "Panneaux Ours/Theirs — mode Ligne",
+            KeyContext::ConflictsResult => "Panneau résultat",
+            KeyContext::ConflictsRebase => "Rebase en cours",
+            KeyContext::ConflictsEdit => "Édition du résultat",
+            KeyContext::Palette => "Palette de commandes",
+            KeyContext::PushDialog => "Dialogue de push",
+            KeyContext::MergePicker => "Sélection de la branche à merger",
+            KeyContext::ResetPicker => "Sélection du mode de reset",
+            KeyContext::PullPicker => "Sélection de la stratégie de pull",
+            KeyContext::Confirm => "Confirmation",
+            KeyContext::CommandOutput => "Sortie de commande",
+            KeyContext::RebaseEditor => "Éditeur de rebase",
+            KeyContext::RebaseProgress => "Progression du rebase",
+            KeyContext::Reword => "Nouveau message (reword)",
+            KeyContext::Transfer => "Transfert en cours",
+        }
+    }
+
+    /// Vue dont l'overlay d'aide affiche ce contexte (None : contexte transverse).
+    pub fn view(self) -> Option<ViewMode> {
+        match self {
+            KeyContext::Graph
+            | KeyContext::GraphFiles
+            | KeyContext::GraphDiff
+            | KeyContext::BranchPanel => Some(ViewMode::Graph),
+            KeyContext::Staging
+            | KeyContext::StagingUnstaged
+            | KeyContext::StagingStaged
+            | KeyContext::StagingDiff
+            | KeyContext::StagingDiffUnstaged
+            | KeyContext::StagingDiffStaged
+            | KeyContext::StagingLines
+            | KeyContext::StagingLinesUnstaged
+            | KeyContext::StagingLinesStaged
+            | KeyContext::CommitMessage
+            | KeyContext::MessageEditor
+            | KeyContext::ConventionalPicker => Some(ViewMode::Staging),
+            KeyContext::Branches
+            | KeyContext::BranchesList
+            | KeyContext::BranchesWorktrees
+            | KeyContext::BranchesStashes
+            | KeyContext::BranchesTags
+            | KeyContext::BranchesRemotes => Some(ViewMode::Branches),
+            KeyContext::Blame => Some(ViewMode::Blame),
+            KeyContext::Reflog | KeyContext::ReflogBranch => Some(ViewMode::Reflog),
+            KeyContext::Conflicts
+            | KeyContext::ConflictsFiles
+            | KeyContext::ConflictsFileMode
+            | KeyContext::ConflictsBlock
+            | KeyContext::ConflictsLine
+            | KeyContext::ConflictsResult
+            | KeyContext::ConflictsRebase
+            | KeyContext::ConflictsEdit => Some(ViewMode::Conflicts),
+            KeyContext::Global
+            | KeyContext::Help
+            | KeyContext::Palette
+            | KeyContext::PushDialog
+            | KeyContext::MergePicker
+            | KeyContext::ResetPicker
+            | KeyContext::PullPicker
+            | KeyContext::Confirm
+            | KeyContext::CommandOutput
+            | KeyContext::RebaseEditor
+            | KeyContext::RebaseProgress
+            | KeyContext::Reword
+            | KeyContext::Transfer => None,
+        }
+    }
+
+    /// Contexte correspondant à un nom de la configuration.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|ctx| ctx.name() == name)
+    }
+
+    /// Action insérant `c` dans la saisie de texte de ce contexte.
+    ///
+    /// Dans une saisie, un caractère sans raccourci est inséré (None : pas une saisie).
+    pub fn insert_action(self, c: char) -> Option<AppAction> {
+        match self {
+            KeyContext::CommitMessage | KeyContext::Reword => {
+                Some(AppAction::Edit(EditAction::InsertChar(c)))
+            }
+            KeyContext::ConventionalPicker => {
+                Some(AppAction::Staging(StagingAction::ConventionalInsertChar(c)))
+            }
+            KeyContext::ReflogBranch => Some(AppAction::Reflog(ReflogAction::BranchInsertChar(c))),
+            KeyContext::ConflictsEdit => Some(AppAction::ConflictEditInsertChar(c)),
+            KeyContext::Palette => Some(AppAction::Palette(PaletteAction::InsertChar(c))),
+            KeyContext::PushDialog => Some(AppAction::Push(PushAction::InsertChar(c))),
+            _ => None,
+        }
+    }
+
+    /// Contextes actifs de la vue graph, du plus prioritaire au moins prioritaire.
+    pub fn graph(focus: FocusPanel) -> Vec<KeyContext> {
+        match focus {
+            FocusPanel::Graph => vec![KeyContext::Graph, KeyContext::Global],
+            FocusPanel::BottomLeft => vec![
+                KeyContext::GraphFiles,
+                KeyContext::Graph,
+                KeyContext::Global,
+            ],
+            FocusPanel::BottomRight => {
+                vec![KeyContext::GraphDiff, KeyContext::Graph, KeyContext::Global]
+            }
+        }
+    }
+
+    /// Contextes actifs de la vue staging.
+    pub fn staging(state: &StagingState) -> Vec<KeyContext> {
+        let unstaged = state.diff_source == DiffSource::Unstaged;
+        let mut contexts = match state.focus {
+            StagingFocus::Unstaged => vec![KeyContext::StagingUnstaged],
+            StagingFocus::Staged => vec![KeyContext::StagingStaged],
+            StagingFocus::Diff if state.diff_selection_anchor.is_some() => vec![
+                if unstaged {
+                    KeyContext::StagingLinesUnstaged
+                } else {
+                    KeyContext::StagingLinesStaged
+                },
+                KeyContext::StagingLines,
+            ],
+            StagingFocus::Diff => vec![
+                if unstaged {
+                    KeyContext::StagingDiffUnstaged
+                } else {
+                    KeyContext::StagingDiffStaged
+                },
+                KeyContext::StagingDiff,
+            ],
+            // L'éditeur du message (ou son assistant) capture toutes les touches.
+            StagingFocus::CommitMessage if state.conventional_picker.is_some() => {
+                return vec![KeyContext::ConventionalPicker]
+            }
+            StagingFocus::CommitMessage => {
+                return vec![KeyContext::CommitMessage, KeyContext::MessageEditor]
+            }
+        };
+        contexts.extend([KeyContext::Staging, KeyContext::Global]);
+        contexts
+    }
+
+    /// Contextes actifs de la vue branches.
+    pub fn branches(section: BranchesSection) -> Vec<KeyContext> {
+        let section = match section {
+            BranchesSection::Branches => KeyContext::BranchesList,
+            BranchesSection::Worktrees => KeyContext::BranchesWorktrees,
+            BranchesSection::Stashes => KeyContext::BranchesStashes,
+            BranchesSection::Tags => KeyContext::BranchesTags,
+            BranchesSection::Remotes => KeyContext::BranchesRemotes,
+        };
+        vec![section, KeyContext::Branches, KeyContext::Global]
+    }
+
+    /// Contextes actifs de la vue conflits.
+    pub fn conflicts(state: &ConflictsState, rebasing: bool) -> Vec<KeyContext> {
+        // L'édition du résultat capture toutes les touches.
+        if state.is_editing {
+            return vec![KeyContext::ConflictsEdit];
+        }
+        let mut contexts = Vec::new();
+        if rebasing {
+            contexts.push(KeyContext::ConflictsRebase);
+        }
+        contexts.push(match state.panel_focus {
+            ConflictPanelFocus::FileList => KeyContext::ConflictsFiles,
+            ConflictPanelFocus::OursPanel | ConflictPanelFocus::TheirsPanel => {
+                match state.resolution_mode {
+                    ConflictResolutionMode::File => KeyContext::ConflictsFileMode,
+                    ConflictResolutionMode::Block => KeyContext::ConflictsBlock,
+                    ConflictResolutionMode::Line => KeyContext::ConflictsLine,
+                }
+            }
+            ConflictPanelFocus::ResultPanel => KeyContext::ConflictsResult,
+        });
+        contexts.extend([KeyContext::Conflicts, KeyContext::Global]);
+        contexts
+    }
+}
+
+/// Contextes actifs pour l'état courant, du plus prioritaire au moins prioritaire.
+///
+/// Les popups et les saisies de texte sont exclusifs : seules leurs touches sont
+/// actives. Le popup de progression d'un transfert ne l'est pas : la vue reste utilisable.
+pub fn active_contexts(state: &AppState) -> Vec<KeyContext> {
+    if state.command_output.is_some() {
+        return vec![KeyContext::CommandOutput];
+    }
+    if state.command_palette.is_some() {
+        return vec![KeyContext::Palette];
+    }
+    if state.push_dialog.is_some() {
+        return vec![KeyContext::PushDialog];
+    }
+    if state.merge_picker.as_ref().is_some_and(|p| p.is_active) {
+        return vec![KeyContext::MergePicker];
+    }
+    if state.reset_picker.as_ref().is_some_and(|p| p.is_active) {
+        return vec![KeyContext::ResetPicker];
+    }
+    if state.pull_picker.as_ref().is_some_and(|p| p.is_active) {
+        return vec![KeyContext::PullPicker];
+    }
+    if state.pending_confirmation.is_some() {
+        return vec![KeyContext::Confirm];
+    }
+    if let Some(ref editor) = state.rebase_editor {
+        return if editor.reword_input.is_some() {
+            vec![KeyContext::Reword, KeyContext::MessageEditor]
+        } else if state.rebase_session.is_some() {
+            vec![KeyContext::RebaseProgress]
+        } else {
+            vec![KeyContext::RebaseEditor]
+        };
+    }
+
+    let mut contexts = match state.view_mode {
+        ViewMode::Reflog
+            if state
+                .reflog_state
+                .as_ref()
+                .is_some_and(|r| r.branch_input.is_some()) =>
+        {
+            return vec![KeyContext::ReflogBranch];
+        }
+        ViewMode::Reflog => vec![KeyContext::Reflog],
+        ViewMode::Help => vec![KeyContext::Help, KeyContext::Global],
+        ViewMode::Blame => vec![KeyContext::Blame, KeyContext::Global],
+        ViewMode::Staging => KeyContext::staging(&state.staging_state),
+        ViewMode::Branches => KeyContext::branches(state.branches_view_state.section),
+        ViewMode::Conflicts => match state.conflicts_state {
+            Some(ref conflicts) => KeyContext::conflicts(conflicts, state.rebase_session.is_some()),
+            None => vec![KeyContext::Global],
+        },
+        ViewMode::Graph if state.show_branch_panel => {
+            vec![KeyContext::BranchPanel, KeyContext::Global]
+        }
+        ViewMode::Graph => KeyContext::graph(state.focus),
+    };
+    // Une saisie de texte garde ses touches pendant un transfert.
+    let typing = contexts
+        .first()
+        .is_some_and(|c| c.insert_action(' ').is_some());
+    if state.remote_task.is_some() && !typing {
+        contexts.insert(0, KeyContext::Transfer);
+    }
+    contexts
+}
+
+/// Raccourci : une action déclenchée par une ou plusieurs séquences de touches.
+#[derive(Debug, Clone)]
+pub struct Binding {
+    /// Contexte dans lequel le raccourci est actif.
+    pub context: KeyContext,
+    /// Identifiant utilisé dans la configuration (ex: "move_down").
+    pub name: &'static str,
+    /// Séquences de touches déclenchant l'action.
+    pub keys: Vec<KeySequence>,
+    /// Action déclenchée.
+    pub action: AppAction,
+    /// Description affichée dans l'overlay d'aide.
+    pub description: &'static str,
+    /// Libellé court dans la barre d'aide (absent : non affiché).
+    pub bar: Option<&'static str>,
+}
+
+impl Binding {
+    /// Affiche le raccourci dans la barre d'aide avec ce libellé.
+    fn bar(mut self, label: &'static str) -> Self {
+        self.bar = Some(label);
+        self
+    }
+
+    /// Libellé des touches pour l'overlay d'aide (ex: "j / ↓").
+    pub fn keys_label(&self) -> String {
+        self.keys
+            .iter()
+            .map(|seq| sequence_label(seq))
+            .collect::<Vec<_>>()
+            .join(" / ")
+    }
+
+    /// Libellé des touches pour la barre d'aide, sans les doublons de navigation.
+    ///
+    /// Les séquences de `shadowed` (prises par un contexte prioritaire) sont ignorées.
+    fn bar_keys_label(&self, shadowed: &[KeySequence]) -> Option<String> {
+        let keys: Vec<&KeySequence> = self
+            .keys
+            .iter()
+            .filter(|seq| !shadowed.contains(seq))
+            .collect();
+        let main: Vec<&KeySequence> = keys
+            .iter()
+            .copied()
+            .filter(|seq| !seq.iter().all(KeyChord::is_navigation))
+            .collect();
+        let shown = if main.is_empty() { keys } else { main };
+        if shown.is_empty() {
+            return None;
+        }
+        Some(
+            shown
+                .into_iter()
+                .map(|seq| sequence_label(seq))
+                .collect::<Vec<_>>()
+                .join("/"),
+        )
+    }
+}
+
+/// Crée un raccourci (les séquences de la table par défaut sont toujours valides).
+fn bind(
+    context: KeyContext,
+    name: &'static str,
+    keys: &[&str],
+    action: AppAction,
+    description: &'static str,
+) -> Binding {
+    Binding {
+        context,
+        name,
+        keys: keys
+            .iter()
+            .map(|k| parse_sequence(k).expect("touche invalide dans la table par défaut"))
+            .collect(),
+        action,
+        description,
+        bar: None,
+    }
+}
+
+/// Résultat de la recherche d'une séquence de touches.
+#[derive(Debug, PartialEq)]
+enum Lookup {
+    /// La séquence déclenche une action.
+    Action(AppAction),
+    /// La séquence est le début d'une séquence plus longue.
+    Prefix,
+    /// Aucun raccourci ne correspond.
+    None,
+}
+
+/// Touches d'une séquence en cours de saisie (ex: premier "g" de "g g").
+#[derive(Debug, Default)]
+pub struct PendingKeys {
+    keys: KeySequence,
+    since: Option<Instant>,
+}
+
+impl PendingKeys {
+    /// Abandonne la séquence en cours.
+    pub fn clear(&mut self) {
+        self.keys.clear();
+        self.since = None;
+    }
+
+    /// Vrai si aucune séquence n'est en cours.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    fn is_expired(&self) -> bool {
+        self.since
+            .is_some_and(|since| since.elapsed() > SEQUENCE_TIMEOUT)
+    }
+}
+
+/// Table des raccourcis.
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<Binding>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self {
+            bindings: default_bindings(),
+        }
+    }
+}
+
+impl Keymap {
+    /// Table par défaut surchargée par la section `[keys]` de la configuration.
+    ///
+    /// Pour chaque contexte : séquence de touches → nom d'action (ou "none").
+    /// Le nom est cherché dans le contexte, puis dans les autres contextes.
+    pub fn with_overrides(
+        overrides: &BTreeMap<String, BTreeMap<String, String>>,
+    ) -> std::result::Result<Self, String> {
+        let mut keymap = Self::default();
+
+        for (context_name, entries) in overrides {
+            let context = KeyContext::from_name(context_name).ok_or_else(|| {
+                format!(
+                    "contexte inconnu '{}' dans [keys] (attendu : {})",
+                    context_name,
+                    KeyContext::ALL.map(KeyContext::name).join(", ")
+                )
+            })?;
+
+            for (keys, target) in entries {
+                let sequence = parse_sequence(keys)?;
+                keymap.unbind(context, &sequence);
+                if target == UNBIND {
+                    continue;
+                }
+
+                if let Some(binding) = keymap
+                    .bindings
+                    .iter_mut()
+                    .find(|b| b.context == context && b.name == target)
+                {
+                    binding.keys.push(sequence);
+                    continue;
+                }
+
+                let template = keymap
+                    .bindings
+                    .iter()
+                    .find(|b| b.name == target)
+                    .cloned()
+                    .ok_or_else(|| {
+                        format!("action inconnue '{}' dans [keys.{}]", target, context_name)
+                    })?;
+                keymap.bindings.push(Binding {
+                    context,
+                    keys: vec![sequence],
+                    bar: None,
+                    ..template
+                });
+            }
+        }
+
+        Ok(keymap)
+    }
+
+    /// Ajoute les commandes personnalisées (prioritaires sur les touches de leur contexte).
+    ///
+    /// Les contextes et les touches ont été validés au chargement du fichier.
+    pub fn with_custom_commands(mut self, commands: &'static [CustomCommand]) -> Self {
+        for (index, command) in commands.iter().enumerate() {
+            let (Some(context), Ok(sequence)) = (
+                KeyContext::from_name(&command.context),
+                parse_sequence(&command.key),
+            ) else {
+                continue;
+            };
+            self.unbind(context, &sequence);
+            self.bindings.push(Binding {
+                context,
+                name: &command.name,
+                keys: vec![sequence],
+                action: AppAction::Command(CommandAction::Run(index)),
+                description: command.description(),
+                bar: None,
+            });
+        }
+        self
+    }
+
+    /// Retire une séquence des raccourcis d'un contexte.
+    fn unbind(&mut self, context: KeyContext, sequence: &[KeyChord]) {
+        for binding in self.bindings.iter_mut().filter(|b| b.context == context) {
+            binding.keys.retain(|seq| seq != sequence);
+        }
+    }
+
+    /// Raccourcis d'un contexte, dans l'ordre de la table.
+    pub fn bindings(&self, context: KeyContext) -> impl Iterator<Item = &Binding> {
+        self.bindings
+            .iter()
+            .filter(move |b| b.context == context && !b.keys.is_empty())
+    }
+
+    /// Raccourcis des contextes donnés (y compris sans touche), une fois par action.
+    pub fn available(&self, contexts: &[KeyContext]) -> Vec<&Binding> {
+        let mut available: Vec<&Binding> = Vec::new();
+        for &context in contexts {
+            for binding in self.bindings.iter().filter(|b| b.context == context) {
+                if !available.iter().any(|b| b.action == binding.action) {
+                    available.push(binding);
+                }
+            }
+        }
+        available
+    }
+
+    /// Touches de l'action dans le premier contexte qui la définit (ex: "c/enter").
+    ///
+    /// None si l'action n'a plus de touche (retirée par la configuration).
+    pub fn action_keys(&self, contexts: &[KeyContext], action: &AppAction) -> Option<String> {
+        contexts.iter().find_map(|&context| {
+            self.bindings(context)
+                .find(|b| b.action == *action)
+                .and_then(|b| b.bar_keys_label(&[]))
+        })
+    }
+
+    /// Cherche une séquence dans les contextes, par ordre de priorité.
+    fn lookup(&self, contexts: &[KeyContext], keys: &[KeyChord]) -> Lookup {
+        for &context in contexts {
+            let mut prefix = false;
+            for binding in self.bindings(context) {
+                for seq in &binding.keys {
+                    if seq.as_slice() == keys {
+                        return Lookup::Action(binding.action.clone());
+                    }
+                    prefix |= seq.len() > keys.len() && seq.starts_with(keys);
+                }
+            }
+            if prefix {
+                return Lookup::Prefix;
+            }
+        }
+        Lookup::None
+    }
+
+    /// Résout une touche pressée en tenant compte de la séquence en cours.
+    pub fn resolve(
+        &self,
+        contexts: &[KeyContext],
+        pending: &mut PendingKeys,
+        key: &KeyEvent,
+    ) -> Option<AppAction> {
+        if pending.is_expired() {
+            pending.clear();
+        }
+
+        let chord = KeyChord::from_event(key);
+        pending.keys.push(chord);
+
+        match self.lookup(contexts, &pending.keys) {
+            Lookup::Action(action) => {
+                pending.clear();
+                Some(action)
+            }
+            Lookup::Prefix => {
+                pending.since = Some(Instant::now());
+                None
+            }
+            Lookup::None => {
+                let interrupted = pending.keys.len() > 1;
+                pending.clear();
+                // La séquence interrompue est abandonnée, la touche est rejouée seule.
+                if interrupted {
+                    self.resolve(contexts, pending, key)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Entrées de la barre d'aide (touches, libellé) pour les contextes donnés.
+    ///
+    /// Les raccourcis partageant un libellé sont regroupés (ex: "j/k:naviguer").
+    pub fn bar_entries(
+        &self,
+        contexts: &[KeyContext],
+        show: impl Fn(&Binding) -> bool,
+    ) -> Vec<(String, &'static str)> {
+        let mut entries: Vec<(String, &'static str)> = Vec::new();
+        let mut shadowed: Vec<KeySequence> = Vec::new();
+        for &context in contexts {
+            for binding in self.bindings(context).filter(|b| show(b)) {
+                let Some(label) = binding.bar else {
+                    continue;
+                };
+                let Some(keys) = binding.bar_keys_label(&shadowed) else {
+                    continue;
+                };
+                match entries.iter_mut().find(|(_, l)| *l == label) {
+                    Some((existing, _)) => {
+                        if !existing.split('/').any(|k| k == keys) {
+                            existing.push('/');
+                            existing.push_str(&keys);
+                        }
+                    }
+                    None => entries.push((keys, label)),
+                }
+            }
+            shadowed.extend(self.bindings(context).flat_map(|b| b.keys.iter().cloned()));
+        }
+        entries
+    }
+
+    /// Texte de la barre d'aide pour les contextes donnés.
+    pub fn bar_text(&self, contexts: &[KeyContext]) -> String {
+        self.bar_entries(contexts, |_| true)
+            .into_iter()
+            .map(|(keys, label)| format!("{}:{}", keys, label))
+            .collect::<Vec<_>>()
+            .join("  ")
+    }
+}
+
+/// Table des raccourcis globale, construite depuis la configuration.
+static KEYMAP: OnceLock<Keymap> = OnceLock::new();
+
+/// Retourne la table des raccourcis courante.
+///
+/// La configuration est validée au chargement : en cas d'erreur on retombe
+/// sur la table par défaut.
+pub fn current() -> &'static Keymap {
+    KEYMAP.get_or_init(|| {
+        Keymap::with_overrides(&crate::config::current().keys)
+            .unwrap_or_default()
+            .with_custom_commands(crate::commands::current())
+    })
+}
+
+/// Table par défaut des raccourcis.
+fn default_bindings() -> Vec<Binding> {
+    use KeyContext as C;
+
+    vec![
+        // ── Global ──
+        bind(
+            C::Global,
+            "graph_view",
+            &["1"],
+            AppAction::SwitchToGraph,
+            "Vue Graph",
+        ),
+        bind(
+            C::Global,
+            "staging_view",
+            &["2"],
+            AppAction::SwitchToStaging,
+            "Vue Staging",
+        ),
+        bind(
+            C::Global,
+            "branches_view",
+            &["3"],
+            AppAction::SwitchToBranches,
+            "Vue Branches",
+        ),
+        bind(
+            C::Global,
+            "conflicts_view",
+            &["4"],
+            AppAction::SwitchToConflicts,
+            "Vue Conflits (si actifs)",
+        ),
+        bind(
+            C::Global,
+            "command_palette",
+            &[":", "ctrl-p"],
+            AppAction::Palette(PaletteAction::Open),
+            "Palette de commandes",
+        ),
+        // ── Graph ──
+        bind(
+            C::Graph,
+            "move_down",
+            &["j", "down"],
+            AppAction::MoveDown,
+            "Commit suivant",
+        )
+        .bar("naviguer"),
+        bind(
+            C::Graph,
+            "move_up",
+            &["k", "up"],
+            AppAction::MoveUp,
+            "Commit précédent",
+        )
+        .bar("naviguer"),
+        bind(
+            C::Graph,
+            "go_top",
+            &["g g", "home"],
+            AppAction::GoTop,
+            "Premier commit",
+        ),
+        bind(
+            C::Graph,
+            "go_bottom",
+            &["G", "end"],
+            AppAction::GoBottom,
+            "Dernier commit",
+        ),
+        bind(
+            C::Graph,
+            "page_down",
+            &["ctrl-d", "pagedown"],
+            AppAction::PageDown,
+            "Page suivante",
+        ),
+        bind(
+            C::Graph,
+            "page_up",
+            &["ctrl-u", "pageup"],
+            AppAction::PageUp,
+            "Page précédente",
+        ),
+        bind(
+            C::Graph,
+            "select",
+            &["enter"],
+            AppAction::Select,
+            "Détail / action",
+        )
+        .bar("détail"),
+        bind(
+            C::Graph,
+            "switch_panel",
+            &["tab"],
+            AppAction::Navigation(NavigationAction::SwitchPanel),
+            "Basculer panneaux",
+        )
+        .bar("panneaux"),
+        bind(
+            C::Graph,
+            "bottom_mode",
+            &["M"],
+            AppAction::SwitchBottomMode,
+            "Fichiers / parents du commit",
+        ),
+        bind(
+            C::Graph,
+            "branches",
+            &["b"],
+            AppAction::BranchList,
+            "Panneau branches",
+        )
+        .bar("branches"),
+        bind(
+            C::Graph,
+            "commit",
+            &["c"],
+            AppAction::CommitPrompt,
+            "Nouveau commit",
+        )
+        .bar("commit"),
+        bind(C::Graph, "stash", &["s"], AppAction::StashPrompt, "Stash").bar("stash"),
+        bind(C::Graph, "merge", &["m"], AppAction::MergePrompt, "Merge").bar("merge"),
+        bind(C::Graph, "push", &["P"], AppAction::GitPush, "Push").bar("push"),
+        bind(
+            C::Graph,
+            "push_options",
+            &["alt-p"],
+            AppAction::Push(PushAction::Open),
+            "Push avec options (force-with-lease, branche distante...)",
+        ),
+        bind(C::Graph, "pull", &["p"], AppAction::GitPull, "Pull"),
+        bind(
+            C::Graph,
+            "pull_options",
+            &["alt-l"],
+            AppAction::Git(GitAction::PullPrompt),
+            "Pull avec choix de la stratégie (merge, rebase, ff-only)",
+        ),
+        bind(C::Graph, "fetch", &["f"], AppAction::GitFetch, "Fetch"),
+        bind(
+            C::Graph,
+            "cherry_pick",
+            &["x"],
+            AppAction::CherryPick,
+            "Cherry-pick",
+        ),
+        bind(
+            C::Graph,
+            "revert",
+            &["R"],
+            AppAction::Git(GitAction::Revert),
+            "Revert (merge : choisir le parent)",
+        ),
+        bind(
+            C::Graph,
+            "rebase",
+            &["i"],
+            AppAction::Rebase(RebaseAction::Open),
+            "Rebase interactif",
+        ),
+        bind(
+            C::Graph,
+            "reset",
+            &["X"],
+            AppAction::Git(GitAction::ResetPrompt),
+            "Reset (soft/mixed/hard)",
+        ),
+        bind(
+            C::Graph,
+            "tag",
+            &["T"],
+            AppAction::Branch(BranchAction::TagCreate),
+            "Tag sur le commit",
+        ),
+        bind(
+            C::Graph,
+            "reflog",
+            &["L"],
+            AppAction::Reflog(ReflogAction::Open),
+            "Reflog (HEAD ou branche)",
+        ),
+        bind(
+            C::Graph,
+            "undo",
+            &["z"],
+            AppAction::Git(GitAction::Undo),
+            "Annuler la dernière action",
+        ),
+        bind(
+            C::Graph,
+            "redo",
+            &["Z"],
+            AppAction::Git(GitAction::Redo),
+            "Rétablir la dernière action annulée",
+        ),
+        bind(
+            C::Graph,
+            "blame",
+            &["B"],
+            AppAction::OpenBlame,
+            "Blame du fichier",
+        ),
+        bind(
+            C::Graph,
+            "search",
+            &["/"],
+            AppAction::OpenSearch,
+            "Ouvrir la recherche",
+        ),
+        bind(
+            C::Graph,
+            "next_result",
+            &["n"],
+            AppAction::NextSearchResult,
+            "Résultat suivant",
+        ),
+        bind(
+            C::Graph,
+            "prev_result",
+            &["N"],
+            AppAction::PrevSearchResult,
+            "Résultat précédent",
+        ),
+        bind(
+            C::Graph,
+            "filter",
+            &["F"],
+            AppAction::OpenFilter,
+            "Filtre avancé",
+        ),
+        bind(
+            C::Graph,
+            "clear_filter",
+            &["ctrl-r"],
+            AppAction::ClearFilter,
+            "Effacer les filtres",
+        )
+        .bar("effacer filtres"),
+        bind(
+            C::Graph,
+            "refresh",
+            &["r"],
+            AppAction::Refresh,
+            "Rafraîchir",
+        )
+        .bar("rafraîchir"),
+        bind(
+            C::Graph,
+            "copy",
+            &["y"],
+            AppAction::CopyPanelContent,
+            "Copier dans le clipboard",
+        ),
+        bind(C::Graph, "help", &["?"], AppAction::ToggleHelp, "Aide").bar("aide"),
+        bind(C::Graph, "quit", &["q"], AppAction::Quit, "Quitter").bar("quitter"),
+        // ── Graph : panneau fichiers ──
+        bind(
+            C::GraphFiles,
+            "file_down",
+            &["j", "down"],
+            AppAction::FileDown,
+            "Fichier suivant",
+        )
+        .bar("fichiers"),
+        bind(
+            C::GraphFiles,
+            "file_up",
+            &["k", "up"],
+            AppAction::FileUp,
+            "Fichier précédent",
+        )
+        .bar("fichiers"),
+        bind(
+            C::GraphFiles,
+            "show_diff",
+            &["enter"],
+            AppAction::SwitchBottomMode,
+            "Afficher le diff du fichier",
+        )
+        .bar("diff"),
+        bind(
+            C::GraphFiles,
+            "back",
+            &["esc"],
+            AppAction::Navigation(NavigationAction::BackToGraph),
+            "Retour au graphe",
+        )
+        .bar("retour"),
+        // ── Graph : panneau diff ──
+        bind(
+            C::GraphDiff,
+            "scroll_down",
+            &["j", "down", "ctrl-d"],
+            AppAction::DiffScrollDown,
+            "Défiler le diff vers le bas",
+        )
+        .bar("défiler"),
+        bind(
+            C::GraphDiff,
+            "scroll_up",
+            &["k", "up", "ctrl-u"],
+            AppAction::DiffScrollUp,
+            "Défiler le diff vers le haut",
+        )
+        .bar("défiler"),
+        bind(
+            C::GraphDiff,
+            "toggle_diff_view",
+            &["v"],
+            AppAction::ToggleDiffViewMode,
+            "Diff unifié / côte à côte",
+        )
+        .bar("vue"),
+        bind(
+            C::GraphDiff,
+            "back",
+            &["esc"],
+            AppAction::SwitchBottomMode,
+            "Retour aux fichiers",
+        )
+        .bar("retour"),
+        // ── Panneau branches du graph ──
+        bind(
+            C::BranchPanel,
+            "close",
+            &["esc", "b"],
+            AppAction::CloseBranchPanel,
+            "Fermer le panneau",
+        ),
+        bind(
+            C::BranchPanel,
+            "move_down",
+            &["j", "down"],
+            AppAction::MoveDown,
+            "Branche suivante",
+        ),
+        bind(
+            C::BranchPanel,
+            "move_up",
+            &["k", "up"],
+            AppAction::MoveUp,
+            "Branche précédente",
+        ),
+        bind(
+            C::BranchPanel,
+            "checkout",
+            &["enter"],
+            AppAction::BranchCheckout,
+            "Checkout",
+        ),
+        bind(
+            C::BranchPanel,
+            "create",
+            &["n"],
+            AppAction::BranchCreate,
+            "Nouvelle branche",
+        ),
+        bind(
+            C::BranchPanel,
+            "delete",
+            &["d"],
+            AppAction::BranchDelete,
+            "Supprimer la branche",
+        ),
+        // ── Overlay d'aide ──
+        bind(
+            C::Help,
+            "close",
+            &["esc", "?"],
+            AppAction::ToggleHelp,
+            "Fermer l'aide",
+        ),
+        bind(C::Help, "quit", &["q"], AppAction::Quit, "Quitter"),
+        // ── Staging ──
+        bind(C::Staging, "push", &["P"], AppAction::GitPush, "Push").bar("push"),
+        bind(
+            C::Staging,
+            "push_options",
+            &["alt-p"],
+            AppAction::Push(PushAction::Open),
+            "Push avec options (force-with-lease, branche distante...)",
+        ),
+        bind(
+            C::Staging,
+            "undo",
+            &["z"],
+            AppAction::Git(GitAction::Undo),
+            "Annuler la dernière action",
+        )
+        .bar("undo/redo"),
+        bind(
+            C::Staging,
+            "redo",
+            &["Z"],
+            AppAction::Git(GitAction::Redo),
+            "Rétablir la dernière action annulée",
+        )
+        .bar("undo/redo"),
+        bind(
+            C::Staging,
+            "refresh",
+            &["r"],
+            AppAction::Refresh,
+            "Rafraîchir",
+        ),
+        bind(
+            C::Staging,
+            "copy",
+            &["y"],
+            AppAction::CopyPanelContent,
+            "Copier dans le clipboard",
+        ),
+        bind(C::Staging, "help", &["?"], AppAction::ToggleHelp, "Aide"),
+        bind(C::Staging, "quit", &["q"], AppAction::Quit, "Quitter").bar("quit"),
+        bind(
+            C::StagingUnstaged,
+            "move_down",
+            &["j", "down"],
+            AppAction::MoveDown,
+            "Fichier suivant",
+        )
+        .bar("nav"),
+        bind(
+            C::StagingUnstaged,
+            "move_up",
+            &["k", "up"],
+            AppAction::MoveUp,
+            "Fichier précédent",
+        )
+        .bar("nav"),
+        bind(
+            C::StagingUnstaged,
+            "stage_file",
+            &["s", "enter"],
+            AppAction::StageFile,
+            "Stager le fichier",
+        )
+        .bar("stage"),
+        bind(
+            C::StagingUnstaged,
+            "stash_file",
+            &["S"],
+            AppAction::StashSelectedFile,
+            "Stasher le fichier",
+        )
+        .bar("stash"),
+        bind(
+            C::StagingUnstaged,
+            "stash_unstaged",
+            &["ctrl-S"],
+            AppAction::StashUnstagedFiles,
+            "Stasher les fichiers non stagés",
+        ),
+        bind(
+            C::StagingUnstaged,
+            "stage_all",
+            &["a"],
+            AppAction::StageAll,
+            "Tout stager",
+        )
+        .bar("stage all"),
+        bind(
+            C::StagingUnstaged,
+            "discard_file",
+            &["d"],
+            AppAction::DiscardFile,
+            "Annuler les modifications du fichier",
+        )
+        .bar("discard"),
+        bind(
+            C::StagingUnstaged,
+            "discard_all",
+            &["D"],
+            AppAction::DiscardAll,
+            "Annuler toutes les modifications",
+        ),
+        bind(
+            C::StagingUnstaged,
+            "switch_focus",
+            &["tab"],
+            AppAction::SwitchStagingFocus,
+            "Panneau suivant",
+        )
+        .bar("→Staged"),
+        bind(
+            C::StagingUnstaged,
+            "commit",
+            &["c"],
+            AppAction::StartCommitMessage,
+            "Saisir le message de commit",
+        )
+        .bar("commit"),
+        bind(
+            C::StagingUnstaged,
+            "commit_editor",
+            &["C"],
+            AppAction::Staging(StagingAction::OpenExternalEditor),
+            "Écrire le message de commit dans l'éditeur externe",
+        ),
+        bind(
+            C::StagingUnstaged,
+            "conventional_commit",
+            &["ctrl-t"],
+            AppAction::Staging(StagingAction::ConventionalOpen),
+            "Commit Conventional Commits (type et scope)",
+        ),
+        bind(
+            C::StagingStaged,
+            "move_down",
+            &["j", "down"],
+            AppAction::MoveDown,
+            "Fichier suivant",
+        )
+        .bar("nav"),
+        bind(
+            C::StagingStaged,
+            "move_up",
+            &["k", "up"],
+            AppAction::MoveUp,
+            "Fichier précédent",
+        )
+        .bar("nav"),
+        bind(
+            C::StagingStaged,
+            "unstage_file",
+            &["u", "enter"],
+            AppAction::UnstageFile,
+            "Unstager le fichier",
+        )
+        .bar("unstage"),
+        bind(
+            C::StagingStaged,
+            "unstage_all",
+            &["U"],
+            AppAction::UnstageAll,
+            "Tout unstager",
+        )
+        .bar("unstage all"),
+        bind(
+            C::StagingStaged,
+            "switch_focus",
+            &["tab"],
+            AppAction::SwitchStagingFocus,
+            "Panneau suivant",
+        )
+        .bar("→Diff"),
+        bind(
+            C::StagingStaged,
+            "commit",
+            &["c"],
+            AppAction::StartCommitMessage,
+            "Saisir le message de commit",
+        )
+        .bar("commit"),
+        bind(
+            C::StagingStaged,
+            "commit_editor",
+            &["C"],
+            AppAction::Staging(StagingAction::OpenExternalEditor),
+            "Écrire le message de commit dans l'éditeur externe",
+        ),
+        bind(
+            C::StagingStaged,
+            "conventional_commit",
+            &["ctrl-t"],
+            AppAction::Staging(StagingAction::ConventionalOpen),
+            "Commit Conventional Commits (type et scope)",
+        ),
+        bind(
+            C::StagingStaged,
+            "amend",
+            &["A"],
+            AppAction::AmendCommit,
+            "Amender le dernier commit",
+        ),
+        bind(
+            C::StagingDiff,
+            "move_down",
+            &["j", "down"],
+            AppAction::MoveDown,
+            "Ligne suivante",
+        )
+        .bar("ligne"),
+        bind(
+            C::StagingDiff,
+            "move_up",
+            &["k", "up"],
+            AppAction::MoveUp,
+            "Ligne précédente",
+        )
+        .bar("ligne"),
+        bind(
+            C::StagingDiff,
+            "next_hunk",
+            &["n"],
+            AppAction::Staging(StagingAction::NextHunk),
+            "Hunk suivant",
+        )
+        .bar("hunk"),
+        bind(
+            C::StagingDiff,
+            "prev_hunk",
+            &["N"],
+            AppAction::Staging(StagingAction::PrevHunk),
+            "Hunk précédent",
+        )
+        .bar("hunk"),
+        bind(
+            C::StagingDiff,
+            "stage_hunk",
+            &["s"],
+            AppAction::Staging(StagingAction::StageHunk),
+            "Stager le hunk",
+        ),
+        bind(
+            C::StagingDiff,
+            "unstage_hunk",
+            &["u"],
+            AppAction::Staging(StagingAction::UnstageHunk),
+            "Unstager le hunk",
+        ),
+        bind(
+            C::StagingDiff,
+            "discard_hunk",
+            &["d"],
+            AppAction::Staging(StagingAction::DiscardHunk),
+            "Annuler les modifications du hunk",
+        )
+        .bar("discard hunk"),
+        bind(
+            C::StagingDiff,
+            "select_lines",
+            &["V"],
+            AppAction::Staging(StagingAction::ToggleLineSelection),
+            "Sélection de lignes",
+        )
+        .bar("sélection"),
+        bind(
+            C::StagingDiff,
+            "toggle_diff_view",
+            &["v"],
+            AppAction::ToggleDiffViewMode,
+            "Diff unifié / côte à côte",
+        )
+        .bar("vue"),
+        bind(
+            C::StagingDiff,
+            "switch_focus",
+            &["tab", "esc"],
+            AppAction::SwitchStagingFocus,
+            "Retour aux fichiers non stagés",
+        )
+        .bar("→Unstaged"),
+        bind(
+            C::StagingDiff,
+            "commit",
+            &["c"],
+            AppAction::StartCommitMessage,
+            "Saisir le message de commit",
+        ),
+        bind(
+            C::StagingDiffUnstaged,
+            "apply_hunk",
+            &["enter"],
+            AppAction::Staging(StagingAction::StageHunk),
+            "Stager le hunk",
+        )
+        .bar("stage hunk"),
+        bind(
+            C::StagingDiffStaged,
+            "apply_hunk",
+            &["enter"],
+            AppAction::Staging(StagingAction::UnstageHunk),
+            "Unstager le hunk",
+        )
+        .bar("unstage hunk"),
+        bind(
+            C::StagingLines,
+            "extend_down",
+            &["j", "down"],
+            AppAction::MoveDown,
+            "Étendre la sélection vers le bas",
+        )
+        .bar("étendre"),
+        bind(
+            C::StagingLines,
+            "extend_up",
+            &["k", "up"],
+            AppAction::MoveUp,
+            "Étendre la sélection vers le haut",
+        )
+        .bar("étendre"),
+        bind(
+            C::StagingLines,
+            "stage_lines",
+            &["s"],
+            AppAction::Staging(StagingAction::StageSelectedLines),
+            "Stager les lignes sélectionnées",
+        ),
+        bind(
+            C::StagingLines,
+            "unstage_lines",
+            &["u"],
+            AppAction::Staging(StagingAction::UnstageSelectedLines),
+            "Unstager les lignes sélectionnées",
+        ),
+        bind(
+            C::StagingLines,
+            "cancel",
+            &["V", "esc"],
+            AppAction::Staging(StagingAction::ToggleLineSelection),
+            "Annuler la sélection",
+        )
+        .bar("annuler"),
+        bind(
+            C::StagingLinesUnstaged,
+            "apply_lines",
+            &["enter"],
+            AppAction::Staging(StagingAction::StageSelectedLines),
+            "Stager les lignes sélectionnées",
+        )
+        .bar("stage lignes"),
+        bind(
+            C::StagingLinesStaged,
+            "apply_lines",
+            &["enter"],
+            AppAction::Staging(StagingAction::UnstageSelectedLines),
+            "Unstager les lignes sélectionnées",
+        )
+        .bar("unstage lignes"),
+        // ── Message de commit ──
+        bind(
+            C::CommitMessage,
+            "confirm",
+            &["ctrl-s", "ctrl-enter", "alt-enter"],
+            AppAction::ConfirmCommit,
+            "Valider le commit",
+        )
+        .bar("valider"),
+        bind(
+            C::CommitMessage,
+            "cancel",
+            &["esc"],
+            AppAction::CancelCommitMessage,
+            "Annuler le commit",
+        )
+        .bar("annuler"),
+        bind(
+            C::CommitMessage,
+            "skip_hooks",
+            &["ctrl-n"],
+            AppAction::Staging(StagingAction::ToggleSkipHooks),
+            "Ignorer les hooks (--no-verify)",
+        )
+        .bar("ignorer les hooks"),
+        bind(
+            C::CommitMessage,
+            "external_editor",
+            &["ctrl-o"],
+            AppAction::Staging(StagingAction::OpenExternalEditor),
+            "Éditer le message dans $EDITOR",
+        )
+        .bar("$EDITOR"),
+        bind(
+            C::CommitMessage,
+            "conventional",
+            &["ctrl-t"],
+            AppAction::Staging(StagingAction::ConventionalOpen),
+            "Assistant Conventional Commits",
+        )
+        .bar("conventional"),
+        bind(
+            C::MessageEditor,
+            "new_line",
+            &["enter"],
+            AppAction::Edit(EditAction::NewLine),
+            "Nouvelle ligne",
+        )
+        .bar("nouvelle ligne"),
+        bind(
+            C::MessageEditor,
+            "delete_char_before",
+            &["backspace"],
+            AppAction::Edit(EditAction::DeleteCharBefore),
+            "Effacer le caractère précédent",
+        ),
+        bind(
+            C::MessageEditor,
+            "delete_char_after",
+            &["delete"],
+            AppAction::Edit(EditAction::DeleteCharAfter),
+            "Effacer le caractère suivant",
+        ),
+        bind(
+            C::MessageEditor,
+            "delete_word_before",
+            &["ctrl-w", "ctrl-backspace", "alt-backspace"],
+            AppAction::Edit(EditAction::DeleteWordBefore),
+            "Effacer le mot précédent",
+        )
+        .bar("effacer le mot"),
+        bind(
+            C::MessageEditor,
+            "cursor_left",
+            &["left"],
+            AppAction::Edit(EditAction::CursorLeft),
+            "Caractère précédent",
+        ),
+        bind(
+            C::MessageEditor,
+            "cursor_right",
+            &["right"],
+            AppAction::Edit(EditAction::CursorRight),
+            "Caractère suivant",
+        ),
+        bind(
+            C::MessageEditor,
+            "cursor_up",
+            &["up"],
+            AppAction::Edit(EditAction::CursorUp),
+            "Ligne précédente",
+        ),
+        bind(
+            C::MessageEditor,
+            "cursor_down",
+            &["down"],
+            AppAction::Edit(EditAction::CursorDown),
+            "Ligne suivante",
+        ),
+        bind(
+            C::MessageEditor,
+            "word_left",
+            &["ctrl-left", "alt-left", "alt-b"],
+            AppAction::Edit(EditAction::WordLeft),
+            "Mot précédent",
+        )
+        .bar("mot"),
+        bind(
+            C::MessageEditor,
+            "word_right",
+            &["ctrl-right", "alt-right", "alt-f"],
+            AppAction::Edit(EditAction::WordRight),
+            "Mot suivant",
+        )
+        .bar("mot"),
+        bind(
+            C::MessageEditor,
+            "line_start",
+            &["home", "ctrl-a"],
+            AppAction::Edit(EditAction::CursorHome),
+            "Début de ligne",
+        ),
+        bind(
+            C::MessageEditor,
+            "line_end",
+            &["end", "ctrl-e"],
+            AppAction::Edit(EditAction::CursorEnd),
+            "Fin de ligne",
+        ),
+        bind(
+            C::ConventionalPicker,
+            "confirm",
+            &["enter"],
+            AppAction::Staging(StagingAction::ConventionalConfirm),
+            "Choisir l'entrée sélectionnée",
+        )
+        .bar("choisir"),
+        bind(
+            C::ConventionalPicker,
+            "next",
+            &["down", "tab", "ctrl-n"],
+            AppAction::Staging(StagingAction::ConventionalNext),
+            "Entrée suivante",
+        ),
+        bind(
+            C::ConventionalPicker,
+            "previous",
+            &["up", "shift-tab", "ctrl-p"],
+            AppAction::Staging(StagingAction::ConventionalPrevious),
+            "Entrée précédente",
+        ),
+        bind(
+            C::ConventionalPicker,
+            "delete_char_before",
+            &["backspace"],
+            AppAction::Staging(StagingAction::ConventionalBackspace),
+            "Effacer le caractère précédent",
+        ),
+        bind(
+            C::ConventionalPicker,
+            "cancel",
+            &["esc"],
+            AppAction::Staging(StagingAction::ConventionalCancel),
+            "Fermer l'assistant",
+        )
+        .bar("annuler"),
+        // ── Branches ──
+        bind(
+            C::Branches,
+            "next_section",
+            &["tab"],
+            AppAction::NextSection,
+            "Section suivante",
+        )
+        .bar("section"),
+        bind(
+            C::Branches,
+            "prev_section",
+            &["backtab"],
+            AppAction::PrevSection,
+            "Section précédente",
+        ),
+        bind(C::Branches, "push", &["P"], AppAction::GitPush, "Push").bar("push"),
+        bind(
+            C::Branches,
+            "push_options",
+            &["alt-p"],
+            AppAction::Push(PushAction::Open),
+            "Push avec options (force-with-lease, branche distante...)",
+        ),
+        bind(
+            C::Branches,
+            "undo",
+            &["z"],
+            AppAction::Git(GitAction::Undo),
+            "Annuler la dernière action",
+        )
+        .bar("undo"),
+        bind(
+            C::Branches,
+            "redo",
+            &["Z"],
+            AppAction::Git(GitAction::Redo),
+            "Rétablir la dernière action annulée",
+        ),
+        bind(
+            C::Branches,
+            "copy",
+            &["y"],
+            AppAction::CopyPanelContent,
+            "Copier dans le clipboard",
+        ),
+        bind(C::Branches, "help", &["?"], AppAction::ToggleHelp, "Aide"),
+        bind(C::Branches, "quit", &["q"], AppAction::Quit, "Quitter"),
+        bind(
+            C::BranchesList,
+            "move_down",
+            &["j", "down"],
+            AppAction::MoveDown,
+            "Branche suivante",
+        ),
+        bind(
+            C::BranchesList,
+            "move_up",
+            &["k", "up"],
+            AppAction::MoveUp,
+            "Branche précédente",
+        ),
+        bind(
+            C::BranchesList,
+            "checkout",
+            &["enter"],
+            AppAction::BranchCheckout,
+            "Checkout",
+        )
+        .bar("checkout"),
+        bind(
+            C::BranchesList,
+            "create",
+            &["n"],
+            AppAction::BranchCreate,
+            "Nouvelle branche",
+        )
+        .bar("new"),
+        bind(
+            C::BranchesList,
+            "delete",
+            &["d"],
+            AppAction::BranchDelete,
+            "Supprimer la branche",
+        )
+        .bar("delete"),
+        bind(
+            C::BranchesList,
+            "rename",
+            &["r"],
+            AppAction::BranchRename,
+            "Renommer la branche",
+        )
+        .bar("rename"),
+        bind(
+            C::BranchesList,
+            "merge",
+            &["m"],
+            AppAction::MergePrompt,
+            "Merger dans HEAD",
+        )
+        .bar("merge"),
+        bind(
+            C::BranchesList,
+            "toggle_remote",
+            &["R"],
+            AppAction::ToggleRemoteBranches,
+            "Afficher / masquer les branches distantes",
+        )
+        .bar("remote"),
+        bind(
+            C::BranchesList,
+            "reflog",
+            &["L"],
+            AppAction::Reflog(ReflogAction::Open),
+            "Reflog de la branche",
+        )
+        .bar("reflog"),
+        bind(
+            C::BranchesList,
+            "push_to",
+            &["p"],
+            AppAction::Branch(BranchAction::PushTo),
+            "Pousser la branche (remote, options)",
+        )
+        .bar("push vers"),
+        bind(
+            C::BranchesWorktrees,
+            "move_down",
+            &["j", "down"],
+            AppAction::MoveDown,
+            "Worktree suivant",
+        ),
+        bind(
+            C::BranchesWorktrees,
+            "move_up",
+            &["k", "up"],
+            AppAction::MoveUp,
+            "Worktree précédent",
+        ),
+        bind(
+            C::BranchesWorktrees,
+            "create",
+            &["n"],
+            AppAction::WorktreeCreate,
+            "Nouveau worktree",
+        )
+        .bar("new"),
+        bind(
+            C::BranchesWorktrees,
+            "delete",
+            &["d"],
+            AppAction::WorktreeRemove,
+            "Supprimer le worktree",
+        )
+        .bar("delete"),
+        bind(
+            C::BranchesStashes,
+            "move_down",
+            &["j", "down"],
+            AppAction::MoveDown,
+            "Stash suivant",
+        ),
+        bind(
+            C::BranchesStashes,
+            "move_up",
+            &["k", "up"],
+            AppAction::MoveUp,
+            "Stash précédent",
+        ),
+        bind(
+            C::BranchesStashes,
+            "file_down",
+            &["l", "right"],
+            AppAction::FileDown,
+            "Fichier suivant du stash",
+        )
+        .bar("fichiers"),
+        bind(
+            C::BranchesStashes,
+            "file_up",
+            &["h", "left"],
+            AppAction::FileUp,
+            "Fichier précédent du stash",
+        )
+        .bar("fichiers"),
+        bind(
+            C::BranchesStashes,
+            "apply",
+            &["a"],
+            AppAction::StashApply,
+            "Appliquer le stash",
+        )
+        .bar("apply"),
+        bind(
+            C::BranchesStashes,
+            "pop",
+            &["p"],
+            AppAction::StashPop,
+            "Appliquer et supprimer",
+        )
+        .bar("pop"),
+        bind(
+            C::BranchesStashes,
+            "drop",
+            &["d"],
+            AppAction::StashDrop,
+            "Supprimer le stash",
+        )
+        .bar("drop"),
+        bind(
+            C::BranchesStashes,
+            "save",
+            &["s"],
+            AppAction::StashSave,
+            "Nouveau stash",
+        )
+        .bar("save"),
+        bind(
+            C::BranchesTags,
+            "move_down",
+            &["j", "down"],
+            AppAction::MoveDown,
+            "Tag suivant",
+        ),
+        bind(
+            C::BranchesTags,
+            "move_up",
+            &["k", "up"],
+            AppAction::MoveUp,
+            "Tag précédent",
+        ),
+        bind(
+            C::BranchesTags,
+            "create",
+            &["n"],
+            AppAction::Branch(BranchAction::TagCreate),
+            "Nouveau tag (sur HEAD)",
+        )
+        .bar("new (sur HEAD)"),
+        bind(
+            C::BranchesTags,
+            "delete",
+            &["d"],
+            AppAction::Branch(BranchAction::TagDelete),
+            "Supprimer le tag",
+        )
+        .bar("delete"),
+        bind(
+            C::BranchesTags,
+            "push",
+            &["p"],
+            AppAction::Branch(BranchAction::TagPush),
+            "Pousser le tag",
+        )
+        .bar("push"),
+        bind(
+            C::BranchesTags,
+            "push_all",
+            &["a"],
+            AppAction::Branch(BranchAction::TagPushAll),
+            "Pousser tous les tags",
+        )
+        .bar("push all"),
+        bind(
+            C::BranchesRemotes,
+            "move_down",
+            &["j", "down"],
+            AppAction::MoveDown,
+            "Remote suivant",
+        ),
+        bind(
+            C::BranchesRemotes,
+            "move_up",
+            &["k", "up"],
+            AppAction::MoveUp,
+            "Remote précédent",
+        ),
+        bind(
+            C::BranchesRemotes,
+            "create",
+            &["n"],
+            AppAction::Branch(BranchAction::RemoteAdd),
+            "Ajouter un remote",
+        )
+        .bar("new"),
+        bind(
+            C::BranchesRemotes,
+            "rename",
+            &["r"],
+            AppAction::Branch(BranchAction::RemoteRename),
+            "Renommer le remote",
+        )
+        .bar("rename"),
+        bind(
+            C::BranchesRemotes,
+            "set_url",
+            &["u"],
+            AppAction::Branch(BranchAction::RemoteSetUrl),
+            "Modifier l'URL",
+        )
+        .bar("url"),
+        bind(
+            C::BranchesRemotes,
+            "set_push_url",
+            &["U"],
+            AppAction::Branch(BranchAction::RemoteSetPushUrl),
+            "Modifier l'URL de push",
+        )
+        .bar("push url"),
+        bind(
+            C::BranchesRemotes,
+            "delete",
+            &["d"],
+            AppAction::Branch(BranchAction::RemoteRemove),
+            "Supprimer le remote",
+        )
+        .bar("delete"),
+        bind(
+            C::BranchesRemotes,
+            "fetch",
+            &["f"],
+            AppAction::Branch(BranchAction::RemoteFetch),
+            "Fetch du remote",
+        )
+        .bar("fetch"),
+        bind(
+            C::BranchesRemotes,
+            "prune",
+            &["p"],
+            AppAction::Branch(BranchAction::RemotePrune),
+            "Fetch avec prune des branches supprimées",
+        )
+        .bar("prune"),
+        // ── Blame ──
+        bind(
+            C::Blame,
+            "close",
+            &["q", "esc"],
+            AppAction::CloseBlame,
+            "Fermer le blame",
+        ),
+        bind(
+            C::Blame,
+            "move_down",
+            &["j", "down"],
+            AppAction::MoveDown,
+            "Ligne suivante",
+        ),
+        bind(
+            C::Blame,
+            "move_up",
+            &["k", "up"],
+            AppAction::MoveUp,
+            "Ligne précédente",
+        ),
+        bind(
+            C::Blame,
+            "go_top",
+            &["g g", "home"],
+            AppAction::GoTop,
+            "Première ligne",
+        ),
+        bind(
+            C::Blame,
+            "go_bottom",
+            &["G", "end"],
+            AppAction::GoBottom,
+            "Dernière ligne",
+        ),
+        bind(
+            C::Blame,
+            "page_down",
+            &["pagedown"],
+            AppAction::PageDown,
+            "Page suivante",
+        ),
+        bind(
+            C::Blame,
+            "page_up",
+            &["pageup"],
+            AppAction::PageUp,
+            "Page précédente",
+        ),
+        bind(
+            C::Blame,
+            "jump",
+            &["enter"],
+            AppAction::JumpToBlameCommit,
+            "Aller au commit de la ligne",
+        ),
+        bind(
+            C::Blame,
+            "copy",
+            &["y"],
+            AppAction::CopyPanelContent,
+            "Copier dans le clipboard",
+        ),
+        // ── Reflog ──
+        bind(
+            C::Reflog,
+            "move_down",
+            &["j", "down"],
+            AppAction::Reflog(ReflogAction::SelectNext),
+            "Entrée suivante",
+        )
+        .bar("naviguer"),
+        bind(
+            C::Reflog,
+            "move_up",
+            &["k", "up"],
+            AppAction::Reflog(ReflogAction::SelectPrevious),
+            "Entrée précédente",
+        )
+        .bar("naviguer"),
+        bind(
+            C::Reflog,
+            "toggle_focus",
+            &["tab"],
+            AppAction::Reflog(ReflogAction::ToggleFocus),
+            "Entrées / fichiers",
+        )
+        .bar("fichiers"),
+        bind(
+            C::Reflog,
+            "checkout",
+            &["c"],
+            AppAction::Reflog(ReflogAction::Checkout),
+            "Checkout de l'entrée (HEAD détachée)",
+        )
+        .bar("checkout"),
+        bind(
+            C::Reflog,
+            "branch",
+            &["b"],
+            AppAction::Reflog(ReflogAction::StartBranch),
+            "Créer une branche sur l'entrée",
+        )
+        .bar("branche"),
+        bind(
+            C::Reflog,
+            "reset",
+            &["X"],
+            AppAction::Reflog(ReflogAction::Reset),
+            "Reset sur l'entrée",
+        )
+        .bar("reset"),
+        bind(
+            C::Reflog,
+            "copy",
+            &["y"],
+            AppAction::CopyPanelContent,
+            "Copier dans le clipboard",
+        )
+        .bar("copier"),
+        bind(
+            C::Reflog,
+            "close",
+            &["q", "esc"],
+            AppAction::Reflog(ReflogAction::Close),
+            "Fermer le reflog",
+        )
+        .bar("fermer"),
+        bind(
+            C::ReflogBranch,
+            "confirm",
+            &["enter"],
+            AppAction::Reflog(ReflogAction::ConfirmBranch),
+            "Créer la branche",
+        ),
+        bind(
+            C::ReflogBranch,
+            "cancel",
+            &["esc"],
+            AppAction::Reflog(ReflogAction::CancelBranch),
+            "Annuler",
+        ),
+        bind(
+            C::ReflogBranch,
+            "delete_char_before",
+            &["backspace"],
+            AppAction::Reflog(ReflogAction::BranchBackspace),
+            "Effacer le caractère précédent",
+        ),
+        // ── Conflits ──
+        bind(
+            C::Conflicts,
+            "next_panel",
+            &["tab"],
+            AppAction::ConflictSwitchPanelForward,
+            "Panneau suivant (Fichiers → Ours → Theirs → Résultat)",
+        )
+        .bar("Panneau"),
+        bind(
+            C::Conflicts,
+            "prev_panel",
+            &["backtab"],
+            AppAction::ConflictSwitchPanelReverse,
+            "Panneau précédent",
+        ),
+        bind(
+            C::Conflicts,
+            "mode_file",
+            &["F"],
+            AppAction::ConflictSetModeFile,
+            "Mode Fichier",
+        )
+        .bar("Mode"),
+        bind(
+            C::Conflicts,
+            "mode_block",
+            &["B"],
+            AppAction::ConflictSetModeBlock,
+            "Mode Bloc",
+        )
+        .bar("Mode"),
+        bind(
+            C::Conflicts,
+            "mode_line",
+            &["L"],
+            AppAction::ConflictSetModeLine,
+            "Mode Ligne",
+        )
+        .bar("Mode"),
+        bind(
+            C::Conflicts,
+            "validate",
+            &["V"],
+            AppAction::ConflictValidateMerge,
+            "Finaliser le merge (créer le commit)",
+        )
+        .bar("Finaliser"),
+        bind(
+            C::Conflicts,
+            "leave",
+            &["q", "esc"],
+            AppAction::ConflictLeaveView,
+            "Quitter la vue conflits",
+        )
+        .bar("Quitter"),
+        bind(
+            C::Conflicts,
+            "abort",
+            &["A"],
+            AppAction::ConflictAbort,
+            "Avorter le merge",
+        )
+        .bar("Avorter"),
+        bind(C::Conflicts, "help", &["?"], AppAction::ToggleHelp, "Aide"),
+        bind(
+            C::ConflictsFiles,
+            "next_file",
+            &["j", "down"],
+            AppAction::ConflictNextFile,
+            "Fichier suivant",
+        )
+        .bar("Nav"),
+        bind(
+            C::ConflictsFiles,
+            "prev_file",
+            &["k", "up"],
+            AppAction::ConflictPrevFile,
+            "Fichier précédent",
+        )
+        .bar("Nav"),
+        bind(
+            C::ConflictsFiles,
+            "choose_ours",
+            &["o", "left"],
+            AppAction::ConflictFileChooseOurs,
+            "Garder la version 'ours' (HEAD)",
+        )
+        .bar("Ours"),
+        bind(
+            C::ConflictsFiles,
+            "choose_theirs",
+            &["t", "right"],
+            AppAction::ConflictFileChooseTheirs,
+            "Garder la version 'theirs' (branche mergée)",
+        )
+        .bar("Theirs"),
+        bind(
+            C::ConflictsFiles,
+            "resolve",
+            &["r"],
+            AppAction::ConflictResolveFile,
+            "Marquer comme résolu",
+        )
+        .bar("Résoudre"),
+        bind(
+            C::ConflictsFileMode,
+            "next_file",
+            &["j", "down"],
+            AppAction::ConflictNextFile,
+            "Fichier suivant",
+        )
+        .bar("Nav"),
+        bind(
+            C::ConflictsFileMode,
+            "prev_file",
+            &["k", "up"],
+            AppAction::ConflictPrevFile,
+            "Fichier précédent",
+        )
+        .bar("Nav"),
+        bind(
+            C::ConflictsFileMode,
+            "choose",
+            &["enter"],
+            AppAction::ConflictEnterResolve,
+            "Garder la version du panneau actif",
+        )
+        .bar("Choisir"),
+        bind(
+            C::ConflictsBlock,
+            "next_section",
+            &["j", "down"],
+            AppAction::ConflictNextSection,
+            "Bloc suivant",
+        )
+        .bar("Nav"),
+        bind(
+            C::ConflictsBlock,
+            "prev_section",
+            &["k", "up"],
+            AppAction::ConflictPrevSection,
+            "Bloc précédent",
+        )
+        .bar("Nav"),
+        bind(
+            C::ConflictsBlock,
+            "choose",
+            &["space"],
+            AppAction::ConflictEnterResolve,
+            "Choisir / déchoisir le bloc",
+        )
+        .bar("Choisir"),
+        bind(
+            C::ConflictsBlock,
+            "choose_both",
+            &["b"],
+            AppAction::ConflictChooseBoth,
+            "Garder les deux versions",
+        )
+        .bar("Les deux"),
+        bind(
+            C::ConflictsBlock,
+            "validate_file",
+            &["enter"],
+            AppAction::ConflictResolveFile,
+            "Valider et sauvegarder le fichier",
+        )
+        .bar("Valider"),
+        bind(
+            C::ConflictsLine,
+            "line_down",
+            &["j", "down"],
+            AppAction::ConflictLineDown,
+            "Ligne suivante",
+        )
+        .bar("Nav"),
+        bind(
+            C::ConflictsLine,
+            "line_up",
+            &["k", "up"],
+            AppAction::ConflictLineUp,
+            "Ligne précédente",
+        )
+        .bar("Nav"),
+        bind(
+            C::ConflictsLine,
+            "toggle_line",
+            &["space"],
+            AppAction::ConflictToggleLine,
+            "Choisir / déchoisir la ligne",
+        )
+        .bar("Choisir ligne"),
+        bind(
+            C::ConflictsLine,
+            "validate_file",
+            &["enter"],
+            AppAction::ConflictResolveFile,
+            "Valider et sauvegarder le fichier",
+        )
+        .bar("Valider"),
+        bind(
+            C::ConflictsResult,
+            "scroll_down",
+            &["j", "down"],
+            AppAction::ConflictResultScrollDown,
+            "Défiler vers le bas",
+        )
+        .bar("Nav"),
+        bind(
+            C::ConflictsResult,
+            "scroll_up",
+            &["k", "up"],
+            AppAction::ConflictResultScrollUp,
+            "Défiler vers le haut",
+        )
+        .bar("Nav"),
+        bind(
+            C::ConflictsResult,
+            "edit",
+            &["i", "e"],
+            AppAction::ConflictStartEditing,
+            "Éditer le résultat",
+        )
+        .bar("Éditer"),
+        bind(
+            C::ConflictsRebase,
+            "continue",
+            &["V"],
+            AppAction::Conflict(ConflictAction::ContinueRebase),
+            "Continuer le rebase",
+        )
+        .bar("Continuer"),
+        bind(
+            C::ConflictsRebase,
+            "skip",
+            &["S"],
+            AppAction::Conflict(ConflictAction::SkipRebase),
+            "Passer le commit",
+        )
+        .bar("Passer"),
+        bind(
+            C::ConflictsRebase,
+            "abort",
+            &["A"],
+            AppAction::Conflict(ConflictAction::AbortRebase),
+            "Abandonner le rebase",
+        )
+        .bar("Abandonner"),
+        // ── Conflits : édition du résultat ──
+        bind(
+            C::ConflictsEdit,
+            "cancel",
+            &["esc"],
+            AppAction::ConflictStopEditing,
+            "Quitter le mode édition",
+        )
+        .bar("Annuler"),
+        bind(
+            C::ConflictsEdit,
+            "save",
+            &["ctrl-s", "ctrl-enter"],
+            AppAction::ConflictConfirmEdit,
+            "Sauvegarder et quitter l'édition",
+        )
+        .bar("Sauvegarder"),
+        bind(
+            C::ConflictsEdit,
+            "cursor_up",
+            &["up"],
+            AppAction::ConflictEditCursorUp,
+            "Ligne précédente",
+        )
+        .bar("Curseur"),
+        bind(
+            C::ConflictsEdit,
+            "cursor_down",
+            &["down"],
+            AppAction::ConflictEditCursorDown,
+            "Ligne suivante",
+        )
+        .bar("Curseur"),
+        bind(
+            C::ConflictsEdit,
+            "cursor_left",
+            &["left"],
+            AppAction::ConflictEditCursorLeft,
+            "Caractère précédent",
+        )
+        .bar("Curseur"),
+        bind(
+            C::ConflictsEdit,
+            "cursor_right",
+            &["right"],
+            AppAction::ConflictEditCursorRight,
+            "Caractère suivant",
+        )
+        .bar("Curseur"),
+        bind(
+            C::ConflictsEdit,
+            "new_line",
+            &["enter"],
+            AppAction::ConflictEditNewline,
+            "Insérer une nouvelle ligne",
+        )
+        .bar("Nouvelle ligne"),
+        bind(
+            C::ConflictsEdit,
+            "delete_char_before",
+            &["backspace"],
+            AppAction::ConflictEditBackspace,
+            "Supprimer le caractère avant",
+        )
+        .bar("Suppr"),
+        bind(
+            C::ConflictsEdit,
+            "delete_char_after",
+            &["delete"],
+            AppAction::ConflictEditDelete,
+            "Supprimer le caractère sous le curseur",
+        ),
+        // ── Popups ──
+        bind(
+            C::Palette,
+            "close",
+            &["esc"],
+            AppAction::Palette(PaletteAction::Close),
+            "Fermer la palette",
+        ),
+        bind(
+            C::Palette,
+            "confirm",
+            &["enter"],
+            AppAction::Palette(PaletteAction::Confirm),
+            "Lancer l'action sélectionnée",
+        ),
+        bind(
+            C::Palette,
+            "next",
+            &["down", "tab", "ctrl-n"],
+            AppAction::Palette(PaletteAction::SelectNext),
+            "Action suivante",
+        ),
+        bind(
+            C::Palette,
+            "previous",
+            &["up", "shift-tab", "ctrl-p"],
+            AppAction::Palette(PaletteAction::SelectPrevious),
+            "Action précédente",
+        ),
+        bind(
+            C::Palette,
+            "delete_char_before",
+            &["backspace"],
+            AppAction::Palette(PaletteAction::Backspace),
+            "Effacer le caractère précédent",
+        ),
+        bind(
+            C::PushDialog,
+            "confirm",
+            &["enter"],
+            AppAction::Push(PushAction::Confirm),
+            "Pousser",
+        )
+        .bar("pousser"),
+        bind(
+            C::PushDialog,
+            "next_field",
+            &["down", "tab"],
+            AppAction::Push(PushAction::NextField),
+            "Champ suivant",
+        )
+        .bar("champ"),
+        bind(
+            C::PushDialog,
+            "previous_field",
+            &["up", "shift-tab"],
+            AppAction::Push(PushAction::PreviousField),
+            "Champ précédent",
+        )
+        .bar("champ"),
+        bind(
+            C::PushDialog,
+            "toggle",
+            &["space"],
+            AppAction::Push(PushAction::Toggle),
+            "Cocher l'option",
+        )
+        .bar("cocher"),
+        bind(
+            C::PushDialog,
+            "next_remote",
+            &["right"],
+            AppAction::Push(PushAction::NextRemote),
+            "Remote suivant",
+        )
+        .bar("remote"),
+        bind(
+            C::PushDialog,
+            "previous_remote",
+            &["left"],
+            AppAction::Push(PushAction::PreviousRemote),
+            "Remote précédent",
+        )
+        .bar("remote"),
+        bind(
+            C::PushDialog,
+            "delete_char_before",
+            &["backspace"],
+            AppAction::Push(PushAction::Backspace),
+            "Effacer le caractère précédent",
+        ),
+        bind(
+            C::PushDialog,
+            "close",
+            &["esc"],
+            AppAction::Push(PushAction::Close),
+            "Annuler",
+        )
+        .bar("annuler"),
+        bind(
+            C::MergePicker,
+            "move_down",
+            &["j", "down"],
+            AppAction::MergePickerDown,
+            "Suivante",
+        ),
+        bind(
+            C::MergePicker,
+            "move_up",
+            &["k", "up"],
+            AppAction::MergePickerUp,
+            "Précédente",
+        ),
+        bind(
+            C::MergePicker,
+            "confirm",
+            &["enter"],
+            AppAction::MergePickerConfirm,
+            "Merger",
+        ),
+        bind(
+            C::MergePicker,
+            "cancel",
+            &["esc"],
+            AppAction::MergePickerCancel,
+            "Annuler",
+        ),
+        bind(
+            C::ResetPicker,
+            "move_down",
+            &["j", "down"],
+            AppAction::ResetPickerDown,
+            "Suivant",
+        ),
+        bind(
+            C::ResetPicker,
+            "move_up",
+            &["k", "up"],
+            AppAction::ResetPickerUp,
+            "Précédent",
+        ),
+        bind(
+            C::ResetPicker,
+            "confirm",
+            &["enter"],
+            AppAction::ResetPickerConfirm,
+            "Reset",
+        ),
+        bind(
+            C::ResetPicker,
+            "cancel",
+            &["esc"],
+            AppAction::ResetPickerCancel,
+            "Annuler",
+        ),
+        bind(
+            C::PullPicker,
+            "move_down",
+            &["j", "down"],
+            AppAction::Git(GitAction::PullPickerNext),
+            "Suivant",
+        ),
+        bind(
+            C::PullPicker,
+            "move_up",
+            &["k", "up"],
+            AppAction::Git(GitAction::PullPickerPrevious),
+            "Précédent",
+        ),
+        bind(
+            C::PullPicker,
+            "confirm",
+            &["enter"],
+            AppAction::Git(GitAction::PullPickerConfirm),
+            "Pull",
+        ),
+        bind(
+            C::PullPicker,
+            "cancel",
+            &["esc"],
+            AppAction::Git(GitAction::PullPickerCancel),
+            "Annuler",
+        ),
+        bind(
+            C::Confirm,
+            "confirm",
+            &["y", "Y"],
+            AppAction::ConfirmAction,
+            "Confirmer",
+        ),
+        bind(
+            C::Confirm,
+            "cancel",
+            &["n", "N", "esc"],
+            AppAction::CancelAction,
+            "Annuler",
+        ),
+        bind(
+            C::CommandOutput,
+            "scroll_down",
+            &["j", "down"],
+            AppAction::Command(CommandAction::ScrollDown),
+            "Défiler vers le bas",
+        ),
+        bind(
+            C::CommandOutput,
+            "scroll_up",
+            &["k", "up"],
+            AppAction::Command(CommandAction::ScrollUp),
+            "Défiler vers le haut",
+        ),
+        bind(
+            C::CommandOutput,
+            "close",
+            &["q", "esc", "enter"],
+            AppAction::Command(CommandAction::CloseOutput),
+            "Fermer",
+        ),
+        bind(
+            C::RebaseEditor,
+            "move_down",
+            &["j", "down"],
+            AppAction::Rebase(RebaseAction::SelectNext),
+            "Commit suivant",
+        )
+        .bar("naviguer"),
+        bind(
+            C::RebaseEditor,
+            "move_up",
+            &["k", "up"],
+            AppAction::Rebase(RebaseAction::SelectPrevious),
+            "Commit précédent",
+        )
+        .bar("naviguer"),
+        bind(
+            C::RebaseEditor,
+            "move_entry_down",
+            &["J"],
+            AppAction::Rebase(RebaseAction::MoveEntryDown),
+            "Descendre le commit",
+        )
+        .bar("déplacer"),
+        bind(
+            C::RebaseEditor,
+            "move_entry_up",
+            &["K"],
+            AppAction::Rebase(RebaseAction::MoveEntryUp),
+            "Monter le commit",
+        )
+        .bar("déplacer"),
+        bind(
+            C::RebaseEditor,
+            "pick",
+            &["p"],
+            AppAction::Rebase(RebaseAction::SetAction(TodoAction::Pick)),
+            "Pick",
+        )
+        .bar("pick"),
+        bind(
+            C::RebaseEditor,
+            "reword",
+            &["r"],
+            AppAction::Rebase(RebaseAction::StartReword),
+            "Reword",
+        )
+        .bar("reword"),
+        bind(
+            C::RebaseEditor,
+            "edit",
+            &["e"],
+            AppAction::Rebase(RebaseAction::SetAction(TodoAction::Edit)),
+            "Edit",
+        )
+        .bar("edit"),
+        bind(
+            C::RebaseEditor,
+            "squash",
+            &["s"],
+            AppAction::Rebase(RebaseAction::SetAction(TodoAction::Squash)),
+            "Squash",
+        )
+        .bar("squash"),
+        bind(
+            C::RebaseEditor,
+            "fixup",
+            &["f"],
+            AppAction::Rebase(RebaseAction::SetAction(TodoAction::Fixup)),
+            "Fixup",
+        )
+        .bar("fixup"),
+        bind(
+            C::RebaseEditor,
+            "drop",
+            &["d"],
+            AppAction::Rebase(RebaseAction::SetAction(TodoAction::Drop)),
+            "Drop",
+        )
+        .bar("drop"),
+        bind(
+            C::RebaseEditor,
+            "execute",
+            &["enter"],
+            AppAction::Rebase(RebaseAction::Execute),
+            "Lancer le rebase",
+        )
+        .bar("lancer"),
+        bind(
+            C::RebaseEditor,
+            "close",
+            &["esc", "q"],
+            AppAction::Rebase(RebaseAction::Close),
+            "Fermer l'éditeur",
+        )
+        .bar("annuler"),
+        bind(
+            C::RebaseProgress,
+            "continue",
+            &["c", "enter"],
+            AppAction::Rebase(RebaseAction::Continue),
+            "Continuer le rebase",
+        )
+        .bar("continuer"),
+        bind(
+            C::RebaseProgress,
+            "skip",
+            &["S"],
+            AppAction::Rebase(RebaseAction::Skip),
+            "Passer le commit",
+        )
+        .bar("passer"),
+        bind(
+            C::RebaseProgress,
+            "abort",
+            &["A"],
+            AppAction::Rebase(RebaseAction::Abort),
+            "Abandonner le rebase",
+        )
+        .bar("abandonner"),
+        bind(
+            C::RebaseProgress,
+            "close",
+            &["esc", "q"],
+            AppAction::Rebase(RebaseAction::Close),
+            "Fermer l'éditeur",
+        )
+        .bar("fermer"),
+        bind(
+            C::Reword,
+            "confirm",
+            &["ctrl-s", "ctrl-enter", "alt-enter"],
+            AppAction::Rebase(RebaseAction::ConfirmReword),
+            "Valider le message",
+        )
+        .bar("valider"),
+        bind(
+            C::Reword,
+            "cancel",
+            &["esc"],
+            AppAction::Rebase(RebaseAction::CancelReword),
+            "Annuler le reword",
+        )
+        .bar("annuler"),
+        bind(
+            C::Transfer,
+            "cancel",
+            &["ctrl-x"],
+            AppAction::Git(GitAction::CancelTransfer),
+            "Annuler le transfert",
+        )
+        .bar("annuler"),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)
+    }
+
+    fn overrides(
+        context: &str,
+        entries: &[(&str, &str)],
+    ) -> BTreeMap<String, BTreeMap<String, String>> {
+        let entries = entries
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        BTreeMap::from([(context.to_string(), entries)])
+    }
+
+    #[test]
+    fn test_key_chord_parsing() {
+        let chord = KeyChord::try_from("ctrl-d").unwrap();
+        assert_eq!(chord.code, KeyCode::Char('d'));
+        assert_eq!(chord.modifiers, KeyModifiers::CONTROL);
+
+        let chord = KeyChord::try_from("shift-tab").unwrap();
+        assert_eq!(chord.code, KeyCode::BackTab);
+        assert_eq!(chord.modifiers, KeyModifiers::NONE);
+
+        let chord = KeyChord::try_from("-").unwrap();
+        assert_eq!(chord.code, KeyCode::Char('-'));
+
+        assert_eq!(parse_sequence("g g").unwrap().len(), 2);
+        assert!(parse_sequence("ctrl-foo").is_err());
+    }
+
+    #[test]
+    fn test_default_keymap_has_no_duplicate_keys() {
+        let keymap = Keymap::default();
+        for context in KeyContext::ALL {
+            let mut seen = Vec::new();
+            for binding in keymap.bindings(context) {
+                for seq in &binding.keys {
+                    assert!(
+                        !seen.contains(seq),
+                        "{} : touche {} en double",
+                        context.name(),
+                        sequence_label(seq)
+                    );
+                    seen.push(seq.clone());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_sequence_g_g() {
+        let keymap = Keymap::default();
+        let contexts = KeyContext::graph(FocusPanel::Graph);
+        let mut pending = PendingKeys::default();
+
+        assert_eq!(keymap.resolve(&contexts, &mut pending, &key('g')), None);
+        assert_eq!(
+            keymap.resolve(&contexts, &mut pending, &key('g')),
+            Some(AppAction::GoTop)
+        );
+
+        // Une séquence interrompue rejoue la touche seule.
+        assert_eq!(keymap.resolve(&contexts, &mut pending, &key('g')), None);
+        assert_eq!(
+            keymap.resolve(&contexts, &mut pending, &key('j')),
+            Some(AppAction::MoveDown)
+        );
+    }
+
+    #[test]
+    fn test_context_priority() {
+        let keymap = Keymap::default();
+        let mut pending = PendingKeys::default();
+
+        let files = KeyContext::graph(FocusPanel::BottomLeft);
+        assert_eq!(
+            keymap.resolve(&files, &mut pending, &key('j')),
+            Some(AppAction::FileDown)
+        );
+        // Les touches du graphe restent actives depuis le panneau fichiers.
+        assert_eq!(
+            keymap.resolve(&files, &mut pending, &key('c')),
+            Some(AppAction::CommitPrompt)
+        );
+    }
+
+    #[test]
+    fn test_conflicts_bar_shows_rebase_keys() {
+        let keymap = Keymap::default();
+        let state = ConflictsState::new(Vec::new(), String::new(), String::new(), String::new());
+
+        let rebasing = keymap.bar_text(&KeyContext::conflicts(&state, true));
+        assert!(rebasing.contains("V:Continuer"));
+        assert!(rebasing.contains("S:Passer"));
+        assert!(rebasing.contains("A:Abandonner"));
+        let merging = keymap.bar_text(&KeyContext::conflicts(&state, false));
+        assert!(!merging.contains("S:Passer"));
+    }
+
+    #[test]
+    fn test_message_editor_keys() {
+        let keymap = Keymap::default();
+        let contexts = [KeyContext::CommitMessage, KeyContext::MessageEditor];
+        let mut pending = PendingKeys::default();
+
+        let ctrl_s = KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL);
+        assert_eq!(
+            keymap.resolve(&contexts, &mut pending, &ctrl_s),
+            Some(AppAction::ConfirmCommit)
+        );
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(
+            keymap.resolve(&contexts, &mut pending, &enter),
+            Some(AppAction::Edit(EditAction::NewLine))
+        );
+
+        // Un caractère sans raccourci est inséré dans le message.
+        assert_eq!(keymap.resolve(&contexts, &mut pending, &key('q')), None);
+        assert_eq!(
+            KeyContext::CommitMessage.insert_action('q'),
+            Some(AppAction::Edit(EditAction::InsertChar('q')))
+        );
+        assert_eq!(KeyContext::Graph.insert_action('q'), None);
+
+        let bar = keymap.bar_text(&contexts);
+        assert!(bar.contains("Ctrl+S"));
+        assert!(bar.contains("Enter:nouvelle ligne"));
+    }
+
+    #[test]
+    fn test_rebinding() {
+        let keymap = Keymap::with_overrides(&overrides(
+            "graph",
+            &[("n", "move_down"), ("j", "none"), ("ctrl-b", "blame")],
+        ))
+        .unwrap();
+        let contexts = KeyContext::graph(FocusPanel::Graph);
+        let mut pending = PendingKeys::default();
+
+        assert_eq!(
+            keymap.resolve(&contexts, &mut pending, &key('n')),
+            Some(AppAction::MoveDown)
+        );
+        assert_eq!(keymap.resolve(&contexts, &mut pending, &key('j')), None);
+        assert_eq!(
+            keymap.resolve(
+                &contexts,
+                &mut pending,
+                &KeyEvent::new(KeyCode::Char('b'), KeyModifiers::CONTROL)
+            ),
+            Some(AppAction::OpenBlame)
+        );
+        // Le libellé de la barre d'aide suit le remappage.
+        assert!(keymap.bar_text(&contexts).starts_with("n/k:naviguer"));
+    }
+
+    #[test]
+    fn test_action_keys_follow_rebinding() {
+        let revert = AppAction::Git(GitAction::Revert);
+        let keymap = Keymap::default();
+        assert_eq!(
+            keymap.action_keys(&[KeyContext::Graph], &revert),
+            Some("R".to_string())
+        );
+
+        let keymap =
+            Keymap::with_overrides(&overrides("graph", &[("X", "revert"), ("R", "none")])).unwrap();
+        assert_eq!(
+            keymap.action_keys(&[KeyContext::Graph], &revert),
+            Some("X".to_string())
+        );
+        let keymap = Keymap::with_overrides(&overrides("graph", &[("R", "none")])).unwrap();
+        assert_eq!(keymap.action_keys(&[KeyContext::Graph], &revert), None);
+    }
+
+    #[test]
+    fn test_rebinding_from_another_context() {
+        let keymap =
+            Keymap::with_overrides(&overrides("staging", &[("ctrl-n", "search")])).unwrap();
+        let contexts = [KeyContext::Staging];
+        let mut pending = PendingKeys::default();
+
+        assert_eq!(
+            keymap.resolve(
+                &contexts,
+                &mut pending,
+                &KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL)
+            ),
+            Some(AppAction::OpenSearch)
+        );
+    }
+
+    #[test]
+    fn test_invalid_overrides() {
+        let err = Keymap::with_overrides(&overrides("grpah", &[("n", "move_down")])).unwrap_err();
+        assert!(err.contains("contexte inconnu 'grpah'"), "{}", err);
+
+        let err = Keymap::with_overrides(&overrides("graph", &[("n", "descendre")])).unwrap_err();
+        assert!(err.contains("action inconnue 'descendre'"), "{}", err);
+
+        let err = Keymap::with_overrides(&overrides("graph", &[("ctrl-foo", "quit")])).unwrap_err();
+        assert!(err.contains("touche invalide 'ctrl-foo'"), "{}", err);
+    }
+}
@@ -50,16 +50,6 @@ impl LoadingSpinner {
         let frame = self.current_frame();
         SPINNER_CHARS[frame]
     }
-
-    /// Vérifie si le spinner est toujours actif (toujours vrai, utiliser pour timeout si besoin).
-    pub fn is_active(&self) -> bool {
-        true
-    }
-
-    /// Change le message du spinner.
-    pub fn set_message(&mut self, message: impl Into<String>) {
-        self.message = message.into();
-    }
 }
 
 /// Rend un overlay de chargement avec spinner.
@@ -2,9 +2,12 @@ pub mod blame_view;
 pub mod branch_panel;
 pub mod branches_layout;
 pub mod branches_view;
+pub mod command_output;
+pub mod command_palette;
 pub mod common;
 pub mod confirm_dialog;
 pub mod conflicts_view;
+pub mod conventional_picker;
 pub mod detail_view;
 pub mod diff_view;
 pub mod files_view;
@@ -14,20 +17,29 @@ pub mod graph_view;
 pub mod help_bar;
 pub mod help_overlay;
 pub mod input;
+pub mod keymap;
 pub mod layout;
 pub mod loading;
 pub mod merge_picker;
 pub mod nav_bar;
+pub mod pull_picker;
+pub mod push_dialog;
+pub mod rebase_editor;
+pub mod reflog_view;
+pub mod reset_picker;
 pub mod search_bar;
 pub mod staging_layout;
 pub mod staging_view;
 pub mod status_bar;
 pub mod theme;
+pub mod transfer_popup;
 
 #[cfg(test)]
 mod tests;
 
-use crate::state::{AppState, FocusPanel, ViewMode};
+use crate::state::{AppState, BottomLeftMode, FocusPanel, StagingFocus, ViewMode};
+use ratatui::layout::Rect;
+use ratatui::widgets::{Clear, Paragraph};
 use ratatui::Frame;
 
 /// Point d'entrée du rendu : dessine tous les panneaux.
@@ -38,6 +50,10 @@ pub fn render(frame: &mut Frame, state: &mut AppState) {
             render_graph_view(frame, state);
         }
         ViewMode::Staging => {
+            // Hauteur utile du panneau diff, pour garder le curseur visible.
+            let editing = state.staging_state.focus == StagingFocus::CommitMessage;
+            let diff_panel = staging_layout::build_staging_layout(frame.area(), editing).diff_panel;
+            state.staging_state.diff_visible_height = diff_panel.height.saturating_sub(2) as usize;
             staging_view::render(
                 frame,
                 &state.staging_state,
@@ -75,16 +91,18 @@ pub fn render(frame: &mut Frame, state: &mut AppState) {
                             &state.current_branch,
                             &state.repo_path,
                             state.current_flash_message(),
+                            state.rebase_session.is_some(),
                         );
                     }
-                    conflicts_view::render_help_overlay(frame, frame.area());
-                    return; // L'overlay de conflits est spécifique
+                    help_overlay::render(frame, frame.area(), ViewMode::Conflicts);
+                    return;
                 }
                 _ => {
                     render_graph_view(frame, state);
                 }
             }
-            help_overlay::render(frame, frame.area());
+            let help_view = state.previous_view_mode.unwrap_or(ViewMode::Graph);
+            help_overlay::render(frame, frame.area(), help_view);
         }
         ViewMode::Branches => {
             branches_view::render(
@@ -108,6 +126,18 @@ pub fn render(frame: &mut Frame, state: &mut AppState) {
                     &state.current_branch,
                     &state.repo_path,
                     state.current_flash_message(),
+                    state.rebase_session.is_some(),
+                );
+            }
+        }
+        ViewMode::Reflog => {
+            if let Some(ref reflog_state) = state.reflog_state {
+                reflog_view::render(
+                    frame,
+                    reflog_state,
+                    &state.repo_path,
+                    state.current_flash_message(),
+                    state.diff_view_mode,
                 );
             }
         }
@@ -120,10 +150,59 @@ pub fn render(frame: &mut Frame, state: &mut AppState) {
         }
     }
 
+    // Rendre le reset picker si actif
+    if let Some(ref picker) = state.reset_picker {
+        if picker.is_active {
+            reset_picker::render(frame, picker, &state.current_branch, frame.area());
+        }
+    }
+
+    // Rendre le pull picker si actif
+    if let Some(ref picker) = state.pull_picker {
+        if picker.is_active {
+            pull_picker::render(frame, picker, &state.current_branch, frame.area());
+        }
+    }
+
+    // Rendre l'éditeur de rebase si ouvert
+    if let Some(ref editor) = state.rebase_editor {
+        rebase_editor::render(frame, editor, state.rebase_session.as_ref(), frame.area());
+    }
+
     // Rendre le dialogue de confirmation si actif
     if let Some(ref action) = state.pending_confirmation {
         confirm_dialog::render(frame, action, frame.area());
     }
+
+    // Progression de l'opération distante en cours (non bloquant)
+    if let Some(ref task) = state.remote_task {
+        transfer_popup::render(frame, task, frame.area());
+    }
+
+    // Rendre la sortie de la commande personnalisée si affichée
+    if let Some(ref output) = state.command_output {
+        command_output::render(frame, output, frame.area());
+    }
+
+    // Rendre le dialogue de push si ouvert
+    if let Some(ref dialog) = state.push_dialog {
+        push_dialog::render(frame, dialog, frame.area());
+    }
+
+    // Rendre la palette de commandes si ouverte (au premier plan)
+    if let Some(ref palette) = state.command_palette {
+        command_palette::render(frame, palette, frame.area());
+    }
+
+    // Indicateur de chargement en arrière-plan (coin supérieur droit, non bloquant)
+    if let Some(ref mut spinner) = state.loading_spinner {
+        let line = loading::render_inline(spinner);
+        let area = frame.area();
+        let width = (line.width() as u16).min(area.width);
+        let spinner_area = Rect::new(area.x + area.width - width, area.y, width, 1);
+        frame.render_widget(Clear, spinner_area);
+        frame.render_widget(Paragraph::new(line), spinner_area);
+    }
 }
 
 /// Rend la vue Graph (vue principale).
@@ -160,10 +239,12 @@ fn render_graph_view(frame: &mut Frame, state: &mut AppState) {
     let total_commits = state.graph_view.rows.len().max(state.graph.len());
     graph_view::render(
         frame,
-        &state.graph,
-        &state.current_branch,
-        state.selected_index,
-        total_commits,
+        graph_view::GraphPanel {
+            graph: &state.graph,
+            current_branch: state.current_branch.as_deref(),
+            selected_index: state.selected_index,
+            total_commits,
+        },
         layout.graph,
         &mut state.graph_state,
         is_graph_focused,
@@ -175,17 +256,32 @@ fn render_graph_view(frame: &mut Frame, state: &mut AppState) {
         hash[..7].to_string()
     });
 
+    // Parents du commit de merge sélectionné (choix de la mainline).
+    let parents = if state.bottom_left_mode == BottomLeftMode::Parents {
+        state.selected_merge_parents()
+    } else {
+        Vec::new()
+    };
+    let list_selected = if parents.is_empty() {
+        state.file_selected_index
+    } else {
+        state.parent_selected_index
+    };
+
     // Rendu du panneau de fichiers.
     let is_files_focused = state.focus == FocusPanel::BottomLeft;
     files_view::render(
         frame,
-        &state.commit_files,
-        &state.status_entries,
-        selected_hash,
-        state.bottom_left_mode.clone(),
+        files_view::FilesPanel {
+            commit_files: &state.commit_files,
+            status_entries: &state.status_entries,
+            parents: &parents,
+            selected_commit_hash: selected_hash,
+            mode: state.bottom_left_mode,
+            selected_index: list_selected,
+        },
         layout.bottom_left,
         is_files_focused,
-        state.file_selected_index,
     );
 
     // Rendu du panneau bas-droit (contextuel selon le focus).
@@ -211,6 +307,7 @@ fn render_graph_view(frame: &mut Frame, state: &mut AppState) {
                 layout.bottom_right,
                 false,
                 state.diff_view_mode,
+                None,
             );
         }
     }
@@ -220,7 +317,8 @@ fn render_graph_view(frame: &mut Frame, state: &mut AppState) {
         frame,
         state.selected_index,
         state.graph.len(),
-        state.bottom_left_mode.clone(),
+        state.graph_view.has_more,
+        state.focus,
         state.graph_filter.is_active(),
         layout.help_bar,
     );
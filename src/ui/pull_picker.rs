@@ -0,0 +1,80 @@
+//! Widget de sélection de la stratégie de pull.
+
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use crate::state::PullPickerState;
+use crate::ui::common::centered_rect_fixed;
+
+/// Rend le sélecteur de stratégie de pull.
+pub fn render(
+    frame: &mut Frame,
+    state: &PullPickerState,
+    current_branch: &Option<String>,
+    area: Rect,
+) {
+    let popup_area = centered_rect_fixed(64, 5, area);
+
+    // Effacer la zone sous le popup
+    frame.render_widget(Clear, popup_area);
+
+    let branch_name = current_branch.as_deref().unwrap_or("HEAD");
+    let title = format!(" Pull '{}' ", branch_name);
+
+    let items: Vec<ListItem> = state
+        .modes
+        .iter()
+        .map(|mode| {
+            // La stratégie configurée (celle de `p`) est signalée par un astérisque.
+            let marker = if *mode == state.configured {
+                "* "
+            } else {
+                "  "
+            };
+            let line = Line::from(vec![
+                Span::styled(marker, Style::default().fg(Color::Green)),
+                Span::raw(format!("{:<8}", mode.label())),
+                Span::styled(mode.description(), Style::default().fg(Color::DarkGray)),
+            ]);
+            ListItem::new(line)
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(title)
+                .title_alignment(Alignment::Center)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        );
+
+    let mut list_state = ListState::default();
+    list_state.select(Some(state.modes.selected_index()));
+
+    frame.render_stateful_widget(list, popup_area, &mut list_state);
+
+    // Barre d'aide sous le popup
+    let help_area = Rect {
+        x: popup_area.x,
+        y: popup_area.y + popup_area.height,
+        width: popup_area.width,
+        height: 1,
+    };
+    let line = Line::from(Span::styled(
+        "j/k:naviguer  Enter:pull  Esc:annuler  *:configurée",
+        Style::default().fg(Color::DarkGray),
+    ));
+    frame.render_widget(Paragraph::new(line).alignment(Alignment::Center), help_area);
+}
@@ -0,0 +1,138 @@
+//! Dialogue de push avec options (remote, branche distante, force-with-lease...).
+
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::state::{PushDialogState, PushField};
+use crate::ui::common::centered_rect_fixed;
+use crate::ui::keymap::{self, KeyContext};
+
+/// Largeur du dialogue (colonnes).
+const DIALOG_WIDTH: u16 = 80;
+
+/// Largeur de la colonne des libellés.
+const LABEL_WIDTH: usize = 18;
+
+/// Rend le dialogue de push.
+pub fn render(frame: &mut Frame, dialog: &PushDialogState, area: Rect) {
+    let request = &dialog.request;
+    let mut lines = Vec::new();
+
+    // Explication du refus précédent
+    if let Some(ref reason) = dialog.rejection {
+        lines.push(Line::from(Span::styled(
+            "⚠ Push refusé",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        )));
+        lines.push(Line::from(Span::styled(
+            reason.as_str(),
+            Style::default().fg(Color::Red),
+        )));
+        lines.push(Line::from(Span::styled(
+            "Faites un pull, ou cochez force-with-lease pour écraser la branche distante.",
+            Style::default().fg(Color::Yellow),
+        )));
+        lines.push(Line::from(""));
+    }
+
+    lines.push(Line::from(vec![
+        Span::styled(
+            format!("  {:<width$}", "Branche locale", width = LABEL_WIDTH),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::styled(
+            request.branch.as_str(),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+    ]));
+
+    for field in PushField::ALL {
+        let selected = dialog.field == field;
+        let marker = if selected { "▸ " } else { "  " };
+        let label_style = if selected {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+
+        let mut spans = vec![Span::styled(marker, Style::default().fg(Color::Yellow))];
+        match field {
+            PushField::Remote => {
+                spans.push(Span::styled(
+                    format!("{:<width$}", field.label(), width = LABEL_WIDTH),
+                    label_style,
+                ));
+                spans.push(Span::styled(
+                    format!("◂ {} ▸", request.remote),
+                    Style::default().fg(Color::Cyan),
+                ));
+            }
+            PushField::RemoteBranch => {
+                spans.push(Span::styled(
+                    format!("{:<width$}", field.label(), width = LABEL_WIDTH),
+                    label_style,
+                ));
+                spans.push(Span::styled(
+                    request.remote_branch.as_str(),
+                    Style::default().fg(Color::Cyan),
+                ));
+            }
+            _ => {
+                let checkbox = if dialog.is_checked(field) {
+                    "[x] "
+                } else {
+                    "[ ] "
+                };
+                spans.push(Span::styled(checkbox, label_style));
+                spans.push(Span::styled(field.label(), label_style));
+                if field == PushField::ForceWithLease {
+                    spans.push(Span::styled(
+                        format!(
+                            "  si {}/{} n'a pas bougé",
+                            request.remote, request.remote_branch
+                        ),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+            }
+        }
+        lines.push(Line::from(spans));
+    }
+
+    let border_color = if dialog.rejection.is_some() {
+        Color::Red
+    } else {
+        Color::Yellow
+    };
+    let popup_area = centered_rect_fixed(DIALOG_WIDTH, lines.len() as u16 + 2, area);
+    frame.render_widget(Clear, popup_area);
+
+    let hints = keymap::current().bar_text(&[KeyContext::PushDialog]);
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(" Push ")
+            .title_bottom(format!(" {} ", hints))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border_color)),
+    );
+    frame.render_widget(paragraph, popup_area);
+
+    // Curseur dans la saisie de la branche distante
+    if dialog.field == PushField::RemoteBranch {
+        // Lignes du refus, puis "Branche locale" et "Remote" avant la saisie
+        let offset = if dialog.rejection.is_some() { 4 } else { 0 };
+        let x =
+            popup_area.x + 3 + LABEL_WIDTH as u16 + request.remote_branch.chars().count() as u16;
+        let y = popup_area.y + 1 + offset + 2;
+        if x < popup_area.x + popup_area.width - 1 {
+            frame.set_cursor_position((x, y));
+        }
+    }
+}
@@ -0,0 +1,162 @@
+//! Éditeur de todo list du rebase interactif.
+
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use crate::git::rebase::{RebaseSession, TodoAction};
+use crate::state::{CommitEditor, RebaseEditorState};
+use crate::ui::common::centered_rect;
+use crate::ui::keymap::{self, KeyContext};
+use crate::ui::staging_view::{commit_status_line, message_line};
+use crate::ui::theme::current_theme;
+
+/// Rend l'éditeur de rebase (ou la progression du rebase en cours).
+pub fn render(
+    frame: &mut Frame,
+    editor: &RebaseEditorState,
+    session: Option<&RebaseSession>,
+    area: Rect,
+) {
+    let popup_area = centered_rect(70, 60, area);
+    frame.render_widget(Clear, popup_area);
+
+    let title = match session {
+        Some(session) => format!(
+            " Rebase en cours ({}/{}) ",
+            session.done_count(),
+            session.total
+        ),
+        None => format!(" Rebase interactif sur {:.7} ", editor.base),
+    };
+
+    let block = Block::default()
+        .title(title)
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    // Liste des entrées restantes
+    let items: Vec<ListItem> = editor
+        .entries
+        .iter()
+        .map(|entry| {
+            let summary = entry.message.as_deref().unwrap_or(&entry.summary);
+            let summary = summary.lines().next().unwrap_or("");
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{:<7}", entry.action.label()),
+                    action_style(entry.action),
+                ),
+                Span::raw(" "),
+                Span::styled(
+                    format!("{:.7}", entry.oid),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::raw(" "),
+                Span::raw(summary.to_string()),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items).highlight_style(
+        Style::default()
+            .bg(Color::DarkGray)
+            .fg(Color::White)
+            .add_modifier(Modifier::BOLD),
+    );
+
+    // Le message en cours de reword remplace la liste
+    if let Some(ref input) = editor.reword_input {
+        render_reword(frame, input, chunks[0]);
+    } else {
+        let mut list_state = ListState::default();
+        if session.is_none() && !editor.entries.is_empty() {
+            list_state.select(Some(editor.entries.selected_index()));
+        }
+        frame.render_stateful_widget(list, chunks[0], &mut list_state);
+    }
+
+    // Ligne d'état : arrêt en cours ou position dans le message (gardé tel quel,
+    // sans lignes de commentaire)
+    let status = if let Some(ref input) = editor.reword_input {
+        commit_status_line(input, None, current_theme())
+    } else if let Some(entry) = session.and_then(|s| s.stopped.as_ref()) {
+        Line::from(Span::styled(
+            format!("Arrêté sur {:.7} {}", entry.oid, entry.summary),
+            Style::default().fg(Color::Cyan),
+        ))
+    } else {
+        Line::from("")
+    };
+    frame.render_widget(Paragraph::new(status), chunks[1]);
+
+    let contexts = if editor.reword_input.is_some() {
+        vec![KeyContext::Reword, KeyContext::MessageEditor]
+    } else if session.is_some() {
+        vec![KeyContext::RebaseProgress]
+    } else {
+        vec![KeyContext::RebaseEditor]
+    };
+    let help_text = keymap::current().bar_text(&contexts);
+    frame.render_widget(
+        Paragraph::new(Line::from(Span::styled(
+            help_text,
+            Style::default().fg(Color::DarkGray),
+        ))),
+        chunks[2],
+    );
+}
+
+/// Rend le message complet en cours de reword, avec les règles du sujet et du corps.
+fn render_reword(frame: &mut Frame, input: &CommitEditor, area: Rect) {
+    if area.height == 0 || area.width == 0 {
+        return;
+    }
+    // Défilement pour garder le curseur visible, comme l'éditeur du staging.
+    let (height, width) = (area.height as usize, area.width as usize);
+    let (cursor_line, cursor_col) = input.cursor();
+    let scroll = cursor_line.saturating_sub(height - 1);
+    let hscroll = cursor_col.saturating_sub(width - 1);
+
+    let lines: Vec<Line> = input
+        .lines()
+        .iter()
+        .enumerate()
+        .skip(scroll)
+        .take(height)
+        .map(|(index, text)| message_line(index, text, hscroll, None, current_theme()))
+        .collect();
+    frame.render_widget(Paragraph::new(lines), area);
+    frame.set_cursor_position((
+        area.x + (cursor_col - hscroll) as u16,
+        area.y + (cursor_line - scroll) as u16,
+    ));
+}
+
+/// Couleur associée à une action de la todo list.
+fn action_style(action: TodoAction) -> Style {
+    let color = match action {
+        TodoAction::Pick => Color::Green,
+        TodoAction::Reword => Color::Cyan,
+        TodoAction::Edit => Color::Magenta,
+        TodoAction::Squash | TodoAction::Fixup => Color::Yellow,
+        TodoAction::Drop => Color::Red,
+    };
+    Style::default().fg(color).add_modifier(Modifier::BOLD)
+}
@@ -0,0 +1,197 @@
+//! Vue du reflog : mouvements de HEAD ou d'une branche.
+
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use crate::git::diff::DiffViewMode;
+use crate::state::{BottomLeftMode, ReflogFocus, ReflogState, ViewMode};
+use crate::ui::common::centered_rect_fixed;
+use crate::ui::keymap::{self, KeyContext};
+use crate::utils::time::format_relative_time;
+
+/// Rend la vue reflog.
+///
+/// Reprend la disposition de la vue graph : le reflog à la place du graphe,
+/// les fichiers et le détail (ou le diff) du commit de l'entrée en bas.
+pub fn render(
+    frame: &mut Frame,
+    state: &ReflogState,
+    repo_path: &str,
+    flash_message: Option<&str>,
+    diff_view_mode: DiffViewMode,
+) {
+    let layout = super::layout::build_layout(frame.area(), false);
+
+    render_status_bar(frame, state, repo_path, flash_message, layout.status_bar);
+    super::nav_bar::render(frame, ViewMode::Reflog, layout.nav_bar, 0);
+    render_entries(frame, state, layout.graph);
+
+    let selected_hash = state.commit.as_ref().map(|c| c.short_hash());
+    super::files_view::render(
+        frame,
+        super::files_view::FilesPanel {
+            commit_files: &state.files,
+            status_entries: &[],
+            parents: &[],
+            selected_commit_hash: selected_hash,
+            mode: BottomLeftMode::Files,
+            selected_index: state.files.selected_index(),
+        },
+        layout.bottom_left,
+        state.focus == ReflogFocus::Files,
+    );
+
+    match state.focus {
+        ReflogFocus::Entries => {
+            super::detail_view::render_node(
+                frame,
+                state.commit.as_ref(),
+                layout.bottom_right,
+                false,
+            );
+        }
+        ReflogFocus::Files => {
+            super::diff_view::render(
+                frame,
+                state.file_diff.as_ref(),
+                0,
+                layout.bottom_right,
+                false,
+                diff_view_mode,
+                None,
+            );
+        }
+    }
+
+    render_help(frame, layout.help_bar);
+
+    if let Some(ref input) = state.branch_input {
+        render_branch_input(frame, input, frame.area());
+    }
+}
+
+/// Rend la status bar de la vue reflog.
+fn render_status_bar(
+    frame: &mut Frame,
+    state: &ReflogState,
+    repo_path: &str,
+    flash_message: Option<&str>,
+    area: Rect,
+) {
+    let content = match flash_message {
+        Some(msg) => format!(
+            " git_sv · reflog · {} · {} · {} ",
+            repo_path,
+            state.display_name(),
+            msg
+        ),
+        None => format!(
+            " git_sv · reflog · {} · {} ",
+            repo_path,
+            state.display_name()
+        ),
+    };
+
+    let line = Line::from(vec![Span::styled(
+        content,
+        Style::default()
+            .fg(Color::Black)
+            .bg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    )]);
+
+    frame.render_widget(
+        Paragraph::new(line).style(Style::default().bg(Color::Cyan)),
+        area,
+    );
+}
+
+/// Rend la liste des entrées du reflog.
+fn render_entries(frame: &mut Frame, state: &ReflogState, area: Rect) {
+    let name = state.display_name();
+    let items: Vec<ListItem> = state
+        .entries
+        .iter()
+        .map(|entry| {
+            let old = if entry.old_oid.is_zero() {
+                "0000000".to_string()
+            } else {
+                format!("{:.7}", entry.old_oid)
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{}@{{{}}} ", name, entry.index),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::styled(old, Style::default().fg(Color::DarkGray)),
+                Span::styled(" → ", Style::default().fg(Color::DarkGray)),
+                Span::styled(
+                    format!("{:.7}", entry.new_oid),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::raw(format!("  {}", entry.message)),
+                Span::styled(
+                    format!("  {}", entry.committer),
+                    Style::default().fg(Color::Green),
+                ),
+                Span::styled(
+                    format!(" · {}", format_relative_time(entry.timestamp)),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]))
+        })
+        .collect();
+
+    let border_style = if state.focus == ReflogFocus::Entries {
+        Style::default().fg(Color::Cyan)
+    } else {
+        Style::default()
+    };
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(format!(" Reflog — {} ({}) ", name, state.entries.len()))
+                .borders(Borders::ALL)
+                .border_style(border_style),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        );
+
+    let mut list_state = ListState::default();
+    list_state.select(Some(state.entries.selected_index()));
+    frame.render_stateful_widget(list, area, &mut list_state);
+}
+
+/// Rend la barre d'aide de la vue reflog.
+fn render_help(frame: &mut Frame, area: Rect) {
+    let keys = keymap::current().bar_text(&[KeyContext::Reflog]);
+    let line = Line::from(vec![Span::styled(
+        format!(" {} ", keys),
+        Style::default().fg(Color::DarkGray),
+    )]);
+    frame.render_widget(Paragraph::new(line), area);
+}
+
+/// Rend la saisie du nom de branche à créer sur l'entrée sélectionnée.
+fn render_branch_input(frame: &mut Frame, input: &str, area: Rect) {
+    let popup = centered_rect_fixed(50, 3, area);
+    frame.render_widget(Clear, popup);
+
+    let paragraph = Paragraph::new(input).block(
+        Block::default()
+            .title(" Nouvelle branche sur l'entrée ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow)),
+    );
+    frame.render_widget(paragraph, popup);
+    frame.set_cursor_position((popup.x + input.chars().count() as u16 + 1, popup.y + 1));
+}
@@ -10,8 +10,13 @@ pub struct StagingLayout {
     pub help_bar: Rect,
 }
 
+/// Hauteur de la zone du message de commit pendant l'édition (bordures comprises).
+const COMMIT_EDITOR_HEIGHT: u16 = 10;
+
 /// Construit le layout de la vue staging.
 ///
+/// La zone du message s'agrandit pendant l'édition (`editing`).
+///
 /// ```text
 /// ┌──────────────────────────────────────────────────────────┐
 /// │  Status Bar (1 ligne)                                    │
@@ -30,15 +35,16 @@ pub struct StagingLayout {
 /// │  Help bar                                                │
 /// └──────────────────────────────────────────────────────────┘
 /// ```
-pub fn build_staging_layout(area: Rect) -> StagingLayout {
+pub fn build_staging_layout(area: Rect, editing: bool) -> StagingLayout {
+    let message_height = if editing { COMMIT_EDITOR_HEIGHT } else { 3 };
     // Split vertical : status_bar + contenu + message + help_bar
     let outer = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(1), // Status bar
-            Constraint::Min(0),    // Contenu principal
-            Constraint::Length(3), // Zone message commit
-            Constraint::Length(2), // Help bar
+            Constraint::Length(1),              // Status bar
+            Constraint::Min(0),                 // Contenu principal
+            Constraint::Length(message_height), // Zone message commit
+            Constraint::Length(2),              // Help bar
         ])
         .split(area);
 
@@ -8,6 +8,8 @@ use ratatui::{
 
 use crate::app::{StagingFocus, StagingState};
 use crate::git::repo::StatusEntry;
+use crate::state::{CommitEditor, SUBJECT_RULER};
+use crate::ui::keymap::{self, KeyContext};
 use crate::ui::theme::current_theme;
 
 /// Rend la vue complète de staging.
@@ -19,7 +21,10 @@ pub fn render(
     flash_message: Option<&str>,
 ) {
     let theme = current_theme();
-    let layout = super::staging_layout::build_staging_layout(frame.area());
+    let layout = super::staging_layout::build_staging_layout(
+        frame.area(),
+        staging_state.focus == StagingFocus::CommitMessage,
+    );
 
     // Status bar.
     render_staging_status_bar(
@@ -53,29 +58,38 @@ pub fn render(
         theme,
     );
 
-    // Panneau diff.
+    // Panneau diff (curseur et hunk courant mis en évidence quand il a le focus).
+    let diff_focused = staging_state.focus == StagingFocus::Diff;
+    let selection = staging_state.diff_selection();
+    let highlight = selection
+        .or_else(|| staging_state.current_hunk_range())
+        .filter(|_| diff_focused)
+        .map(|(start, end)| super::diff_view::DiffHighlight {
+            cursor: staging_state.diff_cursor,
+            start,
+            end,
+            visual: selection.is_some(),
+        });
     super::diff_view::render(
         frame,
         staging_state.current_diff.as_ref(),
         staging_state.diff_scroll,
         layout.diff_panel,
-        staging_state.focus == StagingFocus::Diff,
+        diff_focused,
         staging_state.diff_view_mode,
+        highlight,
     );
 
     // Zone de message commit.
-    render_commit_input(
-        frame,
-        &staging_state.commit_message,
-        staging_state.cursor_position,
-        staging_state.focus == StagingFocus::CommitMessage,
-        !staging_state.staged_files().is_empty(),
-        layout.commit_message,
-        theme,
-    );
+    render_commit_input(frame, staging_state, layout.commit_message, theme);
 
     // Help bar.
-    render_staging_help(frame, &staging_state.focus, layout.help_bar, theme);
+    render_staging_help(frame, staging_state, layout.help_bar, theme);
+
+    // Assistant Conventional Commits par-dessus la vue.
+    if let Some(ref picker) = staging_state.conventional_picker {
+        super::conventional_picker::render(frame, picker, frame.area());
+    }
 }
 
 /// Rend la status bar de la vue staging.
@@ -179,16 +193,17 @@ fn render_file_list(
     frame.render_stateful_widget(list, area, &mut state);
 }
 
-/// Rend le champ de saisie du message de commit.
+/// Rend l'éditeur du message de commit.
 fn render_commit_input(
     frame: &mut Frame,
-    message: &str,
-    cursor_pos: usize,
-    is_focused: bool,
-    has_staged_files: bool,
+    staging_state: &StagingState,
     area: Rect,
     theme: &crate::ui::theme::Theme,
 ) {
+    let editor = &staging_state.commit_editor;
+    let is_focused = staging_state.focus == StagingFocus::CommitMessage;
+    let has_staged_files = !staging_state.staged_files().is_empty();
+
     let border_style = if is_focused {
         Style::default().fg(theme.warning)
     } else {
@@ -196,74 +211,193 @@ fn render_commit_input(
     };
 
     let title = if has_staged_files {
-        " Message de commit (Enter pour valider) "
+        " Message de commit "
     } else {
         " Message de commit (aucun fichier staged) "
     };
-
-    let display_text = if message.is_empty() && !is_focused {
-        "Appuyez sur 'c' pour écrire un message de commit..."
+    let hooks_label = if staging_state.skip_hooks {
+        Span::styled(
+            " hooks ignorés (--no-verify) ",
+            Style::default()
+                .fg(theme.warning)
+                .add_modifier(Modifier::BOLD),
+        )
     } else {
-        message
+        Span::styled(" hooks actifs ", Style::default().fg(theme.text_secondary))
     };
 
-    let text_style = if message.is_empty() && !is_focused {
-        Style::default().fg(theme.text_secondary)
-    } else {
-        Style::default().fg(theme.text_normal)
+    let mut block = Block::default()
+        .title(title)
+        .title(Line::from(hooks_label).right_aligned())
+        .borders(Borders::ALL)
+        .border_style(border_style);
+    if is_focused {
+        block = block.title_bottom(commit_status_line(
+            editor,
+            staging_state.comment_char,
+            theme,
+        ));
+    }
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    if editor.is_empty() && !is_focused {
+        let placeholder = Paragraph::new("Appuyez sur 'c' pour écrire un message de commit...")
+            .style(Style::default().fg(theme.text_secondary));
+        frame.render_widget(placeholder, inner);
+        return;
+    }
+    if inner.height == 0 || inner.width == 0 {
+        return;
+    }
+
+    // Règles Conventional Commits non respectées, sous le message.
+    let rules = &crate::config::current().conventional_commits;
+    // Le message vérifié est celui que le commit enregistrera.
+    let violations = match staging_state.cleaned_message() {
+        Ok(message) if rules.enabled && is_focused && !message.is_empty() => {
+            crate::conventional::check(&message, rules)
+        }
+        _ => Vec::new(),
+    };
+    let shown = violations
+        .len()
+        .min(inner.height.saturating_sub(1) as usize / 2);
+    let violations_area = Rect {
+        y: inner.y + inner.height - shown as u16,
+        height: shown as u16,
+        ..inner
+    };
+    let inner = Rect {
+        height: inner.height - shown as u16,
+        ..inner
     };
+    let violation_lines: Vec<Line> = violations
+        .iter()
+        .take(shown)
+        .map(|violation| Line::styled(format!("✗ {}", violation), Style::default().fg(theme.error)))
+        .collect();
+    frame.render_widget(Paragraph::new(violation_lines), violations_area);
 
-    let paragraph = Paragraph::new(display_text).style(text_style).block(
-        Block::default()
-            .title(title)
-            .borders(Borders::ALL)
-            .border_style(border_style),
-    );
+    // Défilement vertical et horizontal pour garder le curseur visible.
+    let (height, width) = (inner.height as usize, inner.width as usize);
+    let (cursor_line, cursor_col) = editor.cursor();
+    let scroll = cursor_line.saturating_sub(height - 1);
+    let hscroll = cursor_col.saturating_sub(width - 1);
 
-    frame.render_widget(paragraph, area);
+    let lines: Vec<Line> = editor
+        .lines()
+        .iter()
+        .enumerate()
+        .skip(scroll)
+        .take(height)
+        .map(|(index, text)| message_line(index, text, hscroll, staging_state.comment_char, theme))
+        .collect();
+    frame.render_widget(Paragraph::new(lines), inner);
 
-    // Si focalisé, positionner le curseur.
     if is_focused {
-        // Calculer la position du curseur en tenant compte des retours à la ligne
-        let mut cursor_line = 0u16;
-        let mut cursor_col = 0u16;
-        let mut current_pos = 0usize;
-
-        for char in message.chars() {
-            if current_pos >= cursor_pos {
-                break;
-            }
-            if char == '\n' {
-                cursor_line += 1;
-                cursor_col = 0;
-            } else {
-                cursor_col += 1;
-            }
-            current_pos += 1;
-        }
+        frame.set_cursor_position((
+            inner.x + (cursor_col - hscroll) as u16,
+            inner.y + (cursor_line - scroll) as u16,
+        ));
+    }
+}
+
+/// Ligne du message avec sa règle : 50 colonnes pour le sujet, 72 pour le corps.
+///
+/// Le texte au-delà de la règle est mis en évidence ; les commentaires
+/// (`comment_char`), retirés au commit, sont atténués.
+pub(crate) fn message_line(
+    index: usize,
+    text: &str,
+    hscroll: usize,
+    comment_char: Option<char>,
+    theme: &crate::ui::theme::Theme,
+) -> Line<'static> {
+    let chars: Vec<char> = text.chars().collect();
+    let len = chars.len();
+    let text_of = |from: usize, to: usize| chars[from.min(to)..to].iter().collect::<String>();
+
+    if comment_char.is_some_and(|c| text.starts_with(c)) {
+        return Line::styled(
+            text_of(hscroll.min(len), len),
+            Style::default()
+                .fg(theme.text_secondary)
+                .add_modifier(Modifier::DIM),
+        );
+    }
+
+    let ruler = CommitEditor::ruler(index);
+    let mut spans = vec![
+        Span::styled(
+            text_of(hscroll.min(len), ruler.min(len)),
+            Style::default().fg(theme.text_normal),
+        ),
+        Span::styled(
+            text_of(hscroll.max(ruler).min(len), len),
+            Style::default()
+                .fg(theme.error)
+                .add_modifier(Modifier::UNDERLINED),
+        ),
+    ];
+    if len < ruler && hscroll <= ruler {
+        spans.push(Span::raw(" ".repeat(ruler - len.max(hscroll))));
+        spans.push(Span::styled(
+            "│",
+            Style::default()
+                .fg(theme.border_inactive)
+                .add_modifier(Modifier::DIM),
+        ));
+    }
+    Line::from(spans)
+}
+
+/// Position du curseur, longueur du sujet et avertissements de l'éditeur.
+pub(crate) fn commit_status_line(
+    editor: &CommitEditor,
+    comment_char: Option<char>,
+    theme: &crate::ui::theme::Theme,
+) -> Line<'static> {
+    let (line, col) = editor.cursor();
+    let subject_len = editor.subject_len();
+    let subject_style = if subject_len > SUBJECT_RULER {
+        Style::default().fg(theme.warning)
+    } else {
+        Style::default().fg(theme.text_secondary)
+    };
 
-        frame.set_cursor_position((area.x + cursor_col + 1, area.y + cursor_line + 1));
+    let mut spans = vec![
+        Span::styled(
+            format!(" Ln {}, Col {} · ", line + 1, col + 1),
+            Style::default().fg(theme.text_secondary),
+        ),
+        Span::styled(
+            format!("sujet {}/{} ", subject_len, SUBJECT_RULER),
+            subject_style,
+        ),
+    ];
+    if editor.missing_blank_line(comment_char) {
+        spans.push(Span::styled(
+            "· ⚠ ligne vide manquante après le sujet ",
+            Style::default()
+                .fg(theme.warning)
+                .add_modifier(Modifier::BOLD),
+        ));
     }
+    Line::from(spans)
 }
 
 /// Rend la barre d'aide de la vue staging.
 fn render_staging_help(
     frame: &mut Frame,
-    focus: &StagingFocus,
+    staging_state: &StagingState,
     area: Rect,
     theme: &crate::ui::theme::Theme,
 ) {
-    let help_text = match focus {
-        StagingFocus::Unstaged => {
-            "j/k:nav  s/Enter:stage  S:stash  a:stage all  d:discard  Tab:→Staged  c:commit  P:push  1:graph  q:quit"
-        }
-        StagingFocus::Staged => {
-            "j/k:nav  u/Enter:unstage  U:unstage all  Tab:→Diff  c:commit  P:push  1:graph  q:quit"
-        }
-        StagingFocus::Diff => {
-            "j/k:scroll  v:vue  Tab:→Unstaged  Esc:Unstaged  c:commit  P:push  1:graph  q:quit"
-        }
-        StagingFocus::CommitMessage => "Enter:confirmer  Esc:annuler  ←→:curseur",
+    let keys = keymap::current().bar_text(&KeyContext::staging(staging_state));
+    let help_text = match staging_state.focus {
+        _ if staging_state.diff_selection_anchor.is_some() => format!("VISUEL  {}", keys),
+        _ => keys,
     };
 
     let line = Line::from(vec![Span::styled(
@@ -122,8 +122,21 @@ fn detect_theme() -> Theme {
     }
 }
 
-/// Thème global de l'application (détection automatique).
-pub static THEME: std::sync::LazyLock<Theme> = std::sync::LazyLock::new(detect_theme);
+/// Construit le thème à partir de la configuration (base puis couleurs personnalisées).
+fn configured_theme() -> Theme {
+    use crate::config::ThemeBase;
+
+    let theme_config = &crate::config::current().theme;
+    let base = match theme_config.base {
+        ThemeBase::Auto => detect_theme(),
+        ThemeBase::Dark => Theme::dark(),
+        ThemeBase::Light => Theme::light(),
+    };
+    theme_config.apply(base)
+}
+
+/// Thème global de l'application (configuration ou détection automatique).
+pub static THEME: std::sync::LazyLock<Theme> = std::sync::LazyLock::new(configured_theme);
 
 /// Retourne le thème actuel.
 pub fn current_theme() -> &'static Theme {
@@ -0,0 +1,126 @@
+//! Popup de progression d'une opération distante (push, pull, fetch).
+//!
+//! Affiché dans le coin inférieur droit sans bloquer la vue : seules les touches
+//! du contexte `transfer` (annulation) s'ajoutent à celles de la vue.
+
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Gauge, Paragraph},
+    Frame,
+};
+
+use crate::transfer::RemoteTask;
+use crate::ui::keymap::{self, KeyContext};
+
+/// Largeur du popup (colonnes).
+const POPUP_WIDTH: u16 = 52;
+
+/// Hauteur du popup (lignes, bordures comprises).
+const POPUP_HEIGHT: u16 = 6;
+
+/// Rend l'avancement de l'opération distante en cours.
+pub fn render(frame: &mut Frame, task: &RemoteTask, area: Rect) {
+    let width = POPUP_WIDTH.min(area.width);
+    let height = POPUP_HEIGHT.min(area.height);
+    // Au-dessus de la barre d'aide, aligné à droite.
+    let popup_area = Rect::new(
+        area.x + area.width - width,
+        (area.y + area.height)
+            .saturating_sub(height + 1)
+            .max(area.y),
+        width,
+        height,
+    );
+    frame.render_widget(Clear, popup_area);
+
+    let progress = task.progress.snapshot();
+    let cancelling = task.progress.is_cancelled();
+    let color = if cancelling {
+        Color::Yellow
+    } else {
+        Color::Cyan
+    };
+    let title = if cancelling {
+        format!(" {} — annulation… ", task.operation.label())
+    } else {
+        format!(
+            " {} en cours ({}s) ",
+            task.operation.label(),
+            task.elapsed().as_secs()
+        )
+    };
+
+    let block = Block::default()
+        .title(title)
+        .title_bottom(format!(
+            " {} ",
+            keymap::current().bar_text(&[KeyContext::Transfer])
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(color));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+    if inner.height == 0 {
+        return;
+    }
+
+    let objects = if progress.total_objects > 0 {
+        format!("{}/{} objets", progress.objects, progress.total_objects)
+    } else {
+        "En attente du serveur…".to_string()
+    };
+    let ratio = if progress.total_objects > 0 {
+        (progress.objects as f64 / progress.total_objects as f64).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let gauge = Gauge::default()
+        .gauge_style(Style::default().fg(color))
+        .ratio(ratio)
+        .label(format!("{} · {}", objects, format_bytes(progress.bytes)));
+    frame.render_widget(gauge, Rect::new(inner.x, inner.y, inner.width, 1));
+
+    if inner.height > 2 {
+        let message = Paragraph::new(vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                progress.message,
+                Style::default().add_modifier(Modifier::DIM),
+            )),
+        ]);
+        frame.render_widget(
+            message,
+            Rect::new(inner.x, inner.y + 1, inner.width, inner.height - 1),
+        );
+    }
+}
+
+/// Taille lisible (ex: "1.4 Mio").
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["o", "Kio", "Mio", "Gio"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 o");
+        assert_eq!(format_bytes(1536), "1.5 Kio");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 Mio");
+    }
+}
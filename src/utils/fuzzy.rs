@@ -0,0 +1,73 @@
+//! Filtrage flou (fuzzy) de textes courts.
+
+/// Score de correspondance floue de `query` dans `text` (None si pas de correspondance).
+///
+/// Les caractères de la requête doivent apparaître dans l'ordre, sans tenir compte
+/// de la casse. Les caractères consécutifs et les débuts de mots rapportent plus.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i64> {
+    let mut query_chars = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .peekable();
+    if query_chars.peek().is_none() {
+        return Some(0);
+    }
+
+    let mut score = 0;
+    let mut previous_matched = false;
+    let mut previous: Option<char> = None;
+
+    for c in text.chars() {
+        let Some(&wanted) = query_chars.peek() else {
+            break;
+        };
+        let word_start = previous.is_none_or(|p| !p.is_alphanumeric());
+
+        if c.to_lowercase().eq(std::iter::once(wanted)) {
+            score += 1;
+            if previous_matched {
+                score += 5;
+            }
+            if word_start {
+                score += 10;
+            }
+            previous_matched = true;
+            query_chars.next();
+        } else {
+            previous_matched = false;
+        }
+        previous = Some(c);
+    }
+
+    if query_chars.peek().is_some() {
+        return None;
+    }
+    // À score égal, les textes courts d'abord.
+    Some(score * 100 - text.chars().count() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subsequence_matches() {
+        assert!(fuzzy_score("psh", "Push").is_some());
+        assert!(fuzzy_score("PUSH", "push").is_some());
+        assert!(fuzzy_score("", "Push").is_some());
+        assert!(fuzzy_score("hsup", "Push").is_none());
+    }
+
+    #[test]
+    fn test_word_starts_rank_higher() {
+        let word_start = fuzzy_score("nc", "Nouveau commit").unwrap();
+        let inside = fuzzy_score("nc", "Annuler la sélection").unwrap();
+        assert!(word_start > inside);
+
+        let consecutive = fuzzy_score("stash", "Stash").unwrap();
+        let scattered =
+            fuzzy_score("stash", "Stager le fichier sur la branche courante").unwrap_or(0);
+        assert!(consecutive > scattered);
+    }
+}
@@ -1,5 +1,7 @@
 //! Utilitaires divers pour l'application.
 
+pub mod fuzzy;
+pub mod shell;
 pub mod time;
 
 pub use time::{format_absolute_time, format_relative_time};
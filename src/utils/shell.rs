@@ -0,0 +1,83 @@
+//! Lancement des scripts shell : hooks, éditeur et commandes personnalisées.
+//!
+//! Ces scripts sont écrits pour un shell POSIX. Windows ne fournit pas de `sh` :
+//! comme git, on utilise alors celui livré avec Git for Windows.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+
+use crate::error::GitSvError;
+
+/// Commande `sh -c <script>` ; les arguments ajoutés deviennent `$0`, `$1`…
+pub fn sh(script: &str) -> Command {
+    let mut command = Command::new(sh_program());
+    command.arg("-c").arg(script);
+    command
+}
+
+/// Commande exécutant le script `path`.
+///
+/// Sous Windows, un script à shebang n'est pas exécutable directement : il est
+/// lancé par `sh`, qui suit son shebang.
+pub fn script(path: &Path) -> Command {
+    if cfg!(windows) {
+        let mut command = sh("\"$0\" \"$@\"");
+        command.arg(path);
+        command
+    } else {
+        Command::new(path)
+    }
+}
+
+/// Erreur de lancement d'un script, explicite si `sh` est introuvable.
+pub fn spawn_error(source: io::Error, context: String) -> GitSvError {
+    let context = if cfg!(windows) && source.kind() == io::ErrorKind::NotFound {
+        format!(
+            "{} : sh introuvable, Git for Windows est nécessaire",
+            context
+        )
+    } else {
+        context
+    };
+    GitSvError::Io { source, context }
+}
+
+/// Programme `sh` : celui de Git for Windows s'il est trouvé, sinon celui du PATH.
+fn sh_program() -> &'static Path {
+    static SH: OnceLock<PathBuf> = OnceLock::new();
+    SH.get_or_init(|| {
+        let bundled = if cfg!(windows) {
+            git_for_windows_sh()
+        } else {
+            None
+        };
+        bundled.unwrap_or_else(|| PathBuf::from("sh"))
+    })
+}
+
+/// `sh.exe` de Git for Windows, retrouvé à partir de `git --exec-path`
+/// (`<Git>\mingw64\libexec\git-core`).
+fn git_for_windows_sh() -> Option<PathBuf> {
+    let output = Command::new("git").arg("--exec-path").output().ok()?;
+    let exec_path = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    exec_path.ancestors().find_map(|dir| {
+        ["usr/bin/sh.exe", "bin/sh.exe"]
+            .iter()
+            .map(|sh| dir.join(sh))
+            .find(|sh| sh.is_file())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sh_passes_arguments() {
+        let output = sh("echo \"$0:$1\"").arg("a").arg("b c").output().unwrap();
+        assert!(output.status.success());
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "a:b c\n");
+    }
+}
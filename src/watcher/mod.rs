@@ -0,0 +1,140 @@
+//! Surveillance des changements dans le repository git.
+//!
+//! Deux implémentations derrière la même API [`GitWatcher`] :
+//! - native (`notify`) : le répertoire git et l'arbre de travail sont
+//!   surveillés, sans les répertoires ignorés par le `.gitignore` ; les
+//!   événements sur des fichiers ignorés sont filtrés et les rafales regroupées ;
+//! - polling des timestamps des fichiers clés de `.git/`, utilisée quand la
+//!   surveillance native n'est pas disponible.
+//!
+//! Les changements sont rapportés par catégorie ([`RepoChanges`]) pour que le
+//! rafraîchissement ne recharge que les données concernées.
+
+mod native;
+mod polling;
+
+use std::ops::BitOrAssign;
+use std::path::Path;
+
+use crate::error::Result;
+
+use native::NativeWatcher;
+use polling::PollingWatcher;
+
+/// Catégories de changements détectés dans le repository.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RepoChanges {
+    /// HEAD, branches, tags, remotes (`refs/`, `packed-refs`).
+    pub refs: bool,
+    /// Index (fichiers stagés).
+    pub index: bool,
+    /// Fichiers suivis de l'arbre de travail.
+    pub worktree: bool,
+    /// Pile des stashes.
+    pub stash: bool,
+    /// Merge, rebase, cherry-pick ou revert en cours.
+    pub merge: bool,
+}
+
+impl RepoChanges {
+    /// Toutes les catégories (rafraîchissement complet).
+    pub const ALL: Self = Self {
+        refs: true,
+        index: true,
+        worktree: true,
+        stash: true,
+        merge: true,
+    };
+
+    /// Aucun changement détecté ?
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Le status du working directory doit-il être rechargé ?
+    pub fn affects_status(&self) -> bool {
+        self.index || self.worktree || self.merge
+    }
+}
+
+impl BitOrAssign for RepoChanges {
+    fn bitor_assign(&mut self, other: Self) {
+        self.refs |= other.refs;
+        self.index |= other.index;
+        self.worktree |= other.worktree;
+        self.stash |= other.stash;
+        self.merge |= other.merge;
+    }
+}
+
+/// Implémentation de la surveillance.
+enum Backend {
+    Native(NativeWatcher),
+    Polling(PollingWatcher),
+}
+
+/// Surveillant de changements du repository.
+pub struct GitWatcher {
+    backend: Backend,
+}
+
+impl GitWatcher {
+    /// Crée un surveillant pour le repository à la racine donnée.
+    ///
+    /// La surveillance native est préférée ; en cas d'échec, le polling prend le relais.
+    pub fn new(repo_path: impl AsRef<Path>) -> Result<Self> {
+        let repo_path = repo_path.as_ref();
+        let backend = match NativeWatcher::new(repo_path) {
+            Ok(watcher) => Backend::Native(watcher),
+            Err(_) => Backend::Polling(PollingWatcher::new(repo_path)?),
+        };
+        Ok(Self { backend })
+    }
+
+    /// Vérifie si des changements ont eu lieu depuis le dernier appel.
+    ///
+    /// Appelée à chaque tour de la boucle principale ; retourne les catégories
+    /// modifiées (vide si aucun rafraîchissement n'est nécessaire).
+    pub fn check_changed(&mut self) -> Result<RepoChanges> {
+        match self.backend {
+            Backend::Native(ref mut watcher) => watcher.check_changed(),
+            Backend::Polling(ref mut watcher) => watcher.check_changed(),
+        }
+    }
+
+    /// Oublie les changements en attente, après un rafraîchissement.
+    pub fn reset(&mut self) -> Result<()> {
+        match self.backend {
+            Backend::Native(ref mut watcher) => watcher.reset(),
+            Backend::Polling(ref mut watcher) => watcher.reset(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repo_changes_union() {
+        let mut changes = RepoChanges::default();
+        assert!(changes.is_empty());
+
+        changes |= RepoChanges {
+            worktree: true,
+            ..Default::default()
+        };
+        changes |= RepoChanges {
+            refs: true,
+            ..Default::default()
+        };
+        assert!(changes.refs && changes.worktree);
+        assert!(!changes.index && !changes.stash);
+        assert!(changes.affects_status());
+        assert!(!RepoChanges {
+            stash: true,
+            ..Default::default()
+        }
+        .affects_status());
+    }
+}
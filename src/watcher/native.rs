@@ -0,0 +1,366 @@
+//! Surveillance native du système de fichiers (inotify, FSEvents, ReadDirectoryChangesW).
+//!
+//! Le répertoire git est surveillé récursivement. Dans l'arbre de travail,
+//! chaque répertoire non ignoré a son propre watch : les répertoires ignorés
+//! par le `.gitignore` (target/, node_modules/...) ne consomment aucun watch.
+//! Les événements sont filtrés (fichiers `.lock`, objets, fichiers ignorés),
+//! classés par catégorie puis regroupés : une rafale n'est signalée qu'une fois.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::time::{Duration, Instant};
+
+use git2::Repository;
+use notify::event::{AccessKind, AccessMode, ModifyKind};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use super::RepoChanges;
+use crate::error::{GitSvError, Result};
+
+/// Délai sans nouvel événement après lequel une rafale est signalée.
+const COALESCE_DELAY: Duration = Duration::from_millis(200);
+
+/// Délai maximal avant de signaler une rafale continue (ex: build en cours).
+const MAX_DELAY: Duration = Duration::from_secs(1);
+
+/// Surveillant de changements git basé sur les notifications du système.
+pub struct NativeWatcher {
+    /// Watcher `notify` (arrêté à la destruction).
+    watcher: RecommendedWatcher,
+    /// Événements reçus du thread de `notify`.
+    events: Receiver<notify::Result<Event>>,
+    /// Repository, pour tester les chemins ignorés par le `.gitignore`.
+    repo: Repository,
+    /// Répertoires git surveillés (`.git/` et, pour un worktree, le répertoire commun).
+    git_dirs: Vec<PathBuf>,
+    /// Racine de l'arbre de travail (None pour un repository bare).
+    workdir: Option<PathBuf>,
+    /// Catégories modifiées par la rafale en cours.
+    pending: RepoChanges,
+    /// Premier événement pertinent de la rafale en cours.
+    burst_start: Option<Instant>,
+    /// Dernier événement pertinent reçu.
+    last_event: Option<Instant>,
+}
+
+impl NativeWatcher {
+    /// Crée un surveillant pour le repository contenant `repo_path`.
+    ///
+    /// Retourne une erreur si la surveillance native n'est pas disponible
+    /// (plateforme non supportée, limite de watches atteinte...).
+    pub fn new(repo_path: &Path) -> Result<Self> {
+        let repo = Repository::discover(repo_path)?;
+        let git_dir = canonical(repo.path());
+        let workdir = repo.workdir().map(canonical);
+
+        let mut git_dirs = vec![git_dir.clone()];
+        // Un worktree partage les références du repository principal.
+        if let Ok(common) = fs::read_to_string(git_dir.join("commondir")) {
+            git_dirs.push(canonical(&git_dir.join(common.trim())));
+        }
+
+        let (tx, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(tx).map_err(watch_error)?;
+
+        let mut roots: Vec<&PathBuf> = Vec::new();
+        for dir in &git_dirs {
+            if !roots.iter().any(|root| dir.starts_with(root)) {
+                roots.push(dir);
+            }
+        }
+        for root in roots {
+            watcher
+                .watch(root, RecursiveMode::Recursive)
+                .map_err(watch_error)?;
+        }
+        if let Some(ref workdir) = workdir {
+            for dir in worktree_dirs(&repo, workdir, workdir) {
+                watcher
+                    .watch(&dir, RecursiveMode::NonRecursive)
+                    .map_err(watch_error)?;
+            }
+        }
+
+        Ok(Self {
+            watcher,
+            events,
+            repo,
+            git_dirs,
+            workdir,
+            pending: RepoChanges::default(),
+            burst_start: None,
+            last_event: None,
+        })
+    }
+
+    /// Vérifie si des changements ont eu lieu depuis le dernier appel.
+    ///
+    /// Les catégories sont rapportées une fois la rafale d'événements terminée
+    /// (aucun événement depuis `COALESCE_DELAY`) ou après `MAX_DELAY` au plus.
+    pub fn check_changed(&mut self) -> Result<RepoChanges> {
+        while let Ok(event) = self.events.try_recv() {
+            // Une erreur de notify (ex: file d'événements saturée) impose un rafraîchissement complet.
+            let changes = match event {
+                Ok(event) => {
+                    self.watch_new_dirs(&event);
+                    self.classify(&event)
+                }
+                Err(_) => RepoChanges::ALL,
+            };
+            if !changes.is_empty() {
+                let now = Instant::now();
+                self.pending |= changes;
+                self.burst_start.get_or_insert(now);
+                self.last_event = Some(now);
+            }
+        }
+
+        let (Some(start), Some(last)) = (self.burst_start, self.last_event) else {
+            return Ok(RepoChanges::default());
+        };
+        if last.elapsed() >= COALESCE_DELAY || start.elapsed() >= MAX_DELAY {
+            self.burst_start = None;
+            self.last_event = None;
+            return Ok(std::mem::take(&mut self.pending));
+        }
+        Ok(RepoChanges::default())
+    }
+
+    /// Oublie les événements reçus (après un rafraîchissement).
+    pub fn reset(&mut self) -> Result<()> {
+        while self.events.try_recv().is_ok() {}
+        self.pending = RepoChanges::default();
+        self.burst_start = None;
+        self.last_event = None;
+        Ok(())
+    }
+
+    /// Surveille les répertoires créés (ou déplacés) dans l'arbre de travail.
+    fn watch_new_dirs(&mut self, event: &Event) {
+        if !matches!(
+            event.kind,
+            EventKind::Create(_) | EventKind::Modify(ModifyKind::Name(_))
+        ) {
+            return;
+        }
+        let Some(ref workdir) = self.workdir else {
+            return;
+        };
+        for path in event.paths.iter().filter(|path| path.is_dir()) {
+            if is_excluded_dir(&self.repo, workdir, path) {
+                continue;
+            }
+            for dir in worktree_dirs(&self.repo, workdir, path) {
+                // Un échec (ex: limite de watches atteinte) laisse seulement ce répertoire sans surveillance.
+                let _ = self.watcher.watch(&dir, RecursiveMode::NonRecursive);
+            }
+        }
+    }
+
+    /// Catégories modifiées par un événement (vide s'il ne concerne rien d'affiché).
+    fn classify(&self, event: &Event) -> RepoChanges {
+        let mut changes = RepoChanges::default();
+        // Les lectures (libgit2 en ouvre beaucoup) ne modifient rien.
+        if let EventKind::Access(kind) = event.kind {
+            if kind != AccessKind::Close(AccessMode::Write) {
+                return changes;
+            }
+        }
+        for path in &event.paths {
+            if let Some(path_changes) = self.classify_path(path) {
+                changes |= path_changes;
+            }
+        }
+        changes
+    }
+
+    /// Catégorie d'une entrée du répertoire git ou d'un fichier suivi de l'arbre de travail.
+    fn classify_path(&self, path: &Path) -> Option<RepoChanges> {
+        if let Some(relative) = self
+            .git_dirs
+            .iter()
+            .find_map(|dir| path.strip_prefix(dir).ok())
+        {
+            return classify_git_path(relative);
+        }
+
+        let relative = self
+            .workdir
+            .as_ref()
+            .and_then(|workdir| path.strip_prefix(workdir).ok())?;
+        // Racine de l'arbre de travail ou `.git` d'un sous-module.
+        if relative.as_os_str().is_empty() || relative.components().any(|c| c.as_os_str() == ".git")
+        {
+            return None;
+        }
+        if self.repo.is_path_ignored(relative).unwrap_or(false) {
+            return None;
+        }
+        Some(RepoChanges {
+            worktree: true,
+            ..Default::default()
+        })
+    }
+}
+
+/// Catégorie d'un chemin relatif au répertoire git (None : sans effet sur l'affichage).
+fn classify_git_path(relative: &Path) -> Option<RepoChanges> {
+    // Les fichiers verrous sont renommés vers leur nom final une fois écrits.
+    if relative.extension().is_some_and(|ext| ext == "lock") {
+        return None;
+    }
+    let first = relative.components().next()?.as_os_str().to_str()?;
+
+    let mut changes = RepoChanges::default();
+    match first {
+        // `git stash drop` ne réécrit parfois que le reflog de refs/stash.
+        "refs" | "logs" if relative.ends_with("refs/stash") => changes.stash = true,
+        "HEAD" | "packed-refs" | "refs" => changes.refs = true,
+        "index" => changes.index = true,
+        "MERGE_HEAD" | "REBASE_HEAD" | "CHERRY_PICK_HEAD" | "REVERT_HEAD" | "rebase-merge"
+        | "rebase-apply" | "sequencer" => changes.merge = true,
+        _ => return None,
+    }
+    Some(changes)
+}
+
+/// Répertoires à surveiller sous `dir` (inclus), sans descendre dans les répertoires exclus.
+///
+/// Les liens symboliques ne sont pas suivis.
+fn worktree_dirs(repo: &Repository, workdir: &Path, dir: &Path) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        if let Ok(entries) = fs::read_dir(&dir) {
+            pending.extend(
+                entries
+                    .flatten()
+                    .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+                    .map(|entry| entry.path())
+                    .filter(|path| !is_excluded_dir(repo, workdir, path)),
+            );
+        }
+        dirs.push(dir);
+    }
+    dirs
+}
+
+/// Répertoire hors de l'arbre de travail, répertoire git (ou de sous-module) ou ignoré.
+fn is_excluded_dir(repo: &Repository, workdir: &Path, path: &Path) -> bool {
+    let Ok(relative) = path.strip_prefix(workdir) else {
+        return true;
+    };
+    relative.components().any(|c| c.as_os_str() == ".git")
+        || repo.is_path_ignored(relative).unwrap_or(false)
+}
+
+/// Chemin canonique (les chemins des événements sont absolus et résolus).
+fn canonical(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn watch_error(error: notify::Error) -> GitSvError {
+    GitSvError::OperationFailed {
+        operation: "surveillance native",
+        details: error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Attend des changements signalés par le watcher (au plus 3 secondes).
+    fn wait_changes(watcher: &mut NativeWatcher) -> RepoChanges {
+        let deadline = Instant::now() + Duration::from_secs(3);
+        while Instant::now() < deadline {
+            let changes = watcher.check_changed().unwrap();
+            if !changes.is_empty() {
+                return changes;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        RepoChanges::default()
+    }
+
+    #[test]
+    fn test_classify_git_paths() {
+        let classify = |path: &str| classify_git_path(Path::new(path));
+
+        assert!(classify("HEAD").unwrap().refs);
+        assert!(classify("refs/remotes/origin/main").unwrap().refs);
+        assert!(classify("packed-refs").unwrap().refs);
+        assert!(classify("index").unwrap().index);
+        assert!(classify("MERGE_HEAD").unwrap().merge);
+        assert!(classify("rebase-merge/done").unwrap().merge);
+
+        let stash = classify("logs/refs/stash").unwrap();
+        assert!(stash.stash && !stash.refs);
+        assert!(classify("refs/stash").unwrap().stash);
+
+        assert_eq!(classify("index.lock"), None);
+        assert_eq!(classify("refs/heads/main.lock"), None);
+        assert_eq!(classify("objects/ab/cdef"), None);
+        assert_eq!(classify("logs/HEAD"), None);
+    }
+
+    #[test]
+    fn test_worktree_changes_respect_gitignore() {
+        let dir = TempDir::new().unwrap();
+        Repository::init(dir.path()).unwrap();
+        fs::write(dir.path().join(".gitignore"), "*.log\n").unwrap();
+
+        let Ok(mut watcher) = NativeWatcher::new(dir.path()) else {
+            // Surveillance native indisponible dans cet environnement.
+            return;
+        };
+
+        fs::write(dir.path().join("build.log"), "ignoré").unwrap();
+        std::thread::sleep(COALESCE_DELAY * 2);
+        assert!(watcher.check_changed().unwrap().is_empty());
+
+        fs::write(dir.path().join("main.rs"), "fn main() {}").unwrap();
+        let changes = wait_changes(&mut watcher);
+        assert!(changes.worktree);
+        assert!(!changes.refs && !changes.index);
+        assert!(watcher.check_changed().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_ignored_dirs_are_not_watched() {
+        let dir = TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        fs::write(dir.path().join(".gitignore"), "target/\n").unwrap();
+        for sub in ["src/ui", "target/debug/deps"] {
+            fs::create_dir_all(dir.path().join(sub)).unwrap();
+        }
+        let workdir = canonical(dir.path());
+
+        let mut dirs: Vec<PathBuf> = worktree_dirs(&repo, &workdir, &workdir)
+            .into_iter()
+            .map(|d| d.strip_prefix(&workdir).unwrap().to_path_buf())
+            .collect();
+        dirs.sort();
+        let expected: Vec<PathBuf> = ["", "src", "src/ui"].iter().map(PathBuf::from).collect();
+        assert_eq!(dirs, expected);
+    }
+
+    #[test]
+    fn test_new_directories_are_watched() {
+        let dir = TempDir::new().unwrap();
+        Repository::init(dir.path()).unwrap();
+
+        let Ok(mut watcher) = NativeWatcher::new(dir.path()) else {
+            // Surveillance native indisponible dans cet environnement.
+            return;
+        };
+
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        wait_changes(&mut watcher);
+        fs::write(dir.path().join("src/main.rs"), "fn main() {}").unwrap();
+        let changes = wait_changes(&mut watcher);
+        assert!(changes.worktree);
+    }
+}
@@ -1,46 +1,52 @@
-//! Surveillance des changements dans le repository git.
+//! Surveillance par polling des timestamps des fichiers clés de `.git/`.
 //!
-//! Ce module implémente une détection automatique des changements git
-//! en surveillant les timestamps de modification des fichiers clés du
-//! répertoire `.git/` (HEAD, index, refs/). Lorsqu'un changement est
-//! détecté, un flag est levé pour signaler qu'un rafraîchissement
-//! est nécessaire.
-//!
-//! La surveillance utilise un polling périodique avec un debounce
+//! Implémentation de repli quand la surveillance native n'est pas disponible :
+//! un changement est détecté en comparant périodiquement les dates de
+//! modification de HEAD, de l'index et des références, avec un debounce
 //! pour éviter les rafraîchissements excessifs.
 
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant, SystemTime};
 
+use super::RepoChanges;
 use crate::error::Result;
 
-/// Intervalle de vérification des changements (2 secondes).
-const CHECK_INTERVAL: Duration = Duration::from_secs(2);
 /// Délai de debounce après un changement détecté (500ms).
 const DEBOUNCE_DELAY: Duration = Duration::from_millis(500);
 
+/// Fichiers et répertoires surveillés, relatifs au répertoire `.git/`.
+const WATCHED_PATHS: [&str; 7] = [
+    "HEAD",
+    "index",
+    "packed-refs",
+    "refs/heads",
+    "refs/tags",
+    "refs/stash",
+    "MERGE_HEAD",
+];
+
 /// Surveillant de changements git par polling des timestamps.
 ///
 /// Cette structure maintient les timestamps des fichiers surveillés
 /// et détecte les modifications en comparant avec les valeurs
 /// précédentes.
-pub struct GitWatcher {
+pub struct PollingWatcher {
     /// Chemin vers le répertoire `.git/`.
     git_dir: PathBuf,
+    /// Intervalle de vérification des changements (`poll_interval_ms` de la configuration).
+    check_interval: Duration,
     /// Timestamp de dernière vérification.
     last_check: Instant,
     /// Timestamp de dernière modification détectée (pour debounce).
     last_change_detected: Option<Instant>,
-    /// Timestamp du fichier HEAD.
-    head_mtime: Option<SystemTime>,
-    /// Timestamp du fichier index.
-    index_mtime: Option<SystemTime>,
-    /// Timestamp du répertoire refs/heads.
-    refs_mtime: Option<SystemTime>,
+    /// Timestamps des chemins de `WATCHED_PATHS`, dans le même ordre.
+    mtimes: Vec<Option<SystemTime>>,
+    /// Catégories modifiées depuis le dernier changement signalé.
+    pending: RepoChanges,
 }
 
-impl GitWatcher {
+impl PollingWatcher {
     /// Crée un nouveau surveillant pour le repository à la racine donnée.
     ///
     /// # Arguments
@@ -58,11 +64,11 @@ impl GitWatcher {
 
         let mut watcher = Self {
             git_dir,
+            check_interval: crate::config::current().poll_interval(),
             last_check: Instant::now(),
             last_change_detected: None,
-            head_mtime: None,
-            index_mtime: None,
-            refs_mtime: None,
+            mtimes: Vec::new(),
+            pending: RepoChanges::default(),
         };
 
         // Initialiser les timestamps
@@ -73,51 +79,42 @@ impl GitWatcher {
 
     /// Met à jour les timestamps des fichiers surveillés.
     fn update_timestamps(&mut self) -> Result<()> {
-        self.head_mtime = get_mtime(&self.git_dir.join("HEAD"));
-        self.index_mtime = get_mtime(&self.git_dir.join("index"));
-
-        // Le répertoire refs/heads contient les références des branches
-        let refs_heads = self.git_dir.join("refs").join("heads");
-        self.refs_mtime = get_mtime(&refs_heads);
-
+        self.mtimes = WATCHED_PATHS
+            .iter()
+            .map(|path| get_mtime(&self.git_dir.join(path)))
+            .collect();
         Ok(())
     }
 
     /// Vérifie si des changements ont eu lieu depuis le dernier appel.
     ///
     /// Cette méthode doit être appelée régulièrement dans la boucle
-    /// principale. Elle retourne `true` uniquement si :
+    /// principale. Elle retourne des catégories uniquement si :
     /// - L'intervalle de vérification est écoulé
     /// - Un changement est détecté
     /// - Le délai de debounce est écoulé depuis la dernière détection
     ///
     /// # Returns
     ///
-    /// `true` si un rafraîchissement est nécessaire, `false` sinon.
-    pub fn check_changed(&mut self) -> Result<bool> {
+    /// Les catégories modifiées (vide si aucun rafraîchissement n'est nécessaire).
+    pub fn check_changed(&mut self) -> Result<RepoChanges> {
         // Vérifier l'intervalle de polling
-        if self.last_check.elapsed() < CHECK_INTERVAL {
-            return Ok(false);
+        if self.last_check.elapsed() < self.check_interval {
+            return Ok(RepoChanges::default());
         }
 
         self.last_check = Instant::now();
 
-        // Stocker les anciennes valeurs
-        let old_head = self.head_mtime;
-        let old_index = self.index_mtime;
-        let old_refs = self.refs_mtime;
-
-        // Mettre à jour les timestamps
+        // Comparer avec les anciennes valeurs
+        let old_mtimes = std::mem::take(&mut self.mtimes);
         self.update_timestamps()?;
 
-        // Détecter les changements
-        let head_changed = self.head_mtime != old_head;
-        let index_changed = self.index_mtime != old_index;
-        let refs_changed = self.refs_mtime != old_refs;
-
-        if head_changed || index_changed || refs_changed {
-            // Enregistrer le moment de la détection
-            self.last_change_detected = Some(Instant::now());
+        for ((path, old), new) in WATCHED_PATHS.iter().zip(&old_mtimes).zip(&self.mtimes) {
+            if old != new {
+                self.pending |= category(path);
+                // Enregistrer le moment de la détection
+                self.last_change_detected = Some(Instant::now());
+            }
         }
 
         // Vérifier si le debounce est écoulé et qu'un changement a été détecté
@@ -125,11 +122,11 @@ impl GitWatcher {
             if change_time.elapsed() >= DEBOUNCE_DELAY {
                 // Reset pour le prochain changement
                 self.last_change_detected = None;
-                return Ok(true);
+                return Ok(std::mem::take(&mut self.pending));
             }
         }
 
-        Ok(false)
+        Ok(RepoChanges::default())
     }
 
     /// Force une vérification immédiate sans attendre l'intervalle.
@@ -139,10 +136,23 @@ impl GitWatcher {
     pub fn reset(&mut self) -> Result<()> {
         self.last_check = Instant::now();
         self.last_change_detected = None;
+        self.pending = RepoChanges::default();
         self.update_timestamps()
     }
 }
 
+/// Catégorie des changements d'un chemin de `WATCHED_PATHS`.
+fn category(path: &str) -> RepoChanges {
+    let mut changes = RepoChanges::default();
+    match path {
+        "index" => changes.index = true,
+        "refs/stash" => changes.stash = true,
+        "MERGE_HEAD" => changes.merge = true,
+        _ => changes.refs = true,
+    }
+    changes
+}
+
 /// Trouve le répertoire `.git/` à partir d'un chemin donné.
 ///
 /// Cherche dans le chemin donné puis dans ses parents.
@@ -207,7 +217,7 @@ mod tests {
         fs::create_dir(git_dir.join("refs")).unwrap();
         fs::create_dir(git_dir.join("refs/heads")).unwrap();
 
-        let watcher = GitWatcher::new(temp_dir.path());
+        let watcher = PollingWatcher::new(temp_dir.path());
         assert!(watcher.is_ok());
     }
 
@@ -221,13 +231,34 @@ mod tests {
         File::create(git_dir.join("index")).unwrap();
         fs::create_dir_all(git_dir.join("refs/heads")).unwrap();
 
-        let mut watcher = GitWatcher::new(temp_dir.path()).unwrap();
+        let mut watcher = PollingWatcher::new(temp_dir.path()).unwrap();
 
         // Force check immédiate (pas d'intervalle)
-        watcher.last_check = Instant::now() - CHECK_INTERVAL - Duration::from_millis(1);
+        watcher.last_check = Instant::now() - watcher.check_interval - Duration::from_millis(1);
 
         // Pas de changement attendu
-        assert!(!watcher.check_changed().unwrap());
+        assert!(watcher.check_changed().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_watcher_reports_index_change() {
+        let temp_dir = TempDir::new().unwrap();
+        let git_dir = temp_dir.path().join(".git");
+        fs::create_dir_all(git_dir.join("refs/heads")).unwrap();
+        File::create(git_dir.join("HEAD")).unwrap();
+
+        let mut watcher = PollingWatcher::new(temp_dir.path()).unwrap();
+        File::create(git_dir.join("index")).unwrap();
+
+        watcher.last_check = Instant::now() - watcher.check_interval - Duration::from_millis(1);
+        assert!(watcher.check_changed().unwrap().is_empty());
+
+        // Debounce écoulé : le changement est signalé avec sa catégorie
+        watcher.last_change_detected = Some(Instant::now() - DEBOUNCE_DELAY);
+        watcher.last_check = Instant::now() - watcher.check_interval - Duration::from_millis(1);
+        let changes = watcher.check_changed().unwrap();
+        assert!(changes.index);
+        assert!(!changes.refs && !changes.worktree);
     }
 
     #[test]